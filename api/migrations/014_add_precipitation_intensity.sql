@@ -0,0 +1,4 @@
+-- Add WMO precipitation intensity class (API-computed field).
+-- Values: none, trace, light, moderate, heavy. Nullable so existing rows are
+-- unaffected; new inserts will populate it.
+ALTER TABLE forecasts ADD COLUMN precipitation_intensity VARCHAR(20);
@@ -0,0 +1,4 @@
+-- Cache parsed GPX track points so the course endpoint doesn't re-parse XML.
+-- Nullable: populated on the next GPX seed; the API falls back to parsing
+-- course_gpx while it is NULL.
+ALTER TABLE races ADD COLUMN course_points JSONB;
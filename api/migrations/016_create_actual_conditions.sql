@@ -0,0 +1,15 @@
+-- Manually recorded race-day conditions, used to score archived forecasts.
+-- Multiple observations per checkpoint are allowed (e.g. several readings).
+CREATE TABLE actual_conditions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    race_id UUID NOT NULL REFERENCES races(id) ON DELETE CASCADE,
+    checkpoint_id UUID NOT NULL REFERENCES checkpoints(id) ON DELETE CASCADE,
+    observed_at TIMESTAMPTZ NOT NULL,
+    temperature_c DECIMAL NOT NULL,
+    wind_speed_ms DECIMAL NOT NULL,
+    precipitation_mm DECIMAL,
+    precipitation_type VARCHAR(50),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_actual_conditions_race ON actual_conditions(race_id, checkpoint_id);
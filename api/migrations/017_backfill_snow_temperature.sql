@@ -0,0 +1,19 @@
+-- Backfill snow_temperature_c for rows inserted before migration 013.
+-- Mirrors services::forecast::calculate_snow_temperature:
+--   T_base       = min(air_temp, dew_point)
+--   cloud_factor = 1 - clamp(cloud_cover / 100, 0, 1)
+--   wind_damping = 1 / (1 + wind_speed / 5)
+--   T_snow       = min(T_base - cloud_factor * 3 * wind_damping, 0)
+-- rounded to 1 decimal place like f64_to_decimal_1dp.
+UPDATE forecasts
+SET snow_temperature_c = ROUND(
+    LEAST(
+        LEAST(temperature_c, dew_point_c)
+            - (1 - LEAST(GREATEST(cloud_cover_pct / 100, 0), 1))
+              * 3
+              / (1 + wind_speed_ms / 5),
+        0
+    ),
+    1
+)
+WHERE snow_temperature_c IS NULL;
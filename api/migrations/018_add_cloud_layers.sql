@@ -0,0 +1,6 @@
+-- Add low/medium/high cloud layer fractions from yr.no's complete endpoint.
+-- Nullable: older rows and some model runs don't provide them.
+ALTER TABLE forecasts
+    ADD COLUMN cloud_cover_low_pct NUMERIC(5,1),
+    ADD COLUMN cloud_cover_medium_pct NUMERIC(5,1),
+    ADD COLUMN cloud_cover_high_pct NUMERIC(5,1);
@@ -0,0 +1,3 @@
+-- Add snow depth from yr.no instant details. Only provided for some
+-- locations, so the column stays nullable.
+ALTER TABLE forecasts ADD COLUMN snow_depth_cm NUMERIC(6,1);
@@ -0,0 +1,3 @@
+-- Haversine length of the GPX track, measured at seed time to sanity-check
+-- the declared distance_km. NULL for files without a <trk>.
+ALTER TABLE races ADD COLUMN computed_track_length_km NUMERIC(8,3);
@@ -0,0 +1,4 @@
+-- Add blowing snow / ground drift risk (API-computed field).
+-- Values: none, possible, likely, severe. Nullable so existing rows are
+-- unaffected; new inserts will populate it.
+ALTER TABLE forecasts ADD COLUMN blowing_snow_risk VARCHAR(20);
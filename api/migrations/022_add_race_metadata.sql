@@ -0,0 +1,5 @@
+-- Optional race metadata from the GPX <wb:race> extension.
+ALTER TABLE races ADD COLUMN country VARCHAR(100);
+ALTER TABLE races ADD COLUMN website TEXT;
+ALTER TABLE races ADD COLUMN discipline VARCHAR(20);
+ALTER TABLE races ADD COLUMN elevation_gain_m NUMERIC(7,1);
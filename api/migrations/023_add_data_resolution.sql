@@ -0,0 +1,4 @@
+-- Temporal resolution of the yr.no entry a forecast was extracted from.
+-- Values: hourly, six_hourly. Nullable so existing rows are unaffected;
+-- new inserts will populate it.
+ALTER TABLE forecasts ADD COLUMN data_resolution VARCHAR(20);
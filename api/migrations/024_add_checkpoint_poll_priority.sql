@@ -0,0 +1,6 @@
+-- Poll priority per checkpoint (1 = lowest, 5 = highest). The poller
+-- refreshes higher-priority checkpoints first. GPX re-seeding does not
+-- touch this column, so operator overrides survive a re-seed.
+ALTER TABLE checkpoints
+    ADD COLUMN poll_priority INTEGER NOT NULL DEFAULT 3
+    CHECK (poll_priority BETWEEN 1 AND 5);
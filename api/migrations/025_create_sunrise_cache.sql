@@ -0,0 +1,12 @@
+-- Cache of MET Norway sunrise/sunset times per location and date.
+-- Rows older than 24 hours are refetched. NULL sunrise/sunset means the
+-- sun does not rise or set that day (polar night / midnight sun).
+CREATE TABLE sunrise_cache (
+    latitude DECIMAL(8,4) NOT NULL,
+    longitude DECIMAL(8,4) NOT NULL,
+    date DATE NOT NULL,
+    sunrise TIMESTAMPTZ,
+    sunset TIMESTAMPTZ,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (latitude, longitude, date)
+);
@@ -0,0 +1,4 @@
+-- yr.no probability_of_thunder (%) for the period following forecast_time.
+-- Nullable: yr.no omits it for many locations and periods, and existing
+-- rows are unaffected. The lightning risk class is derived at response time.
+ALTER TABLE forecasts ADD COLUMN thunder_probability_pct DECIMAL(4,1);
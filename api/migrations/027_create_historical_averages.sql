@@ -0,0 +1,15 @@
+-- Historical average conditions per race checkpoint and UTC hour of the year.
+-- Replaced at startup from the CSV in HISTORICAL_AVERAGES_FILE. Matched to
+-- checkpoints by race name and distance, since race/checkpoint UUIDs change
+-- between editions.
+CREATE TABLE historical_averages (
+    race_name TEXT NOT NULL,
+    checkpoint_distance_km DECIMAL(6,2) NOT NULL,
+    month SMALLINT NOT NULL CHECK (month BETWEEN 1 AND 12),
+    day SMALLINT NOT NULL CHECK (day BETWEEN 1 AND 31),
+    hour SMALLINT NOT NULL CHECK (hour BETWEEN 0 AND 23),
+    avg_temperature_c DECIMAL(5,2) NOT NULL,
+    avg_wind_speed_ms DECIMAL(5,2) NOT NULL,
+    avg_precipitation_mm DECIMAL(6,2) NOT NULL,
+    PRIMARY KEY (race_name, checkpoint_distance_km, month, day, hour)
+);
@@ -0,0 +1,6 @@
+-- no-transaction
+-- Distinct model runs per checkpoint for the forecast history endpoint.
+-- Built CONCURRENTLY (outside a transaction) so large forecast tables stay
+-- writable for the poller while the index is created.
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_forecasts_model_run
+    ON forecasts (checkpoint_id, yr_model_run_at);
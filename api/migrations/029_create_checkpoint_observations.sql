@@ -0,0 +1,18 @@
+-- Weather observations submitted for a single checkpoint (e.g. by marshals
+-- after a race). Scored against archived forecasts alongside
+-- actual_conditions by the forecast accuracy endpoint.
+CREATE TABLE checkpoint_observations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    checkpoint_id UUID NOT NULL REFERENCES checkpoints(id) ON DELETE CASCADE,
+    observed_at TIMESTAMPTZ NOT NULL,
+    temperature_c DECIMAL NOT NULL,
+    wind_speed_ms DECIMAL NOT NULL,
+    precipitation_mm DECIMAL NOT NULL,
+    precipitation_type VARCHAR(10) NOT NULL
+        CHECK (precipitation_type IN ('snow', 'rain', 'sleet', 'none')),
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_checkpoint_observations_checkpoint
+    ON checkpoint_observations(checkpoint_id, observed_at);
@@ -0,0 +1,10 @@
+-- Terrain elevations looked up from OpenTopoData for GPX checkpoints
+-- without an elevation (ENRICH_ELEVATIONS=true). Kept indefinitely:
+-- terrain doesn't change.
+CREATE TABLE elevation_cache (
+    latitude DECIMAL(8,4) NOT NULL,
+    longitude DECIMAL(8,4) NOT NULL,
+    elevation_m DECIMAL NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (latitude, longitude)
+);
@@ -0,0 +1,10 @@
+-- How often each checkpoint's forecast (and forecast history) is requested,
+-- for capacity planning and poller prioritisation.
+CREATE TABLE checkpoint_request_counts (
+    checkpoint_id UUID PRIMARY KEY REFERENCES checkpoints(id) ON DELETE CASCADE,
+    request_count BIGINT NOT NULL DEFAULT 0,
+    last_requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_checkpoint_request_counts_count
+    ON checkpoint_request_counts(request_count DESC);
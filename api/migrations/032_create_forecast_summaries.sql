@@ -0,0 +1,9 @@
+-- Pre-computed race forecasts for pinned target durations, refreshed after
+-- each poll cycle and served by GET /api/v1/forecasts/race/:id/cached-summary.
+CREATE TABLE forecast_summaries (
+    race_id UUID NOT NULL REFERENCES races(id) ON DELETE CASCADE,
+    target_duration_hours NUMERIC(5,2) NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL,
+    summary_json JSONB NOT NULL,
+    PRIMARY KEY (race_id, target_duration_hours)
+);
@@ -0,0 +1,3 @@
+-- Race category from the GPX <wb:race> extension (e.g. classic, skate,
+-- biathlon, pursuit); filterable via GET /api/v1/races?category=.
+ALTER TABLE races ADD COLUMN category VARCHAR(50);
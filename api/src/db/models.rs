@@ -20,6 +20,35 @@ pub struct YrCachedResponse {
     pub created_at: DateTime<Utc>,
 }
 
+/// Freshness metadata of a cached yr.no response (admin raw-cache endpoint).
+#[derive(Debug, Clone, FromRow)]
+pub struct YrCacheMetadata {
+    pub fetched_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_modified: Option<String>,
+}
+
+/// Request counter of a checkpoint, with names for display (admin stats).
+#[derive(Debug, Clone, FromRow)]
+pub struct CheckpointRequestCount {
+    pub checkpoint_id: Uuid,
+    pub checkpoint_name: String,
+    pub race_id: Uuid,
+    pub race_name: String,
+    pub race_year: i32,
+    pub request_count: i64,
+    pub last_requested_at: DateTime<Utc>,
+}
+
+/// A pre-computed race forecast for one pinned target duration (keyed by
+/// race and duration).
+#[derive(Debug, Clone, FromRow)]
+pub struct ForecastSummary {
+    pub computed_at: DateTime<Utc>,
+    /// Serialized `RaceForecastResponse`
+    pub summary_json: serde_json::Value,
+}
+
 /// Race summary (without GPX data), used for list and summary endpoints.
 #[derive(Debug, Clone, FromRow)]
 pub struct Race {
@@ -28,6 +57,23 @@ pub struct Race {
     pub year: i32,
     pub start_time: DateTime<Utc>,
     pub distance_km: Decimal,
+    /// Haversine length of the GPX track (NULL if the file had no track)
+    pub computed_track_length_km: Option<Decimal>,
+    /// Optional metadata from the GPX `<wb:race>` extension
+    pub country: Option<String>,
+    pub website: Option<String>,
+    pub discipline: Option<String>,
+    pub category: Option<String>,
+    pub elevation_gain_m: Option<Decimal>,
+    pub created_at: DateTime<Utc>,
+    /// Bumped by every seed upsert and start-time update
+    pub updated_at: DateTime<Utc>,
+    /// Number of checkpoints (subquery in `RACE_COLS`)
+    pub checkpoint_count: i64,
+    /// Whether a checkpoint sits at 0 km
+    pub has_start_checkpoint: bool,
+    /// Whether a checkpoint sits at the race's `distance_km`
+    pub has_finish_checkpoint: bool,
 }
 
 /// A checkpoint along a race course.
@@ -42,6 +88,32 @@ pub struct Checkpoint {
     pub longitude: Decimal,
     pub elevation_m: Decimal,
     pub sort_order: i32,
+    /// Poller refresh priority, 1 (lowest) to 5 (highest), default 3
+    pub poll_priority: i32,
+    pub created_at: DateTime<Utc>,
+    /// Bumped on every GPX re-seed and poll priority change
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+impl Checkpoint {
+    /// Test checkpoint at 0 km, (0, 0) and 0 m with the default poll
+    /// priority. Override fields with struct update syntax.
+    pub(crate) fn fixture(name: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            race_id: Uuid::nil(),
+            name: name.to_string(),
+            distance_km: Decimal::ZERO,
+            latitude: Decimal::ZERO,
+            longitude: Decimal::ZERO,
+            elevation_m: Decimal::ZERO,
+            sort_order: 0,
+            poll_priority: 3,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
 }
 
 /// A weather forecast record for a checkpoint at a specific time.
@@ -78,6 +150,25 @@ pub struct Forecast {
     /// Estimated snow surface temperature in °C (calculated from air temp, cloud cover, wind).
     /// NULL for rows created before this column was added.
     pub snow_temperature_c: Option<Decimal>,
+    /// WMO precipitation intensity class: "none", "trace", "light", "moderate", "heavy".
+    /// NULL for rows created before this column was added.
+    pub precipitation_intensity: Option<String>,
+    /// Low/medium/high cloud layer fractions (%). NULL for older rows.
+    pub cloud_cover_low_pct: Option<Decimal>,
+    pub cloud_cover_medium_pct: Option<Decimal>,
+    pub cloud_cover_high_pct: Option<Decimal>,
+    /// Snow depth in cm. Location-dependent; NULL when yr.no doesn't provide it.
+    pub snow_depth_cm: Option<Decimal>,
+    /// Blowing snow / ground drift risk: "none", "possible", "likely", "severe".
+    /// NULL for rows created before this column was added.
+    pub blowing_snow_risk: Option<String>,
+
+    /// Temporal resolution of the source yr.no entry: "hourly" or "six_hourly".
+    /// NULL for rows created before this column was added.
+    pub data_resolution: Option<String>,
+    /// yr.no `probability_of_thunder` in %. NULL when yr.no omits it or for
+    /// rows created before this column was added.
+    pub thunder_probability_pct: Option<Decimal>,
 
     /// When yr.no's weather model generated this forecast.
     /// NULL for rows created before this column was added.
@@ -85,3 +176,35 @@ pub struct Forecast {
 
     pub created_at: DateTime<Utc>,
 }
+
+/// Sunrise and sunset for a location and date (`sunrise_cache`).
+/// `None` when the sun does not rise or set that day.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct SunTimes {
+    pub sunrise: Option<DateTime<Utc>>,
+    pub sunset: Option<DateTime<Utc>>,
+}
+
+/// A manually recorded race-day observation at a checkpoint.
+#[derive(Debug, Clone, FromRow)]
+pub struct ActualCondition {
+    pub checkpoint_id: Uuid,
+    pub observed_at: DateTime<Utc>,
+    pub temperature_c: Decimal,
+    pub wind_speed_ms: Decimal,
+}
+
+/// A weather observation submitted for a single checkpoint.
+#[derive(Debug, Clone, FromRow)]
+pub struct CheckpointObservation {
+    pub id: Uuid,
+    pub checkpoint_id: Uuid,
+    pub observed_at: DateTime<Utc>,
+    pub temperature_c: Decimal,
+    pub wind_speed_ms: Decimal,
+    pub precipitation_mm: Decimal,
+    /// "snow", "rain", "sleet" or "none"
+    pub precipitation_type: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
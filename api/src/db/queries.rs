@@ -1,11 +1,20 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::Stream;
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use serde::Serialize;
+use sqlx::{Connection, PgConnection, PgPool};
+use std::sync::LazyLock;
 use uuid::Uuid;
 
-use super::models::{Checkpoint, Forecast, Race, YrCachedResponse};
-use crate::helpers::f64_to_decimal_full;
-use crate::services::gpx::GpxRace;
+use super::models::{
+    ActualCondition, Checkpoint, CheckpointObservation, CheckpointRequestCount, Forecast,
+    ForecastSummary, Race, SunTimes, YrCacheMetadata, YrCachedResponse,
+};
+use crate::helpers::{dec_to_f64, f64_to_decimal_full};
+use crate::services::climate::HistoricalAverage;
+use crate::services::elevation::ElevationClient;
+use crate::services::gpx::{extract_track_points, CoursePoint, GpxRace};
+use crate::services::yr::ForecastResolution;
 
 /// Forecast time tolerance window (hours). SQL queries use a ±N hour BETWEEN
 /// range so the composite index (checkpoint_id, forecast_time, fetched_at DESC)
@@ -25,7 +34,10 @@ const FORECAST_COLS: &str = "\
     wind_direction_deg, wind_gust_ms, \
     precipitation_mm, precipitation_min_mm, precipitation_max_mm, \
     humidity_pct, dew_point_c, cloud_cover_pct, uv_index, symbol_code, \
-    feels_like_c, precipitation_type, snow_temperature_c, yr_model_run_at, created_at";
+    feels_like_c, precipitation_type, snow_temperature_c, precipitation_intensity, \
+    cloud_cover_low_pct, cloud_cover_medium_pct, cloud_cover_high_pct, snow_depth_cm, \
+    blowing_snow_risk, data_resolution, thunder_probability_pct, \
+    yr_model_run_at, created_at";
 
 /// Forecast SELECT column list with `f.` table alias prefix.
 ///
@@ -37,7 +49,15 @@ const FORECAST_COLS_F: &str = "\
     f.wind_direction_deg, f.wind_gust_ms, \
     f.precipitation_mm, f.precipitation_min_mm, f.precipitation_max_mm, \
     f.humidity_pct, f.dew_point_c, f.cloud_cover_pct, f.uv_index, f.symbol_code, \
-    f.feels_like_c, f.precipitation_type, f.snow_temperature_c, f.yr_model_run_at, f.created_at";
+    f.feels_like_c, f.precipitation_type, f.snow_temperature_c, f.precipitation_intensity, \
+    f.cloud_cover_low_pct, f.cloud_cover_medium_pct, f.cloud_cover_high_pct, f.snow_depth_cm, \
+    f.blowing_snow_risk, f.data_resolution, f.thunder_probability_pct, \
+    f.yr_model_run_at, f.created_at";
+
+/// Checkpoint SELECT column list. Keep in sync with the `Checkpoint` model struct.
+const CHECKPOINT_COLS: &str =
+    "id, race_id, name, distance_km, latitude, longitude, elevation_m, sort_order, poll_priority, \
+     created_at, updated_at";
 
 /// Forecast INSERT column list (excludes `id` and `created_at` which are auto-generated).
 const FORECAST_INSERT_COLS: &str = "\
@@ -47,7 +67,10 @@ const FORECAST_INSERT_COLS: &str = "\
     wind_direction_deg, wind_gust_ms, \
     precipitation_mm, precipitation_min_mm, precipitation_max_mm, \
     humidity_pct, dew_point_c, cloud_cover_pct, uv_index, symbol_code, \
-    feels_like_c, precipitation_type, snow_temperature_c, yr_model_run_at";
+    feels_like_c, precipitation_type, snow_temperature_c, precipitation_intensity, \
+    cloud_cover_low_pct, cloud_cover_medium_pct, cloud_cover_high_pct, snow_depth_cm, \
+    blowing_snow_risk, data_resolution, thunder_probability_pct, \
+    yr_model_run_at";
 
 /// Internal helper for the batch forecast query — includes an `idx` column
 /// from `WITH ORDINALITY` to preserve input ordering. All forecast fields are
@@ -81,6 +104,14 @@ pub(crate) struct ForecastWithIdx {
     pub feels_like_c: Option<Decimal>,
     pub precipitation_type: Option<String>,
     pub snow_temperature_c: Option<Decimal>,
+    pub precipitation_intensity: Option<String>,
+    pub cloud_cover_low_pct: Option<Decimal>,
+    pub cloud_cover_medium_pct: Option<Decimal>,
+    pub cloud_cover_high_pct: Option<Decimal>,
+    pub snow_depth_cm: Option<Decimal>,
+    pub blowing_snow_risk: Option<String>,
+    pub data_resolution: Option<String>,
+    pub thunder_probability_pct: Option<Decimal>,
     pub yr_model_run_at: Option<DateTime<Utc>>,
     pub created_at: Option<DateTime<Utc>>,
 }
@@ -114,6 +145,14 @@ impl ForecastWithIdx {
             feels_like_c: self.feels_like_c?,
             precipitation_type: self.precipitation_type?,
             snow_temperature_c: self.snow_temperature_c,
+            precipitation_intensity: self.precipitation_intensity,
+            cloud_cover_low_pct: self.cloud_cover_low_pct,
+            cloud_cover_medium_pct: self.cloud_cover_medium_pct,
+            cloud_cover_high_pct: self.cloud_cover_high_pct,
+            snow_depth_cm: self.snow_depth_cm,
+            blowing_snow_risk: self.blowing_snow_risk,
+            data_resolution: self.data_resolution,
+            thunder_probability_pct: self.thunder_probability_pct,
             yr_model_run_at: self.yr_model_run_at,
             created_at: self.created_at?,
         })
@@ -145,6 +184,14 @@ pub(crate) struct InsertForecastParams {
     pub(crate) feels_like_c: Decimal,
     pub(crate) precipitation_type: String,
     pub(crate) snow_temperature_c: Decimal,
+    pub(crate) precipitation_intensity: String,
+    pub(crate) cloud_cover_low_pct: Option<Decimal>,
+    pub(crate) cloud_cover_medium_pct: Option<Decimal>,
+    pub(crate) cloud_cover_high_pct: Option<Decimal>,
+    pub(crate) snow_depth_cm: Option<Decimal>,
+    pub(crate) blowing_snow_risk: String,
+    pub(crate) data_resolution: String,
+    pub(crate) thunder_probability_pct: Option<Decimal>,
     pub(crate) yr_model_run_at: Option<DateTime<Utc>>,
 }
 
@@ -185,6 +232,30 @@ pub(crate) async fn get_yr_cached_response_any(
     .await
 }
 
+#[derive(sqlx::FromRow)]
+struct YrRawResponseRow {
+    raw_response: serde_json::Value,
+    #[sqlx(flatten)]
+    metadata: YrCacheMetadata,
+}
+
+/// Raw cached yr.no JSON for a checkpoint with its freshness metadata,
+/// regardless of expiry (admin debugging).
+pub(crate) async fn get_yr_raw_response(
+    pool: &PgPool,
+    checkpoint_id: Uuid,
+) -> Result<Option<(serde_json::Value, YrCacheMetadata)>, sqlx::Error> {
+    let row = sqlx::query_as::<_, YrRawResponseRow>(
+        "SELECT raw_response, fetched_at, expires_at, last_modified
+         FROM yr_responses
+         WHERE checkpoint_id = $1",
+    )
+    .bind(checkpoint_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|r| (r.raw_response, r.metadata)))
+}
+
 /// Update expires_at and optionally last_modified on a yr.no cached response.
 /// Used when yr.no returns 304 Not Modified with updated caching headers.
 /// If `last_modified` is None, the existing value is preserved via COALESCE.
@@ -244,29 +315,431 @@ pub(crate) async fn upsert_yr_cached_response(
     .await
 }
 
+// ---------------------------------------------------------------------------
+// sunrise_cache
+// ---------------------------------------------------------------------------
+
+/// Cached sunrise/sunset for a location and date, if fetched within 24 hours.
+pub(crate) async fn get_sunrise_cached(
+    pool: &PgPool,
+    latitude: Decimal,
+    longitude: Decimal,
+    date: NaiveDate,
+) -> Result<Option<SunTimes>, sqlx::Error> {
+    sqlx::query_as::<_, SunTimes>(
+        "SELECT sunrise, sunset FROM sunrise_cache
+         WHERE latitude = $1 AND longitude = $2 AND date = $3
+           AND fetched_at > NOW() - INTERVAL '24 hours'",
+    )
+    .bind(latitude)
+    .bind(longitude)
+    .bind(date)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Insert or refresh the sunrise/sunset row for a location and date.
+pub(crate) async fn upsert_sunrise_cache(
+    pool: &PgPool,
+    latitude: Decimal,
+    longitude: Decimal,
+    date: NaiveDate,
+    times: &SunTimes,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO sunrise_cache (latitude, longitude, date, sunrise, sunset, fetched_at)
+         VALUES ($1, $2, $3, $4, $5, NOW())
+         ON CONFLICT (latitude, longitude, date) DO UPDATE SET
+             sunrise = EXCLUDED.sunrise,
+             sunset = EXCLUDED.sunset,
+             fetched_at = EXCLUDED.fetched_at",
+    )
+    .bind(latitude)
+    .bind(longitude)
+    .bind(date)
+    .bind(times.sunrise)
+    .bind(times.sunset)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// elevation_cache
+// ---------------------------------------------------------------------------
+
+/// Cached terrain elevation for a location (coordinates rounded to 4 dp).
+pub(crate) async fn get_cached_elevation(
+    pool: &PgPool,
+    latitude: Decimal,
+    longitude: Decimal,
+) -> Result<Option<Decimal>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT elevation_m FROM elevation_cache WHERE latitude = $1 AND longitude = $2",
+    )
+    .bind(latitude)
+    .bind(longitude)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Store a looked-up terrain elevation. Existing rows are kept.
+pub(crate) async fn insert_elevation_cache(
+    pool: &PgPool,
+    latitude: Decimal,
+    longitude: Decimal,
+    elevation_m: Decimal,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO elevation_cache (latitude, longitude, elevation_m)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (latitude, longitude) DO NOTHING",
+    )
+    .bind(latitude)
+    .bind(longitude)
+    .bind(elevation_m)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// checkpoint_request_counts
+// ---------------------------------------------------------------------------
+
+/// Count one forecast request for a checkpoint: starts its count at 1, or
+/// adds 1 to an existing count.
+pub(crate) async fn increment_checkpoint_request_count(
+    pool: &PgPool,
+    checkpoint_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO checkpoint_request_counts (checkpoint_id, request_count, last_requested_at)
+         VALUES ($1, 1, NOW())
+         ON CONFLICT (checkpoint_id) DO UPDATE SET
+             request_count = checkpoint_request_counts.request_count + 1,
+             last_requested_at = EXCLUDED.last_requested_at",
+    )
+    .bind(checkpoint_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// The `limit` most requested checkpoints, most requested first; ties go to
+/// the most recently requested.
+pub(crate) async fn get_popular_checkpoints(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<CheckpointRequestCount>, sqlx::Error> {
+    sqlx::query_as::<_, CheckpointRequestCount>(
+        "SELECT n.checkpoint_id, c.name AS checkpoint_name, r.id AS race_id,
+                r.name AS race_name, r.year AS race_year,
+                n.request_count, n.last_requested_at
+         FROM checkpoint_request_counts n
+         JOIN checkpoints c ON c.id = n.checkpoint_id
+         JOIN races r ON r.id = c.race_id
+         ORDER BY n.request_count DESC, n.last_requested_at DESC
+         LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+// ---------------------------------------------------------------------------
+// forecast_summaries
+// ---------------------------------------------------------------------------
+
+/// Store (or replace) the pre-computed forecast of a race for one target duration.
+pub(crate) async fn upsert_forecast_summary(
+    pool: &PgPool,
+    race_id: Uuid,
+    target_duration_hours: Decimal,
+    summary_json: &serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO forecast_summaries (race_id, target_duration_hours, computed_at, summary_json)
+         VALUES ($1, $2, NOW(), $3)
+         ON CONFLICT (race_id, target_duration_hours) DO UPDATE SET
+             computed_at = EXCLUDED.computed_at,
+             summary_json = EXCLUDED.summary_json",
+    )
+    .bind(race_id)
+    .bind(target_duration_hours)
+    .bind(summary_json)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// The pre-computed forecast of a race for a target duration, if any.
+pub(crate) async fn get_forecast_summary(
+    pool: &PgPool,
+    race_id: Uuid,
+    target_duration_hours: Decimal,
+) -> Result<Option<ForecastSummary>, sqlx::Error> {
+    sqlx::query_as::<_, ForecastSummary>(
+        "SELECT computed_at, summary_json
+         FROM forecast_summaries
+         WHERE race_id = $1 AND target_duration_hours = $2",
+    )
+    .bind(race_id)
+    .bind(target_duration_hours)
+    .fetch_optional(pool)
+    .await
+}
+
+// ---------------------------------------------------------------------------
+// historical_averages
+// ---------------------------------------------------------------------------
+
+#[derive(sqlx::FromRow)]
+struct HistoricalAverageRow {
+    race_name: String,
+    checkpoint_distance_km: Decimal,
+    month: i16,
+    day: i16,
+    hour: i16,
+    avg_temperature_c: Decimal,
+    avg_wind_speed_ms: Decimal,
+    avg_precipitation_mm: Decimal,
+}
+
+impl From<HistoricalAverageRow> for HistoricalAverage {
+    fn from(r: HistoricalAverageRow) -> Self {
+        HistoricalAverage {
+            race_name: r.race_name,
+            checkpoint_distance_km: dec_to_f64(r.checkpoint_distance_km),
+            month: r.month as u32,
+            day: r.day as u32,
+            hour: r.hour as u32,
+            avg_temperature_c: dec_to_f64(r.avg_temperature_c),
+            avg_wind_speed_ms: dec_to_f64(r.avg_wind_speed_ms),
+            avg_precipitation_mm: dec_to_f64(r.avg_precipitation_mm),
+        }
+    }
+}
+
+/// Replace the contents of `historical_averages` with `rows` in a single
+/// transaction, so the table always mirrors the CSV loaded at startup.
+pub(crate) async fn replace_historical_averages(
+    pool: &PgPool,
+    rows: &[HistoricalAverage],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM historical_averages")
+        .execute(&mut *tx)
+        .await?;
+    for r in rows {
+        sqlx::query(
+            "INSERT INTO historical_averages
+                 (race_name, checkpoint_distance_km, month, day, hour,
+                  avg_temperature_c, avg_wind_speed_ms, avg_precipitation_mm)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             ON CONFLICT (race_name, checkpoint_distance_km, month, day, hour) DO UPDATE SET
+                 avg_temperature_c = EXCLUDED.avg_temperature_c,
+                 avg_wind_speed_ms = EXCLUDED.avg_wind_speed_ms,
+                 avg_precipitation_mm = EXCLUDED.avg_precipitation_mm",
+        )
+        .bind(&r.race_name)
+        .bind(f64_to_decimal_full(r.checkpoint_distance_km))
+        .bind(r.month as i16)
+        .bind(r.day as i16)
+        .bind(r.hour as i16)
+        .bind(f64_to_decimal_full(r.avg_temperature_c))
+        .bind(f64_to_decimal_full(r.avg_wind_speed_ms))
+        .bind(f64_to_decimal_full(r.avg_precipitation_mm))
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// All historical averages for a race name.
+pub(crate) async fn get_historical_averages(
+    pool: &PgPool,
+    race_name: &str,
+) -> Result<Vec<HistoricalAverage>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, HistoricalAverageRow>(
+        "SELECT race_name, checkpoint_distance_km, month, day, hour,
+                avg_temperature_c, avg_wind_speed_ms, avg_precipitation_mm
+         FROM historical_averages
+         WHERE race_name = $1",
+    )
+    .bind(race_name)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(HistoricalAverage::from).collect())
+}
+
 // ---------------------------------------------------------------------------
 // Race queries
 // ---------------------------------------------------------------------------
 
+/// Race summary SELECT column list (everything except the GPX/course blobs).
+///
+/// Keep in sync with the `Race` model struct in `models.rs`.
+const RACE_COLS: &str = "\
+    id, name, year, start_time, distance_km, computed_track_length_km, \
+    country, website, discipline, category, elevation_gain_m, created_at, updated_at, \
+    (SELECT COUNT(*) FROM checkpoints cp WHERE cp.race_id = races.id) AS checkpoint_count, \
+    EXISTS (SELECT 1 FROM checkpoints cp \
+            WHERE cp.race_id = races.id AND cp.distance_km = 0) AS has_start_checkpoint, \
+    EXISTS (SELECT 1 FROM checkpoints cp \
+            WHERE cp.race_id = races.id AND cp.distance_km = races.distance_km) \
+        AS has_finish_checkpoint";
+
+/// Same columns as `RACE_COLS` with `r.` table alias prefix for use in JOINs.
+const RACE_COLS_R: &str = "\
+    r.id, r.name, r.year, r.start_time, r.distance_km, r.computed_track_length_km, \
+    r.country, r.website, r.discipline, r.category, r.elevation_gain_m, \
+    r.created_at, r.updated_at, \
+    (SELECT COUNT(*) FROM checkpoints cp WHERE cp.race_id = r.id) AS checkpoint_count, \
+    EXISTS (SELECT 1 FROM checkpoints cp \
+            WHERE cp.race_id = r.id AND cp.distance_km = 0) AS has_start_checkpoint, \
+    EXISTS (SELECT 1 FROM checkpoints cp \
+            WHERE cp.race_id = r.id AND cp.distance_km = r.distance_km) \
+        AS has_finish_checkpoint";
+
+/// Forecast coverage columns of the race list, for races aliased `r`.
+///
+/// `forecast_available` checks for a non-expired yr.no cache row whose last
+/// timeseries entry (the forecast horizon) is past the race start;
+/// `days_until_forecast_available` counts the days until the start enters
+/// yr.no's ~10-day range (NULL once it has). Both scan `yr_responses`, so
+/// they are only selected by the race list, not by every race query.
+const RACE_FORECAST_AVAILABILITY_COLS: &str = "\
+    EXISTS (SELECT 1 FROM checkpoints cp \
+            JOIN yr_responses y ON y.checkpoint_id = cp.id \
+            WHERE cp.race_id = r.id AND y.expires_at > NOW() \
+              AND (y.raw_response->'properties'->'timeseries'->-1->>'time')::timestamptz \
+                  > r.start_time) AS forecast_available, \
+    CASE WHEN r.start_time::date - NOW()::date > 10 \
+         THEN GREATEST(0, (r.start_time::date - NOW()::date) - 10) END \
+        AS days_until_forecast_available";
+
+/// A race with its forecast coverage, as listed by GET /api/v1/races.
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct RaceListRow {
+    #[sqlx(flatten)]
+    pub race: Race,
+    /// Whether a current yr.no cache row covers the race start
+    pub forecast_available: bool,
+    /// Days until the race start is within yr.no's ~10-day range (NULL once it is)
+    pub days_until_forecast_available: Option<i32>,
+}
+
 /// Get a race summary (no GPX blob) — lightweight existence check + metadata.
 pub(crate) async fn get_race_summary(pool: &PgPool, id: Uuid) -> Result<Option<Race>, sqlx::Error> {
-    sqlx::query_as::<_, Race>(
-        "SELECT id, name, year, start_time, distance_km FROM races WHERE id = $1",
+    sqlx::query_as::<_, Race>(&format!("SELECT {RACE_COLS} FROM races WHERE id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// List all races (summary only, no GPX) with their forecast coverage,
+/// optionally only those of one `category`.
+pub(crate) async fn list_races(
+    pool: &PgPool,
+    category: Option<&str>,
+) -> Result<Vec<RaceListRow>, sqlx::Error> {
+    sqlx::query_as::<_, RaceListRow>(&format!(
+        "SELECT {RACE_COLS_R}, {RACE_FORECAST_AVAILABILITY_COLS} FROM races r
+         WHERE $1::varchar IS NULL OR r.category = $1
+         ORDER BY r.year DESC, r.name"
+    ))
+    .bind(category)
+    .fetch_all(pool)
+    .await
+}
+
+/// Last time anything shown by the race list may have changed (`None` when
+/// there are no races): the newest race `updated_at`, and for the forecast
+/// coverage columns the newest yr.no fetch, the newest cache expiry already
+/// passed, and the start of the current day (`days_until_forecast_available`
+/// counts in days).
+pub(crate) async fn latest_race_update(
+    pool: &PgPool,
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT GREATEST(
+                    MAX(r.updated_at),
+                    (SELECT MAX(fetched_at) FROM yr_responses),
+                    (SELECT MAX(expires_at) FROM yr_responses WHERE expires_at <= NOW()),
+                    date_trunc('day', NOW())
+                )
+         FROM races r
+         HAVING COUNT(*) > 0",
     )
-    .bind(id)
     .fetch_optional(pool)
     .await
+    .map(Option::flatten)
 }
 
-/// List all races (summary only, no GPX).
-pub(crate) async fn list_races(pool: &PgPool) -> Result<Vec<Race>, sqlx::Error> {
-    sqlx::query_as::<_, Race>(
-        "SELECT id, name, year, start_time, distance_km FROM races ORDER BY year DESC, name",
-    )
+/// A race list row with the distance from a search point to its start checkpoint.
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct RaceNear {
+    #[sqlx(flatten)]
+    pub race: RaceListRow,
+    pub start_distance_km: f64,
+}
+
+/// Races whose start checkpoint (sort_order 0) lies within `radius_km` of
+/// (`lat`, `lon`), nearest first, optionally only those of one `category`.
+///
+/// Great-circle distance via the spherical law of cosines; the acos argument
+/// is clamped to [-1, 1] so floating-point error at zero distance can't make
+/// it NaN.
+pub(crate) async fn find_races_near(
+    pool: &PgPool,
+    lat: f64,
+    lon: f64,
+    radius_km: f64,
+    category: Option<&str>,
+) -> Result<Vec<RaceNear>, sqlx::Error> {
+    sqlx::query_as::<_, RaceNear>(&format!(
+        "SELECT {RACE_COLS_R}, {RACE_FORECAST_AVAILABILITY_COLS}, d.start_distance_km
+         FROM races r
+         JOIN checkpoints c ON c.race_id = r.id AND c.sort_order = 0
+         CROSS JOIN LATERAL (
+            SELECT 6371 * acos(LEAST(1.0, GREATEST(-1.0,
+                cos(radians($1)) * cos(radians(c.latitude::float8))
+                    * cos(radians(c.longitude::float8) - radians($2))
+                + sin(radians($1)) * sin(radians(c.latitude::float8))
+            ))) AS start_distance_km
+         ) d
+         WHERE d.start_distance_km <= $3
+           AND ($4::varchar IS NULL OR r.category = $4)
+         ORDER BY d.start_distance_km, r.year DESC, r.name"
+    ))
+    .bind(lat)
+    .bind(lon)
+    .bind(radius_km)
+    .bind(category)
     .fetch_all(pool)
     .await
 }
 
+/// Update a race's start time. Returns the updated race summary, or None if
+/// the race doesn't exist.
+pub(crate) async fn update_race_start_time(
+    pool: &PgPool,
+    id: Uuid,
+    start_time: DateTime<Utc>,
+) -> Result<Option<Race>, sqlx::Error> {
+    sqlx::query_as::<_, Race>(&format!(
+        "UPDATE races SET start_time = $2, updated_at = NOW()
+         WHERE id = $1
+         RETURNING {RACE_COLS}"
+    ))
+    .bind(id)
+    .bind(start_time)
+    .fetch_optional(pool)
+    .await
+}
+
 /// Get just the GPX XML for a race (for course coordinate extraction).
 pub(crate) async fn get_race_course_gpx(
     pool: &PgPool,
@@ -279,22 +752,76 @@ pub(crate) async fn get_race_course_gpx(
     Ok(row.map(|r| r.0))
 }
 
-/// Get all checkpoints for a race, ordered by sort_order.
+/// Get the cached, pre-parsed course points for a race.
+///
+/// Returns None when the race doesn't exist or `course_points` hasn't been
+/// populated yet (callers fall back to parsing `course_gpx`).
+pub(crate) async fn get_race_course_points(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<Option<Vec<CoursePoint>>, sqlx::Error> {
+    let row: Option<(Option<sqlx::types::Json<Vec<CoursePoint>>>,)> =
+        sqlx::query_as("SELECT course_points FROM races WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+    Ok(row.and_then(|r| r.0).map(|points| points.0))
+}
+
+/// Get all checkpoints for a race in course order.
 pub(crate) async fn get_checkpoints(
     pool: &PgPool,
     race_id: Uuid,
 ) -> Result<Vec<Checkpoint>, sqlx::Error> {
-    sqlx::query_as::<_, Checkpoint>(
-        "SELECT id, race_id, name, distance_km, latitude, longitude, elevation_m, sort_order
+    get_checkpoints_ordered(pool, race_id, "sort_order").await
+}
+
+/// Get all checkpoints for a race ordered by `order_by`, an `ORDER BY`
+/// clause built from trusted constants (never user input).
+pub(crate) async fn get_checkpoints_ordered(
+    pool: &PgPool,
+    race_id: Uuid,
+    order_by: &str,
+) -> Result<Vec<Checkpoint>, sqlx::Error> {
+    sqlx::query_as::<_, Checkpoint>(&format!(
+        "SELECT {CHECKPOINT_COLS}
          FROM checkpoints
          WHERE race_id = $1
-         ORDER BY sort_order",
-    )
+         ORDER BY {order_by}"
+    ))
     .bind(race_id)
     .fetch_all(pool)
     .await
 }
 
+/// Bounding box of a race's checkpoints as `(min_lat, max_lat, min_lon, max_lon)`.
+/// Returns None if the race has no checkpoints (or doesn't exist).
+pub(crate) async fn get_race_checkpoint_bounds(
+    pool: &PgPool,
+    race_id: Uuid,
+) -> Result<Option<(Decimal, Decimal, Decimal, Decimal)>, sqlx::Error> {
+    let row: (
+        Option<Decimal>,
+        Option<Decimal>,
+        Option<Decimal>,
+        Option<Decimal>,
+    ) = sqlx::query_as(
+        "SELECT MIN(latitude), MAX(latitude), MIN(longitude), MAX(longitude)
+         FROM checkpoints
+         WHERE race_id = $1",
+    )
+    .bind(race_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(match row {
+        (Some(min_lat), Some(max_lat), Some(min_lon), Some(max_lon)) => {
+            Some((min_lat, max_lat, min_lon, max_lon))
+        }
+        _ => None,
+    })
+}
+
 /// Get the latest forecast for a checkpoint closest to a given forecast time.
 ///
 /// Uses a BETWEEN range (±3 hours) so the composite index on
@@ -323,6 +850,36 @@ pub(crate) async fn get_latest_forecast(
         .await
 }
 
+/// Get the forecast closest to `forecast_time` as it was known at `issued_before`
+/// (i.e. the latest row fetched at or before that instant).
+///
+/// Same ±3 hour tolerance window as `get_latest_forecast`. Used to score
+/// archived forecasts against recorded conditions at a fixed lead time.
+pub(crate) async fn get_forecast_issued_before(
+    pool: &PgPool,
+    checkpoint_id: Uuid,
+    forecast_time: DateTime<Utc>,
+    issued_before: DateTime<Utc>,
+) -> Result<Option<Forecast>, sqlx::Error> {
+    let query = format!(
+        "SELECT {FORECAST_COLS}
+         FROM forecasts
+         WHERE checkpoint_id = $1
+           AND forecast_time BETWEEN $2 - INTERVAL '{h} hours' AND $2 + INTERVAL '{h} hours'
+           AND fetched_at <= $3
+         ORDER BY ABS(EXTRACT(EPOCH FROM (forecast_time - $2))),
+                  fetched_at DESC
+         LIMIT 1",
+        h = FORECAST_TIME_TOLERANCE_HOURS,
+    );
+    sqlx::query_as::<_, Forecast>(&query)
+        .bind(checkpoint_id)
+        .bind(forecast_time)
+        .bind(issued_before)
+        .fetch_optional(pool)
+        .await
+}
+
 /// Batch get the latest forecast for multiple (checkpoint_id, forecast_time) pairs.
 ///
 /// Returns one Forecast per input pair (in the same order), or None if no
@@ -387,16 +944,52 @@ pub(crate) async fn get_latest_forecasts_batch(
 /// Prevents unbounded result sets for long-running forecast tracking.
 pub(crate) const MAX_FORECAST_HISTORY_ENTRIES: i32 = 200;
 
+/// Maximum number of rows streamed by one forecast export.
+pub(crate) const MAX_FORECAST_EXPORT_ROWS: i32 = 10_000;
+
+/// Built once so the export stream can borrow a `'static` query string.
+static FORECASTS_IN_RANGE_QUERY: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "SELECT {FORECAST_COLS}
+         FROM forecasts
+         WHERE checkpoint_id = $1
+           AND fetched_at BETWEEN $2 AND $3
+         ORDER BY fetched_at ASC, forecast_time ASC
+         LIMIT {MAX_FORECAST_EXPORT_ROWS}"
+    )
+});
+
+/// Stream every forecast row for a checkpoint fetched between `from` and `to`
+/// (inclusive), oldest fetch first, capped at [`MAX_FORECAST_EXPORT_ROWS`].
+pub(crate) fn get_forecasts_in_range(
+    pool: &PgPool,
+    checkpoint_id: Uuid,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> impl Stream<Item = Result<Forecast, sqlx::Error>> + '_ {
+    sqlx::query_as::<_, Forecast>(FORECASTS_IN_RANGE_QUERY.as_str())
+        .bind(checkpoint_id)
+        .bind(from)
+        .bind(to)
+        .fetch(pool)
+}
+
 /// Get forecast history for a checkpoint at a specific forecast time.
 ///
 /// Returns one entry per yr.no model run (deduplicated server-side),
 /// ordered by model run time ascending. When `yr_model_run_at` is NULL
 /// (pre-poller legacy rows), `fetched_at` is used as the fallback via
 /// `COALESCE`. For each model run, only the latest `fetched_at` is kept.
+/// `since`/`until` (inclusive) restrict `fetched_at` when given.
+/// `min_resolution` = `Hourly` keeps only rows with `data_resolution = 'hourly'`
+/// (legacy rows without a resolution are dropped).
 pub(crate) async fn get_forecast_history(
     pool: &PgPool,
     checkpoint_id: Uuid,
     forecast_time: DateTime<Utc>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    min_resolution: ForecastResolution,
 ) -> Result<Vec<Forecast>, sqlx::Error> {
     let query = format!(
         "SELECT DISTINCT ON (COALESCE(yr_model_run_at, fetched_at))
@@ -410,6 +1003,9 @@ pub(crate) async fn get_forecast_history(
                ORDER BY ABS(EXTRACT(EPOCH FROM (forecast_time - $2)))
                LIMIT 1
            )
+           AND ($3::timestamptz IS NULL OR fetched_at >= $3)
+           AND ($4::timestamptz IS NULL OR fetched_at <= $4)
+           AND (NOT $5 OR data_resolution = 'hourly')
          ORDER BY COALESCE(yr_model_run_at, fetched_at) ASC, fetched_at DESC
          LIMIT {limit}",
         h = FORECAST_TIME_TOLERANCE_HOURS,
@@ -418,10 +1014,70 @@ pub(crate) async fn get_forecast_history(
     sqlx::query_as::<_, Forecast>(&query)
         .bind(checkpoint_id)
         .bind(forecast_time)
+        .bind(since)
+        .bind(until)
+        .bind(min_resolution == ForecastResolution::Hourly)
         .fetch_all(pool)
         .await
 }
 
+/// Maximum forecast rows aggregated by the wind rose endpoint (most recent
+/// fetches first).
+pub(crate) const MAX_WIND_ROSE_FORECASTS: i32 = 20_000;
+
+/// Forecasts fetched for a checkpoint within the last `days` days, newest
+/// fetch first (capped at [`MAX_WIND_ROSE_FORECASTS`]).
+pub(crate) async fn get_recent_forecasts_for_checkpoint(
+    pool: &PgPool,
+    checkpoint_id: Uuid,
+    days: u32,
+) -> Result<Vec<Forecast>, sqlx::Error> {
+    sqlx::query_as::<_, Forecast>(&format!(
+        "SELECT {FORECAST_COLS}
+         FROM forecasts
+         WHERE checkpoint_id = $1
+           AND fetched_at > NOW() - make_interval(days => $2)
+         ORDER BY fetched_at DESC
+         LIMIT {MAX_WIND_ROSE_FORECASTS}"
+    ))
+    .bind(checkpoint_id)
+    .bind(days as i32)
+    .fetch_all(pool)
+    .await
+}
+
+static COUNT_MODEL_RUNS_QUERY: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "SELECT COUNT(DISTINCT yr_model_run_at)
+         FROM forecasts
+         WHERE checkpoint_id = $1
+           AND forecast_time = (
+               SELECT forecast_time FROM forecasts
+               WHERE checkpoint_id = $1
+                 AND forecast_time BETWEEN $2 - INTERVAL '{h} hours' AND $2 + INTERVAL '{h} hours'
+               ORDER BY ABS(EXTRACT(EPOCH FROM (forecast_time - $2)))
+               LIMIT 1
+           )",
+        h = FORECAST_TIME_TOLERANCE_HOURS,
+    )
+});
+
+/// Distinct yr.no model runs stored for a checkpoint at the yr.no time slot
+/// nearest `forecast_time` (same slot resolution as
+/// [`get_forecast_history`]), as a count: the history endpoint only reports
+/// how many there are. Legacy rows without a model run are not counted.
+pub(crate) async fn get_distinct_model_runs_for_checkpoint(
+    pool: &PgPool,
+    checkpoint_id: Uuid,
+    forecast_time: DateTime<Utc>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(COUNT_MODEL_RUNS_QUERY.as_str())
+        .bind(checkpoint_id)
+        .bind(forecast_time)
+        .fetch_one(pool)
+        .await
+}
+
 /// Insert a single forecast record, deduplicating by
 /// `(checkpoint_id, forecast_time, yr_model_run_at)`.
 ///
@@ -445,7 +1101,8 @@ pub(crate) async fn insert_forecast(
                 gen_random_uuid(), $1, $2, $3, $4,
                 $5, $6, $7, $8, $9, $10, $11, $12,
                 $13, $14, $15, $16, $17, $18, $19, $20,
-                $21, $22, $23, $24
+                $21, $22, $23, $24, $25, $26, $27, $28,
+                $29, $30, $31, $32
              )
              ON CONFLICT (checkpoint_id, forecast_time, yr_model_run_at)
                 WHERE yr_model_run_at IS NOT NULL
@@ -459,7 +1116,8 @@ pub(crate) async fn insert_forecast(
                 gen_random_uuid(), $1, $2, $3, $4,
                 $5, $6, $7, $8, $9, $10, $11, $12,
                 $13, $14, $15, $16, $17, $18, $19, $20,
-                $21, $22, $23, $24
+                $21, $22, $23, $24, $25, $26, $27, $28,
+                $29, $30, $31, $32
              )
              ON CONFLICT (checkpoint_id, forecast_time)
                 WHERE yr_model_run_at IS NULL
@@ -492,6 +1150,14 @@ pub(crate) async fn insert_forecast(
         .bind(p.feels_like_c)
         .bind(&p.precipitation_type)
         .bind(p.snow_temperature_c)
+        .bind(&p.precipitation_intensity)
+        .bind(p.cloud_cover_low_pct)
+        .bind(p.cloud_cover_medium_pct)
+        .bind(p.cloud_cover_high_pct)
+        .bind(p.snow_depth_cm)
+        .bind(&p.blowing_snow_risk)
+        .bind(&p.data_resolution)
+        .bind(p.thunder_probability_pct)
         .bind(p.yr_model_run_at)
         .fetch_optional(pool)
         .await
@@ -502,58 +1168,243 @@ pub(crate) async fn get_checkpoint(
     pool: &PgPool,
     checkpoint_id: Uuid,
 ) -> Result<Option<Checkpoint>, sqlx::Error> {
-    sqlx::query_as::<_, Checkpoint>(
-        "SELECT id, race_id, name, distance_km, latitude, longitude, elevation_m, sort_order
-         FROM checkpoints WHERE id = $1",
-    )
+    sqlx::query_as::<_, Checkpoint>(&format!(
+        "SELECT {CHECKPOINT_COLS}
+         FROM checkpoints WHERE id = $1"
+    ))
     .bind(checkpoint_id)
     .fetch_optional(pool)
     .await
 }
 
+/// Set a checkpoint's poll priority. Returns false if the checkpoint does not
+/// exist or belongs to another race.
+pub(crate) async fn update_checkpoint_poll_priority(
+    pool: &PgPool,
+    race_id: Uuid,
+    checkpoint_id: Uuid,
+    poll_priority: i32,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE checkpoints SET poll_priority = $3, updated_at = NOW()
+         WHERE id = $2 AND race_id = $1",
+    )
+    .bind(race_id)
+    .bind(checkpoint_id)
+    .bind(poll_priority)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Parameters for recording an actual (observed) condition.
+#[derive(Debug)]
+pub(crate) struct InsertActualConditionParams {
+    pub checkpoint_id: Uuid,
+    pub observed_at: DateTime<Utc>,
+    pub temperature_c: Decimal,
+    pub wind_speed_ms: Decimal,
+    pub precipitation_mm: Option<Decimal>,
+    pub precipitation_type: Option<String>,
+}
+
+/// Record a batch of actual conditions for a race in a single transaction.
+/// Returns the number of rows inserted.
+pub(crate) async fn insert_actual_conditions(
+    pool: &PgPool,
+    race_id: Uuid,
+    conditions: &[InsertActualConditionParams],
+) -> Result<usize, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for c in conditions {
+        sqlx::query(
+            "INSERT INTO actual_conditions
+                 (race_id, checkpoint_id, observed_at, temperature_c, wind_speed_ms,
+                  precipitation_mm, precipitation_type)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(race_id)
+        .bind(c.checkpoint_id)
+        .bind(c.observed_at)
+        .bind(c.temperature_c)
+        .bind(c.wind_speed_ms)
+        .bind(c.precipitation_mm)
+        .bind(&c.precipitation_type)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(conditions.len())
+}
+
+/// Get all recorded actual conditions for a race, ordered by checkpoint and time.
+///
+/// Includes observations submitted per checkpoint (`checkpoint_observations`)
+/// for the race's checkpoints.
+pub(crate) async fn get_actual_conditions(
+    pool: &PgPool,
+    race_id: Uuid,
+) -> Result<Vec<ActualCondition>, sqlx::Error> {
+    sqlx::query_as::<_, ActualCondition>(
+        "SELECT checkpoint_id, observed_at, temperature_c, wind_speed_ms
+         FROM actual_conditions
+         WHERE race_id = $1
+         UNION ALL
+         SELECT o.checkpoint_id, o.observed_at, o.temperature_c, o.wind_speed_ms
+         FROM checkpoint_observations o
+         JOIN checkpoints c ON c.id = o.checkpoint_id
+         WHERE c.race_id = $1
+         ORDER BY checkpoint_id, observed_at",
+    )
+    .bind(race_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Columns selected for [`CheckpointObservation`].
+const OBSERVATION_COLS: &str = "\
+    id, checkpoint_id, observed_at, temperature_c, wind_speed_ms, precipitation_mm, \
+    precipitation_type, notes, created_at";
+
+/// Parameters for storing a checkpoint observation.
+pub(crate) struct InsertObservationParams {
+    pub checkpoint_id: Uuid,
+    pub observed_at: DateTime<Utc>,
+    pub temperature_c: Decimal,
+    pub wind_speed_ms: Decimal,
+    pub precipitation_mm: Decimal,
+    pub precipitation_type: String,
+    pub notes: Option<String>,
+}
+
+/// Store a checkpoint observation and return the created row.
+pub(crate) async fn insert_checkpoint_observation(
+    pool: &PgPool,
+    params: &InsertObservationParams,
+) -> Result<CheckpointObservation, sqlx::Error> {
+    let query = format!(
+        "INSERT INTO checkpoint_observations
+             (checkpoint_id, observed_at, temperature_c, wind_speed_ms, precipitation_mm,
+              precipitation_type, notes)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING {OBSERVATION_COLS}"
+    );
+    sqlx::query_as::<_, CheckpointObservation>(&query)
+        .bind(params.checkpoint_id)
+        .bind(params.observed_at)
+        .bind(params.temperature_c)
+        .bind(params.wind_speed_ms)
+        .bind(params.precipitation_mm)
+        .bind(&params.precipitation_type)
+        .bind(&params.notes)
+        .fetch_one(pool)
+        .await
+}
+
+/// List a checkpoint's observations, oldest first, optionally only those
+/// observed at or after `from`.
+pub(crate) async fn get_checkpoint_observations(
+    pool: &PgPool,
+    checkpoint_id: Uuid,
+    from: Option<DateTime<Utc>>,
+) -> Result<Vec<CheckpointObservation>, sqlx::Error> {
+    let query = format!(
+        "SELECT {OBSERVATION_COLS}
+         FROM checkpoint_observations
+         WHERE checkpoint_id = $1
+           AND ($2::timestamptz IS NULL OR observed_at >= $2)
+         ORDER BY observed_at"
+    );
+    sqlx::query_as::<_, CheckpointObservation>(&query)
+        .bind(checkpoint_id)
+        .bind(from)
+        .fetch_all(pool)
+        .await
+}
+
 /// Upsert a race and its checkpoints from parsed GPX data.
 ///
 /// Uses INSERT ON CONFLICT (name, year) for the race, and
 /// INSERT ON CONFLICT (race_id, sort_order) for each checkpoint.
 /// Deletes orphan checkpoints that no longer exist in the GPX.
 /// All operations run within a single transaction.
-/// Returns the race UUID (existing or newly created).
+/// Returns the race UUID (existing or newly created) and `is_new`, which is
+/// true when the race was inserted rather than updated.
+///
+/// With an `elevation_client`, checkpoints at 0 m (missing `<ele>`) get
+/// their terrain elevation looked up before the transaction starts.
 pub(crate) async fn upsert_race_from_gpx(
     pool: &PgPool,
     race: &GpxRace,
-) -> Result<Uuid, sqlx::Error> {
+    elevation_client: Option<&ElevationClient>,
+) -> Result<(Uuid, bool), sqlx::Error> {
     let distance_km = f64_to_decimal_full(race.distance_km);
     let start_time_utc: chrono::DateTime<chrono::Utc> = race.start_time.into();
 
+    // Pre-parse track points so the course endpoint doesn't re-parse the XML.
+    // A parse failure is not fatal — the course endpoint falls back to course_gpx.
+    let course_points = match extract_track_points(&race.gpx_xml) {
+        Ok(points) => Some(sqlx::types::Json(points)),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to extract track points for race '{}' ({}): {}",
+                race.name,
+                race.year,
+                e
+            );
+            None
+        }
+    };
+
+    let elevations = match elevation_client {
+        Some(client) => client.checkpoint_elevations(pool, race).await,
+        None => race.checkpoints.iter().map(|cp| cp.elevation_m).collect(),
+    };
+
     let mut tx = pool.begin().await?;
 
     // Upsert the race
-    let row: (Uuid,) = sqlx::query_as(
-        "INSERT INTO races (id, name, year, start_time, distance_km, course_gpx)
-         VALUES (gen_random_uuid(), $1, $2, $3, $4, $5)
+    // xmax = 0 only for a freshly inserted row version (not an update)
+    let (race_id, is_new): (Uuid, bool) = sqlx::query_as(
+        "INSERT INTO races (id, name, year, start_time, distance_km, course_gpx, course_points,
+                            computed_track_length_km, country, website, discipline,
+                            category, elevation_gain_m)
+         VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
          ON CONFLICT (name, year) DO UPDATE SET
              start_time = EXCLUDED.start_time,
              distance_km = EXCLUDED.distance_km,
              course_gpx = EXCLUDED.course_gpx,
+             course_points = EXCLUDED.course_points,
+             computed_track_length_km = EXCLUDED.computed_track_length_km,
+             country = EXCLUDED.country,
+             website = EXCLUDED.website,
+             discipline = EXCLUDED.discipline,
+             category = EXCLUDED.category,
+             elevation_gain_m = EXCLUDED.elevation_gain_m,
              updated_at = NOW()
-         RETURNING id",
+         RETURNING id, (xmax = 0) AS is_new",
     )
     .bind(&race.name)
     .bind(race.year)
     .bind(start_time_utc)
     .bind(distance_km)
     .bind(&race.gpx_xml)
+    .bind(course_points)
+    .bind(race.computed_track_length_km.map(f64_to_decimal_full))
+    .bind(&race.country)
+    .bind(&race.website)
+    .bind(&race.discipline)
+    .bind(&race.category)
+    .bind(race.elevation_gain_m.map(f64_to_decimal_full))
     .fetch_one(&mut *tx)
     .await?;
 
-    let race_id = row.0;
-
     // Upsert each checkpoint
-    for (i, cp) in race.checkpoints.iter().enumerate() {
+    for (i, (cp, elevation_m)) in race.checkpoints.iter().zip(elevations).enumerate() {
         let cp_distance = f64_to_decimal_full(cp.distance_km);
         let cp_lat = f64_to_decimal_full(cp.latitude);
         let cp_lon = f64_to_decimal_full(cp.longitude);
-        let cp_ele = f64_to_decimal_full(cp.elevation_m);
+        let cp_ele = f64_to_decimal_full(elevation_m);
         let sort_order = i as i32;
 
         sqlx::query(
@@ -589,13 +1440,339 @@ pub(crate) async fn upsert_race_from_gpx(
 
     tx.commit().await?;
 
-    Ok(race_id)
+    Ok((race_id, is_new))
+}
+
+/// A stored checkpoint that the GPX file would overwrite at the same position.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct CheckpointChange {
+    pub(crate) sort_order: i32,
+    /// Checkpoint name in the GPX file
+    pub(crate) name: String,
+    /// Columns whose stored value differs from the GPX file
+    pub(crate) changed_fields: Vec<&'static str>,
+}
+
+/// What `upsert_race_from_gpx` would change for one GPX race (seed dry run).
+///
+/// Races are matched on `(name, year)` and checkpoints on `sort_order`,
+/// exactly as the upsert does.
+#[derive(Debug, Serialize)]
+pub(crate) struct RaceDiff {
+    pub(crate) name: String,
+    pub(crate) year: i32,
+    /// No race with this name and year exists yet; it would be inserted.
+    pub(crate) new_race: bool,
+    pub(crate) start_time_changed: bool,
+    pub(crate) distance_km_changed: bool,
+    /// Other race columns whose stored value differs from the GPX file
+    pub(crate) changed_fields: Vec<&'static str>,
+    pub(crate) checkpoints_added: Vec<String>,
+    pub(crate) checkpoints_removed: Vec<String>,
+    pub(crate) checkpoints_changed: Vec<CheckpointChange>,
+}
+
+impl RaceDiff {
+    /// Whether seeding this race would write anything.
+    pub(crate) fn has_changes(&self) -> bool {
+        self.new_race
+            || self.start_time_changed
+            || self.distance_km_changed
+            || !self.changed_fields.is_empty()
+            || !self.checkpoints_added.is_empty()
+            || !self.checkpoints_removed.is_empty()
+            || !self.checkpoints_changed.is_empty()
+    }
+}
+
+/// A stored race with the course columns `RACE_COLS` leaves out, for the
+/// seed dry run.
+#[derive(Debug, sqlx::FromRow)]
+struct StoredRace {
+    #[sqlx(flatten)]
+    race: Race,
+    course_gpx: String,
+    course_points: Option<sqlx::types::Json<serde_json::Value>>,
+}
+
+/// Compare a parsed GPX race against the stored race without writing.
+pub(crate) async fn diff_race_from_gpx(
+    pool: &PgPool,
+    race: &GpxRace,
+) -> Result<RaceDiff, sqlx::Error> {
+    let existing = sqlx::query_as::<_, StoredRace>(&format!(
+        "SELECT {RACE_COLS}, course_gpx, course_points FROM races WHERE name = $1 AND year = $2"
+    ))
+    .bind(&race.name)
+    .bind(race.year)
+    .fetch_optional(pool)
+    .await?;
+
+    let checkpoints = match &existing {
+        Some(r) => get_checkpoints(pool, r.race.id).await?,
+        None => Vec::new(),
+    };
+
+    Ok(compute_race_diff(existing.as_ref(), &checkpoints, race))
+}
+
+/// Race columns other than `start_time` and `distance_km` that the upsert
+/// would change.
+fn changed_race_fields(existing: &StoredRace, race: &GpxRace) -> Vec<&'static str> {
+    // The upsert stores NULL when the track can't be extracted
+    let course_points = extract_track_points(&race.gpx_xml)
+        .ok()
+        .and_then(|points| serde_json::to_value(points).ok());
+    let stored = &existing.race;
+    [
+        ("course_gpx", existing.course_gpx != race.gpx_xml),
+        (
+            "course_points",
+            existing.course_points.as_ref().map(|p| &p.0) != course_points.as_ref(),
+        ),
+        (
+            "computed_track_length_km",
+            stored.computed_track_length_km
+                != race.computed_track_length_km.map(f64_to_decimal_full),
+        ),
+        ("country", stored.country != race.country),
+        ("website", stored.website != race.website),
+        ("discipline", stored.discipline != race.discipline),
+        ("category", stored.category != race.category),
+        (
+            "elevation_gain_m",
+            stored.elevation_gain_m != race.elevation_gain_m.map(f64_to_decimal_full),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(field, changed)| changed.then_some(field))
+    .collect()
+}
+
+/// Pure diff between stored state and a GPX race. Values are compared after
+/// the same decimal conversion the upsert applies.
+fn compute_race_diff(
+    existing: Option<&StoredRace>,
+    stored: &[Checkpoint],
+    race: &GpxRace,
+) -> RaceDiff {
+    let start_time_utc: DateTime<Utc> = race.start_time.into();
+
+    let checkpoints_changed = race
+        .checkpoints
+        .iter()
+        .zip(stored)
+        .filter_map(|(new, old)| {
+            let mut changed_fields = Vec::new();
+            if new.name != old.name {
+                changed_fields.push("name");
+            }
+            if f64_to_decimal_full(new.distance_km) != old.distance_km {
+                changed_fields.push("distance_km");
+            }
+            if f64_to_decimal_full(new.latitude) != old.latitude {
+                changed_fields.push("latitude");
+            }
+            if f64_to_decimal_full(new.longitude) != old.longitude {
+                changed_fields.push("longitude");
+            }
+            // Seeding replaces a 0 m elevation with a looked-up one, so only
+            // an elevation set in the file can be compared
+            if new.elevation_m != 0.0 && f64_to_decimal_full(new.elevation_m) != old.elevation_m {
+                changed_fields.push("elevation_m");
+            }
+            (!changed_fields.is_empty()).then(|| CheckpointChange {
+                sort_order: old.sort_order,
+                name: new.name.clone(),
+                changed_fields,
+            })
+        })
+        .collect();
+
+    RaceDiff {
+        name: race.name.clone(),
+        year: race.year,
+        new_race: existing.is_none(),
+        start_time_changed: existing.is_some_and(|r| r.race.start_time != start_time_utc),
+        distance_km_changed: existing
+            .is_some_and(|r| r.race.distance_km != f64_to_decimal_full(race.distance_km)),
+        changed_fields: existing
+            .map(|r| changed_race_fields(r, race))
+            .unwrap_or_default(),
+        checkpoints_added: race
+            .checkpoints
+            .iter()
+            .skip(stored.len())
+            .map(|c| c.name.clone())
+            .collect(),
+        checkpoints_removed: stored
+            .iter()
+            .skip(race.checkpoints.len())
+            .map(|c| c.name.clone())
+            .collect(),
+        checkpoints_changed,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Integrity checks (admin)
+// ---------------------------------------------------------------------------
+
+/// Checkpoints whose `race_id` points to a missing race.
+pub(crate) async fn find_orphan_checkpoints(pool: &PgPool) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT c.id FROM checkpoints c
+         LEFT JOIN races r ON r.id = c.race_id
+         WHERE r.id IS NULL
+         ORDER BY c.id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// `yr_responses` rows whose `checkpoint_id` points to a missing checkpoint.
+pub(crate) async fn find_orphan_yr_responses(pool: &PgPool) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT y.id FROM yr_responses y
+         LEFT JOIN checkpoints c ON c.id = y.checkpoint_id
+         WHERE c.id IS NULL
+         ORDER BY y.id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Missing checkpoint IDs still referenced by `forecasts` rows.
+pub(crate) async fn find_orphan_forecast_checkpoints(
+    pool: &PgPool,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT DISTINCT f.checkpoint_id FROM forecasts f
+         LEFT JOIN checkpoints c ON c.id = f.checkpoint_id
+         WHERE c.id IS NULL
+         ORDER BY f.checkpoint_id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Races whose checkpoint `sort_order` values are not exactly `0..n`.
+/// `UNIQUE (race_id, sort_order)` rules out duplicates, so a wrong minimum
+/// or maximum means a gap.
+pub(crate) async fn find_races_with_sort_order_gaps(
+    pool: &PgPool,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT race_id FROM checkpoints
+         GROUP BY race_id
+         HAVING MIN(sort_order) <> 0 OR MAX(sort_order) <> COUNT(*) - 1
+         ORDER BY race_id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Races without any checkpoints.
+pub(crate) async fn find_races_without_checkpoints(
+    pool: &PgPool,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT r.id FROM races r
+         WHERE NOT EXISTS (SELECT 1 FROM checkpoints c WHERE c.race_id = r.id)
+         ORDER BY r.id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+// ---------------------------------------------------------------------------
+// Maintenance
+// ---------------------------------------------------------------------------
+
+/// Statements run by [`run_db_maintenance`], in order.
+pub const DB_MAINTENANCE_STATEMENTS: [&str; 3] = [
+    "VACUUM ANALYZE forecasts",
+    "ANALYZE checkpoints",
+    "ANALYZE yr_responses",
+];
+
+/// Vacuum and analyze the tables the poller writes to. Runs on a dedicated
+/// connection outside any transaction, since VACUUM cannot run inside one.
+pub async fn run_db_maintenance(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    for statement in DB_MAINTENANCE_STATEMENTS {
+        sqlx::query(statement).execute(&mut *conn).await?;
+    }
+    Ok(())
+}
+
+/// Forecasts of checkpoints with recorded actual conditions are kept: the
+/// forecast accuracy comparison needs them for as long as the race is kept.
+const DELETE_OLD_FORECASTS: &str = "DELETE FROM forecasts f
+     WHERE f.fetched_at < NOW() - INTERVAL '1 day' * $1
+       AND NOT EXISTS (
+           SELECT 1 FROM actual_conditions a WHERE a.checkpoint_id = f.checkpoint_id
+       )
+       AND NOT EXISTS (
+           SELECT 1 FROM checkpoint_observations o WHERE o.checkpoint_id = f.checkpoint_id
+       )";
+
+/// Delete forecast rows fetched more than `older_than_days` days ago, except
+/// for checkpoints with recorded actual conditions or observations (both feed
+/// [`get_actual_conditions`]).
+/// Returns the number of rows deleted.
+pub(crate) async fn delete_old_forecasts(
+    pool: &PgPool,
+    older_than_days: i32,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(DELETE_OLD_FORECASTS)
+        .bind(older_than_days)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
 }
 
 // ---------------------------------------------------------------------------
 // Poller queries
 // ---------------------------------------------------------------------------
 
+/// Advisory lock key for the poller: with several API instances behind a
+/// load balancer, only the instance holding it polls yr.no.
+const POLL_LOCK_KEY: i64 = 1_234_567_890;
+
+/// Try to take the poller advisory lock. Advisory locks belong to a session,
+/// so the lock lives on the returned connection until
+/// [`release_poll_lock`]. The connection is opened with the pool's options
+/// but outside the pool, so a long poll cycle doesn't hold back API requests.
+/// Returns None if another instance holds the lock.
+pub(crate) async fn try_acquire_poll_lock(
+    pool: &PgPool,
+) -> Result<Option<PgConnection>, sqlx::Error> {
+    let mut conn = PgConnection::connect_with(&pool.connect_options()).await?;
+    let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+        .bind(POLL_LOCK_KEY)
+        .fetch_one(&mut conn)
+        .await?;
+    if acquired {
+        return Ok(Some(conn));
+    }
+    if let Err(e) = conn.close().await {
+        tracing::debug!("Poller: failed to close lock connection: {}", e);
+    }
+    Ok(None)
+}
+
+/// Release the poller advisory lock held by `conn` and close it. If the
+/// unlock fails the connection is dropped, which ends the session and also
+/// releases the lock.
+pub(crate) async fn release_poll_lock(mut conn: PgConnection) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(POLL_LOCK_KEY)
+        .execute(&mut conn)
+        .await?;
+    conn.close().await
+}
+
 /// A race with all its checkpoints, used by the background poller.
 pub(crate) struct RaceWithCheckpoints {
     pub(crate) race: Race,
@@ -609,13 +1786,13 @@ pub(crate) async fn get_upcoming_races_with_checkpoints(
     pool: &PgPool,
     lookahead_days: i64,
 ) -> Result<Vec<RaceWithCheckpoints>, sqlx::Error> {
-    let races = sqlx::query_as::<_, Race>(
-        "SELECT id, name, year, start_time, distance_km
+    let races = sqlx::query_as::<_, Race>(&format!(
+        "SELECT {RACE_COLS}
          FROM races
          WHERE start_time BETWEEN NOW() - INTERVAL '1 day'
            AND NOW() + $1 * INTERVAL '1 day'
-         ORDER BY start_time",
-    )
+         ORDER BY start_time"
+    ))
     .bind(lookahead_days as f64)
     .fetch_all(pool)
     .await?;
@@ -645,6 +1822,26 @@ pub(crate) async fn get_earliest_expiry(
     Ok(row.and_then(|r| r.0))
 }
 
+/// `(checkpoint_id, fetched_at, expires_at)` of the cached yr.no responses
+/// of the given checkpoints (latest per checkpoint).
+pub(crate) async fn get_yr_cache_times(
+    pool: &PgPool,
+    checkpoint_ids: &[Uuid],
+) -> Result<Vec<(Uuid, DateTime<Utc>, DateTime<Utc>)>, sqlx::Error> {
+    if checkpoint_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    sqlx::query_as(
+        "SELECT checkpoint_id, MAX(fetched_at), MAX(expires_at)
+         FROM yr_responses
+         WHERE checkpoint_id = ANY($1)
+         GROUP BY checkpoint_id",
+    )
+    .bind(checkpoint_ids)
+    .fetch_all(pool)
+    .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -656,4 +1853,190 @@ mod tests {
     fn test_forecast_time_tolerance_hours_is_positive() {
         assert!(FORECAST_TIME_TOLERANCE_HOURS > 0);
     }
+
+    #[test]
+    fn test_snow_temperature_in_insert_and_select_columns() {
+        for cols in [FORECAST_COLS, FORECAST_COLS_F, FORECAST_INSERT_COLS] {
+            assert!(cols.contains("snow_temperature_c"));
+        }
+    }
+
+    fn forecast_row(snow_temperature_c: Option<Decimal>) -> ForecastWithIdx {
+        ForecastWithIdx {
+            idx: 1,
+            id: Some(Uuid::new_v4()),
+            checkpoint_id: Some(Uuid::new_v4()),
+            forecast_time: Some(Utc::now()),
+            fetched_at: Some(Utc::now()),
+            source: Some("yr.no".to_string()),
+            temperature_c: Some(Decimal::new(-50, 1)),
+            temperature_percentile_10_c: None,
+            temperature_percentile_90_c: None,
+            wind_speed_ms: Some(Decimal::new(32, 1)),
+            wind_speed_percentile_10_ms: None,
+            wind_speed_percentile_90_ms: None,
+            wind_direction_deg: Some(Decimal::new(180, 0)),
+            wind_gust_ms: None,
+            precipitation_mm: Some(Decimal::ZERO),
+            precipitation_min_mm: None,
+            precipitation_max_mm: None,
+            humidity_pct: Some(Decimal::new(75, 0)),
+            dew_point_c: Some(Decimal::new(-85, 1)),
+            cloud_cover_pct: Some(Decimal::new(50, 0)),
+            uv_index: None,
+            symbol_code: Some("cloudy".to_string()),
+            feels_like_c: Some(Decimal::new(-90, 1)),
+            precipitation_type: Some("none".to_string()),
+            snow_temperature_c,
+            precipitation_intensity: Some("none".to_string()),
+            cloud_cover_low_pct: None,
+            cloud_cover_medium_pct: None,
+            cloud_cover_high_pct: None,
+            snow_depth_cm: None,
+            blowing_snow_risk: None,
+            data_resolution: None,
+            thunder_probability_pct: None,
+            yr_model_run_at: None,
+            created_at: Some(Utc::now()),
+        }
+    }
+
+    #[test]
+    fn test_into_forecast_preserves_snow_temperature() {
+        let f = forecast_row(Some(Decimal::new(-94, 1)))
+            .into_forecast()
+            .unwrap();
+        assert_eq!(f.snow_temperature_c, Some(Decimal::new(-94, 1)));
+
+        // Legacy rows without the column value stay NULL rather than failing
+        let legacy = forecast_row(None).into_forecast().unwrap();
+        assert_eq!(legacy.snow_temperature_c, None);
+    }
+
+    /// The Vasaloppet fixture as it would be stored by `upsert_race_from_gpx`.
+    fn stored_vasaloppet() -> (GpxRace, StoredRace, Vec<Checkpoint>) {
+        let gpx = crate::services::gpx::parse_gpx(
+            include_str!("../../../data/vasaloppet-2026.gpx"),
+            Default::default(),
+        )
+        .unwrap();
+        let race_id = Uuid::new_v4();
+        let race = Race {
+            id: race_id,
+            name: gpx.name.clone(),
+            year: gpx.year,
+            start_time: gpx.start_time.into(),
+            distance_km: f64_to_decimal_full(gpx.distance_km),
+            computed_track_length_km: gpx.computed_track_length_km.map(f64_to_decimal_full),
+            country: gpx.country.clone(),
+            website: gpx.website.clone(),
+            discipline: gpx.discipline.clone(),
+            category: gpx.category.clone(),
+            elevation_gain_m: gpx.elevation_gain_m.map(f64_to_decimal_full),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            checkpoint_count: gpx.checkpoints.len() as i64,
+            has_start_checkpoint: true,
+            has_finish_checkpoint: true,
+        };
+        let race = StoredRace {
+            race,
+            course_gpx: gpx.gpx_xml.clone(),
+            course_points: Some(sqlx::types::Json(
+                serde_json::to_value(extract_track_points(&gpx.gpx_xml).unwrap()).unwrap(),
+            )),
+        };
+        let checkpoints = gpx
+            .checkpoints
+            .iter()
+            .enumerate()
+            .map(|(i, c)| Checkpoint {
+                race_id,
+                distance_km: f64_to_decimal_full(c.distance_km),
+                latitude: f64_to_decimal_full(c.latitude),
+                longitude: f64_to_decimal_full(c.longitude),
+                elevation_m: f64_to_decimal_full(c.elevation_m),
+                sort_order: i as i32,
+                ..Checkpoint::fixture(&c.name)
+            })
+            .collect();
+        (gpx, race, checkpoints)
+    }
+
+    #[test]
+    fn test_race_diff_unchanged_and_new() {
+        let (gpx, race, checkpoints) = stored_vasaloppet();
+        let diff = compute_race_diff(Some(&race), &checkpoints, &gpx);
+        assert!(!diff.has_changes(), "{:?}", diff);
+
+        let diff = compute_race_diff(None, &[], &gpx);
+        assert!(diff.new_race);
+        assert_eq!(diff.checkpoints_added.len(), gpx.checkpoints.len());
+        assert!(diff.has_changes());
+    }
+
+    #[test]
+    fn test_race_diff_detects_changes() {
+        let (mut gpx, race, checkpoints) = stored_vasaloppet();
+        gpx.start_time += chrono::Duration::minutes(30);
+        gpx.checkpoints[1].elevation_m += 5.0;
+        let removed = gpx.checkpoints.pop().unwrap();
+
+        let diff = compute_race_diff(Some(&race), &checkpoints, &gpx);
+        assert!(diff.start_time_changed);
+        assert!(!diff.distance_km_changed);
+        assert!(diff.changed_fields.is_empty());
+        assert!(diff.checkpoints_added.is_empty());
+        assert_eq!(diff.checkpoints_removed, vec![removed.name]);
+        assert_eq!(
+            diff.checkpoints_changed,
+            vec![CheckpointChange {
+                sort_order: 1,
+                name: gpx.checkpoints[1].name.clone(),
+                changed_fields: vec!["elevation_m"],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_race_diff_ignores_missing_gpx_elevation() {
+        let (mut gpx, race, mut checkpoints) = stored_vasaloppet();
+        // Stored with an enriched elevation, the file still has none
+        gpx.checkpoints[2].elevation_m = 0.0;
+        checkpoints[2].elevation_m = Decimal::from(412);
+        let diff = compute_race_diff(Some(&race), &checkpoints, &gpx);
+        assert!(!diff.has_changes(), "{:?}", diff);
+    }
+
+    #[test]
+    fn test_race_diff_detects_race_column_changes() {
+        let (mut gpx, mut race, checkpoints) = stored_vasaloppet();
+        gpx.country = Some("NO".to_string());
+        gpx.category = None;
+        gpx.elevation_gain_m = Some(1234.0);
+        let diff = compute_race_diff(Some(&race), &checkpoints, &gpx);
+        assert_eq!(
+            diff.changed_fields,
+            vec!["country", "category", "elevation_gain_m"]
+        );
+        assert!(diff.has_changes());
+
+        // A race seeded before course_points existed gets them backfilled
+        race.course_points = None;
+        let (gpx, ..) = stored_vasaloppet();
+        let diff = compute_race_diff(Some(&race), &checkpoints, &gpx);
+        assert_eq!(diff.changed_fields, vec!["course_points"]);
+
+        // Editing the track changes the stored file, its points and length
+        let (mut gpx, race, checkpoints) = stored_vasaloppet();
+        gpx.gpx_xml =
+            gpx.gpx_xml
+                .replacen(r#"<trkpt lat="61.110250""#, r#"<trkpt lat="61.110350""#, 1);
+        gpx.computed_track_length_km = Some(90.1);
+        let diff = compute_race_diff(Some(&race), &checkpoints, &gpx);
+        assert_eq!(
+            diff.changed_fields,
+            vec!["course_gpx", "course_points", "computed_track_length_km"]
+        );
+    }
 }
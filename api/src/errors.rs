@@ -3,20 +3,85 @@ use axum::response::{IntoResponse, Response};
 use serde::Serialize;
 use utoipa::ToSchema;
 
-/// Standard error response body.
+/// Legacy error response body (v1).
+///
+/// Superseded by [`ErrorResponseV2`], which keeps the same `error` field and
+/// adds `error_code`, so v1 clients keep working unchanged. Kept in the
+/// OpenAPI spec until clients have migrated to matching on `error_code`.
 #[derive(Debug, Serialize, ToSchema)]
+#[schema(deprecated)]
 pub struct ErrorResponse {
     /// Human-readable error message
     pub error: String,
 }
 
+/// Error response body returned by every endpoint.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponseV2 {
+    /// Human-readable error message (same as v1; not stable, do not parse)
+    pub error: String,
+    /// Machine-readable error code
+    pub error_code: AppErrorCode,
+}
+
+/// Machine-readable error codes, serialized in `SCREAMING_SNAKE_CASE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AppErrorCode {
+    /// 404 — no race with the given ID
+    RaceNotFound,
+    /// 404 — no checkpoint with the given ID (or not in the given race)
+    CheckpointNotFound,
+    /// 404 — no cached yr.no response for the checkpoint, or no pre-computed
+    /// race forecast summary
+    CacheNotFound,
+    /// 404 — not a known yr.no `symbol_code`
+    SymbolCodeNotFound,
+    /// 400 — a datetime parameter is not valid ISO 8601
+    InvalidDatetime,
+    /// 400 — `target_duration_hours` is out of range
+    InvalidDuration,
+    /// 400 — any other invalid parameter or request body
+    InvalidParameter,
+    /// 401 — missing or wrong `X-API-Key`, or admin endpoints disabled
+    Unauthorized,
+    /// 409 — the request conflicts with the current state
+    Conflict,
+    /// 413 — the request body exceeds the endpoint's size limit
+    PayloadTooLarge,
+    /// 422 — a JSON Patch `test` operation did not match the current value
+    PatchTestFailed,
+    /// 502 — yr.no is unavailable and nothing is cached
+    YrUnavailable,
+    /// 503 — the request exceeded its timeout
+    RequestTimeout,
+    /// 503 — every database connection is busy; retry shortly
+    DbPoolExhausted,
+    /// 500 — unexpected internal error
+    InternalError,
+    /// 500 — database error
+    DbError,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
-    #[error("Not found: {0}")]
-    NotFound(String),
+    #[error("Not found: {1}")]
+    NotFound(AppErrorCode, String),
 
-    #[error("Bad request: {0}")]
-    BadRequest(String),
+    #[error("Bad request: {1}")]
+    BadRequest(AppErrorCode, String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Request too large: {0}")]
+    RequestTooLarge(String),
+
+    #[error("Patch test failed: {0}")]
+    PatchTestFailed(String),
 
     #[error("External service error: {0}")]
     ExternalServiceError(String),
@@ -24,15 +89,44 @@ pub enum AppError {
     #[error("Internal error: {0}")]
     InternalError(String),
 
+    #[error("Request timeout")]
+    RequestTimeout,
+
+    #[error("Database connection pool exhausted")]
+    PoolExhausted,
+
     #[error("Database error: {0}")]
     DatabaseError(#[from] sqlx::Error),
 }
 
+impl AppError {
+    /// Machine-readable code for this error.
+    pub fn code(&self) -> AppErrorCode {
+        match self {
+            AppError::NotFound(code, _) | AppError::BadRequest(code, _) => *code,
+            AppError::Unauthorized(_) => AppErrorCode::Unauthorized,
+            AppError::Conflict(_) => AppErrorCode::Conflict,
+            AppError::RequestTooLarge(_) => AppErrorCode::PayloadTooLarge,
+            AppError::PatchTestFailed(_) => AppErrorCode::PatchTestFailed,
+            AppError::ExternalServiceError(_) => AppErrorCode::YrUnavailable,
+            AppError::InternalError(_) => AppErrorCode::InternalError,
+            AppError::RequestTimeout => AppErrorCode::RequestTimeout,
+            AppError::PoolExhausted => AppErrorCode::DbPoolExhausted,
+            AppError::DatabaseError(_) => AppErrorCode::DbError,
+        }
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let error_code = self.code();
         let (status, message) = match self {
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::NotFound(_, msg) => (StatusCode::NOT_FOUND, msg),
+            AppError::BadRequest(_, msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg),
+            AppError::RequestTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg),
+            AppError::PatchTestFailed(msg) => (StatusCode::UNPROCESSABLE_ENTITY, msg),
             AppError::ExternalServiceError(msg) => {
                 tracing::error!("External service error: {}", msg);
                 (
@@ -40,6 +134,14 @@ impl IntoResponse for AppError {
                     "External service unavailable".to_string(),
                 )
             }
+            AppError::RequestTimeout => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Request timeout".to_string(),
+            ),
+            AppError::PoolExhausted => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Server busy, retry shortly".to_string(),
+            ),
             AppError::InternalError(msg) => {
                 tracing::error!("Internal error: {}", msg);
                 (
@@ -56,7 +158,14 @@ impl IntoResponse for AppError {
             }
         };
 
-        (status, axum::Json(ErrorResponse { error: message })).into_response()
+        (
+            status,
+            axum::Json(ErrorResponseV2 {
+                error: message,
+                error_code,
+            }),
+        )
+            .into_response()
     }
 }
 
@@ -65,3 +174,116 @@ impl From<crate::services::gpx::GpxError> for AppError {
         AppError::InternalError(format!("GPX parsing error: {}", err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn response_parts(err: AppError) -> (StatusCode, serde_json::Value) {
+        let response = err.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_each_variant_maps_to_code_and_status() {
+        let cases = [
+            (
+                AppError::NotFound(AppErrorCode::RaceNotFound, "Race x not found".into()),
+                StatusCode::NOT_FOUND,
+                "RACE_NOT_FOUND",
+            ),
+            (
+                AppError::NotFound(AppErrorCode::CheckpointNotFound, "Checkpoint x".into()),
+                StatusCode::NOT_FOUND,
+                "CHECKPOINT_NOT_FOUND",
+            ),
+            (
+                AppError::NotFound(AppErrorCode::CacheNotFound, "No cache".into()),
+                StatusCode::NOT_FOUND,
+                "CACHE_NOT_FOUND",
+            ),
+            (
+                AppError::NotFound(AppErrorCode::SymbolCodeNotFound, "Unknown".into()),
+                StatusCode::NOT_FOUND,
+                "SYMBOL_CODE_NOT_FOUND",
+            ),
+            (
+                AppError::BadRequest(AppErrorCode::InvalidDatetime, "Invalid datetime".into()),
+                StatusCode::BAD_REQUEST,
+                "INVALID_DATETIME",
+            ),
+            (
+                AppError::BadRequest(AppErrorCode::InvalidDuration, "bad".into()),
+                StatusCode::BAD_REQUEST,
+                "INVALID_DURATION",
+            ),
+            (
+                AppError::BadRequest(AppErrorCode::InvalidParameter, "bad".into()),
+                StatusCode::BAD_REQUEST,
+                "INVALID_PARAMETER",
+            ),
+            (
+                AppError::Unauthorized("Invalid API key".into()),
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+            ),
+            (
+                AppError::Conflict("busy".into()),
+                StatusCode::CONFLICT,
+                "CONFLICT",
+            ),
+            (
+                AppError::RequestTooLarge("Request body too large".into()),
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "PAYLOAD_TOO_LARGE",
+            ),
+            (
+                AppError::PatchTestFailed("start_time did not match".into()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "PATCH_TEST_FAILED",
+            ),
+            (
+                AppError::ExternalServiceError("yr.no returned HTTP 500".into()),
+                StatusCode::BAD_GATEWAY,
+                "YR_UNAVAILABLE",
+            ),
+            (
+                AppError::RequestTimeout,
+                StatusCode::SERVICE_UNAVAILABLE,
+                "REQUEST_TIMEOUT",
+            ),
+            (
+                AppError::PoolExhausted,
+                StatusCode::SERVICE_UNAVAILABLE,
+                "DB_POOL_EXHAUSTED",
+            ),
+            (
+                AppError::InternalError("boom".into()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_ERROR",
+            ),
+            (
+                AppError::DatabaseError(sqlx::Error::RowNotFound),
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DB_ERROR",
+            ),
+        ];
+
+        for (err, status, code) in cases {
+            let (actual_status, body) = response_parts(err).await;
+            assert_eq!(actual_status, status, "{}", code);
+            assert_eq!(body["error_code"], code);
+            assert!(body["error"].is_string(), "{}: v1 `error` field kept", code);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_internal_details_not_leaked() {
+        let (_, body) = response_parts(AppError::InternalError("secret path".into())).await;
+        assert_eq!(body["error"], "Internal server error");
+    }
+}
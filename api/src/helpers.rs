@@ -7,14 +7,24 @@
 //! - `f64_to_decimal_full`: preserves full f64 precision (geo: lat, lon, elevation, distance)
 //!
 //! Both return `Decimal::ZERO` for non-finite inputs (NaN, ±Inf).
+//!
+//! Also home to small time helpers shared by routes and the poller, and
+//! [`SingleflightMap`] for deduplicating concurrent fetches.
 
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use dashmap::DashMap;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+use tokio::sync::watch;
 
 /// Convert an f64 to Decimal, rounded to 1 decimal place.
 ///
 /// Used for weather values (temperature, wind speed, etc.) where 0.1°C / 0.1 m/s
 /// precision is sufficient and consistent rounding avoids false uniqueness in dedup.
+/// Rounds half away from zero (e.g. -2.25 → -2.3).
 pub(crate) fn f64_to_decimal_1dp(v: f64) -> Decimal {
     if !v.is_finite() {
         tracing::warn!(
@@ -23,7 +33,9 @@ pub(crate) fn f64_to_decimal_1dp(v: f64) -> Decimal {
         );
         return Decimal::ZERO;
     }
-    Decimal::from_str_exact(&format!("{:.1}", v)).unwrap_or_default()
+    Decimal::from_f64(v)
+        .map(|d| d.round_dp_with_strategy(1, RoundingStrategy::MidpointAwayFromZero))
+        .unwrap_or_default()
 }
 
 /// Convert an optional f64 to Decimal (1 decimal place), returning None if input is None.
@@ -58,6 +70,99 @@ pub(crate) fn opt_dec_to_f64(d: Option<Decimal>) -> Option<f64> {
     d.and_then(|v| v.to_f64())
 }
 
+/// Interval between yr.no model runs.
+const MODEL_RUN_INTERVAL_HOURS: i64 = 6;
+
+/// Estimate when yr.no's next model run appears: `model_run_at` + 6 hours,
+/// snapped to the nearest 6-hour boundary (00, 06, 12, 18 UTC). Halfway
+/// cases round up.
+pub(crate) fn estimate_next_model_run(model_run_at: DateTime<Utc>) -> DateTime<Utc> {
+    let interval = Duration::hours(MODEL_RUN_INTERVAL_HOURS);
+    let next = model_run_at + interval;
+    // The Unix epoch is at 00 UTC, so epoch-aligned steps are the boundaries
+    next.duration_round(interval).unwrap_or(next)
+}
+
+/// Result slot of one in-flight call: `None` until the leader finishes.
+type Flight<V> = Arc<watch::Sender<Option<Result<V, String>>>>;
+
+/// Deduplicates concurrent calls per key: while a call for a key is in
+/// flight, later callers wait for its result instead of starting their own.
+pub(crate) struct SingleflightMap<K, V> {
+    inflight: DashMap<K, Flight<V>>,
+}
+
+impl<K, V> Default for SingleflightMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn default() -> Self {
+        Self {
+            inflight: DashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> std::fmt::Debug for SingleflightMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SingleflightMap")
+            .field("inflight", &self.inflight.len())
+            .finish()
+    }
+}
+
+/// Removes the leader's entry when its call completes or is cancelled, so a
+/// dropped leader wakes its waiters instead of stranding them.
+struct FlightGuard<'a, K: Eq + Hash, V> {
+    map: &'a DashMap<K, Flight<V>>,
+    key: K,
+}
+
+impl<K: Eq + Hash, V> Drop for FlightGuard<'_, K, V> {
+    fn drop(&mut self) {
+        self.map.remove(&self.key);
+    }
+}
+
+impl<K, V> SingleflightMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// Run `f` for `key`, or wait for the result of a call already in flight.
+    /// If that call is cancelled before finishing, a waiter takes over and
+    /// runs its own `f` (shared with the remaining waiters).
+    pub(crate) async fn run<F, Fut>(&self, key: K, f: F) -> Result<V, String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, String>>,
+    {
+        loop {
+            let mut rx = match self.inflight.entry(key.clone()) {
+                dashmap::Entry::Occupied(entry) => entry.get().subscribe(),
+                dashmap::Entry::Vacant(entry) => {
+                    let (tx, _) = watch::channel(None);
+                    let tx = Arc::new(tx);
+                    entry.insert(tx.clone());
+                    let _guard = FlightGuard {
+                        map: &self.inflight,
+                        key,
+                    };
+                    let result = f().await;
+                    tx.send_replace(Some(result.clone()));
+                    return result;
+                }
+            };
+            let result = rx.wait_for(Option::is_some).await.map(|r| r.clone());
+            if let Ok(result) = result {
+                return result.expect("checked by wait_for");
+            }
+            // The leader was dropped without a result: retry, possibly as
+            // the new leader
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +181,15 @@ mod tests {
         assert_eq!(d, Decimal::from_str("3.2").unwrap());
     }
 
+    #[test]
+    fn test_f64_to_decimal_1dp_negative() {
+        assert_eq!(
+            f64_to_decimal_1dp(-7.46),
+            Decimal::from_str("-7.5").unwrap()
+        );
+        assert_eq!(f64_to_decimal_1dp(-0.04), Decimal::ZERO);
+    }
+
     #[test]
     fn test_f64_to_decimal_1dp_nan() {
         assert_eq!(f64_to_decimal_1dp(f64::NAN), Decimal::ZERO);
@@ -133,4 +247,106 @@ mod tests {
         let d = Decimal::from_str("3.14").unwrap();
         assert!((opt_dec_to_f64(Some(d)).unwrap() - 3.14).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_estimate_next_model_run_on_boundary() {
+        let run = "2026-03-01T06:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            estimate_next_model_run(run),
+            "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        // 18 UTC rolls over to midnight
+        let run = "2026-03-01T18:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            estimate_next_model_run(run),
+            "2026-03-02T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_estimate_next_model_run_snaps_to_nearest_boundary() {
+        let expected = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        // 07:20 + 6h = 13:20 → 12:00
+        assert_eq!(
+            estimate_next_model_run(expected("2026-03-01T07:20:00Z")),
+            expected("2026-03-01T12:00:00Z")
+        );
+        // 10:45 + 6h = 16:45 → 18:00
+        assert_eq!(
+            estimate_next_model_run(expected("2026-03-01T10:45:00Z")),
+            expected("2026-03-01T18:00:00Z")
+        );
+        // 09:00 + 6h = 15:00, exactly halfway → 18:00
+        assert_eq!(
+            estimate_next_model_run(expected("2026-03-01T09:00:00Z")),
+            expected("2026-03-01T18:00:00Z")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_singleflight_concurrent_callers_share_one_call() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let map = SingleflightMap::<u32, u32>::default();
+        let calls = AtomicUsize::new(0);
+        let call = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            // Keep the call in flight while the other callers arrive
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            Ok(7)
+        };
+
+        let results = futures::future::join_all((0..10).map(|_| map.run(1, call))).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(results.iter().all(|r| *r == Ok(7)));
+
+        // Once finished, the next call for the key runs again
+        assert_eq!(map.run(1, call).await, Ok(7));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_singleflight_cancelled_leader_hands_over_to_waiter() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let map = Arc::new(SingleflightMap::<u32, u32>::default());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let leader = tokio::spawn({
+            let map = map.clone();
+            async move { map.run(1, std::future::pending).await }
+        });
+        tokio::task::yield_now().await;
+        let waiters: Vec<_> = (0..3)
+            .map(|_| {
+                let (map, calls) = (map.clone(), calls.clone());
+                tokio::spawn(async move {
+                    map.run(1, || async {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                        Ok(2)
+                    })
+                    .await
+                })
+            })
+            .collect();
+        tokio::task::yield_now().await;
+
+        // One waiter takes over and the others share its result
+        leader.abort();
+        for waiter in waiters {
+            assert_eq!(waiter.await.unwrap(), Ok(2));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        // The key is free again
+        assert_eq!(map.run(1, || async { Ok(3) }).await, Ok(3));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_f64_to_decimal_1dp_within_half_step(v in -100.0f64..100.0) {
+            let d = f64_to_decimal_1dp(v);
+            proptest::prop_assert!((d.to_f64().unwrap() - v).abs() <= 0.05 + 1e-9);
+            proptest::prop_assert!(d.scale() <= 1);
+        }
+    }
 }
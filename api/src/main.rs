@@ -1,24 +1,42 @@
 // Weather Bingo API v0.1
-use axum::{routing::get, Router};
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post},
+    Router,
+};
 use sqlx::postgres::PgPoolOptions;
+use std::future::IntoFuture;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 use tower_http::cors::{Any, CorsLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use utoipa::OpenApi;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
 mod config;
 mod db;
 mod errors;
 mod helpers;
+mod middleware;
 mod routes;
 mod services;
 
 use config::AppConfig;
+use middleware::api_key::{AdminApiKey, API_KEY_HEADER};
+use middleware::swagger_auth::SwaggerAuthKey;
+use middleware::timeout::RequestTimeouts;
+use routes::admin::AdminState;
 use routes::forecasts::AppState;
-use services::poller::{PollerState, SharedPollerState};
+use routes::poller::PollerStatusState;
+use routes::races::{RaceCourseState, RaceUpdateState};
+use services::elevation::ElevationClient;
+use services::extraction_cache::ExtractionCache;
+use services::forecast::PacingParams;
+use services::poller::{poller_control, PollerConfig, PollerState, SharedPollerState};
+use services::sunrise::SunriseClient;
 use services::yr::YrClient;
 
 /// Maximum number of connections in the database pool.
@@ -43,37 +61,194 @@ const DB_POOL_MIN_CONNECTIONS: u32 = 2;
         (name = "Races", description = "Race and checkpoint management"),
         (name = "Forecasts", description = "Weather forecast retrieval and history"),
         (name = "Poller", description = "Background forecast poller status"),
+        (name = "Admin", description = "Operational endpoints (require X-API-Key)"),
+        (name = "Meta", description = "Static reference data (yr.no symbol codes)"),
     ),
+    modifiers(&SecurityAddon),
     paths(
         routes::health::health_check,
+        routes::schema::get_schema,
         routes::races::list_races,
+        routes::races::update_race,
         routes::races::get_race_course,
         routes::races::get_checkpoints,
+        routes::races::update_checkpoint,
+        routes::races::get_race_bounds,
+        routes::races::get_race_stats,
+        routes::checkpoints::get_nearby_resorts,
+        routes::checkpoints::create_observation,
+        routes::checkpoints::list_observations,
+        routes::accuracy::record_actual_conditions,
+        routes::accuracy::get_forecast_accuracy,
         routes::forecasts::get_checkpoint_forecast,
         routes::forecasts::get_checkpoint_forecast_history,
+        routes::forecasts::get_checkpoint_wind_rose,
+        routes::forecasts::export_checkpoint_forecasts,
+        routes::forecasts::get_checkpoint_forecast_multi,
         routes::forecasts::get_race_forecast,
+        routes::forecasts::get_race_timeline,
+        routes::forecasts::get_race_uv_exposure,
+        routes::forecasts::get_race_vs_historical,
+        routes::forecasts::get_race_precipitation_forecast,
+        routes::summaries::get_cached_race_summary,
         routes::poller::get_poller_status,
+        routes::admin::trigger_poll,
+        routes::admin::pause_poller,
+        routes::admin::resume_poller,
+        routes::admin::integrity_check,
+        routes::admin::get_raw_yr_cache,
+        routes::admin::get_popular_checkpoints,
+        routes::admin::purge_old_forecasts,
+        routes::meta::list_symbol_codes,
+        routes::meta::get_symbol_code,
+        routes::admin::validate_gpx_upload,
     ),
     components(
         schemas(
             routes::health::HealthResponse,
+            routes::schema::SchemaFormat,
             routes::races::RaceListItem,
+            routes::races::UpdateRaceRequest,
             services::gpx::CoursePoint,
             routes::races::CheckpointResponse,
+            routes::races::CheckpointSort,
+            routes::races::UpdateCheckpointRequest,
+            routes::races::RaceBounds,
+            routes::races::RaceStatsResponse,
+            services::gpx::ElevationStats,
+            services::pacing::PacingModel,
+            services::resort::NearbyResort,
+            routes::checkpoints::CreateObservationRequest,
+            routes::checkpoints::ObservationResponse,
+            routes::accuracy::ActualConditionInput,
+            routes::accuracy::RecordedConditionsResponse,
+            routes::accuracy::LeadTimeAccuracy,
+            routes::accuracy::CheckpointAccuracy,
             routes::forecasts::Weather,
             routes::forecasts::ForecastResponse,
             routes::forecasts::ForecastHistoryEntry,
             routes::forecasts::ForecastHistoryResponse,
+            routes::forecasts::ForecastExportRow,
+            routes::forecasts::WindRoseSector,
+            services::yr::ForecastResolution,
             routes::forecasts::RaceForecastCheckpoint,
             routes::forecasts::RaceForecastResponse,
+            routes::forecasts::RaceExtremeCheckpoint,
+            routes::forecasts::RaceTimelineSlot,
+            routes::forecasts::RaceTimelineResponse,
+            routes::forecasts::UvExposureResponse,
+            routes::forecasts::HistoricalComparisonCheckpoint,
+            routes::forecasts::RaceHistoricalComparisonResponse,
+            routes::forecasts::CheckpointSnowfall,
+            routes::forecasts::RacePrecipitationForecastResponse,
             services::poller::PollerState,
             services::poller::CheckpointPollStatus,
+            routes::admin::AdminMessage,
+            routes::admin::IntegrityReport,
+            routes::admin::IntegrityIssue,
+            routes::admin::PopularCheckpoint,
+            routes::admin::PurgedForecasts,
+            routes::meta::SymbolCode,
+            services::gpx::GpxValidationReport,
+            services::gpx::GpxValidationIssue,
+            services::gpx::GpxParsedSummary,
             errors::ErrorResponse,
+            errors::ErrorResponseV2,
+            errors::AppErrorCode,
         )
     )
 )]
 struct ApiDoc;
 
+/// Registers the `api_key` security scheme used by admin endpoints.
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(API_KEY_HEADER))),
+            );
+        }
+    }
+}
+
+/// Load the races to seed: YAML fixtures from `TEST_RACE_DATA_DIR` when set,
+/// otherwise GPX files from `DATA_DIR` followed by `RACE_DATA_URLS`. The
+/// error is a ready-to-log message.
+async fn load_seed_races(config: &AppConfig) -> Result<Vec<services::gpx::GpxRace>, String> {
+    if let Some(dir) = &config.test_race_data_dir {
+        tracing::warn!(
+            "TEST_RACE_DATA_DIR set — seeding fixture races from {}",
+            dir
+        );
+        return services::fixture::load_races_from_yaml(std::path::Path::new(dir))
+            .map_err(|e| format!("Failed to load race fixtures from {}: {}", dir, e));
+    }
+    let data_dir = std::path::Path::new(&config.data_dir);
+    let mut races = services::gpx::load_races_from_dir(
+        data_dir,
+        config.gpx_distance_tolerance_km,
+        config.checkpoint_identifier_mode,
+    )
+    .map_err(|e| {
+        format!(
+            "Failed to load GPX files from {}: {}",
+            data_dir.display(),
+            e
+        )
+    })?;
+    if races.is_empty() {
+        tracing::warn!("No GPX files found in {}", data_dir.display());
+    }
+    races.extend(
+        services::gpx::load_races_from_urls(
+            &config.race_data_urls,
+            config.gpx_distance_tolerance_km,
+            config.checkpoint_identifier_mode,
+        )
+        .await,
+    );
+    Ok(races)
+}
+
+/// Print what seeding would change as JSON on stdout, without writing.
+///
+/// Returns the process exit code: 0 when nothing would change, 1 when at
+/// least one race differs, 2 when the GPX files or the database could not
+/// be read.
+async fn seed_dry_run(pool: &sqlx::PgPool, config: &AppConfig) -> i32 {
+    let races = match load_seed_races(config).await {
+        Ok(races) => races,
+        Err(e) => {
+            tracing::error!("{}", e);
+            return 2;
+        }
+    };
+
+    let mut diffs = Vec::with_capacity(races.len());
+    for race in &races {
+        match db::queries::diff_race_from_gpx(pool, race).await {
+            Ok(diff) => diffs.push(diff),
+            Err(e) => {
+                tracing::error!("Failed to diff race '{}' ({}): {}", race.name, race.year, e);
+                return 2;
+            }
+        }
+    }
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&diffs).expect("RaceDiff serializes to JSON")
+    );
+    if diffs.iter().any(|d| d.has_changes()) {
+        1
+    } else {
+        0
+    }
+}
+
 #[tokio::main]
 async fn main() {
     // Initialize tracing
@@ -88,12 +263,19 @@ async fn main() {
     let config = AppConfig::from_env();
 
     // Set up database connection pool
+    let masked_database_url = config::mask_connection_string(config.database_url.expose());
+    tracing::info!("Connecting to database at {}", masked_database_url);
     let pool = PgPoolOptions::new()
         .max_connections(DB_POOL_MAX_CONNECTIONS)
         .min_connections(DB_POOL_MIN_CONNECTIONS)
-        .connect(&config.database_url)
+        .connect(config.database_url.expose())
         .await
-        .expect("Failed to connect to database");
+        .unwrap_or_else(|e| {
+            panic!(
+                "Failed to connect to database at {}: {}",
+                masked_database_url, e
+            )
+        });
 
     // Run migrations
     sqlx::migrate!()
@@ -103,19 +285,47 @@ async fn main() {
 
     tracing::info!("Database migrations completed");
 
-    // Seed races from GPX files
-    let data_dir = std::path::Path::new(&config.data_dir);
-    match services::gpx::load_races_from_dir(data_dir) {
+    if config.seed_dry_run || std::env::args().any(|a| a == "--dry-run") {
+        std::process::exit(seed_dry_run(&pool, &config).await);
+    }
+
+    // Seed races from GPX files (or test fixtures)
+    let elevation_client = config
+        .enrich_elevations
+        .then(|| ElevationClient::new(&config.elevation_api_url));
+    let mut new_race_ids = Vec::new();
+    match load_seed_races(&config).await {
         Ok(races) => {
-            for race in &races {
-                match db::queries::upsert_race_from_gpx(&pool, race).await {
-                    Ok(race_id) => {
+            for (i, race) in races.iter().enumerate() {
+                let overlap = services::gpx::detect_route_overlap(&races[..i], race);
+                if let Some(report) = overlap.as_ref().filter(|r| r.possible_duplicate) {
+                    tracing::info!(
+                        "Race '{}' ({}) shares {:.0}% of checkpoints with {} — it may be a duplicate",
+                        race.name,
+                        race.year,
+                        report.similarity * 100.0,
+                        report.route_similar_to
+                    );
+                }
+                match db::queries::upsert_race_from_gpx(&pool, race, elevation_client.as_ref())
+                    .await
+                {
+                    Ok((race_id, is_new)) => {
+                        if is_new {
+                            new_race_ids.push(race_id);
+                        }
+                        let route_similar_to = overlap
+                            .filter(|r| !r.possible_duplicate)
+                            .map(|r| r.route_similar_to);
                         tracing::info!(
-                            "Seeded race '{}' ({}) with {} checkpoints → id={}",
+                            "Seeded race '{}' ({}) with {} checkpoints → id={}{}",
                             race.name,
                             race.year,
                             race.checkpoints.len(),
-                            race_id
+                            race_id,
+                            route_similar_to
+                                .map(|s| format!(", route_similar_to={}", s))
+                                .unwrap_or_default()
                         );
                     }
                     Err(e) => {
@@ -128,59 +338,193 @@ async fn main() {
                     }
                 }
             }
-            if races.is_empty() {
-                tracing::warn!("No GPX files found in {}", data_dir.display());
-            }
-        }
-        Err(e) => {
-            tracing::error!(
-                "Failed to load GPX files from {}: {}",
-                data_dir.display(),
-                e
-            );
         }
+        Err(e) => tracing::error!("{}", e),
+    }
+
+    // Load historical averages (replaces the table contents)
+    match services::climate::load_historical_averages(std::path::Path::new(
+        &config.historical_averages_file,
+    )) {
+        Ok(averages) => match db::queries::replace_historical_averages(&pool, &averages).await {
+            Ok(()) => tracing::info!("Loaded {} historical averages", averages.len()),
+            Err(e) => tracing::error!("Failed to store historical averages: {}", e),
+        },
+        Err(e) => tracing::error!("Failed to load {}: {}", config.historical_averages_file, e),
     }
 
     // Create yr.no client
-    let yr_client = YrClient::new(&config.yr_user_agent);
+    let yr_client = YrClient::from_config(&config);
 
     // Build shared application state
     let app_state = AppState {
         pool: pool.clone(),
         yr_client: yr_client.clone(),
+        extraction_cache: Arc::new(ExtractionCache::new(config.yr_extraction_cache_capacity)),
+        sunrise_client: SunriseClient::from_yr_client(&yr_client),
+        poller_config: PollerConfig::from_config(&config),
+        pacing_params: PacingParams::from_config(&config),
+        pacing_fatigue_factor: config.pacing_fatigue_factor,
+        slow_request_threshold_ms: config.slow_request_threshold_ms,
+        debug_timing: config.debug_timing,
     };
 
+    // Cancelled on SIGTERM/SIGINT — stops background tasks and starts connection draining
+    let shutdown = CancellationToken::new();
+
     // Create shared poller state and spawn background poller
     let poller_state: SharedPollerState = Arc::new(RwLock::new(PollerState::new()));
+    let (poller_control, poller_trigger) = poller_control();
+    let poller_config = PollerConfig::from_config(&config);
     tokio::spawn(services::poller::run_poller(
         pool.clone(),
         yr_client,
         poller_state.clone(),
+        shutdown.clone(),
+        poller_trigger,
+        poller_config,
     ));
 
-    // CORS — read-only API, restrict methods to GET; expose X-Forecast-Stale
+    // Refresh pre-computed race forecasts after every poll cycle
+    let summary_state = app_state.clone();
+    tokio::spawn(services::summaries::run_forecast_summaries(
+        pool.clone(),
+        app_state.poller_config.lookahead_days,
+        move |race, hours| {
+            let state = summary_state.clone();
+            async move { routes::summaries::compute_summary(&state, race, hours).await }
+        },
+        poller_control.cycles_completed(),
+        shutdown.clone(),
+    ));
+
+    if config.db_maintenance_interval_hours > 0 {
+        tokio::spawn(services::maintenance::run_db_maintenance(
+            pool.clone(),
+            std::time::Duration::from_secs(config.db_maintenance_interval_hours * 3600),
+            poller_state.clone(),
+            shutdown.clone(),
+        ));
+    }
+
+    if config.forecast_retention_days > 0 {
+        tokio::spawn(services::maintenance::run_forecast_retention(
+            pool.clone(),
+            config.forecast_retention_days,
+            poller_state.clone(),
+            shutdown.clone(),
+        ));
+    }
+
+    // Warm the yr.no cache for races seen for the first time, so the first
+    // user request doesn't pay for a cold fetch
+    if !new_race_ids.is_empty() {
+        tokio::spawn(services::forecast::preheat_yr_caches(
+            pool.clone(),
+            app_state.yr_client.clone(),
+            new_race_ids,
+            shutdown.clone(),
+        ));
+    }
+
+    if config.watch_data_dir {
+        tokio::spawn(services::gpx_watcher::run_gpx_watcher(
+            pool.clone(),
+            std::path::PathBuf::from(&config.data_dir),
+            config.gpx_distance_tolerance_km,
+            config.checkpoint_identifier_mode,
+            elevation_client,
+            shutdown.clone(),
+        ));
+    }
+
+    if config.admin_api_key.is_none() {
+        tracing::warn!("ADMIN_API_KEY not set — admin endpoints are disabled");
+    }
+    let admin_api_key = AdminApiKey::new(
+        config
+            .admin_api_key
+            .as_ref()
+            .map(|k| k.expose().to_string()),
+    );
+
+    // CORS — mostly read-only API, restrict methods to GET/POST/PATCH/DELETE; expose X-Forecast-Stale
     let cors = CorsLayer::new()
         .allow_origin(Any)
-        .allow_methods([axum::http::Method::GET])
+        .allow_methods([
+            axum::http::Method::GET,
+            axum::http::Method::POST,
+            axum::http::Method::PATCH,
+            axum::http::Method::DELETE,
+        ])
         .allow_headers(Any)
-        .expose_headers(["X-Forecast-Stale"
-            .parse::<axum::http::HeaderName>()
-            .unwrap()]);
+        .expose_headers([
+            "X-Forecast-Stale"
+                .parse::<axum::http::HeaderName>()
+                .unwrap(),
+            axum::http::header::LINK,
+        ]);
 
     // Build router
     // Race routes use PgPool state directly; forecast routes use AppState.
     let race_routes = Router::new()
         .route("/api/v1/races", get(routes::races::list_races))
-        .route(
-            "/api/v1/races/:id/course",
-            get(routes::races::get_race_course),
-        )
         .route(
             "/api/v1/races/:id/checkpoints",
             get(routes::races::get_checkpoints),
         )
+        .route(
+            "/api/v1/races/:id/bounds",
+            get(routes::races::get_race_bounds),
+        )
+        .route(
+            "/api/v1/races/:id/stats",
+            get(routes::races::get_race_stats),
+        )
+        // Recording conditions requires X-API-Key; accuracy reads are public
+        .route(
+            "/api/v1/races/:id/actual-conditions",
+            post(routes::accuracy::record_actual_conditions).route_layer(
+                axum::middleware::from_fn_with_state(
+                    admin_api_key.clone(),
+                    middleware::api_key::require_api_key,
+                ),
+            ),
+        )
+        .route(
+            "/api/v1/races/:id/forecast-accuracy",
+            get(routes::accuracy::get_forecast_accuracy),
+        )
         .with_state(pool.clone());
 
+    let resorts = services::resort::load_resorts(std::path::Path::new(&config.resort_data_file))
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to load {}: {}", config.resort_data_file, e);
+            Vec::new()
+        });
+    tracing::info!("Loaded {} ski resorts", resorts.len());
+    let checkpoint_routes = Router::new()
+        .route(
+            "/api/v1/checkpoints/:id/nearby-resorts",
+            get(routes::checkpoints::get_nearby_resorts),
+        )
+        // Submitting observations requires X-API-Key; listing is public
+        .route(
+            "/api/v1/checkpoints/:id/observations",
+            get(routes::checkpoints::list_observations).merge(
+                post(routes::checkpoints::create_observation).route_layer(
+                    axum::middleware::from_fn_with_state(
+                        admin_api_key.clone(),
+                        middleware::api_key::require_api_key,
+                    ),
+                ),
+            ),
+        )
+        .with_state(routes::checkpoints::CheckpointState {
+            pool: pool.clone(),
+            resorts: Arc::new(resorts),
+        });
+
     let forecast_routes = Router::new()
         .route(
             "/api/v1/forecasts/checkpoint/:checkpoint_id",
@@ -190,31 +534,222 @@ async fn main() {
             "/api/v1/forecasts/checkpoint/:checkpoint_id/history",
             get(routes::forecasts::get_checkpoint_forecast_history),
         )
+        .route(
+            "/api/v1/forecasts/checkpoint/:checkpoint_id/wind-rose",
+            get(routes::forecasts::get_checkpoint_wind_rose),
+        )
+        .route(
+            "/api/v1/forecasts/checkpoint/:checkpoint_id/export",
+            get(routes::forecasts::export_checkpoint_forecasts),
+        )
+        .route(
+            "/api/v1/forecasts/checkpoint/:checkpoint_id/multi",
+            get(routes::forecasts::get_checkpoint_forecast_multi),
+        )
         .route(
             "/api/v1/forecasts/race/:race_id",
             get(routes::forecasts::get_race_forecast),
         )
+        .route(
+            "/api/v1/forecasts/race/:race_id/timeline",
+            get(routes::forecasts::get_race_timeline),
+        )
+        .route(
+            "/api/v1/forecasts/race/:race_id/uv-exposure",
+            get(routes::forecasts::get_race_uv_exposure),
+        )
+        .route(
+            "/api/v1/forecasts/race/:race_id/vs-historical",
+            get(routes::forecasts::get_race_vs_historical),
+        )
+        .route(
+            "/api/v1/forecasts/race/:race_id/precipitation-forecast",
+            get(routes::forecasts::get_race_precipitation_forecast),
+        )
+        .route(
+            "/api/v1/forecasts/race/:race_id/cached-summary",
+            get(routes::summaries::get_cached_race_summary),
+        )
         .with_state(app_state.clone());
 
-    // Health check uses PgPool to verify DB connectivity
+    // Health check uses PgPool to verify DB connectivity and reads SharedPollerState
+    // for poller liveness
     let health_routes = Router::new()
         .route("/api/v1/health", get(routes::health::health_check))
-        .with_state(pool);
+        .with_state(routes::health::HealthState {
+            pool: pool.clone(),
+            yr_client: app_state.yr_client.clone(),
+            poller_state: poller_state.clone(),
+            poller_liveness_threshold_minutes: config.poller_liveness_threshold_minutes,
+        });
 
-    // Poller status uses SharedPollerState
+    // Poller status reads SharedPollerState
     let poller_routes = Router::new()
         .route(
             "/api/v1/poller/status",
             get(routes::poller::get_poller_status),
         )
-        .with_state(poller_state);
+        .with_state(PollerStatusState {
+            poller_state: poller_state.clone(),
+            stale_warn_minutes: config.forecast_stale_warn_minutes,
+        });
+
+    // The course endpoint computes pacing fractions with the configured constants
+    let race_course_routes = Router::new()
+        .route(
+            "/api/v1/races/:id/course",
+            get(routes::races::get_race_course),
+        )
+        .with_state(RaceCourseState {
+            pool: pool.clone(),
+            pacing_params: app_state.pacing_params,
+        });
+
+    // Start time updates re-extract forecasts with the poller's pacing bounds
+    // and require X-API-Key
+    let race_update_routes = Router::new()
+        .route("/api/v1/races/:id", patch(routes::races::update_race))
+        .route_layer(axum::middleware::from_fn_with_state(
+            admin_api_key.clone(),
+            middleware::api_key::require_api_key,
+        ))
+        .with_state(RaceUpdateState {
+            pool: pool.clone(),
+            poller_config,
+        });
+
+    // Checkpoint updates share the race routes' state but require X-API-Key
+    let race_admin_routes = Router::new()
+        .route(
+            "/api/v1/races/:id/checkpoints/:checkpoint_id",
+            patch(routes::races::update_checkpoint),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            admin_api_key.clone(),
+            middleware::api_key::require_api_key,
+        ))
+        .with_state(pool.clone());
+
+    // Admin routes require X-API-Key
+    let admin_routes = Router::new()
+        .route(
+            "/api/v1/admin/poller/trigger",
+            post(routes::admin::trigger_poll),
+        )
+        .route(
+            "/api/v1/admin/poller/pause",
+            post(routes::admin::pause_poller),
+        )
+        .route(
+            "/api/v1/admin/poller/resume",
+            post(routes::admin::resume_poller),
+        )
+        .route(
+            "/api/v1/admin/integrity-check",
+            get(routes::admin::integrity_check),
+        )
+        .route(
+            "/api/v1/admin/cache/checkpoint/:id/raw",
+            get(routes::admin::get_raw_yr_cache),
+        )
+        .route(
+            "/api/v1/admin/stats/popular-checkpoints",
+            get(routes::admin::get_popular_checkpoints),
+        )
+        .route(
+            "/api/v1/admin/forecasts",
+            delete(routes::admin::purge_old_forecasts),
+        )
+        .route(
+            "/api/v1/gpx/validate",
+            post(routes::admin::validate_gpx_upload),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            admin_api_key,
+            middleware::api_key::require_api_key,
+        ))
+        .layer(DefaultBodyLimit::max(
+            middleware::body_limit::ADMIN_MAX_BODY_SIZE_BYTES,
+        ))
+        .with_state(AdminState {
+            poller_state: poller_state.clone(),
+            poller_control,
+            pool: pool.clone(),
+            checkpoint_identifier: config.checkpoint_identifier_mode,
+        });
+
+    // The API documentation (Swagger UI, OpenAPI JSON and the versioned
+    // schema) sits behind Basic auth when SWAGGER_AUTH_KEY is set
+    let swagger_auth_key = SwaggerAuthKey::new(
+        config
+            .swagger_auth_key
+            .as_ref()
+            .map(|k| k.expose().to_string()),
+    );
+
+    // Versioned OpenAPI schema (JSON or YAML), stateless
+    let schema_routes = Router::new()
+        .route("/api/v1/schema", get(routes::schema::get_schema))
+        .layer(axum::middleware::from_fn_with_state(
+            swagger_auth_key.clone(),
+            middleware::swagger_auth::require_swagger_auth,
+        ));
+
+    // Static reference data, stateless
+    let meta_routes = Router::new()
+        .route(
+            "/api/v1/meta/symbol-codes",
+            get(routes::meta::list_symbol_codes),
+        )
+        .route(
+            "/api/v1/meta/symbol-codes/:code",
+            get(routes::meta::get_symbol_code),
+        );
+
+    // Swagger UI + OpenAPI JSON
+    let docs_routes = Router::from(
+        SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()),
+    )
+    .layer(axum::middleware::from_fn_with_state(
+        swagger_auth_key,
+        middleware::swagger_auth::require_swagger_auth,
+    ));
 
     let app = Router::new()
         .merge(health_routes)
+        .merge(schema_routes)
+        .merge(meta_routes)
         .merge(race_routes)
+        .merge(race_course_routes)
+        .merge(race_update_routes)
+        .merge(checkpoint_routes)
         .merge(forecast_routes)
         .merge(poller_routes)
-        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .merge(admin_routes)
+        .merge(race_admin_routes)
+        .merge(docs_routes)
+        // Admin routes override this with their own, larger limit
+        .layer(DefaultBodyLimit::max(config.max_body_size_bytes))
+        .layer(axum::middleware::from_fn(
+            middleware::body_limit::json_payload_too_large,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            middleware::envelope::ResponseEnvelope {
+                enabled: config.response_envelope,
+            },
+            middleware::envelope::wrap_json_response,
+        ))
+        .layer(axum::middleware::from_fn(
+            middleware::link_header::add_link_header,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            pool,
+            middleware::pool_guard::reject_when_pool_exhausted,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            RequestTimeouts::new(config.request_timeout_secs),
+            middleware::timeout::request_timeout,
+        ))
         .layer(cors);
 
     // Start server
@@ -228,7 +763,60 @@ async fn main() {
     let listener = tokio::net::TcpListener::bind(addr)
         .await
         .expect("Failed to bind TCP listener");
-    axum::serve(listener, app)
-        .await
-        .expect("Server terminated unexpectedly");
+    let mut server = tokio::spawn(
+        axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown_signal(shutdown.clone()))
+            .into_future(),
+    );
+
+    // Wait for a shutdown signal, then give in-flight requests a bounded
+    // amount of time to complete.
+    tokio::select! {
+        result = &mut server => {
+            result
+                .expect("Server task panicked")
+                .expect("Server terminated unexpectedly");
+            return;
+        }
+        _ = shutdown.cancelled() => {}
+    }
+
+    let drain_timeout = std::time::Duration::from_secs(config.shutdown_timeout_secs);
+    match tokio::time::timeout(drain_timeout, server).await {
+        Ok(Ok(Ok(()))) => tracing::info!("All connections drained, shutting down"),
+        Ok(Ok(Err(e))) => tracing::error!("Server error during shutdown: {}", e),
+        Ok(Err(e)) => tracing::error!("Server task failed during shutdown: {}", e),
+        Err(_) => tracing::warn!(
+            "Connections not drained within {}s, forcing shutdown",
+            config.shutdown_timeout_secs
+        ),
+    }
+}
+
+/// Resolve when SIGTERM or SIGINT (Ctrl+C) is received, cancelling `shutdown`.
+async fn shutdown_signal(shutdown: CancellationToken) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+
+    tracing::info!("Received shutdown signal, draining connections...");
+    shutdown.cancel();
 }
@@ -0,0 +1,90 @@
+//! API key authentication for admin endpoints.
+//!
+//! Admin routes require an `X-API-Key` header matching `ADMIN_API_KEY`. When
+//! no key is configured, admin routes reject every request.
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::errors::AppError;
+
+/// Header carrying the admin API key.
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Configured admin API key (`None` disables admin routes).
+#[derive(Debug, Clone)]
+pub struct AdminApiKey(Option<Arc<str>>);
+
+impl AdminApiKey {
+    pub fn new(key: Option<String>) -> Self {
+        Self(key.map(Arc::from))
+    }
+
+    /// Check a presented key against the configured one.
+    fn verify(&self, presented: Option<&str>) -> Result<(), AppError> {
+        let expected = self
+            .0
+            .as_deref()
+            .ok_or_else(|| AppError::Unauthorized("Admin API is disabled".to_string()))?;
+        match presented {
+            Some(key) if constant_time_eq(key.as_bytes(), expected.as_bytes()) => Ok(()),
+            Some(_) => Err(AppError::Unauthorized("Invalid API key".to_string())),
+            None => Err(AppError::Unauthorized(format!(
+                "Missing {} header",
+                API_KEY_HEADER
+            ))),
+        }
+    }
+}
+
+/// Middleware: reject requests without a valid `X-API-Key` header.
+pub async fn require_api_key(
+    State(key): State<AdminApiKey>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let presented = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok());
+    key.verify(presented)?;
+    Ok(next.run(request).await)
+}
+
+/// Compare two byte strings without short-circuiting on the first mismatch.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_accepts_matching_key() {
+        let key = AdminApiKey::new(Some("s3cret".to_string()));
+        assert!(key.verify(Some("s3cret")).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_or_missing_key() {
+        let key = AdminApiKey::new(Some("s3cret".to_string()));
+        assert!(matches!(
+            key.verify(Some("s3cre")),
+            Err(AppError::Unauthorized(_))
+        ));
+        assert!(matches!(key.verify(None), Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_verify_rejects_all_when_unconfigured() {
+        let key = AdminApiKey::new(None);
+        assert!(matches!(
+            key.verify(Some("anything")),
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+}
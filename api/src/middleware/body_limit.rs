@@ -0,0 +1,83 @@
+//! Request body size limits.
+//!
+//! Body-consuming extractors (`Json`, `Bytes`, ...) enforce the limit set by
+//! `DefaultBodyLimit` on the route group, but reject with a plain-text 413.
+//! This middleware turns that into the usual JSON error body.
+
+use axum::extract::Request;
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::errors::AppError;
+
+/// Body limit for admin endpoints (bytes), which may carry GPX files.
+pub const ADMIN_MAX_BODY_SIZE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Middleware: replace an extractor's plain-text 413 rejection with an
+/// `ErrorResponseV2` body.
+pub async fn json_payload_too_large(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE || is_json(&response) {
+        return response;
+    }
+    AppError::RequestTooLarge("Request body too large".to_string()).into_response()
+}
+
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::DefaultBodyLimit;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/echo",
+                post(|Json(body): Json<serde_json::Value>| async move { Json(body) }),
+            )
+            .layer(DefaultBodyLimit::max(64))
+            .layer(axum::middleware::from_fn(json_payload_too_large))
+    }
+
+    fn post_json(body: String) -> Request {
+        Request::post("/echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_returns_413_json() {
+        let body = format!("{{\"name\":\"{}\"}}", "x".repeat(100));
+        let response = app().oneshot(post_json(body)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["error"], "Request body too large");
+        assert_eq!(json["error_code"], "PAYLOAD_TOO_LARGE");
+    }
+
+    #[tokio::test]
+    async fn test_body_within_limit_passes_through() {
+        let response = app()
+            .oneshot(post_json("{\"name\":\"Vasaloppet\"}".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,166 @@
+//! Optional response envelope (`RESPONSE_ENVELOPE=true`).
+//!
+//! Some consumers prefer every payload wrapped as
+//! `{ "data": <body>, "meta": { "request_id", "api_version", "timestamp" } }`.
+//! Only successful (2xx) `application/json` responses under `/api/v1/` are
+//! wrapped; errors keep their `ErrorResponseV2` shape, and NDJSON, YAML and
+//! the OpenAPI documents pass through untouched.
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// API version reported in `meta.api_version`.
+const API_VERSION: &str = "0.1.0";
+
+/// Incoming header whose value is reused as `meta.request_id`.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// JSON endpoints that are never wrapped (schema documents must stay valid
+/// OpenAPI).
+const UNWRAPPED_PATHS: [&str; 1] = ["/api/v1/schema"];
+
+/// Whether responses are wrapped.
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseEnvelope {
+    pub enabled: bool,
+}
+
+/// Middleware: wrap successful JSON responses in the `data`/`meta` envelope.
+pub async fn wrap_json_response(
+    State(envelope): State<ResponseEnvelope>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if !envelope.enabled || !path.starts_with("/api/v1/") || UNWRAPPED_PATHS.contains(&path) {
+        return next.run(request).await;
+    }
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let response = next.run(request).await;
+    if !response.status().is_success() || !is_json(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Failed to buffer response for envelope: {}", e);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let data: serde_json::Value = match serde_json::from_slice(&bytes) {
+        Ok(data) => data,
+        // Not actually JSON: pass the body through unchanged
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+
+    let wrapped = serde_json::json!({
+        "data": data,
+        "meta": {
+            "request_id": request_id,
+            "api_version": API_VERSION,
+            "timestamp": Utc::now().to_rfc3339(),
+        },
+    });
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(wrapped.to_string()))
+}
+
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use tower::ServiceExt;
+
+    use crate::errors::{AppError, AppErrorCode};
+
+    fn app(enabled: bool) -> Router {
+        Router::new()
+            .route(
+                "/api/v1/races",
+                get(|| async { Json(serde_json::json!([{ "name": "Vasaloppet" }])) }),
+            )
+            .route(
+                "/api/v1/races/missing",
+                get(|| async {
+                    AppError::NotFound(AppErrorCode::RaceNotFound, "Race not found".to_string())
+                        .into_response()
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                ResponseEnvelope { enabled },
+                wrap_json_response,
+            ))
+    }
+
+    async fn get_json(
+        app: Router,
+        uri: &str,
+        request_id: Option<&str>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::get(uri);
+        if let Some(id) = request_id {
+            request = request.header(REQUEST_ID_HEADER, id);
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_envelope_enabled_wraps_success() {
+        let (status, json) = get_json(app(true), "/api/v1/races", Some("req-1")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["data"][0]["name"], "Vasaloppet");
+        assert_eq!(json["meta"]["request_id"], "req-1");
+        assert_eq!(json["meta"]["api_version"], "0.1.0");
+        assert!(json["meta"]["timestamp"].is_string());
+
+        // Without X-Request-Id a fresh UUID is generated
+        let (_, json) = get_json(app(true), "/api/v1/races", None).await;
+        let id = json["meta"]["request_id"].as_str().unwrap();
+        assert!(Uuid::parse_str(id).is_ok());
+
+        // Errors keep their standard shape
+        let (status, json) = get_json(app(true), "/api/v1/races/missing", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(json["error_code"], "RACE_NOT_FOUND");
+        assert!(json.get("data").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_envelope_disabled_passes_through() {
+        let (status, json) = get_json(app(false), "/api/v1/races", Some("req-1")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json, serde_json::json!([{ "name": "Vasaloppet" }]));
+    }
+}
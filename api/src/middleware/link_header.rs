@@ -0,0 +1,109 @@
+//! `Link` headers pointing clients at the resources they usually fetch next.
+//!
+//! The race forecast links the race's checkpoints and course; the checkpoint
+//! forecast links its history for the same `datetime`. Browsers and HTTP
+//! clients can use these to prefetch. The route template (`MatchedPath`)
+//! selects the links.
+
+use axum::extract::{MatchedPath, Request};
+use axum::http::{header, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+
+/// Middleware: add a `Link` header to successful responses of routes with
+/// related resources.
+pub async fn add_link_header(
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let links = matched_path
+        .as_ref()
+        .and_then(|p| related_links(p.as_str(), request.uri().path(), request.uri().query()));
+    let mut response = next.run(request).await;
+    if let Some(value) = links
+        .filter(|_| response.status().is_success())
+        .and_then(|links| HeaderValue::from_str(&links).ok())
+    {
+        response.headers_mut().insert(header::LINK, value);
+    }
+    response
+}
+
+/// `Link` header value for a matched route template and request path, or
+/// None if the route has no related resources.
+fn related_links(template: &str, path: &str, query: Option<&str>) -> Option<String> {
+    let id = path.rsplit('/').next()?;
+    match template {
+        "/api/v1/forecasts/race/:race_id" => Some(format!(
+            "</api/v1/races/{id}/checkpoints>; rel=\"related\", \
+             </api/v1/races/{id}/course>; rel=\"related\""
+        )),
+        "/api/v1/forecasts/checkpoint/:checkpoint_id" => {
+            // History needs the same `datetime`, so keep the query string
+            let query = query.map(|q| format!("?{q}")).unwrap_or_default();
+            Some(format!(
+                "</api/v1/forecasts/checkpoint/{id}/history{query}>; rel=\"related\""
+            ))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/api/v1/forecasts/race/:race_id", get(|| async { "{}" }))
+            .route(
+                "/api/v1/forecasts/checkpoint/:checkpoint_id",
+                get(|| async { "{}" }),
+            )
+            .route("/api/v1/races/:id", get(|| async { StatusCode::NOT_FOUND }))
+            .layer(axum::middleware::from_fn(add_link_header))
+    }
+
+    async fn link(uri: &str) -> Option<String> {
+        let response = app()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        response
+            .headers()
+            .get(header::LINK)
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_race_forecast_links_checkpoints_and_course() {
+        assert_eq!(
+            link("/api/v1/forecasts/race/abc?target_duration_hours=8").await,
+            Some(
+                "</api/v1/races/abc/checkpoints>; rel=\"related\", \
+                 </api/v1/races/abc/course>; rel=\"related\""
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_forecast_links_history() {
+        assert_eq!(
+            link("/api/v1/forecasts/checkpoint/cp1?datetime=2026-03-01T08:00:00Z").await,
+            Some(
+                "</api/v1/forecasts/checkpoint/cp1/history?datetime=2026-03-01T08:00:00Z>; \
+                 rel=\"related\""
+                    .to_string()
+            )
+        );
+        // Other routes (and errors) get no Link header
+        assert_eq!(link("/api/v1/races/abc").await, None);
+    }
+}
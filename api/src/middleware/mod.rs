@@ -0,0 +1,7 @@
+pub mod api_key;
+pub mod body_limit;
+pub mod envelope;
+pub mod link_header;
+pub mod pool_guard;
+pub mod swagger_auth;
+pub mod timeout;
@@ -0,0 +1,48 @@
+//! Fail fast when the database connection pool is exhausted.
+//!
+//! With every connection checked out, new requests would otherwise queue in
+//! `pool.acquire()` until the request timeout. Returning 503 immediately lets
+//! clients back off and keeps the queue from growing.
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use sqlx::PgPool;
+
+use crate::errors::AppError;
+
+/// Whether the pool is at its connection limit with no idle connection.
+fn is_exhausted(size: u32, idle: usize, max_connections: u32) -> bool {
+    size >= max_connections && idle == 0
+}
+
+/// Middleware: reject the request with 503 while the pool is exhausted.
+pub async fn reject_when_pool_exhausted(
+    State(pool): State<PgPool>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let max_connections = pool.options().get_max_connections();
+    if is_exhausted(pool.size(), pool.num_idle(), max_connections) {
+        tracing::warn!(
+            "All {} database connections busy — rejecting {}",
+            max_connections,
+            request.uri().path()
+        );
+        return Err(AppError::PoolExhausted);
+    }
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_exhausted() {
+        assert!(is_exhausted(5, 0, 5));
+        assert!(!is_exhausted(5, 1, 5));
+        // Pool still growing: a new connection can be opened
+        assert!(!is_exhausted(3, 0, 5));
+    }
+}
@@ -0,0 +1,187 @@
+//! HTTP Basic authentication for the API documentation.
+//!
+//! When `SWAGGER_AUTH_KEY` is set, `/swagger-ui/*`, `/api-docs/openapi.json`
+//! and `/api/v1/schema` require an `Authorization: Basic` header with any
+//! username and the key as password. Unset leaves the documentation public.
+//! API endpoints keep their own auth (see [`api_key`](super::api_key)).
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use super::api_key::constant_time_eq;
+use crate::errors::AppError;
+
+/// Challenge sent with 401 responses so browsers prompt for credentials.
+const BASIC_CHALLENGE: &str = "Basic realm=\"API documentation\", charset=\"UTF-8\"";
+
+/// Configured documentation password (`None` leaves the docs public).
+#[derive(Debug, Clone)]
+pub struct SwaggerAuthKey(Option<Arc<str>>);
+
+impl SwaggerAuthKey {
+    pub fn new(key: Option<String>) -> Self {
+        Self(key.map(Arc::from))
+    }
+
+    /// Check an `Authorization` header value against the configured key.
+    fn verify(&self, authorization: Option<&str>) -> bool {
+        let Some(expected) = self.0.as_deref() else {
+            return true;
+        };
+        basic_password(authorization)
+            .is_some_and(|password| constant_time_eq(password.as_bytes(), expected.as_bytes()))
+    }
+}
+
+/// Password of an `Authorization: Basic <base64(user:password)>` header.
+fn basic_password(authorization: Option<&str>) -> Option<String> {
+    let (scheme, encoded) = authorization?.trim().split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("basic") {
+        return None;
+    }
+    let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+    let (_user, password) = decoded.split_once(':')?;
+    Some(password.to_string())
+}
+
+/// Middleware: reject documentation requests without valid Basic credentials.
+pub async fn require_swagger_auth(
+    State(key): State<SwaggerAuthKey>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    if key.verify(authorization) {
+        return next.run(request).await;
+    }
+    let mut response = AppError::Unauthorized("API documentation requires credentials".to_string())
+        .into_response();
+    response.headers_mut().insert(
+        header::WWW_AUTHENTICATE,
+        HeaderValue::from_static(BASIC_CHALLENGE),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+    use utoipa::openapi::OpenApiBuilder;
+    use utoipa_swagger_ui::SwaggerUi;
+
+    /// The documentation routes as wired in `main`.
+    fn app(key: Option<&str>) -> Router {
+        let layer = axum::middleware::from_fn_with_state(
+            SwaggerAuthKey::new(key.map(str::to_string)),
+            require_swagger_auth,
+        );
+        Router::from(
+            SwaggerUi::new("/swagger-ui")
+                .url("/api-docs/openapi.json", OpenApiBuilder::new().build()),
+        )
+        .layer(layer.clone())
+        .merge(
+            Router::new()
+                .route("/api/v1/schema", get(crate::routes::schema::get_schema))
+                .layer(layer),
+        )
+    }
+
+    async fn status(app: Router, uri: &str, authorization: Option<String>) -> StatusCode {
+        let mut request = Request::get(uri);
+        if let Some(value) = authorization {
+            request = request.header(header::AUTHORIZATION, value);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    fn basic(user: &str, password: &str) -> Option<String> {
+        Some(format!(
+            "Basic {}",
+            STANDARD.encode(format!("{user}:{password}"))
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_swagger_ui_requires_key_when_configured() {
+        let app = app(Some("d0cs"));
+        assert_eq!(
+            status(app.clone(), "/swagger-ui/", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(app.clone(), "/swagger-ui/", basic("anyone", "wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(app.clone(), "/swagger-ui/", basic("anyone", "d0cs")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(app, "/api-docs/openapi.json", basic("", "d0cs")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_versioned_schema_requires_key_when_configured() {
+        let app = app(Some("d0cs"));
+        assert_eq!(
+            status(app.clone(), "/api/v1/schema", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(
+                app.clone(),
+                "/api/v1/schema?format=yaml",
+                basic("x", "wrong")
+            )
+            .await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(app, "/api/v1/schema", basic("x", "d0cs")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_swagger_ui_public_without_key() {
+        assert_eq!(
+            status(app(None), "/swagger-ui/", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(app(None), "/api/v1/schema", None).await,
+            StatusCode::OK
+        );
+    }
+
+    #[test]
+    fn test_basic_password_parsing() {
+        assert_eq!(
+            basic_password(basic("user", "pa:ss").as_deref()),
+            Some("pa:ss".to_string())
+        );
+        assert_eq!(basic_password(Some("Bearer abc")), None);
+        assert_eq!(basic_password(Some("Basic not-base64!")), None);
+        assert_eq!(basic_password(None), None);
+    }
+}
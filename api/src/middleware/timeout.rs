@@ -0,0 +1,135 @@
+//! Per-endpoint request timeouts.
+//!
+//! A single global timeout doesn't fit every route: the health check should
+//! fail fast, while the race forecast may need to refresh yr.no caches for
+//! every checkpoint. The route template (`MatchedPath`) selects the budget.
+
+use std::future::Future;
+use std::time::Duration;
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::errors::AppError;
+
+/// Timeout for the health check endpoint (seconds).
+const HEALTH_TIMEOUT_SECS: u64 = 5;
+
+/// Timeout for the race forecast endpoints, which may fetch from yr.no for
+/// every checkpoint (seconds).
+const RACE_FORECAST_TIMEOUT_SECS: u64 = 60;
+
+/// Route templates under this prefix get the race forecast budget, including
+/// sub-routes added later.
+const RACE_FORECAST_PREFIX: &str = "/api/v1/forecasts/race/";
+
+/// Timeout budgets, keyed by endpoint category.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestTimeouts {
+    /// Applies to every route without a dedicated category.
+    pub default: Duration,
+    pub health: Duration,
+    pub race_forecast: Duration,
+}
+
+impl RequestTimeouts {
+    pub fn new(default_secs: u64) -> Self {
+        Self {
+            default: Duration::from_secs(default_secs),
+            health: Duration::from_secs(HEALTH_TIMEOUT_SECS),
+            race_forecast: Duration::from_secs(RACE_FORECAST_TIMEOUT_SECS),
+        }
+    }
+
+    /// Select the timeout for a matched route template (e.g. `/api/v1/health`).
+    /// Unmatched requests (404 fallback) use the default.
+    fn for_path(&self, matched_path: Option<&str>) -> Duration {
+        match matched_path {
+            Some("/api/v1/health") => self.health,
+            // Extracts up to 12 forecast times in one request
+            Some("/api/v1/forecasts/checkpoint/:checkpoint_id/multi") => self.race_forecast,
+            Some(path) if path.starts_with(RACE_FORECAST_PREFIX) => self.race_forecast,
+            _ => self.default,
+        }
+    }
+}
+
+/// Middleware: abort the request with 503 if the handler exceeds its budget.
+pub async fn request_timeout(
+    State(timeouts): State<RequestTimeouts>,
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = matched_path.as_ref().map(MatchedPath::as_str);
+    let limit = timeouts.for_path(path);
+    run_with_timeout(limit, next.run(request)).await
+}
+
+/// Await `fut`, returning a 503 response if it does not complete within `limit`.
+async fn run_with_timeout<F>(limit: Duration, fut: F) -> Response
+where
+    F: Future<Output = Response>,
+{
+    match tokio::time::timeout(limit, fut).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!("Request exceeded {}s timeout", limit.as_secs());
+            AppError::RequestTimeout.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    #[test]
+    fn test_for_path_selects_category() {
+        let t = RequestTimeouts::new(30);
+        assert_eq!(t.for_path(Some("/api/v1/health")), Duration::from_secs(5));
+        for path in [
+            "/api/v1/forecasts/race/:race_id",
+            "/api/v1/forecasts/race/:race_id/timeline",
+            "/api/v1/forecasts/race/:race_id/uv-exposure",
+            "/api/v1/forecasts/race/:race_id/vs-historical",
+            "/api/v1/forecasts/race/:race_id/precipitation-forecast",
+            "/api/v1/forecasts/race/:race_id/cached-summary",
+            "/api/v1/forecasts/checkpoint/:checkpoint_id/multi",
+        ] {
+            assert_eq!(t.for_path(Some(path)), Duration::from_secs(60), "{}", path);
+        }
+        for path in [
+            "/api/v1/races/:id/checkpoints",
+            "/api/v1/forecasts/checkpoint/:checkpoint_id",
+            "/api/v1/forecasts/checkpoint/:checkpoint_id/history",
+        ] {
+            assert_eq!(t.for_path(Some(path)), Duration::from_secs(30), "{}", path);
+        }
+        assert_eq!(t.for_path(None), Duration::from_secs(30));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_handler_returns_503() {
+        let slow = async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            StatusCode::OK.into_response()
+        };
+        let response = run_with_timeout(Duration::from_secs(5), slow).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fast_handler_passes_through() {
+        let fast = async {
+            tokio::time::sleep(Duration::from_secs(4)).await;
+            StatusCode::OK.into_response()
+        };
+        let handle = tokio::spawn(run_with_timeout(Duration::from_secs(5), fast));
+        tokio::time::advance(Duration::from_secs(4)).await;
+        let response = handle.await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
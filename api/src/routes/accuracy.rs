@@ -0,0 +1,400 @@
+//! Forecast accuracy endpoints.
+//!
+//! POST /api/v1/races/:id/actual-conditions — record observed race-day conditions.
+//! GET  /api/v1/races/:id/forecast-accuracy — score archived forecasts against them.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::db::queries::{self, InsertActualConditionParams};
+use crate::errors::{AppError, AppErrorCode, ErrorResponseV2};
+use crate::helpers::{dec_to_f64, f64_to_decimal_full};
+
+/// Lead times (hours before race start) at which archived forecasts are scored.
+const ACCURACY_LEAD_TIMES_HOURS: [i64; 4] = [24, 12, 6, 1];
+
+/// One observed condition at a checkpoint.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ActualConditionInput {
+    /// Checkpoint UUID (must belong to the race)
+    pub checkpoint_id: Uuid,
+    /// Observation time in ISO 8601 / RFC 3339 format. Defaults to the race start time.
+    pub observed_at: Option<String>,
+    /// Observed air temperature in °C
+    pub temperature_c: f64,
+    /// Observed wind speed in m/s
+    pub wind_speed_ms: f64,
+    /// Observed precipitation in mm
+    pub precipitation_mm: Option<f64>,
+    /// Observed precipitation type ("snow", "rain", "sleet", "none")
+    pub precipitation_type: Option<String>,
+}
+
+/// Response for POST /api/v1/races/:id/actual-conditions.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecordedConditionsResponse {
+    /// Number of observations stored
+    pub recorded: usize,
+}
+
+/// Forecast error at a single lead time.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LeadTimeAccuracy {
+    /// Forecasts fetched at least this many hours before race start
+    pub hours_before: i64,
+    /// Number of observations a forecast could be matched to
+    pub forecasts_compared: usize,
+    /// Mean absolute temperature error in °C (null if no forecast was available)
+    pub temp_mae_c: Option<f64>,
+    /// Mean absolute wind speed error in m/s (null if no forecast was available)
+    pub wind_mae_ms: Option<f64>,
+}
+
+/// Forecast accuracy for one checkpoint.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CheckpointAccuracy {
+    pub checkpoint_id: Uuid,
+    pub checkpoint_name: String,
+    pub lead_times: Vec<LeadTimeAccuracy>,
+}
+
+/// Record observed race-day conditions for one or more checkpoints.
+#[utoipa::path(
+    post,
+    path = "/api/v1/races/{id}/actual-conditions",
+    tag = "Races",
+    params(
+        ("id" = Uuid, Path, description = "Race UUID"),
+    ),
+    request_body = Vec<ActualConditionInput>,
+    responses(
+        (status = 201, description = "Conditions recorded", body = RecordedConditionsResponse),
+        (status = 400, description = "Empty body, invalid observed_at, or checkpoint not in race", body = ErrorResponseV2),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn record_actual_conditions(
+    State(pool): State<PgPool>,
+    Path(race_id): Path<Uuid>,
+    Json(body): Json<Vec<ActualConditionInput>>,
+) -> Result<(StatusCode, Json<RecordedConditionsResponse>), AppError> {
+    if body.is_empty() {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            "At least one condition is required".to_string(),
+        ));
+    }
+
+    let race = queries::get_race_summary(&pool, race_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::RaceNotFound,
+                format!("Race {} not found", race_id),
+            )
+        })?;
+
+    let race_checkpoints: HashSet<Uuid> = queries::get_checkpoints(&pool, race_id)
+        .await?
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+
+    let params = condition_params(race_id, race.start_time, &race_checkpoints, body)?;
+    let recorded = queries::insert_actual_conditions(&pool, race_id, &params).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(RecordedConditionsResponse { recorded }),
+    ))
+}
+
+/// Validate submitted conditions against the race's checkpoints and convert
+/// them to insert parameters. `observed_at` defaults to `race_start`.
+fn condition_params(
+    race_id: Uuid,
+    race_start: DateTime<Utc>,
+    race_checkpoints: &HashSet<Uuid>,
+    body: Vec<ActualConditionInput>,
+) -> Result<Vec<InsertActualConditionParams>, AppError> {
+    let mut params = Vec::with_capacity(body.len());
+    for c in body {
+        if !race_checkpoints.contains(&c.checkpoint_id) {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!(
+                    "Checkpoint {} does not belong to race {}",
+                    c.checkpoint_id, race_id
+                ),
+            ));
+        }
+        let observed_at: DateTime<Utc> = match c.observed_at {
+            Some(s) => s.parse().map_err(|e| {
+                AppError::BadRequest(
+                    AppErrorCode::InvalidDatetime,
+                    format!("Invalid observed_at: {}", e),
+                )
+            })?,
+            None => race_start,
+        };
+        params.push(InsertActualConditionParams {
+            checkpoint_id: c.checkpoint_id,
+            observed_at,
+            temperature_c: f64_to_decimal_full(c.temperature_c),
+            wind_speed_ms: f64_to_decimal_full(c.wind_speed_ms),
+            precipitation_mm: c.precipitation_mm.map(f64_to_decimal_full),
+            precipitation_type: c.precipitation_type,
+        });
+    }
+    Ok(params)
+}
+
+/// Compare archived forecasts to recorded conditions at fixed lead times.
+///
+/// For each observation and lead time, the forecast closest to the
+/// observation time that was fetched at least that many hours before race
+/// start is used. Only checkpoints with recorded conditions are returned.
+#[utoipa::path(
+    get,
+    path = "/api/v1/races/{id}/forecast-accuracy",
+    tag = "Races",
+    params(
+        ("id" = Uuid, Path, description = "Race UUID"),
+    ),
+    responses(
+        (status = 200, description = "Forecast error per checkpoint and lead time", body = Vec<CheckpointAccuracy>),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_forecast_accuracy(
+    State(pool): State<PgPool>,
+    Path(race_id): Path<Uuid>,
+) -> Result<Json<Vec<CheckpointAccuracy>>, AppError> {
+    let race = queries::get_race_summary(&pool, race_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::RaceNotFound,
+                format!("Race {} not found", race_id),
+            )
+        })?;
+
+    let actuals = queries::get_actual_conditions(&pool, race_id).await?;
+    let checkpoints = queries::get_checkpoints(&pool, race_id).await?;
+
+    let mut result = Vec::new();
+    for cp in checkpoints {
+        let observations: Vec<_> = actuals
+            .iter()
+            .filter(|a| a.checkpoint_id == cp.id)
+            .collect();
+        if observations.is_empty() {
+            continue;
+        }
+
+        let mut lead_times = Vec::with_capacity(ACCURACY_LEAD_TIMES_HOURS.len());
+        for hours_before in ACCURACY_LEAD_TIMES_HOURS {
+            let issued_before = race.start_time - Duration::hours(hours_before);
+            let mut temp_pairs = Vec::new();
+            let mut wind_pairs = Vec::new();
+            for obs in &observations {
+                if let Some(f) = queries::get_forecast_issued_before(
+                    &pool,
+                    cp.id,
+                    obs.observed_at,
+                    issued_before,
+                )
+                .await?
+                {
+                    temp_pairs.push((dec_to_f64(f.temperature_c), dec_to_f64(obs.temperature_c)));
+                    wind_pairs.push((dec_to_f64(f.wind_speed_ms), dec_to_f64(obs.wind_speed_ms)));
+                }
+            }
+            lead_times.push(LeadTimeAccuracy {
+                hours_before,
+                forecasts_compared: temp_pairs.len(),
+                temp_mae_c: mean_absolute_error(&temp_pairs),
+                wind_mae_ms: mean_absolute_error(&wind_pairs),
+            });
+        }
+
+        result.push(CheckpointAccuracy {
+            checkpoint_id: cp.id,
+            checkpoint_name: cp.name,
+            lead_times,
+        });
+    }
+
+    Ok(Json(result))
+}
+
+/// Mean absolute error over `(forecast, actual)` pairs. None if empty.
+fn mean_absolute_error(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.is_empty() {
+        return None;
+    }
+    let total: f64 = pairs.iter().map(|(f, a)| (f - a).abs()).sum();
+    Some(total / pairs.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::api_key::{require_api_key, AdminApiKey, API_KEY_HEADER};
+    use axum::body::Body;
+    use axum::http::{header, Request};
+    use axum::routing::post;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    /// The actual-conditions route as wired in `main`, on a pool that never
+    /// connects (requests below are rejected before any query).
+    fn app() -> Router {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        Router::new()
+            .route(
+                "/api/v1/races/:id/actual-conditions",
+                post(record_actual_conditions).route_layer(axum::middleware::from_fn_with_state(
+                    AdminApiKey::new(Some("s3cret".to_string())),
+                    require_api_key,
+                )),
+            )
+            .with_state(pool)
+    }
+
+    async fn post_conditions(api_key: Option<&str>, body: &str) -> StatusCode {
+        let mut request = Request::post(format!(
+            "/api/v1/races/{}/actual-conditions",
+            Uuid::new_v4()
+        ))
+        .header(header::CONTENT_TYPE, "application/json");
+        if let Some(key) = api_key {
+            request = request.header(API_KEY_HEADER, key);
+        }
+        app()
+            .oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    fn input(checkpoint_id: Uuid, observed_at: Option<&str>) -> ActualConditionInput {
+        ActualConditionInput {
+            checkpoint_id,
+            observed_at: observed_at.map(str::to_string),
+            temperature_c: -8.5,
+            wind_speed_ms: 3.0,
+            precipitation_mm: None,
+            precipitation_type: Some("snow".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_actual_conditions_requires_api_key() {
+        let body = format!(
+            r#"[{{"checkpoint_id":"{}","temperature_c":-5,"wind_speed_ms":2}}]"#,
+            Uuid::new_v4()
+        );
+        assert_eq!(post_conditions(None, &body).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            post_conditions(Some("wrong"), &body).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_actual_conditions_rejects_invalid_body() {
+        let key = Some("s3cret");
+        assert_eq!(post_conditions(key, "[]").await, StatusCode::BAD_REQUEST);
+        // Missing required temperature_c
+        let missing = format!(
+            r#"[{{"checkpoint_id":"{}","wind_speed_ms":2}}]"#,
+            Uuid::new_v4()
+        );
+        assert_eq!(
+            post_conditions(key, &missing).await,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            post_conditions(key, r#"{"not":"a list"}"#).await,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[test]
+    fn test_condition_params_rejects_checkpoint_of_other_race() {
+        let race_id = Uuid::new_v4();
+        let own = Uuid::new_v4();
+        let foreign = Uuid::new_v4();
+        let checkpoints = HashSet::from([own]);
+        let err = condition_params(
+            race_id,
+            Utc::now(),
+            &checkpoints,
+            vec![input(own, None), input(foreign, None)],
+        )
+        .unwrap_err();
+        match err {
+            AppError::BadRequest(AppErrorCode::InvalidParameter, msg) => {
+                assert!(msg.contains(&foreign.to_string()), "{}", msg);
+            }
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_condition_params_observed_at() {
+        let own = Uuid::new_v4();
+        let checkpoints = HashSet::from([own]);
+        let start = "2026-03-01T08:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let params = condition_params(
+            Uuid::nil(),
+            start,
+            &checkpoints,
+            vec![
+                input(own, None),
+                input(own, Some("2026-03-01T10:30:00+01:00")),
+            ],
+        )
+        .unwrap();
+        assert_eq!(params[0].observed_at, start);
+        assert_eq!(params[1].observed_at, start + Duration::minutes(90));
+        assert_eq!(params[0].temperature_c, f64_to_decimal_full(-8.5));
+
+        assert!(matches!(
+            condition_params(
+                Uuid::nil(),
+                start,
+                &checkpoints,
+                vec![input(own, Some("yesterday"))]
+            ),
+            Err(AppError::BadRequest(AppErrorCode::InvalidDatetime, _))
+        ));
+    }
+
+    #[test]
+    fn test_mae_empty_is_none() {
+        assert_eq!(mean_absolute_error(&[]), None);
+    }
+
+    #[test]
+    fn test_mae_uses_absolute_differences() {
+        // Errors of +2 and -4 must not cancel out
+        let mae = mean_absolute_error(&[(-3.0, -5.0), (1.0, 5.0)]).unwrap();
+        assert!((mae - 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_lead_times_descending() {
+        assert!(ACCURACY_LEAD_TIMES_HOURS.windows(2).all(|w| w[0] > w[1]));
+    }
+}
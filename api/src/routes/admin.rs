@@ -0,0 +1,706 @@
+//! Admin HTTP endpoints (require `X-API-Key`).
+//!
+//! POST /api/v1/admin/poller/trigger — force an immediate poll cycle.
+//! POST /api/v1/admin/poller/pause   — stop polling (e.g. during a yr.no outage).
+//! POST /api/v1/admin/poller/resume  — resume polling.
+//! GET  /api/v1/admin/integrity-check — report database consistency problems.
+//! GET  /api/v1/admin/cache/checkpoint/:id/raw — raw cached yr.no response.
+//! GET  /api/v1/admin/stats/popular-checkpoints — most requested checkpoints.
+//! DELETE /api/v1/admin/forecasts — purge old forecast rows.
+//! POST /api/v1/gpx/validate — validate a GPX file without loading it.
+
+use axum::body::Bytes;
+use axum::extract::{FromRequest, Multipart, Path, Query, Request, State};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::Json;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::db::models::{CheckpointRequestCount, YrCacheMetadata};
+use crate::db::queries;
+use crate::errors::{AppError, AppErrorCode, ErrorResponseV2};
+use crate::services::gpx::{validate_gpx, CheckpointIdentifierMode, GpxValidationReport};
+use crate::services::poller::{PollerControl, SharedPollerState, TriggerOutcome};
+
+/// Shared state for admin routes.
+#[derive(Clone)]
+pub(crate) struct AdminState {
+    pub poller_state: SharedPollerState,
+    pub poller_control: PollerControl,
+    pub pool: PgPool,
+    /// GPX checkpoint tags accepted by validation (`CHECKPOINT_IDENTIFIER_MODE`)
+    pub checkpoint_identifier: CheckpointIdentifierMode,
+}
+
+/// Maximum number of IDs listed per failed integrity check.
+const INTEGRITY_DETAILS_LIMIT: usize = 50;
+
+/// Generic acknowledgement body for admin actions.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminMessage {
+    /// Human-readable result
+    pub message: String,
+}
+
+/// Force the background poller to start a poll cycle immediately.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/poller/trigger",
+    tag = "Admin",
+    security(("api_key" = [])),
+    responses(
+        (status = 202, description = "Poll cycle triggered", body = AdminMessage),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+        (status = 409, description = "A poll cycle is already running, or the poller is paused", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn trigger_poll(
+    State(state): State<AdminState>,
+) -> Result<(StatusCode, Json<AdminMessage>), AppError> {
+    match state.poller_control.trigger() {
+        TriggerOutcome::Triggered => {
+            tracing::info!("Manual poll cycle triggered via admin API");
+            Ok((
+                StatusCode::ACCEPTED,
+                Json(AdminMessage {
+                    message: "Poll cycle triggered".to_string(),
+                }),
+            ))
+        }
+        TriggerOutcome::AlreadyRunning => Err(AppError::Conflict(
+            "A poll cycle is already running".to_string(),
+        )),
+        TriggerOutcome::Paused => Err(AppError::Conflict(
+            "Poller is paused; resume it first".to_string(),
+        )),
+        TriggerOutcome::PollerStopped => Err(AppError::InternalError(
+            "Poller trigger channel closed".to_string(),
+        )),
+    }
+}
+
+/// Pause the background poller. Idempotent.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/poller/pause",
+    tag = "Admin",
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Poller paused", body = AdminMessage),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn pause_poller(State(state): State<AdminState>) -> Json<AdminMessage> {
+    let message = if state.poller_control.pause() {
+        let mut s = state.poller_state.write().await;
+        s.paused = true;
+        s.paused_since = Some(Utc::now());
+        tracing::warn!("Poller paused via admin API");
+        "Poller paused"
+    } else {
+        "Poller already paused"
+    };
+    Json(AdminMessage {
+        message: message.to_string(),
+    })
+}
+
+/// Resume a paused background poller. Idempotent.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/poller/resume",
+    tag = "Admin",
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Poller resumed", body = AdminMessage),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn resume_poller(State(state): State<AdminState>) -> Json<AdminMessage> {
+    let message = if state.poller_control.resume() {
+        let mut s = state.poller_state.write().await;
+        s.paused = false;
+        s.paused_since = None;
+        tracing::info!("Poller resumed via admin API");
+        "Poller resumed"
+    } else {
+        "Poller is not paused"
+    };
+    Json(AdminMessage {
+        message: message.to_string(),
+    })
+}
+
+/// One failed integrity check.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IntegrityIssue {
+    /// Check identifier, e.g. "orphan_checkpoints"
+    pub check: String,
+    /// Number of offending rows (or referenced IDs)
+    pub count: usize,
+    /// Offending IDs, truncated to the first 50
+    pub details: Vec<Uuid>,
+}
+
+/// Response for GET /api/v1/admin/integrity-check.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IntegrityReport {
+    /// Only checks that found problems are listed
+    pub issues: Vec<IntegrityIssue>,
+    /// True when every check passed
+    pub healthy: bool,
+}
+
+/// Run database consistency checks.
+///
+/// Checks: checkpoints without a race, `yr_responses` and `forecasts` rows
+/// referencing missing checkpoints, races whose checkpoint `sort_order` is
+/// not contiguous from 0, and races without checkpoints. Always 200 —
+/// inspect `healthy`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/integrity-check",
+    tag = "Admin",
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Integrity report", body = IntegrityReport),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn integrity_check(
+    State(state): State<AdminState>,
+) -> Result<Json<IntegrityReport>, AppError> {
+    let pool = &state.pool;
+    let checks = vec![
+        (
+            "orphan_checkpoints",
+            queries::find_orphan_checkpoints(pool).await?,
+        ),
+        (
+            "orphan_yr_responses",
+            queries::find_orphan_yr_responses(pool).await?,
+        ),
+        (
+            "orphan_forecasts",
+            queries::find_orphan_forecast_checkpoints(pool).await?,
+        ),
+        (
+            "checkpoint_sort_order_gaps",
+            queries::find_races_with_sort_order_gaps(pool).await?,
+        ),
+        (
+            "races_without_checkpoints",
+            queries::find_races_without_checkpoints(pool).await?,
+        ),
+    ];
+
+    let report = build_integrity_report(checks);
+    if !report.healthy {
+        tracing::warn!(
+            "Integrity check found problems: {}",
+            report
+                .issues
+                .iter()
+                .map(|i| format!("{}={}", i.check, i.count))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    Ok(Json(report))
+}
+
+/// Get the raw cached yr.no response for a checkpoint.
+///
+/// Returns the stored `raw_response` JSON unchanged (expired or not), for
+/// debugging forecast extraction without database access.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/cache/checkpoint/{id}/raw",
+    tag = "Admin",
+    security(("api_key" = [])),
+    params(("id" = Uuid, Path, description = "Checkpoint UUID")),
+    responses(
+        (status = 200, description = "Raw yr.no Locationforecast JSON", body = Object,
+         headers(
+             ("X-Fetched-At" = String, description = "When the response was fetched (RFC 3339)"),
+             ("X-Expires-At" = String, description = "yr.no Expires time (RFC 3339)"),
+             ("X-Last-Modified" = String, description = "yr.no Last-Modified header, if any")
+         )),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+        (status = 404, description = "No cached response for the checkpoint", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn get_raw_yr_cache(
+    State(state): State<AdminState>,
+    Path(checkpoint_id): Path<Uuid>,
+) -> Result<(HeaderMap, Json<serde_json::Value>), AppError> {
+    let (raw, metadata) = queries::get_yr_raw_response(&state.pool, checkpoint_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::CacheNotFound,
+                format!("No cached yr.no response for checkpoint {}", checkpoint_id),
+            )
+        })?;
+    Ok((cache_metadata_headers(&metadata), Json(raw)))
+}
+
+/// Default and maximum `limit` for the popular-checkpoints endpoint.
+const DEFAULT_POPULAR_CHECKPOINTS: i64 = 10;
+const MAX_POPULAR_CHECKPOINTS: i64 = 100;
+
+/// Query parameters for GET /api/v1/admin/stats/popular-checkpoints.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PopularCheckpointsQuery {
+    /// Number of checkpoints to return (1–100, default 10)
+    pub limit: Option<i64>,
+}
+
+impl PopularCheckpointsQuery {
+    /// The requested limit, defaulted and range-checked.
+    fn limit(&self) -> Result<i64, AppError> {
+        let limit = self.limit.unwrap_or(DEFAULT_POPULAR_CHECKPOINTS);
+        if !(1..=MAX_POPULAR_CHECKPOINTS).contains(&limit) {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!("limit must be between 1 and {}", MAX_POPULAR_CHECKPOINTS),
+            ));
+        }
+        Ok(limit)
+    }
+}
+
+/// A checkpoint with its forecast request count.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PopularCheckpoint {
+    pub checkpoint_id: Uuid,
+    pub checkpoint_name: String,
+    pub race_id: Uuid,
+    pub race_name: String,
+    pub race_year: i32,
+    /// Checkpoint forecast and forecast history requests
+    pub request_count: i64,
+    /// When the checkpoint was last requested (RFC 3339)
+    pub last_requested_at: String,
+}
+
+impl From<CheckpointRequestCount> for PopularCheckpoint {
+    fn from(c: CheckpointRequestCount) -> Self {
+        Self {
+            checkpoint_id: c.checkpoint_id,
+            checkpoint_name: c.checkpoint_name,
+            race_id: c.race_id,
+            race_name: c.race_name,
+            race_year: c.race_year,
+            request_count: c.request_count,
+            last_requested_at: c.last_requested_at.to_rfc3339(),
+        }
+    }
+}
+
+/// List the most requested checkpoints.
+///
+/// Counts requests to the checkpoint forecast and forecast history
+/// endpoints, most requested first. Useful for capacity planning and for
+/// deciding which checkpoints deserve a higher poll priority.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/stats/popular-checkpoints",
+    tag = "Admin",
+    security(("api_key" = [])),
+    params(PopularCheckpointsQuery),
+    responses(
+        (status = 200, description = "Most requested checkpoints", body = Vec<PopularCheckpoint>),
+        (status = 400, description = "limit out of range", body = ErrorResponseV2),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn get_popular_checkpoints(
+    State(state): State<AdminState>,
+    Query(params): Query<PopularCheckpointsQuery>,
+) -> Result<Json<Vec<PopularCheckpoint>>, AppError> {
+    let checkpoints = queries::get_popular_checkpoints(&state.pool, params.limit()?)
+        .await?
+        .into_iter()
+        .map(PopularCheckpoint::from)
+        .collect();
+    Ok(Json(checkpoints))
+}
+
+/// Query parameters for DELETE /api/v1/admin/forecasts.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PurgeForecastsQuery {
+    /// Delete forecasts fetched more than this many days ago (at least 1)
+    pub older_than_days: i32,
+}
+
+impl PurgeForecastsQuery {
+    /// The requested age, range-checked so a purge never deletes today's data.
+    fn older_than_days(&self) -> Result<i32, AppError> {
+        if self.older_than_days < 1 {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                "older_than_days must be at least 1".to_string(),
+            ));
+        }
+        Ok(self.older_than_days)
+    }
+}
+
+/// Result of a forecast purge.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PurgedForecasts {
+    /// Number of forecast rows deleted
+    pub deleted: u64,
+}
+
+/// Delete forecast rows older than `older_than_days`.
+///
+/// Forecasts of checkpoints with recorded actual conditions or observations
+/// are kept. The
+/// same purge runs daily in the background when
+/// `FORECAST_RETENTION_DAYS` is set.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/forecasts",
+    tag = "Admin",
+    security(("api_key" = [])),
+    params(PurgeForecastsQuery),
+    responses(
+        (status = 200, description = "Number of forecasts deleted", body = PurgedForecasts),
+        (status = 400, description = "Missing or invalid older_than_days", body = ErrorResponseV2),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn purge_old_forecasts(
+    State(state): State<AdminState>,
+    Query(params): Query<PurgeForecastsQuery>,
+) -> Result<Json<PurgedForecasts>, AppError> {
+    let older_than_days = params.older_than_days()?;
+    let deleted = queries::delete_old_forecasts(&state.pool, older_than_days).await?;
+    tracing::info!(
+        "Admin: deleted {} forecasts older than {} days",
+        deleted,
+        older_than_days
+    );
+    Ok(Json(PurgedForecasts { deleted }))
+}
+
+/// Validate a GPX file without loading it.
+///
+/// Accepts `multipart/form-data` with the file in a `file` field, or the raw
+/// GPX document as the body. Reports all errors `parse_gpx` would reject,
+/// plus warnings (checkpoints without elevation, coordinates with fewer than
+/// 4 decimal places, no track points). Always 200 for readable input —
+/// inspect `valid`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/gpx/validate",
+    tag = "Admin",
+    security(("api_key" = [])),
+    request_body(
+        description = "GPX file as multipart field `file`, or the raw GPX XML",
+        content((String = "multipart/form-data"), (String = "application/gpx+xml"))
+    ),
+    responses(
+        (status = 200, description = "Validation report", body = GpxValidationReport),
+        (status = 400, description = "Body is not UTF-8 or has no `file` part", body = ErrorResponseV2),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+        (status = 413, description = "File larger than the admin body limit", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn validate_gpx_upload(
+    State(state): State<AdminState>,
+    request: Request,
+) -> Result<Json<GpxValidationReport>, AppError> {
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("multipart/form-data"));
+    let body = if is_multipart {
+        let multipart = Multipart::from_request(request, &state)
+            .await
+            .map_err(|e| body_error(e.status(), e.body_text()))?;
+        multipart_file(multipart).await?
+    } else {
+        Bytes::from_request(request, &state)
+            .await
+            .map_err(|e| body_error(e.status(), e.body_text()))?
+    };
+    let content = String::from_utf8(body.to_vec()).map_err(|_| {
+        AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            "GPX file must be UTF-8".to_string(),
+        )
+    })?;
+
+    // GPX parsing is CPU-bound — run on the blocking thread pool
+    let identifier = state.checkpoint_identifier;
+    let report = tokio::task::spawn_blocking(move || validate_gpx(&content, identifier))
+        .await
+        .map_err(|e| AppError::InternalError(format!("GPX validation task failed: {}", e)))?;
+    Ok(Json(report))
+}
+
+/// Content of the `file` part of a `multipart/form-data` body.
+async fn multipart_file(mut multipart: Multipart) -> Result<Bytes, AppError> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| body_error(e.status(), e.body_text()))?
+    {
+        if field.name() == Some("file") {
+            return field
+                .bytes()
+                .await
+                .map_err(|e| body_error(e.status(), e.body_text()));
+        }
+    }
+    Err(AppError::BadRequest(
+        AppErrorCode::InvalidParameter,
+        "multipart body has no 'file' part".to_string(),
+    ))
+}
+
+/// An error for an unreadable request body: 413 when it exceeds the body
+/// limit, 400 otherwise.
+fn body_error(status: StatusCode, message: String) -> AppError {
+    if status == StatusCode::PAYLOAD_TOO_LARGE {
+        AppError::RequestTooLarge(message)
+    } else {
+        AppError::BadRequest(AppErrorCode::InvalidParameter, message)
+    }
+}
+
+/// `X-Fetched-At` / `X-Expires-At` / `X-Last-Modified` headers for a cache row.
+fn cache_metadata_headers(metadata: &YrCacheMetadata) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let mut insert = |name: &'static str, value: &str| {
+        if let Ok(value) = HeaderValue::from_str(value) {
+            headers.insert(HeaderName::from_static(name), value);
+        }
+    };
+    insert("x-fetched-at", &metadata.fetched_at.to_rfc3339());
+    insert("x-expires-at", &metadata.expires_at.to_rfc3339());
+    if let Some(last_modified) = &metadata.last_modified {
+        insert("x-last-modified", last_modified);
+    }
+    headers
+}
+
+/// Turn `(check, offending IDs)` pairs into a report, keeping only failures.
+fn build_integrity_report(checks: Vec<(&str, Vec<Uuid>)>) -> IntegrityReport {
+    let issues: Vec<IntegrityIssue> = checks
+        .into_iter()
+        .filter(|(_, ids)| !ids.is_empty())
+        .map(|(check, mut ids)| {
+            let count = ids.len();
+            ids.truncate(INTEGRITY_DETAILS_LIMIT);
+            IntegrityIssue {
+                check: check.to_string(),
+                count,
+                details: ids,
+            }
+        })
+        .collect();
+    IntegrityReport {
+        healthy: issues.is_empty(),
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::api_key::{require_api_key, AdminApiKey, API_KEY_HEADER};
+    use crate::services::poller::{poller_control, PollerState};
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::routing::{delete, get};
+    use axum::Router;
+    use chrono::TimeZone;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_cache_metadata_headers() {
+        let metadata = YrCacheMetadata {
+            fetched_at: Utc.with_ymd_and_hms(2026, 2, 28, 10, 0, 0).unwrap(),
+            expires_at: Utc.with_ymd_and_hms(2026, 2, 28, 10, 30, 0).unwrap(),
+            last_modified: Some("Sat, 28 Feb 2026 09:58:00 GMT".to_string()),
+        };
+        let headers = cache_metadata_headers(&metadata);
+        assert_eq!(headers["X-Fetched-At"], "2026-02-28T10:00:00+00:00");
+        assert_eq!(headers["X-Expires-At"], "2026-02-28T10:30:00+00:00");
+        assert_eq!(headers["X-Last-Modified"], "Sat, 28 Feb 2026 09:58:00 GMT");
+
+        let headers = cache_metadata_headers(&YrCacheMetadata {
+            last_modified: None,
+            ..metadata
+        });
+        assert!(!headers.contains_key("X-Last-Modified"));
+    }
+
+    #[test]
+    fn test_popular_checkpoint_from_request_count() {
+        let row = CheckpointRequestCount {
+            checkpoint_id: Uuid::new_v4(),
+            checkpoint_name: "Evertsberg".to_string(),
+            race_id: Uuid::new_v4(),
+            race_name: "Vasaloppet".to_string(),
+            race_year: 2026,
+            request_count: 42,
+            last_requested_at: Utc.with_ymd_and_hms(2026, 2, 28, 10, 30, 0).unwrap(),
+        };
+        let json = serde_json::to_value(PopularCheckpoint::from(row.clone())).unwrap();
+        assert_eq!(json["checkpoint_id"], row.checkpoint_id.to_string());
+        assert_eq!(json["checkpoint_name"], "Evertsberg");
+        assert_eq!(json["race_id"], row.race_id.to_string());
+        assert_eq!(json["race_name"], "Vasaloppet");
+        assert_eq!(json["race_year"], 2026);
+        assert_eq!(json["request_count"], 42);
+        assert_eq!(json["last_requested_at"], "2026-02-28T10:30:00+00:00");
+    }
+
+    /// Extract the multipart body of a request with `body` and boundary `XyZ`.
+    async fn multipart(body: &'static str) -> Multipart {
+        let request = Request::post("/api/v1/gpx/validate")
+            .header(header::CONTENT_TYPE, "multipart/form-data; boundary=XyZ")
+            .body(Body::from(body))
+            .unwrap();
+        Multipart::from_request(request, &()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_multipart_file() {
+        let body = multipart(
+            "--XyZ\r\n\
+             Content-Disposition: form-data; name=\"note\"\r\n\r\n\
+             hello\r\n\
+             --XyZ\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"race.gpx\"\r\n\
+             Content-Type: application/gpx+xml\r\n\r\n\
+             <gpx></gpx>\r\n\
+             --XyZ--\r\n",
+        )
+        .await;
+        assert_eq!(multipart_file(body).await.unwrap(), "<gpx></gpx>");
+
+        let body = multipart(
+            "--XyZ\r\n\
+             Content-Disposition: form-data; name=\"note\"\r\n\r\n\
+             hello\r\n\
+             --XyZ--\r\n",
+        )
+        .await;
+        assert!(matches!(
+            multipart_file(body).await,
+            Err(AppError::BadRequest(AppErrorCode::InvalidParameter, _))
+        ));
+    }
+
+    #[test]
+    fn test_integrity_report_healthy_when_no_ids() {
+        let report = build_integrity_report(vec![("a", vec![]), ("b", vec![])]);
+        assert!(report.healthy);
+        assert!(report.issues.is_empty());
+    }
+
+    #[test]
+    fn test_integrity_report_truncates_details_but_counts_all() {
+        let ids: Vec<Uuid> = (0..60).map(|_| Uuid::new_v4()).collect();
+        let report =
+            build_integrity_report(vec![("a", vec![]), ("races_without_checkpoints", ids)]);
+        assert!(!report.healthy);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].check, "races_without_checkpoints");
+        assert_eq!(report.issues[0].count, 60);
+        assert_eq!(report.issues[0].details.len(), INTEGRITY_DETAILS_LIMIT);
+    }
+
+    #[test]
+    fn test_popular_checkpoints_limit() {
+        let query = |limit| PopularCheckpointsQuery { limit };
+        assert_eq!(query(None).limit().unwrap(), 10);
+        assert_eq!(query(Some(100)).limit().unwrap(), 100);
+        assert!(query(Some(0)).limit().is_err());
+        assert!(query(Some(101)).limit().is_err());
+    }
+
+    #[test]
+    fn test_purge_forecasts_older_than_days() {
+        let query = |older_than_days| PurgeForecastsQuery { older_than_days };
+        assert_eq!(query(30).older_than_days().unwrap(), 30);
+        assert_eq!(query(1).older_than_days().unwrap(), 1);
+        assert!(matches!(
+            query(0).older_than_days(),
+            Err(AppError::BadRequest(AppErrorCode::InvalidParameter, _))
+        ));
+        assert!(query(-5).older_than_days().is_err());
+    }
+
+    /// Admin routes as wired in `main`, on a pool that never connects
+    /// (requests below are rejected before any query).
+    fn admin_app() -> Router {
+        let state = AdminState {
+            poller_state: Arc::new(RwLock::new(PollerState::new())),
+            poller_control: poller_control().0,
+            pool: PgPool::connect_lazy("postgres://localhost/unused").unwrap(),
+            checkpoint_identifier: CheckpointIdentifierMode::default(),
+        };
+        Router::new()
+            .route(
+                "/api/v1/admin/stats/popular-checkpoints",
+                get(get_popular_checkpoints),
+            )
+            .route("/api/v1/admin/forecasts", delete(purge_old_forecasts))
+            .route_layer(axum::middleware::from_fn_with_state(
+                AdminApiKey::new(Some("s3cret".to_string())),
+                require_api_key,
+            ))
+            .with_state(state)
+    }
+
+    async fn send(request: axum::http::request::Builder, api_key: Option<&str>) -> StatusCode {
+        let request = match api_key {
+            Some(key) => request.header(API_KEY_HEADER, key),
+            None => request,
+        };
+        admin_app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    async fn purge(api_key: Option<&str>, older_than_days: i32) -> StatusCode {
+        let uri = format!("/api/v1/admin/forecasts?older_than_days={older_than_days}");
+        send(Request::delete(uri), api_key).await
+    }
+
+    async fn popular(api_key: Option<&str>, limit: i64) -> StatusCode {
+        let uri = format!("/api/v1/admin/stats/popular-checkpoints?limit={limit}");
+        send(Request::get(uri), api_key).await
+    }
+
+    #[tokio::test]
+    async fn test_purge_old_forecasts_requires_api_key() {
+        assert_eq!(purge(None, 30).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(purge(Some("wrong"), 30).await, StatusCode::UNAUTHORIZED);
+        // With the key, an invalid age is rejected before touching the database
+        assert_eq!(purge(Some("s3cret"), 0).await, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_popular_checkpoints_requires_api_key() {
+        assert_eq!(popular(None, 10).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(popular(Some("wrong"), 10).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(popular(Some("s3cret"), 0).await, StatusCode::BAD_REQUEST);
+        assert_eq!(popular(Some("s3cret"), 101).await, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,304 @@
+//! Checkpoint HTTP endpoints.
+//!
+//! - GET /api/v1/checkpoints/:id/nearby-resorts?radius_km=5
+//! - POST /api/v1/checkpoints/:id/observations (requires `X-API-Key`)
+//! - GET /api/v1/checkpoints/:id/observations?from=ISO8601
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::db::models::CheckpointObservation;
+use crate::db::queries::{self, InsertObservationParams};
+use crate::errors::{AppError, AppErrorCode, ErrorResponseV2};
+use crate::helpers::{dec_to_f64, f64_to_decimal_full};
+use crate::services::forecast::get_checkpoint;
+use crate::services::resort::{resorts_within, NearbyResort, Resort};
+
+/// Default search radius for nearby resorts (km).
+const DEFAULT_RESORT_RADIUS_KM: f64 = 5.0;
+
+/// Largest accepted search radius for nearby resorts (km).
+const MAX_RESORT_RADIUS_KM: f64 = 100.0;
+
+/// Shared state for checkpoint endpoints.
+#[derive(Clone)]
+pub(crate) struct CheckpointState {
+    pub(crate) pool: PgPool,
+    pub(crate) resorts: Arc<Vec<Resort>>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct NearbyResortsQuery {
+    /// Search radius in km (default 5, max 100)
+    pub radius_km: Option<f64>,
+}
+
+/// List known ski resorts near a checkpoint, nearest first.
+///
+/// Resorts come from the static `RESORT_DATA_FILE` loaded at startup.
+#[utoipa::path(
+    get,
+    path = "/api/v1/checkpoints/{id}/nearby-resorts",
+    tag = "Races",
+    params(
+        ("id" = Uuid, Path, description = "Checkpoint UUID"),
+        NearbyResortsQuery,
+    ),
+    responses(
+        (status = 200, description = "Resorts within the radius, nearest first", body = Vec<NearbyResort>),
+        (status = 400, description = "radius_km out of range", body = ErrorResponseV2),
+        (status = 404, description = "Checkpoint not found", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn get_nearby_resorts(
+    State(state): State<CheckpointState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<NearbyResortsQuery>,
+) -> Result<Json<Vec<NearbyResort>>, AppError> {
+    let radius_km = params.radius_km.unwrap_or(DEFAULT_RESORT_RADIUS_KM);
+    if !(radius_km > 0.0 && radius_km <= MAX_RESORT_RADIUS_KM) {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            format!(
+                "radius_km must be between 0 (exclusive) and {}",
+                MAX_RESORT_RADIUS_KM
+            ),
+        ));
+    }
+    let checkpoint = get_checkpoint(&state.pool, id).await?;
+    Ok(Json(resorts_within(
+        &state.resorts,
+        dec_to_f64(checkpoint.latitude),
+        dec_to_f64(checkpoint.longitude),
+        radius_km,
+    )))
+}
+
+/// Accepted values for an observation's `precipitation_type`.
+const OBSERVATION_PRECIPITATION_TYPES: [&str; 4] = ["snow", "rain", "sleet", "none"];
+
+/// Request body for POST /api/v1/checkpoints/:id/observations.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateObservationRequest {
+    /// Observation time in ISO 8601 / RFC 3339 format
+    pub observed_at: String,
+    /// Observed air temperature in °C
+    pub temperature_c: f64,
+    /// Observed wind speed in m/s
+    pub wind_speed_ms: f64,
+    /// Observed precipitation in mm
+    pub precipitation_mm: f64,
+    /// "snow", "rain", "sleet" or "none"
+    pub precipitation_type: String,
+    /// Free-text remarks (e.g. "fresh snow on track")
+    pub notes: Option<String>,
+}
+
+/// A stored checkpoint observation.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ObservationResponse {
+    pub id: Uuid,
+    pub checkpoint_id: Uuid,
+    /// Observation time (RFC 3339)
+    pub observed_at: String,
+    pub temperature_c: f64,
+    pub wind_speed_ms: f64,
+    pub precipitation_mm: f64,
+    pub precipitation_type: String,
+    pub notes: Option<String>,
+    /// When the observation was submitted (RFC 3339)
+    pub created_at: String,
+}
+
+impl From<CheckpointObservation> for ObservationResponse {
+    fn from(o: CheckpointObservation) -> Self {
+        Self {
+            id: o.id,
+            checkpoint_id: o.checkpoint_id,
+            observed_at: o.observed_at.to_rfc3339(),
+            temperature_c: dec_to_f64(o.temperature_c),
+            wind_speed_ms: dec_to_f64(o.wind_speed_ms),
+            precipitation_mm: dec_to_f64(o.precipitation_mm),
+            precipitation_type: o.precipitation_type,
+            notes: o.notes,
+            created_at: o.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ObservationsQuery {
+    /// Only observations at or after this time (ISO 8601)
+    pub from: Option<String>,
+}
+
+/// Validate an observation body and convert it to insert parameters.
+fn observation_params(
+    checkpoint_id: Uuid,
+    body: CreateObservationRequest,
+) -> Result<InsertObservationParams, AppError> {
+    let observed_at: DateTime<Utc> = body.observed_at.parse().map_err(|e| {
+        AppError::BadRequest(
+            AppErrorCode::InvalidDatetime,
+            format!("Invalid observed_at: {}", e),
+        )
+    })?;
+    let invalid = |msg: &str| AppError::BadRequest(AppErrorCode::InvalidParameter, msg.to_string());
+    if !body.temperature_c.is_finite() {
+        return Err(invalid("temperature_c must be a finite number"));
+    }
+    if !(body.wind_speed_ms.is_finite() && body.wind_speed_ms >= 0.0) {
+        return Err(invalid("wind_speed_ms must be a non-negative number"));
+    }
+    if !(body.precipitation_mm.is_finite() && body.precipitation_mm >= 0.0) {
+        return Err(invalid("precipitation_mm must be a non-negative number"));
+    }
+    if !OBSERVATION_PRECIPITATION_TYPES.contains(&body.precipitation_type.as_str()) {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            format!(
+                "precipitation_type must be one of: {}",
+                OBSERVATION_PRECIPITATION_TYPES.join(", ")
+            ),
+        ));
+    }
+
+    Ok(InsertObservationParams {
+        checkpoint_id,
+        observed_at,
+        temperature_c: f64_to_decimal_full(body.temperature_c),
+        wind_speed_ms: f64_to_decimal_full(body.wind_speed_ms),
+        precipitation_mm: f64_to_decimal_full(body.precipitation_mm),
+        precipitation_type: body.precipitation_type,
+        notes: body.notes.filter(|n| !n.trim().is_empty()),
+    })
+}
+
+/// Submit an actual weather observation for a checkpoint.
+///
+/// Observations are included when scoring forecasts in
+/// `GET /api/v1/races/:id/forecast-accuracy`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/checkpoints/{id}/observations",
+    tag = "Races",
+    params(
+        ("id" = Uuid, Path, description = "Checkpoint UUID"),
+    ),
+    request_body = CreateObservationRequest,
+    responses(
+        (status = 201, description = "Observation stored", body = ObservationResponse),
+        (status = 400, description = "Invalid observed_at or values", body = ErrorResponseV2),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+        (status = 404, description = "Checkpoint not found", body = ErrorResponseV2),
+    ),
+    security(("api_key" = [])),
+)]
+pub(crate) async fn create_observation(
+    State(state): State<CheckpointState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CreateObservationRequest>,
+) -> Result<(StatusCode, Json<ObservationResponse>), AppError> {
+    let params = observation_params(id, body)?;
+    get_checkpoint(&state.pool, id).await?;
+    let observation = queries::insert_checkpoint_observation(&state.pool, &params).await?;
+    Ok((StatusCode::CREATED, Json(observation.into())))
+}
+
+/// List observations submitted for a checkpoint, oldest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/checkpoints/{id}/observations",
+    tag = "Races",
+    params(
+        ("id" = Uuid, Path, description = "Checkpoint UUID"),
+        ObservationsQuery,
+    ),
+    responses(
+        (status = 200, description = "Observations for the checkpoint", body = Vec<ObservationResponse>),
+        (status = 400, description = "Invalid from datetime", body = ErrorResponseV2),
+        (status = 404, description = "Checkpoint not found", body = ErrorResponseV2),
+    )
+)]
+pub(crate) async fn list_observations(
+    State(state): State<CheckpointState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ObservationsQuery>,
+) -> Result<Json<Vec<ObservationResponse>>, AppError> {
+    let from: Option<DateTime<Utc>> = params
+        .from
+        .map(|s| {
+            s.parse().map_err(|e| {
+                AppError::BadRequest(
+                    AppErrorCode::InvalidDatetime,
+                    format!("Invalid from: {}", e),
+                )
+            })
+        })
+        .transpose()?;
+    get_checkpoint(&state.pool, id).await?;
+    let observations = queries::get_checkpoint_observations(&state.pool, id, from).await?;
+    Ok(Json(observations.into_iter().map(Into::into).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body() -> CreateObservationRequest {
+        CreateObservationRequest {
+            observed_at: "2026-03-01T09:30:00+01:00".to_string(),
+            temperature_c: -6.5,
+            wind_speed_ms: 3.2,
+            precipitation_mm: 0.4,
+            precipitation_type: "snow".to_string(),
+            notes: Some("  ".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_observation_params_valid() {
+        let params = observation_params(Uuid::nil(), body()).unwrap();
+        assert_eq!(
+            params.observed_at,
+            "2026-03-01T08:30:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(params.precipitation_type, "snow");
+        // Blank notes are dropped
+        assert_eq!(params.notes, None);
+    }
+
+    #[test]
+    fn test_observation_params_rejects_invalid_values() {
+        let bad_time = CreateObservationRequest {
+            observed_at: "yesterday".to_string(),
+            ..body()
+        };
+        assert!(matches!(
+            observation_params(Uuid::nil(), bad_time),
+            Err(AppError::BadRequest(AppErrorCode::InvalidDatetime, _))
+        ));
+
+        let bad_type = CreateObservationRequest {
+            precipitation_type: "hail".to_string(),
+            ..body()
+        };
+        assert!(matches!(
+            observation_params(Uuid::nil(), bad_type),
+            Err(AppError::BadRequest(AppErrorCode::InvalidParameter, _))
+        ));
+
+        let negative_wind = CreateObservationRequest {
+            wind_speed_ms: -1.0,
+            ..body()
+        };
+        assert!(observation_params(Uuid::nil(), negative_wind).is_err());
+    }
+}
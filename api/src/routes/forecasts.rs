@@ -2,35 +2,77 @@
 //!
 //! - GET /api/v1/forecasts/checkpoint/:checkpoint_id?datetime=ISO8601
 //! - GET /api/v1/forecasts/checkpoint/:checkpoint_id/history?datetime=ISO8601
+//! - GET /api/v1/forecasts/checkpoint/:checkpoint_id/export?from=ISO8601&to=ISO8601
 //! - GET /api/v1/forecasts/race/:race_id?target_duration_hours=N
+//! - GET /api/v1/forecasts/race/:race_id/vs-historical?target_duration_hours=N
+//! - GET /api/v1/forecasts/race/:race_id/precipitation-forecast?target_duration_hours=N
 
+use axum::body::Body;
 use axum::extract::{Path, Query, State};
-use axum::http::HeaderMap;
-use axum::Json;
+use axum::http::{header, HeaderMap};
+use axum::response::{IntoResponse, Response};
+use axum::{BoxError, Json};
 use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::db::models::SunTimes;
 use crate::db::{models, queries};
-use crate::errors::{AppError, ErrorResponse};
-use crate::helpers::{dec_to_f64, opt_dec_to_f64};
+use crate::errors::{AppError, AppErrorCode, ErrorResponseV2};
+use crate::helpers::{dec_to_f64, estimate_next_model_run, opt_dec_to_f64};
+use crate::services::climate::match_historical_average;
+use crate::services::extraction_cache::ExtractionCache;
 
 /// Maximum allowed value for `target_duration_hours` query parameter (3 days).
 const MAX_TARGET_DURATION_HOURS: f64 = 72.0;
+
+/// Maximum number of datetimes per multi-datetime checkpoint forecast request.
+const MAX_MULTI_DATETIMES: usize = 12;
 use crate::services::forecast::{
-    calculate_pass_time_fractions, calculate_pass_time_weighted, compute_pacing_profile,
-    get_checkpoint, interpolate_fraction_from_profile, resolve_forecast, resolve_race_forecasts,
-    CheckpointWithTime, PacingCheckpoint,
+    accumulate_snowfall, assess_blowing_snow, assess_forecast_confidence,
+    calculate_pass_time_fractions, calculate_pass_time_weighted, calculate_uv_exposure,
+    check_temperature_lapse_consistency, checkpoint_range, classify_lightning_risk,
+    classify_precipitation_intensity, compute_pacing_profile, compute_timeline_slots,
+    compute_wind_rose, difficulty_label, enrich_pacing_checkpoints_with_haversine, get_checkpoint,
+    interpolate_fraction_from_profile, partial_pass_times, percentile_spread, rate_race_difficulty,
+    rebase_fractions, resolve_forecast, resolve_forecasts_at_times, resolve_race_forecasts,
+    start_at_checkpoint_index, CheckpointWithTime, LapseRateSample, LapseRateWarning,
+    PacingCheckpoint, PacingParams, ResolvedForecast, WindSector, WIND_ROSE_LABELS,
+    WIND_ROSE_SECTORS,
 };
 use crate::services::gpx::{compute_track_profile, extract_track_points};
-use crate::services::yr::YrClient;
+use crate::services::pacing::{
+    calculate_pass_time_fractions_with_fatigue, FatiguePacingModel, PacingModel,
+};
+use crate::services::poller::{
+    compute_extraction_times, model_run_age_minutes, nearest_time_slot, PollerConfig,
+};
+use crate::services::sunrise::{dawn_minutes_before, SunriseClient};
+use crate::services::yr::{ForecastResolution, YrClient};
+
+use super::sparse::{FieldsQuery, Sparse};
 
 /// Shared application state for forecast endpoints.
 #[derive(Clone)]
 pub(crate) struct AppState {
     pub(crate) pool: sqlx::PgPool,
     pub(crate) yr_client: YrClient,
+    pub(crate) extraction_cache: Arc<ExtractionCache>,
+    pub(crate) sunrise_client: SunriseClient,
+    /// Speed bounds for the per-checkpoint pass-through window.
+    pub(crate) poller_config: PollerConfig,
+    /// Gradient cost constants for elevation-adjusted pacing.
+    pub(crate) pacing_params: PacingParams,
+    /// Fatigue factor for `?pacing_model=fatigue`.
+    pub(crate) pacing_fatigue_factor: f64,
+    /// Forecast resolutions slower than this log a timing breakdown.
+    pub(crate) slow_request_threshold_ms: u64,
+    /// Include `total_request_ms` in race forecast responses.
+    pub(crate) debug_timing: bool,
 }
 
 // ---------------------------------------------------------------------------
@@ -41,6 +83,107 @@ pub(crate) struct AppState {
 pub struct ForecastQuery {
     /// Target datetime in ISO 8601 format (e.g. "2026-03-01T08:00:00Z")
     pub datetime: String,
+    /// History only: keep entries fetched at or after this time (ISO 8601)
+    pub since: Option<String>,
+    /// History only: keep entries fetched at or before this time (ISO 8601)
+    pub until: Option<String>,
+    /// `six_hourly` (default) accepts all data; `hourly` treats times covered
+    /// only by 6-hourly yr.no data as unavailable
+    #[serde(default = "default_min_resolution")]
+    #[param(value_type = Option<ForecastResolution>)]
+    pub min_resolution: ForecastResolution,
+}
+
+fn default_min_resolution() -> ForecastResolution {
+    ForecastResolution::SixHourly
+}
+
+/// Optional `(since, until)` bounds of a history query.
+type HistoryBounds = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+impl ForecastQuery {
+    /// The history `since`/`until` bounds; `since` must be before `until`
+    /// when both are given.
+    fn history_bounds(&self) -> Result<HistoryBounds, AppError> {
+        let since = parse_bound("since", self.since.as_deref())?;
+        let until = parse_bound("until", self.until.as_deref())?;
+        if let (Some(since), Some(until)) = (since, until) {
+            if since >= until {
+                return Err(AppError::BadRequest(
+                    AppErrorCode::InvalidDatetime,
+                    "since must be before until".to_string(),
+                ));
+            }
+        }
+        Ok((since, until))
+    }
+}
+
+/// Parse an optional ISO 8601 query parameter called `name`.
+fn parse_bound(name: &str, value: Option<&str>) -> Result<Option<DateTime<Utc>>, AppError> {
+    value
+        .map(|v| {
+            v.parse::<DateTime<Utc>>().map_err(|e| {
+                AppError::BadRequest(
+                    AppErrorCode::InvalidDatetime,
+                    format!("Invalid {}: {}", name, e),
+                )
+            })
+        })
+        .transpose()
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ForecastExportQuery {
+    /// Start of the `fetched_at` range, ISO 8601 (inclusive)
+    pub from: String,
+    /// End of the `fetched_at` range, ISO 8601 (inclusive)
+    pub to: String,
+    /// Output format; only "ndjson" (the default) is supported
+    pub format: Option<String>,
+}
+
+impl ForecastExportQuery {
+    /// The parsed `fetched_at` range; `from` may equal `to` but not follow it.
+    fn range(&self) -> Result<(DateTime<Utc>, DateTime<Utc>), AppError> {
+        let from: DateTime<Utc> = self.from.parse().map_err(|e| {
+            AppError::BadRequest(
+                AppErrorCode::InvalidDatetime,
+                format!("Invalid from: {}", e),
+            )
+        })?;
+        let to: DateTime<Utc> = self.to.parse().map_err(|e| {
+            AppError::BadRequest(AppErrorCode::InvalidDatetime, format!("Invalid to: {}", e))
+        })?;
+        if from > to {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidDatetime,
+                "from must not be after to".to_string(),
+            ));
+        }
+        Ok((from, to))
+    }
+}
+
+/// Default and maximum look-back (days) for the wind rose.
+const DEFAULT_WIND_ROSE_DAYS: u32 = 7;
+const MAX_WIND_ROSE_DAYS: u32 = 30;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct WindRoseQuery {
+    /// Aggregate forecasts fetched in the last N days (1–30, default 7)
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct MultiForecastQuery {
+    /// Comma-separated ISO 8601 datetimes, at most 12
+    /// (e.g. "2026-03-01T07:00:00Z,2026-03-01T08:00:00Z")
+    pub datetimes: String,
+    /// `six_hourly` (default) or `hourly` (see the single-datetime endpoint)
+    #[serde(default = "default_min_resolution")]
+    #[param(value_type = Option<ForecastResolution>)]
+    pub min_resolution: ForecastResolution,
 }
 
 #[derive(Debug, Deserialize, IntoParams)]
@@ -49,6 +192,107 @@ pub struct RaceForecastQuery {
     pub target_duration_hours: f64,
 }
 
+/// Pacing model selection for the race forecast.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PacingModelQuery {
+    /// `elevation` (default) or `fatigue` (late segments cost more time)
+    #[serde(default)]
+    pub pacing_model: PacingModel,
+}
+
+/// Target durations from which the race forecast defaults to 2-hour slots.
+const TWO_HOUR_SLOTS_FROM_DURATION_HOURS: f64 = 10.0;
+
+/// Forecast slot interval for the race forecast.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TimeSlotQuery {
+    /// 1 (each checkpoint's exact pass-through time) or 2 (forecasts at the
+    /// nearest even hour from the start). Defaults to 2 for
+    /// `target_duration_hours` >= 10, otherwise 1.
+    pub time_slot_interval_hours: Option<u32>,
+}
+
+impl TimeSlotQuery {
+    /// The requested interval, or the default for `target_duration_hours`.
+    fn interval_hours(&self, target_duration_hours: f64) -> Result<u32, AppError> {
+        match self.time_slot_interval_hours {
+            Some(hours @ (1 | 2)) => Ok(hours),
+            Some(hours) => Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!("time_slot_interval_hours must be 1 or 2, got {}", hours),
+            )),
+            None if target_duration_hours >= TWO_HOUR_SLOTS_FROM_DURATION_HOURS => Ok(2),
+            None => Ok(1),
+        }
+    }
+}
+
+/// Stale-data handling for the race forecast.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct StaleFilterQuery {
+    /// `true` (default) shows cached data for checkpoints where yr.no was
+    /// unreachable; `false` marks them `forecast_available: false` instead
+    #[serde(default = "default_include_stale")]
+    pub include_stale: bool,
+}
+
+fn default_include_stale() -> bool {
+    true
+}
+
+/// Minimum share of checkpoints with a forecast for the race forecast.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CoverageQuery {
+    /// Percentage (0–100, default 0) of checkpoints that must have a
+    /// forecast; below it the response sets `coverage_warning`
+    #[serde(default)]
+    pub minimum_coverage_pct: f64,
+}
+
+impl CoverageQuery {
+    fn validate(&self) -> Result<(), AppError> {
+        if !(0.0..=100.0).contains(&self.minimum_coverage_pct) {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!(
+                    "minimum_coverage_pct must be between 0 and 100, got {}",
+                    self.minimum_coverage_pct
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Percentage of checkpoints with `forecast_available`, rounded to 0.1
+/// (0 for a race without checkpoints).
+fn forecast_coverage_pct(checkpoints: &[RaceForecastCheckpoint]) -> f64 {
+    if checkpoints.is_empty() {
+        return 0.0;
+    }
+    let available = checkpoints
+        .iter()
+        .filter(|cp| cp.forecast_available)
+        .count();
+    let pct = available as f64 / checkpoints.len() as f64 * 100.0;
+    (pct * 10.0).round() / 10.0
+}
+
+/// Optional partial-course filter for the race forecast (e.g. a relay leg).
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct CheckpointRangeQuery {
+    /// First checkpoint of the partial course (defaults to the start).
+    /// With a range, `target_duration_hours` is the duration of the partial course.
+    pub start_checkpoint_id: Option<Uuid>,
+    /// Last checkpoint of the partial course (defaults to the finish)
+    pub end_checkpoint_id: Option<Uuid>,
+    /// Checkpoint where entrants of a shorter distance start, at the race
+    /// start time. Earlier checkpoints are dropped and pacing restarts there;
+    /// `target_duration_hours` covers the rest of the course. Cannot be
+    /// combined with `start_checkpoint_id`.
+    pub start_at_checkpoint: Option<Uuid>,
+}
+
 // ---------------------------------------------------------------------------
 // Response types — Section 9.4
 // ---------------------------------------------------------------------------
@@ -79,6 +323,16 @@ pub struct Weather {
     pub wind_speed_percentile_10_ms: Option<f64>,
     /// 90th percentile wind speed
     pub wind_speed_percentile_90_ms: Option<f64>,
+    /// Temperature P90 − P10 in °C (detail view only, when both percentiles are known)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature_uncertainty_c: Option<f64>,
+    /// Wind speed P90 − P10 in m/s (detail view only, when both percentiles are known)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind_uncertainty_ms: Option<f64>,
+    /// Forecast confidence from the temperature spread: "high" (< 2°C),
+    /// "medium" (2–5°C), or "low" (> 5°C) (detail view only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forecast_confidence: Option<String>,
     /// Wind direction in degrees (0 = north, 90 = east)
     pub wind_direction_deg: f64,
     /// Wind gust speed in m/s (detail view only)
@@ -94,6 +348,10 @@ pub struct Weather {
     pub precipitation_max_mm: Option<f64>,
     /// Precipitation type: "snow", "rain", "sleet", or "none"
     pub precipitation_type: String,
+    /// WMO precipitation intensity: "none", "trace", "light", "moderate", or "heavy"
+    pub precipitation_intensity: String,
+    /// Blowing snow / ground drift risk: "none", "possible", "likely", or "severe"
+    pub blowing_snow_risk: String,
     /// Relative humidity percentage (detail view only)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub humidity_pct: Option<f64>,
@@ -103,11 +361,37 @@ pub struct Weather {
     /// Cloud cover percentage (detail view only)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cloud_cover_pct: Option<f64>,
+    /// Low cloud cover percentage (detail view only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloud_cover_low_pct: Option<f64>,
+    /// Medium cloud cover percentage (detail view only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloud_cover_medium_pct: Option<f64>,
+    /// High cloud cover percentage (detail view only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloud_cover_high_pct: Option<f64>,
+    /// Snow depth in cm, when yr.no provides it for the location (detail view only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snow_depth_cm: Option<f64>,
     /// UV index (detail view only)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub uv_index: Option<f64>,
     /// yr.no weather symbol code (e.g. "cloudy", "lightssnowshowers_day")
     pub symbol_code: String,
+    /// Probability of thunder in % (detail view only, when yr.no provides it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thunder_probability_pct: Option<f64>,
+    /// Lightning risk from the thunder probability: "none" (< 5%), "low"
+    /// (5–25%), "moderate" (25–60%) or "high" (> 60%) (detail view only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lightning_risk: Option<String>,
+    /// Sunrise on the forecast day (ISO 8601, checkpoint detail view only;
+    /// omitted during polar night or when the sunrise API is unavailable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunrise: Option<String>,
+    /// Sunset on the forecast day (ISO 8601, checkpoint detail view only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<String>,
 }
 
 impl Weather {
@@ -123,17 +407,42 @@ impl Weather {
             wind_speed_ms: dec_to_f64(f.wind_speed_ms),
             wind_speed_percentile_10_ms: opt_dec_to_f64(f.wind_speed_percentile_10_ms),
             wind_speed_percentile_90_ms: opt_dec_to_f64(f.wind_speed_percentile_90_ms),
+            temperature_uncertainty_c: percentile_spread(
+                f.temperature_percentile_10_c,
+                f.temperature_percentile_90_c,
+            ),
+            wind_uncertainty_ms: percentile_spread(
+                f.wind_speed_percentile_10_ms,
+                f.wind_speed_percentile_90_ms,
+            ),
+            forecast_confidence: assess_forecast_confidence(
+                f.temperature_percentile_10_c,
+                f.temperature_percentile_90_c,
+            )
+            .map(str::to_string),
             wind_direction_deg: dec_to_f64(f.wind_direction_deg),
             wind_gust_ms: opt_dec_to_f64(f.wind_gust_ms),
             precipitation_mm: dec_to_f64(f.precipitation_mm),
             precipitation_min_mm: opt_dec_to_f64(f.precipitation_min_mm),
             precipitation_max_mm: opt_dec_to_f64(f.precipitation_max_mm),
             precipitation_type: f.precipitation_type.clone(),
+            precipitation_intensity: precipitation_intensity(f),
+            blowing_snow_risk: blowing_snow_risk(f),
             humidity_pct: Some(dec_to_f64(f.humidity_pct)),
             dew_point_c: Some(dec_to_f64(f.dew_point_c)),
             cloud_cover_pct: Some(dec_to_f64(f.cloud_cover_pct)),
+            cloud_cover_low_pct: opt_dec_to_f64(f.cloud_cover_low_pct),
+            cloud_cover_medium_pct: opt_dec_to_f64(f.cloud_cover_medium_pct),
+            cloud_cover_high_pct: opt_dec_to_f64(f.cloud_cover_high_pct),
+            snow_depth_cm: opt_dec_to_f64(f.snow_depth_cm),
             uv_index: opt_dec_to_f64(f.uv_index),
             symbol_code: f.symbol_code.clone(),
+            thunder_probability_pct: opt_dec_to_f64(f.thunder_probability_pct),
+            lightning_risk: f
+                .thunder_probability_pct
+                .map(|p| classify_lightning_risk(dec_to_f64(p)).to_string()),
+            sunrise: None,
+            sunset: None,
         }
     }
 
@@ -150,21 +459,56 @@ impl Weather {
             wind_speed_ms: dec_to_f64(f.wind_speed_ms),
             wind_speed_percentile_10_ms: opt_dec_to_f64(f.wind_speed_percentile_10_ms),
             wind_speed_percentile_90_ms: opt_dec_to_f64(f.wind_speed_percentile_90_ms),
+            temperature_uncertainty_c: None,
+            wind_uncertainty_ms: None,
+            forecast_confidence: None,
             wind_direction_deg: dec_to_f64(f.wind_direction_deg),
             wind_gust_ms: None,
             precipitation_mm: dec_to_f64(f.precipitation_mm),
             precipitation_min_mm: opt_dec_to_f64(f.precipitation_min_mm),
             precipitation_max_mm: opt_dec_to_f64(f.precipitation_max_mm),
             precipitation_type: f.precipitation_type.clone(),
+            precipitation_intensity: precipitation_intensity(f),
+            blowing_snow_risk: blowing_snow_risk(f),
             humidity_pct: Some(dec_to_f64(f.humidity_pct)),
             dew_point_c: None,
             cloud_cover_pct: Some(dec_to_f64(f.cloud_cover_pct)),
+            cloud_cover_low_pct: None,
+            cloud_cover_medium_pct: None,
+            cloud_cover_high_pct: None,
+            snow_depth_cm: None,
             uv_index: None,
             symbol_code: f.symbol_code.clone(),
+            thunder_probability_pct: None,
+            lightning_risk: None,
+            sunrise: None,
+            sunset: None,
         }
     }
 }
 
+/// Stored intensity class, or a classification of `precipitation_mm` for rows
+/// that predate the column (treated as an hourly amount).
+fn precipitation_intensity(f: &models::Forecast) -> String {
+    f.precipitation_intensity.clone().unwrap_or_else(|| {
+        classify_precipitation_intensity(dec_to_f64(f.precipitation_mm)).to_string()
+    })
+}
+
+/// Stored blowing snow risk, or an assessment from the stored fields for rows
+/// that predate the column.
+fn blowing_snow_risk(f: &models::Forecast) -> String {
+    f.blowing_snow_risk.clone().unwrap_or_else(|| {
+        assess_blowing_snow(
+            dec_to_f64(f.wind_speed_ms),
+            dec_to_f64(f.temperature_c),
+            dec_to_f64(f.precipitation_mm),
+            &f.precipitation_type,
+        )
+        .to_string()
+    })
+}
+
 /// Checkpoint forecast response (Section 9.4).
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ForecastResponse {
@@ -184,6 +528,14 @@ pub struct ForecastResponse {
     /// When yr.no's weather model generated this forecast (ISO 8601).
     /// Null for older rows that predate this tracking, or when forecast is unavailable.
     pub yr_model_run_at: Option<String>,
+    /// Minutes since `yr_model_run_at`. Null when the model run time is unknown.
+    pub model_run_age_minutes: Option<u32>,
+    /// When yr.no's next model run is expected (ISO 8601): 6 hours after
+    /// `yr_model_run_at`, snapped to 00/06/12/18 UTC. Null when the model run
+    /// time is unknown.
+    pub next_model_run_expected_at: Option<String>,
+    /// True when `next_model_run_expected_at` has passed without a newer run.
+    pub model_run_overdue: bool,
     /// Forecast data source (e.g. "yr.no"). Null when forecast is unavailable.
     pub source: Option<String>,
     /// Whether this forecast is stale (yr.no was unreachable, serving cached data)
@@ -192,8 +544,17 @@ pub struct ForecastResponse {
     /// Null when yr.no cache is unavailable (stale fallback).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub forecast_horizon: Option<String>,
+    /// Temporal precision of the source yr.no entry: "hourly" or "six_hourly".
+    /// Within a 6-hourly window, consecutive hourly requests return the same values.
+    /// Null when the forecast is unavailable or the row predates this tracking.
+    pub data_resolution: Option<String>,
     /// Full weather data. Null when `forecast_available` is false.
     pub weather: Option<Weather>,
+    /// Minutes from sunrise to `forecast_time`: negative when the skier
+    /// passes before sunrise and needs lights. Only on the single-checkpoint
+    /// endpoint; null when sunrise is unknown or the sun does not rise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dawn_minutes_before_race: Option<i64>,
 }
 
 /// A single historical forecast entry showing weather at a previous fetch time.
@@ -220,10 +581,49 @@ pub struct ForecastHistoryResponse {
     pub checkpoint_name: String,
     /// The datetime the forecast is for (ISO 8601)
     pub forecast_time: String,
-    /// Historical forecast entries, ordered by fetch time
+    /// Number of distinct yr.no model runs stored for this time slot,
+    /// regardless of `since`/`until` and the entry limit
+    pub model_run_count: i64,
+    /// Historical forecast entries, one per model run, ordered by model run time
     pub history: Vec<ForecastHistoryEntry>,
 }
 
+impl ForecastHistoryResponse {
+    /// History of `checkpoint` from its stored forecasts (one per model run).
+    /// `forecast_time` is that of the matched yr.no slot, or the requested
+    /// time when nothing matched.
+    fn new(
+        checkpoint: models::Checkpoint,
+        requested_time: DateTime<Utc>,
+        forecasts: &[models::Forecast],
+        model_run_count: i64,
+    ) -> Self {
+        let history = forecasts
+            .iter()
+            .map(|f| {
+                let model_run_at = f.yr_model_run_at.unwrap_or(f.fetched_at).to_rfc3339();
+                ForecastHistoryEntry {
+                    fetched_at: f.fetched_at.to_rfc3339(),
+                    yr_model_run_at: f.yr_model_run_at.map(|dt| dt.to_rfc3339()),
+                    model_run_at,
+                    weather: Weather::full(f),
+                }
+            })
+            .collect();
+        let forecast_time = forecasts
+            .first()
+            .map_or(requested_time, |f| f.forecast_time)
+            .to_rfc3339();
+        Self {
+            checkpoint_id: checkpoint.id,
+            checkpoint_name: checkpoint.name,
+            forecast_time,
+            model_run_count,
+            history,
+        }
+    }
+}
+
 /// A checkpoint with its expected weather in the race forecast (Section 9.6).
 #[derive(Debug, Serialize, ToSchema)]
 pub struct RaceForecastCheckpoint {
@@ -236,13 +636,38 @@ pub struct RaceForecastCheckpoint {
     /// Expected pass-through time based on elevation-adjusted pacing (ISO 8601)
     pub expected_time: String,
     /// Whether forecast data is available for this checkpoint's expected time.
-    /// `false` when the race date is beyond yr.no's ~10-day forecast horizon.
+    /// `false` when the race date is beyond yr.no's ~10-day forecast horizon,
+    /// or for stale data with `include_stale=false`.
     pub forecast_available: bool,
+    /// Whether this checkpoint's forecast is stale (yr.no was unreachable,
+    /// cached data)
+    pub stale: bool,
     /// Simplified weather at expected pass-through time.
     /// Null when `forecast_available` is false.
     pub weather: Option<Weather>,
 }
 
+impl RaceForecastCheckpoint {
+    /// Build a checkpoint entry. With `include_stale` false, stale data is
+    /// withheld rather than shown as if current.
+    fn new(cpwt: &CheckpointWithTime, resolved: &ResolvedForecast, include_stale: bool) -> Self {
+        let weather = resolved
+            .forecast
+            .as_ref()
+            .filter(|_| include_stale || !resolved.is_stale)
+            .map(Weather::simplified);
+        Self {
+            checkpoint_id: cpwt.checkpoint.id,
+            name: cpwt.checkpoint.name.clone(),
+            distance_km: dec_to_f64(cpwt.checkpoint.distance_km),
+            expected_time: cpwt.forecast_time.to_rfc3339(),
+            forecast_available: weather.is_some(),
+            stale: resolved.is_stale,
+            weather,
+        }
+    }
+}
+
 /// Full race forecast response with weather at all checkpoints (Section 9.6).
 #[derive(Debug, Serialize, ToSchema)]
 pub struct RaceForecastResponse {
@@ -250,8 +675,13 @@ pub struct RaceForecastResponse {
     pub race_id: Uuid,
     /// Race name
     pub race_name: String,
+    /// Race category from the GPX, e.g. "classic" or "skate" (null if not set)
+    pub category: Option<String>,
     /// Target duration used for pacing calculation
     pub target_duration_hours: f64,
+    /// Forecast slot interval used: 1 (exact pass-through times) or 2
+    /// (nearest even hour from the start)
+    pub time_slot_hours: u32,
     /// When yr.no's weather model generated the forecast data (ISO 8601).
     /// Uses the oldest model run across all checkpoints, or null if unknown.
     pub yr_model_run_at: Option<String>,
@@ -259,10 +689,298 @@ pub struct RaceForecastResponse {
     /// Uses the minimum horizon across all checkpoints (most conservative), or null if unknown.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub forecast_horizon: Option<String>,
+    /// Weather-based race difficulty on a 0–10 scale (5.0 = neutral)
+    pub difficulty_rating: f64,
+    /// Difficulty label: "easy", "moderate", "hard", or "extreme"
+    pub difficulty_label: String,
+    /// Checkpoint with the lowest feels-like temperature (null without forecasts)
+    pub coldest_checkpoint: Option<RaceExtremeCheckpoint>,
+    /// Checkpoint with the highest wind speed (null without forecasts)
+    pub windiest_checkpoint: Option<RaceExtremeCheckpoint>,
+    /// Most common precipitation type among checkpoints with precipitation,
+    /// or "none" when the whole course is dry
+    pub most_likely_precipitation_type: String,
+    /// Spread of snow temperature across the course (max − min) in °C,
+    /// over checkpoints with a forecast (null without any)
+    pub snow_temperature_gradient_c: Option<f64>,
+    /// Snow temperature at the first checkpoint in °C (null without a forecast there)
+    pub snow_temp_at_start_c: Option<f64>,
+    /// Snow temperature at the last checkpoint in °C (null without a forecast there)
+    pub snow_temp_at_finish_c: Option<f64>,
+    /// Whether one wax covers the whole course (gradient below 3 °C)
+    pub uniform_wax_possible: bool,
+    /// Percentage of checkpoints with `forecast_available: true`
+    pub forecast_coverage_pct: f64,
+    /// Whether `forecast_coverage_pct` is below the requested `minimum_coverage_pct`
+    pub coverage_warning: bool,
+    /// First checkpoint of the forecast course (the race start unless a
+    /// checkpoint range or `start_at_checkpoint` was given; null without checkpoints)
+    pub effective_start_checkpoint: Option<Uuid>,
+    /// Server-side handling time in milliseconds (only with `DEBUG_TIMING=true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_request_ms: Option<u64>,
     /// Weather forecasts at each checkpoint
     pub checkpoints: Vec<RaceForecastCheckpoint>,
 }
 
+/// A checkpoint singled out in the race forecast summary (coldest, windiest).
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct RaceExtremeCheckpoint {
+    /// Checkpoint UUID
+    pub checkpoint_id: Uuid,
+    /// Checkpoint name
+    pub name: String,
+    /// Distance from race start in km
+    pub distance_km: f64,
+    /// Expected pass-through time (ISO 8601)
+    pub expected_time: String,
+    /// Air temperature in °C
+    pub temperature_c: f64,
+    /// Feels-like temperature in °C
+    pub feels_like_c: f64,
+    /// Wind speed in m/s
+    pub wind_speed_ms: f64,
+}
+
+impl RaceExtremeCheckpoint {
+    fn new(cp: &RaceForecastCheckpoint, weather: &Weather) -> Self {
+        Self {
+            checkpoint_id: cp.checkpoint_id,
+            name: cp.name.clone(),
+            distance_km: cp.distance_km,
+            expected_time: cp.expected_time.clone(),
+            temperature_c: weather.temperature_c,
+            feels_like_c: weather.feels_like_c,
+            wind_speed_ms: weather.wind_speed_ms,
+        }
+    }
+
+    /// Checkpoint minimising `key` among those with a forecast (first on ties).
+    fn min_by(
+        checkpoints: &[RaceForecastCheckpoint],
+        key: impl Fn(&Weather) -> f64,
+    ) -> Option<Self> {
+        checkpoints
+            .iter()
+            .filter(|cp| cp.forecast_available)
+            .filter_map(|cp| cp.weather.as_ref().map(|w| (cp, w)))
+            .fold(
+                None::<(&RaceForecastCheckpoint, &Weather)>,
+                |best, (cp, w)| match best {
+                    Some((_, bw)) if key(bw) <= key(w) => best,
+                    _ => Some((cp, w)),
+                },
+            )
+            .map(|(cp, w)| Self::new(cp, w))
+    }
+
+    /// Checkpoint with the lowest feels-like temperature.
+    fn coldest(checkpoints: &[RaceForecastCheckpoint]) -> Option<Self> {
+        Self::min_by(checkpoints, |w| w.feels_like_c)
+    }
+
+    /// Checkpoint with the highest wind speed.
+    fn windiest(checkpoints: &[RaceForecastCheckpoint]) -> Option<Self> {
+        Self::min_by(checkpoints, |w| -w.wind_speed_ms)
+    }
+}
+
+/// Mode of `precipitation_type` over checkpoints with a forecast and
+/// non-zero precipitation; "none" if there are none. Ties go to the type
+/// seen first along the course.
+fn most_likely_precipitation_type(checkpoints: &[RaceForecastCheckpoint]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for weather in checkpoints
+        .iter()
+        .filter(|cp| cp.forecast_available)
+        .filter_map(|cp| cp.weather.as_ref())
+        .filter(|w| w.precipitation_mm > 0.0)
+    {
+        match counts
+            .iter_mut()
+            .find(|(t, _)| *t == weather.precipitation_type)
+        {
+            Some((_, n)) => *n += 1,
+            None => counts.push((&weather.precipitation_type, 1)),
+        }
+    }
+    counts
+        .iter()
+        .fold(None::<(&str, usize)>, |best, &(t, n)| match best {
+            Some((_, bn)) if bn >= n => best,
+            _ => Some((t, n)),
+        })
+        .map_or("none", |(t, _)| t)
+        .to_string()
+}
+
+/// Snow temperature spread below which one wax suits the whole course (°C).
+const UNIFORM_WAX_MAX_GRADIENT_C: f64 = 3.0;
+
+/// Snow temperature across the course, for wax selection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SnowTemperatureSpread {
+    gradient_c: Option<f64>,
+    at_start_c: Option<f64>,
+    at_finish_c: Option<f64>,
+    uniform_wax_possible: bool,
+}
+
+impl SnowTemperatureSpread {
+    /// From per-checkpoint snow temperatures in course order (None where
+    /// there is no forecast or no snow temperature).
+    fn new(snow_temps: &[Option<f64>]) -> Self {
+        let known = snow_temps.iter().flatten().copied();
+        let range = known.fold(None::<(f64, f64)>, |range, t| match range {
+            Some((lo, hi)) => Some((lo.min(t), hi.max(t))),
+            None => Some((t, t)),
+        });
+        let gradient_c = range.map(|(lo, hi)| ((hi - lo) * 10.0).round() / 10.0);
+        Self {
+            gradient_c,
+            at_start_c: snow_temps.first().copied().flatten(),
+            at_finish_c: snow_temps.last().copied().flatten(),
+            uniform_wax_possible: gradient_c.is_some_and(|g| g < UNIFORM_WAX_MAX_GRADIENT_C),
+        }
+    }
+}
+
+/// UV exposure estimate for a race.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UvExposureResponse {
+    /// Race UUID
+    pub race_id: Uuid,
+    /// Target duration used for pacing calculation
+    pub target_duration_hours: f64,
+    /// Sum of UV index × hours over the race (UV-hours)
+    pub total_uv_exposure: f64,
+    /// Highest UV index at any checkpoint pass-through time
+    pub max_uv_index: f64,
+    /// Risk category from the peak UV index: "low", "moderate", "high", or "very high"
+    pub uv_risk_category: String,
+    /// Sun protection advice for the risk category
+    pub recommendation: String,
+    /// Checkpoints with a UV forecast (yr.no only provides UV for ~60h ahead)
+    pub checkpoints_with_uv_data: usize,
+}
+
+/// Current forecast vs. historical average at one checkpoint.
+///
+/// Historical fields and anomalies are null when no average exists for the
+/// checkpoint and hour; current fields are null beyond the yr.no horizon.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HistoricalComparisonCheckpoint {
+    /// Checkpoint UUID
+    pub checkpoint_id: Uuid,
+    /// Checkpoint name
+    pub checkpoint: String,
+    /// Distance from start in km
+    pub distance_km: f64,
+    /// Expected pass-through time (ISO 8601)
+    pub expected_time: DateTime<Utc>,
+    /// Forecast temperature in °C
+    pub current_temperature_c: Option<f64>,
+    /// Historical average temperature in °C
+    pub historical_avg_temperature_c: Option<f64>,
+    /// Forecast minus historical temperature in °C
+    pub temperature_anomaly_c: Option<f64>,
+    /// Forecast wind speed in m/s
+    pub current_wind_speed_ms: Option<f64>,
+    /// Historical average wind speed in m/s
+    pub historical_avg_wind_speed_ms: Option<f64>,
+    /// Forecast minus historical wind speed in m/s
+    pub wind_speed_anomaly_ms: Option<f64>,
+    /// Forecast precipitation in mm/h (six-hourly totals are divided by 6)
+    pub current_precipitation_mm: Option<f64>,
+    /// Historical average precipitation in mm/h
+    pub historical_avg_precipitation_mm: Option<f64>,
+    /// Forecast minus historical precipitation in mm/h
+    pub precipitation_anomaly_mm: Option<f64>,
+}
+
+/// Snowfall expected at one checkpoint while the field passes it.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CheckpointSnowfall {
+    /// Checkpoint UUID
+    pub checkpoint_id: Uuid,
+    /// Checkpoint name
+    pub name: String,
+    /// Distance from start in km
+    pub distance_km: f64,
+    /// Expected pass-through time for the target duration (ISO 8601)
+    pub expected_time: DateTime<Utc>,
+    /// First hourly slot of the window (fastest skiers)
+    pub window_start: DateTime<Utc>,
+    /// Last hourly slot of the window (slowest skiers)
+    pub window_end: DateTime<Utc>,
+    /// Precipitation summed over the window, weighted by snow share
+    /// (snow 1.0, sleet 0.5, rain 0) in mm water equivalent
+    pub total_expected_snowfall_mm: f64,
+    /// Highest snow-weighted hourly rate in the window (mm/h)
+    pub max_hourly_intensity_mm: f64,
+    /// Hourly slots with a forecast (slots beyond the yr.no horizon are skipped)
+    pub hours_with_data: usize,
+}
+
+/// Snowfall accumulation along a race.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RacePrecipitationForecastResponse {
+    /// Race UUID
+    pub race_id: Uuid,
+    /// Target duration used for pacing calculation
+    pub target_duration_hours: f64,
+    /// Per-checkpoint accumulation, in course order
+    pub checkpoints: Vec<CheckpointSnowfall>,
+}
+
+/// Race forecast compared against historical averages.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RaceHistoricalComparisonResponse {
+    /// Race UUID
+    pub race_id: Uuid,
+    /// Race name (historical averages are matched on this)
+    pub race_name: String,
+    /// Target duration used for pacing calculation
+    pub target_duration_hours: f64,
+    /// Per-checkpoint comparison, in course order
+    pub checkpoints: Vec<HistoricalComparisonCheckpoint>,
+}
+
+/// One hour of the race timeline.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RaceTimelineSlot {
+    /// Full-hour time of the slot (ISO 8601)
+    pub time: String,
+    /// Name of the checkpoint starting the segment the racer is expected to be in
+    pub segment_from: String,
+    /// Name of the checkpoint ending that segment
+    pub segment_to: String,
+    /// Checkpoint nearest the racer's expected position, whose forecast is shown
+    pub checkpoint_id: Uuid,
+    /// Name of that checkpoint
+    pub checkpoint_name: String,
+    /// Whether a forecast is available for this checkpoint and hour
+    pub forecast_available: bool,
+    /// Simplified weather for the checkpoint at this hour.
+    /// Null when `forecast_available` is false.
+    pub weather: Option<Weather>,
+}
+
+/// Hour-by-hour race timeline response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RaceTimelineResponse {
+    /// Race UUID
+    pub race_id: Uuid,
+    /// Race name
+    pub race_name: String,
+    /// Target duration used for pacing calculation
+    pub target_duration_hours: f64,
+    /// True when the race window exceeds the 72-slot cap and later hours were dropped
+    pub truncated: bool,
+    /// Hourly slots from race start to finish
+    pub slots: Vec<RaceTimelineSlot>,
+}
+
 // ---------------------------------------------------------------------------
 // Handlers
 // ---------------------------------------------------------------------------
@@ -285,61 +1003,213 @@ pub struct RaceForecastResponse {
          headers(
              ("X-Forecast-Stale" = String, description = "Set to 'true' when serving cached data because yr.no is unreachable")
          )),
-        (status = 400, description = "Invalid datetime format", body = ErrorResponse),
-        (status = 404, description = "Checkpoint not found", body = ErrorResponse),
-        (status = 502, description = "External service error (yr.no unreachable, no cache)", body = ErrorResponse),
+        (status = 400, description = "Invalid datetime format or unknown field in `fields`", body = ErrorResponseV2),
+        (status = 404, description = "Checkpoint not found", body = ErrorResponseV2),
+        (status = 502, description = "External service error (yr.no unreachable, no cache)", body = ErrorResponseV2),
     )
 )]
 pub async fn get_checkpoint_forecast(
     State(state): State<AppState>,
     Path(checkpoint_id): Path<Uuid>,
     Query(params): Query<ForecastQuery>,
-) -> Result<(HeaderMap, Json<ForecastResponse>), AppError> {
-    let forecast_time: DateTime<Utc> = params
-        .datetime
-        .parse()
-        .map_err(|e| AppError::BadRequest(format!("Invalid datetime: {}", e)))?;
+    Query(fields): Query<FieldsQuery>,
+) -> Result<(HeaderMap, Json<Sparse<ForecastResponse>>), AppError> {
+    let fields = fields.selection()?;
+    let forecast_time: DateTime<Utc> = params.datetime.parse().map_err(|e| {
+        AppError::BadRequest(
+            AppErrorCode::InvalidDatetime,
+            format!("Invalid datetime: {}", e),
+        )
+    })?;
 
     let checkpoint = get_checkpoint(&state.pool, checkpoint_id).await?;
+    count_checkpoint_request(&state.pool, checkpoint_id);
 
-    let (maybe_forecast, is_stale, forecast_horizon) =
-        resolve_forecast(&state.pool, &state.yr_client, &checkpoint, forecast_time).await?;
-
-    let horizon_str = forecast_horizon.map(|dt| dt.to_rfc3339());
+    let (resolved, sun) = tokio::join!(
+        resolve_forecast(
+            &state.pool,
+            &state.yr_client,
+            &checkpoint,
+            forecast_time,
+            params.min_resolution,
+        ),
+        state.sunrise_client.get_sun_times(
+            &state.pool,
+            dec_to_f64(checkpoint.latitude),
+            dec_to_f64(checkpoint.longitude),
+            forecast_time,
+        ),
+    );
+    let (forecast, is_stale, forecast_horizon) = resolved?;
 
-    let response = match maybe_forecast {
-        Some(forecast) => ForecastResponse {
-            checkpoint_id: checkpoint.id,
-            checkpoint_name: checkpoint.name.clone(),
-            forecast_time: forecast.forecast_time.to_rfc3339(),
-            forecast_available: true,
-            fetched_at: Some(forecast.fetched_at.to_rfc3339()),
-            yr_model_run_at: forecast.yr_model_run_at.map(|dt| dt.to_rfc3339()),
-            source: Some(forecast.source.clone()),
-            stale: is_stale,
-            forecast_horizon: horizon_str,
-            weather: Some(Weather::full(&forecast)),
+    let mut response = forecast_response(
+        &checkpoint,
+        forecast_time,
+        ResolvedForecast {
+            forecast,
+            is_stale,
+            forecast_horizon,
         },
+    );
+    // Sun times are a nice-to-have: never fail the forecast over them
+    match sun {
+        Ok(sun) => apply_sun_times(&mut response, &sun, forecast_time),
+        Err(e) => tracing::warn!("Sunrise lookup for {} failed: {}", checkpoint.id, e),
+    }
+
+    let mut headers = HeaderMap::new();
+    if is_stale {
+        headers.insert("X-Forecast-Stale", "true".parse().unwrap());
+    }
+
+    Ok((headers, Json(Sparse::new(response, fields))))
+}
+
+/// Get forecasts for a checkpoint at several datetimes in one request.
+///
+/// The yr.no cache for the checkpoint is refreshed at most once and all
+/// datetimes are extracted from it in a single pass. Results are returned in
+/// the order of the `datetimes` parameter.
+#[utoipa::path(
+    get,
+    path = "/api/v1/forecasts/checkpoint/{checkpoint_id}/multi",
+    tag = "Forecasts",
+    params(
+        ("checkpoint_id" = Uuid, Path, description = "Checkpoint UUID"),
+        MultiForecastQuery,
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "Forecasts for the checkpoint, one per requested datetime", body = Vec<ForecastResponse>,
+         headers(
+             ("X-Forecast-Stale" = String, description = "Set to 'true' when serving cached data because yr.no is unreachable")
+         )),
+        (status = 400, description = "Invalid datetime, or no / more than 12 datetimes, or unknown field in `fields`", body = ErrorResponseV2),
+        (status = 404, description = "Checkpoint not found", body = ErrorResponseV2),
+        (status = 502, description = "External service error (yr.no unreachable, no cache)", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_checkpoint_forecast_multi(
+    State(state): State<AppState>,
+    Path(checkpoint_id): Path<Uuid>,
+    Query(params): Query<MultiForecastQuery>,
+    Query(fields): Query<FieldsQuery>,
+) -> Result<(HeaderMap, Json<Sparse<Vec<ForecastResponse>>>), AppError> {
+    let fields = fields.selection()?;
+    let forecast_times = parse_datetimes(&params.datetimes)?;
+
+    let checkpoint = get_checkpoint(&state.pool, checkpoint_id).await?;
+
+    let resolved = resolve_forecasts_at_times(
+        &state.pool,
+        &state.yr_client,
+        &checkpoint,
+        &forecast_times,
+        params.min_resolution,
+    )
+    .await?;
+
+    let is_stale = resolved.iter().any(|r| r.is_stale);
+    let responses: Vec<ForecastResponse> = forecast_times
+        .iter()
+        .zip(resolved)
+        .map(|(&forecast_time, res)| forecast_response(&checkpoint, forecast_time, res))
+        .collect();
+
+    let mut headers = HeaderMap::new();
+    if is_stale {
+        headers.insert("X-Forecast-Stale", "true".parse().unwrap());
+    }
+
+    Ok((headers, Json(Sparse::new(responses, fields))))
+}
+
+/// Parse a comma-separated list of 1–12 RFC 3339 datetimes.
+fn parse_datetimes(datetimes: &str) -> Result<Vec<DateTime<Utc>>, AppError> {
+    let forecast_times = datetimes
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse().map_err(|e| {
+                AppError::BadRequest(
+                    AppErrorCode::InvalidDatetime,
+                    format!("Invalid datetime '{}': {}", s, e),
+                )
+            })
+        })
+        .collect::<Result<Vec<DateTime<Utc>>, AppError>>()?;
+    if forecast_times.is_empty() || forecast_times.len() > MAX_MULTI_DATETIMES {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            format!(
+                "datetimes must contain between 1 and {} values",
+                MAX_MULTI_DATETIMES
+            ),
+        ));
+    }
+    Ok(forecast_times)
+}
+
+/// Build the checkpoint forecast response for a resolved forecast.
+fn forecast_response(
+    checkpoint: &models::Checkpoint,
+    requested_time: DateTime<Utc>,
+    resolved: ResolvedForecast,
+) -> ForecastResponse {
+    let horizon_str = resolved.forecast_horizon.map(|dt| dt.to_rfc3339());
+
+    match resolved.forecast {
+        Some(forecast) => {
+            let now = Utc::now();
+            let next_model_run = forecast.yr_model_run_at.map(estimate_next_model_run);
+            ForecastResponse {
+                checkpoint_id: checkpoint.id,
+                checkpoint_name: checkpoint.name.clone(),
+                forecast_time: forecast.forecast_time.to_rfc3339(),
+                forecast_available: true,
+                fetched_at: Some(forecast.fetched_at.to_rfc3339()),
+                yr_model_run_at: forecast.yr_model_run_at.map(|dt| dt.to_rfc3339()),
+                model_run_age_minutes: forecast
+                    .yr_model_run_at
+                    .map(|dt| model_run_age_minutes(dt, now)),
+                next_model_run_expected_at: next_model_run.map(|dt| dt.to_rfc3339()),
+                model_run_overdue: next_model_run.is_some_and(|dt| dt < now),
+                source: Some(forecast.source.clone()),
+                stale: resolved.is_stale,
+                forecast_horizon: horizon_str,
+                data_resolution: forecast.data_resolution.clone(),
+                weather: Some(Weather::full(&forecast)),
+                dawn_minutes_before_race: None,
+            }
+        }
         None => ForecastResponse {
             checkpoint_id: checkpoint.id,
             checkpoint_name: checkpoint.name.clone(),
-            forecast_time: forecast_time.to_rfc3339(),
+            forecast_time: requested_time.to_rfc3339(),
             forecast_available: false,
             fetched_at: None,
             yr_model_run_at: None,
+            model_run_age_minutes: None,
+            next_model_run_expected_at: None,
+            model_run_overdue: false,
             source: None,
             stale: false,
             forecast_horizon: horizon_str,
+            data_resolution: None,
             weather: None,
+            dawn_minutes_before_race: None,
         },
-    };
-
-    let mut headers = HeaderMap::new();
-    if is_stale {
-        headers.insert("X-Forecast-Stale", "true".parse().unwrap());
     }
+}
 
-    Ok((headers, Json(response)))
+/// Add sunrise/sunset to a checkpoint forecast response.
+fn apply_sun_times(response: &mut ForecastResponse, sun: &SunTimes, requested_time: DateTime<Utc>) {
+    response.dawn_minutes_before_race = dawn_minutes_before(sun, requested_time);
+    if let Some(weather) = response.weather.as_mut() {
+        weather.sunrise = sun.sunrise.map(|dt| dt.to_rfc3339());
+        weather.sunset = sun.sunset.map(|dt| dt.to_rfc3339());
+    }
 }
 
 /// Get the forecast history for a checkpoint, showing how predictions evolved.
@@ -347,6 +1217,8 @@ pub async fn get_checkpoint_forecast(
 /// Returns all previously fetched forecasts for a checkpoint at the given
 /// datetime, ordered by fetch time. This allows users to see how the
 /// forecast has changed over days/hours leading up to the race.
+/// `since`/`until` limit the entries to a `fetched_at` range;
+/// `min_resolution=hourly` keeps only entries from hourly yr.no data.
 #[utoipa::path(
     get,
     path = "/api/v1/forecasts/checkpoint/{checkpoint_id}/history",
@@ -354,11 +1226,12 @@ pub async fn get_checkpoint_forecast(
     params(
         ("checkpoint_id" = Uuid, Path, description = "Checkpoint UUID"),
         ForecastQuery,
+        FieldsQuery,
     ),
     responses(
         (status = 200, description = "Forecast history for the checkpoint", body = ForecastHistoryResponse),
-        (status = 400, description = "Invalid datetime format", body = ErrorResponse),
-        (status = 404, description = "Checkpoint not found", body = ErrorResponse),
+        (status = 400, description = "Invalid datetime format, or since is not before until", body = ErrorResponseV2),
+        (status = 404, description = "Checkpoint not found", body = ErrorResponseV2),
     )
 )]
 pub async fn get_checkpoint_forecast_history(
@@ -366,92 +1239,289 @@ pub async fn get_checkpoint_forecast_history(
     Path(checkpoint_id): Path<Uuid>,
     Query(params): Query<ForecastQuery>,
 ) -> Result<Json<ForecastHistoryResponse>, AppError> {
-    let forecast_time: DateTime<Utc> = params
-        .datetime
-        .parse()
-        .map_err(|e| AppError::BadRequest(format!("Invalid datetime: {}", e)))?;
+    let forecast_time: DateTime<Utc> = params.datetime.parse().map_err(|e| {
+        AppError::BadRequest(
+            AppErrorCode::InvalidDatetime,
+            format!("Invalid datetime: {}", e),
+        )
+    })?;
 
-    let checkpoint = get_checkpoint(&state.pool, checkpoint_id).await?;
+    let (since, until) = params.history_bounds()?;
 
-    let forecasts =
-        queries::get_forecast_history(&state.pool, checkpoint_id, forecast_time).await?;
+    let checkpoint = get_checkpoint(&state.pool, checkpoint_id).await?;
+    count_checkpoint_request(&state.pool, checkpoint_id);
 
-    let history: Vec<ForecastHistoryEntry> = forecasts
-        .iter()
-        .map(|f| {
-            let model_run_at = f.yr_model_run_at.unwrap_or(f.fetched_at).to_rfc3339();
-            ForecastHistoryEntry {
-                fetched_at: f.fetched_at.to_rfc3339(),
-                yr_model_run_at: f.yr_model_run_at.map(|dt| dt.to_rfc3339()),
-                model_run_at,
-                weather: Weather::full(f),
-            }
-        })
-        .collect();
+    let forecasts = queries::get_forecast_history(
+        &state.pool,
+        checkpoint_id,
+        forecast_time,
+        since,
+        until,
+        params.min_resolution,
+    )
+    .await?;
+    let model_run_count =
+        queries::get_distinct_model_runs_for_checkpoint(&state.pool, checkpoint_id, forecast_time)
+            .await?;
 
-    let response_time = if let Some(first) = forecasts.first() {
-        first.forecast_time.to_rfc3339()
-    } else {
-        forecast_time.to_rfc3339()
-    };
+    Ok(Json(ForecastHistoryResponse::new(
+        checkpoint,
+        forecast_time,
+        &forecasts,
+        model_run_count,
+    )))
+}
 
-    Ok(Json(ForecastHistoryResponse {
-        checkpoint_id: checkpoint.id,
-        checkpoint_name: checkpoint.name,
-        forecast_time: response_time,
-        history,
-    }))
+/// One 22.5° wind rose sector (wind blowing *from* this direction).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WindRoseSector {
+    /// Compass label, e.g. "N", "NNE"
+    pub direction: &'static str,
+    /// Sector centre in degrees (0 = north, clockwise)
+    pub center_deg: f64,
+    /// Forecasts with wind from this sector
+    pub count: u32,
+    /// Mean wind speed in m/s (0 when count is 0)
+    pub mean_speed_ms: f64,
+    /// Highest wind speed in m/s (0 when count is 0)
+    pub max_speed_ms: f64,
 }
 
-/// Get weather forecasts for all checkpoints in a race.
+/// Get wind rose data for a checkpoint's recent forecasts.
 ///
-/// Calculates expected pass-through times for each checkpoint using
-/// elevation-adjusted pacing based on the target duration, then returns
-/// the latest weather forecast for each checkpoint at its expected time.
+/// Aggregates `wind_direction_deg` / `wind_speed_ms` of every forecast
+/// fetched in the last `days` days (all forecast times and model runs) into
+/// 16 direction sectors, clockwise from north.
 #[utoipa::path(
     get,
-    path = "/api/v1/forecasts/race/{race_id}",
+    path = "/api/v1/forecasts/checkpoint/{checkpoint_id}/wind-rose",
     tag = "Forecasts",
     params(
-        ("race_id" = Uuid, Path, description = "Race UUID"),
-        RaceForecastQuery,
+        ("checkpoint_id" = Uuid, Path, description = "Checkpoint UUID"),
+        WindRoseQuery,
     ),
     responses(
-        (status = 200, description = "Race forecast with weather at all checkpoints", body = RaceForecastResponse,
-         headers(
-             ("X-Forecast-Stale" = String, description = "Set to 'true' when serving cached data because yr.no is unreachable")
-         )),
-        (status = 400, description = "Invalid query parameters", body = ErrorResponse),
-        (status = 404, description = "Race not found", body = ErrorResponse),
+        (status = 200, description = "16 wind rose sectors, clockwise from north", body = Vec<WindRoseSector>),
+        (status = 400, description = "days outside 1–30", body = ErrorResponseV2),
+        (status = 404, description = "Checkpoint not found", body = ErrorResponseV2),
     )
 )]
-pub async fn get_race_forecast(
+pub async fn get_checkpoint_wind_rose(
     State(state): State<AppState>,
-    Path(race_id): Path<Uuid>,
-    Query(params): Query<RaceForecastQuery>,
-) -> Result<(HeaderMap, Json<RaceForecastResponse>), AppError> {
-    // Validate target_duration_hours — check is_finite() first because NaN
-    // passes range comparisons (NaN <= 0.0 is false, NaN > 72.0 is also false).
-    if !params.target_duration_hours.is_finite() {
+    Path(checkpoint_id): Path<Uuid>,
+    Query(params): Query<WindRoseQuery>,
+) -> Result<Json<Vec<WindRoseSector>>, AppError> {
+    let days = params.days.unwrap_or(DEFAULT_WIND_ROSE_DAYS);
+    if !(1..=MAX_WIND_ROSE_DAYS).contains(&days) {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            format!("days must be between 1 and {}", MAX_WIND_ROSE_DAYS),
+        ));
+    }
+
+    get_checkpoint(&state.pool, checkpoint_id).await?;
+    let forecasts =
+        queries::get_recent_forecasts_for_checkpoint(&state.pool, checkpoint_id, days).await?;
+
+    Ok(Json(wind_rose_sectors(&compute_wind_rose(&forecasts))))
+}
+
+/// Label wind rose sectors with their compass direction and centre angle.
+fn wind_rose_sectors(sectors: &[WindSector]) -> Vec<WindRoseSector> {
+    let width = 360.0 / WIND_ROSE_SECTORS as f64;
+    sectors
+        .iter()
+        .zip(WIND_ROSE_LABELS)
+        .enumerate()
+        .map(|(i, (sector, direction))| WindRoseSector {
+            direction,
+            center_deg: i as f64 * width,
+            count: sector.count,
+            mean_speed_ms: sector.mean_speed_ms,
+            max_speed_ms: sector.max_speed_ms,
+        })
+        .collect()
+}
+
+/// One NDJSON line of a forecast export: row metadata plus the full weather.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ForecastExportRow {
+    pub id: Uuid,
+    pub checkpoint_id: Uuid,
+    pub forecast_time: String,
+    pub fetched_at: String,
+    /// When yr.no's weather model generated this forecast (null for legacy rows)
+    pub yr_model_run_at: Option<String>,
+    pub source: String,
+    /// "hourly" or "six_hourly" (null for legacy rows)
+    pub data_resolution: Option<String>,
+    #[serde(flatten)]
+    pub weather: Weather,
+}
+
+impl ForecastExportRow {
+    fn from_forecast(f: &models::Forecast) -> Self {
+        Self {
+            id: f.id,
+            checkpoint_id: f.checkpoint_id,
+            forecast_time: f.forecast_time.to_rfc3339(),
+            fetched_at: f.fetched_at.to_rfc3339(),
+            yr_model_run_at: f.yr_model_run_at.map(|dt| dt.to_rfc3339()),
+            source: f.source.clone(),
+            data_resolution: f.data_resolution.clone(),
+            weather: Weather::full(f),
+        }
+    }
+
+    /// The row as one NDJSON line, newline included.
+    fn to_line(&self) -> Result<String, serde_json::Error> {
+        let mut line = serde_json::to_string(self)?;
+        line.push('\n');
+        Ok(line)
+    }
+}
+
+/// Export every stored forecast for a checkpoint as newline-delimited JSON.
+///
+/// Selects all rows whose `fetched_at` lies between `from` and `to`
+/// (every model run and forecast time, not one datetime), oldest fetch
+/// first, capped at 10 000 rows. Rows are streamed as they are read.
+#[utoipa::path(
+    get,
+    path = "/api/v1/forecasts/checkpoint/{checkpoint_id}/export",
+    tag = "Forecasts",
+    params(
+        ("checkpoint_id" = Uuid, Path, description = "Checkpoint UUID"),
+        ForecastExportQuery,
+    ),
+    responses(
+        (status = 200, description = "One forecast per line", content_type = "application/x-ndjson", body = ForecastExportRow),
+        (status = 400, description = "Invalid datetime, range or format", body = ErrorResponseV2),
+        (status = 404, description = "Checkpoint not found", body = ErrorResponseV2),
+    )
+)]
+pub async fn export_checkpoint_forecasts(
+    State(state): State<AppState>,
+    Path(checkpoint_id): Path<Uuid>,
+    Query(params): Query<ForecastExportQuery>,
+) -> Result<Response, AppError> {
+    if let Some(format) = params.format.as_deref().filter(|f| *f != "ndjson") {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            format!(
+                "Unsupported export format '{}': only 'ndjson' is available",
+                format
+            ),
+        ));
+    }
+    let (from, to) = params.range()?;
+
+    get_checkpoint(&state.pool, checkpoint_id).await?;
+
+    // The row stream borrows the pool, so it is drained in its own task and
+    // forwarded to the response body through a channel.
+    let (mut tx, rx) = futures::channel::mpsc::channel::<Result<String, BoxError>>(64);
+    let pool = state.pool;
+    tokio::spawn(async move {
+        let mut rows = queries::get_forecasts_in_range(&pool, checkpoint_id, from, to);
+        while let Some(row) = rows.next().await {
+            let line = row.map_err(BoxError::from).and_then(|f| {
+                ForecastExportRow::from_forecast(&f)
+                    .to_line()
+                    .map_err(Into::into)
+            });
+            let failed = line.is_err();
+            if let Err(e) = &line {
+                tracing::error!("Forecast export for {} failed: {}", checkpoint_id, e);
+            }
+            // A closed channel means the client disconnected
+            if tx.send(line).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(rx),
+    )
+        .into_response())
+}
+
+/// Validate `target_duration_hours` for race forecast endpoints.
+pub(crate) fn validate_target_duration(target_duration_hours: f64) -> Result<(), AppError> {
+    // Check is_finite() first because NaN passes range comparisons (NaN <= 0.0 is false, NaN > 72.0 is also false).
+    if !target_duration_hours.is_finite() {
         return Err(AppError::BadRequest(
+            AppErrorCode::InvalidDuration,
             "target_duration_hours must be a finite number".to_string(),
         ));
     }
-    if params.target_duration_hours <= 0.0
-        || params.target_duration_hours > MAX_TARGET_DURATION_HOURS
-    {
-        return Err(AppError::BadRequest(format!(
-            "target_duration_hours must be between 0 (exclusive) and {}",
-            MAX_TARGET_DURATION_HOURS as u64
-        )));
+    if target_duration_hours <= 0.0 || target_duration_hours > MAX_TARGET_DURATION_HOURS {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidDuration,
+            format!(
+                "target_duration_hours must be between 0 (exclusive) and {}",
+                MAX_TARGET_DURATION_HOURS as u64
+            ),
+        ));
     }
+    Ok(())
+}
 
-    // Use lightweight query — no GPX blob
-    let race = queries::get_race_summary(&state.pool, race_id)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Race {} not found", race_id)))?;
+/// Count a checkpoint request in the background (popular-checkpoints stats).
+/// Failures are logged and never affect the response.
+fn count_checkpoint_request(pool: &PgPool, checkpoint_id: Uuid) {
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        if let Err(e) = queries::increment_checkpoint_request_count(&pool, checkpoint_id).await {
+            tracing::warn!(
+                "Failed to count request for checkpoint {}: {}",
+                checkpoint_id,
+                e
+            );
+        }
+    });
+}
 
-    let checkpoints = queries::get_checkpoints(&state.pool, race_id).await?;
+/// Compute each checkpoint's expected pass-through time for a race using
+/// elevation-adjusted pacing (track-aware when the GPX parses).
+///
+/// With a checkpoint range, only that partial course is returned and
+/// `target_duration_hours` is its duration (see [`partial_pass_times`]).
+/// With `start_at_checkpoint`, checkpoints before it are dropped and it is
+/// passed at the race start time.
+/// With `fatigue`, the elevation-adjusted fractions are re-weighted for
+/// cumulative fatigue.
+async fn race_checkpoints_with_times(
+    pool: &sqlx::PgPool,
+    race: &models::Race,
+    target_duration_hours: f64,
+    range: &CheckpointRangeQuery,
+    pacing: &PacingParams,
+    fatigue: Option<FatiguePacingModel>,
+) -> Result<Vec<CheckpointWithTime>, AppError> {
+    let mut checkpoints = queries::get_checkpoints(pool, race.id).await?;
+    let start_at_checkpoint = range.start_at_checkpoint;
+    if let Some(id) = start_at_checkpoint {
+        if range.start_checkpoint_id.is_some() {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                "start_at_checkpoint cannot be combined with start_checkpoint_id".to_string(),
+            ));
+        }
+        let start = start_at_checkpoint_index(&checkpoints, id)?;
+        checkpoints.drain(..start);
+    }
+    let range = if range.start_checkpoint_id.is_some() || range.end_checkpoint_id.is_some() {
+        Some(checkpoint_range(
+            &checkpoints,
+            range.start_checkpoint_id,
+            range.end_checkpoint_id,
+        )?)
+    } else {
+        None
+    };
 
     // Compute elevation-adjusted time fractions
     let pacing_inputs: Vec<PacingCheckpoint> = checkpoints
@@ -459,83 +1529,700 @@ pub async fn get_race_forecast(
         .map(|cp| PacingCheckpoint {
             distance_km: dec_to_f64(cp.distance_km),
             elevation_m: dec_to_f64(cp.elevation_m),
+            latitude: dec_to_f64(cp.latitude),
+            longitude: dec_to_f64(cp.longitude),
         })
         .collect();
 
+    let simple_fractions = |inputs: &[PacingCheckpoint]| match fatigue {
+        Some(model) => {
+            calculate_pass_time_fractions_with_fatigue(inputs, pacing, model.fatigue_factor)
+        }
+        None => calculate_pass_time_fractions(inputs, pacing),
+    };
+
     // Load GPX track for track-aware pacing (uses full elevation profile
     // instead of net elevation between checkpoints)
-    let time_fractions = match queries::get_race_course_gpx(&state.pool, race_id).await? {
+    let time_fractions = match queries::get_race_course_gpx(pool, race.id).await? {
         Some(gpx_xml) => match extract_track_points(&gpx_xml) {
             Ok(course_points) => {
                 let track = compute_track_profile(&course_points);
                 tracing::debug!(
                     "Track-aware pacing: {} track points for race {}",
                     track.len(),
-                    race_id
+                    race.id
                 );
 
                 // Compute per-track-point pacing profile then derive checkpoint fractions
-                let profile_raw = compute_pacing_profile(&track, 500);
+                let profile_raw = compute_pacing_profile(&track, 500, pacing);
+
+                // Place checkpoints on the profile by their distance along the
+                // same track rather than the rounded declared distances. The
+                // enrichment would count gaps between track segments, so
+                // multi-segment courses keep the declared distances.
+                let mut measured_inputs = pacing_inputs.clone();
+                if course_points.iter().all(|p| p.segment_index == 0) {
+                    let lats: Vec<f64> = course_points.iter().map(|p| p.lat).collect();
+                    let lons: Vec<f64> = course_points.iter().map(|p| p.lon).collect();
+                    enrich_pacing_checkpoints_with_haversine(&mut measured_inputs, &lats, &lons);
+                }
 
                 // Derive checkpoint fractions from the profile (single source of truth)
-                pacing_inputs
+                let mut fractions: Vec<f64> = measured_inputs
                     .iter()
                     .map(|cp| interpolate_fraction_from_profile(&profile_raw, cp.distance_km))
-                    .collect()
+                    .collect();
+                if start_at_checkpoint.is_some() {
+                    fractions = rebase_fractions(&fractions);
+                }
+                match fatigue {
+                    Some(model) => model.apply(&fractions),
+                    None => fractions,
+                }
             }
             Err(e) => {
                 tracing::warn!(
                     "Failed to parse GPX track for race {}, falling back to simple pacing: {}",
-                    race_id,
+                    race.id,
                     e
                 );
-                calculate_pass_time_fractions(&pacing_inputs)
+                simple_fractions(&pacing_inputs)
             }
         },
         None => {
-            tracing::debug!("No GPX track for race {}, using simple pacing", race_id);
-            calculate_pass_time_fractions(&pacing_inputs)
+            tracing::debug!("No GPX track for race {}, using simple pacing", race.id);
+            simple_fractions(&pacing_inputs)
         }
     };
 
+    if let Some((start, end)) = range {
+        let times = partial_pass_times(
+            &time_fractions,
+            start,
+            end,
+            race.start_time,
+            target_duration_hours,
+        );
+        return Ok(checkpoints
+            .into_iter()
+            .skip(start)
+            .zip(times)
+            .map(|(cp, forecast_time)| CheckpointWithTime {
+                checkpoint: cp,
+                forecast_time,
+            })
+            .collect());
+    }
+
     // Build checkpoint + expected time pairs using elevation-adjusted pacing
-    let checkpoints_with_times: Vec<CheckpointWithTime> = checkpoints
+    Ok(checkpoints
         .into_iter()
         .zip(time_fractions.iter())
         .map(|(cp, &fraction)| {
-            let expected_time = calculate_pass_time_weighted(
-                race.start_time,
-                fraction,
-                params.target_duration_hours,
-            );
+            let expected_time =
+                calculate_pass_time_weighted(race.start_time, fraction, target_duration_hours);
             CheckpointWithTime {
                 checkpoint: cp,
                 forecast_time: expected_time,
             }
         })
+        .collect())
+}
+
+/// Estimate UV exposure over the course of a race.
+///
+/// Uses the same elevation-adjusted pass-through times as the race forecast;
+/// each segment's duration is weighted by the average UV index at its two ends.
+#[utoipa::path(
+    get,
+    path = "/api/v1/forecasts/race/{race_id}/uv-exposure",
+    tag = "Forecasts",
+    params(
+        ("race_id" = Uuid, Path, description = "Race UUID"),
+        RaceForecastQuery,
+    ),
+    responses(
+        (status = 200, description = "UV exposure estimate for the race", body = UvExposureResponse),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponseV2),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_race_uv_exposure(
+    State(state): State<AppState>,
+    Path(race_id): Path<Uuid>,
+    Query(params): Query<RaceForecastQuery>,
+) -> Result<Json<UvExposureResponse>, AppError> {
+    validate_target_duration(params.target_duration_hours)?;
+
+    let race = queries::get_race_summary(&state.pool, race_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::RaceNotFound,
+                format!("Race {} not found", race_id),
+            )
+        })?;
+
+    let checkpoints_with_times = race_checkpoints_with_times(
+        &state.pool,
+        &race,
+        params.target_duration_hours,
+        &CheckpointRangeQuery::default(),
+        &state.pacing_params,
+        None,
+    )
+    .await?;
+    let resolved = resolve_race_forecasts(
+        &state.pool,
+        &state.yr_client,
+        &state.extraction_cache,
+        &checkpoints_with_times,
+        state.slow_request_threshold_ms,
+    )
+    .await?;
+
+    let report = calculate_uv_exposure(&checkpoints_with_times, &resolved);
+
+    Ok(Json(UvExposureResponse {
+        race_id: race.id,
+        target_duration_hours: params.target_duration_hours,
+        total_uv_exposure: report.total_uv_exposure,
+        max_uv_index: report.max_uv_index,
+        uv_risk_category: report.uv_risk_category.to_string(),
+        recommendation: report.recommendation.to_string(),
+        checkpoints_with_uv_data: report.checkpoints_with_uv_data,
+    }))
+}
+
+/// Compare the race forecast against historical averages.
+///
+/// Uses the same elevation-adjusted pass-through times as the race forecast.
+/// Historical averages are matched by race name, checkpoint distance
+/// (within 1 km) and the UTC month/day/hour of the pass-through time.
+#[utoipa::path(
+    get,
+    path = "/api/v1/forecasts/race/{race_id}/vs-historical",
+    tag = "Forecasts",
+    params(
+        ("race_id" = Uuid, Path, description = "Race UUID"),
+        RaceForecastQuery,
+    ),
+    responses(
+        (status = 200, description = "Forecast vs. historical averages per checkpoint", body = RaceHistoricalComparisonResponse),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponseV2),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_race_vs_historical(
+    State(state): State<AppState>,
+    Path(race_id): Path<Uuid>,
+    Query(params): Query<RaceForecastQuery>,
+) -> Result<Json<RaceHistoricalComparisonResponse>, AppError> {
+    validate_target_duration(params.target_duration_hours)?;
+
+    let race = queries::get_race_summary(&state.pool, race_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::RaceNotFound,
+                format!("Race {} not found", race_id),
+            )
+        })?;
+
+    let checkpoints_with_times = race_checkpoints_with_times(
+        &state.pool,
+        &race,
+        params.target_duration_hours,
+        &CheckpointRangeQuery::default(),
+        &state.pacing_params,
+        None,
+    )
+    .await?;
+    let (resolved, averages) = tokio::try_join!(
+        resolve_race_forecasts(
+            &state.pool,
+            &state.yr_client,
+            &state.extraction_cache,
+            &checkpoints_with_times,
+            state.slow_request_threshold_ms,
+        ),
+        async {
+            queries::get_historical_averages(&state.pool, &race.name)
+                .await
+                .map_err(AppError::from)
+        },
+    )?;
+
+    let checkpoints = checkpoints_with_times
+        .iter()
+        .zip(&resolved)
+        .map(|(cpwt, r)| {
+            let cp = &cpwt.checkpoint;
+            let distance_km = dec_to_f64(cp.distance_km);
+            let current = r.forecast.as_ref().map(|f| {
+                let precipitation = dec_to_f64(f.precipitation_mm);
+                let precipitation_per_hour = if f.data_resolution.as_deref()
+                    == Some(ForecastResolution::SixHourly.as_str())
+                {
+                    precipitation / 6.0
+                } else {
+                    precipitation
+                };
+                (
+                    dec_to_f64(f.temperature_c),
+                    dec_to_f64(f.wind_speed_ms),
+                    precipitation_per_hour,
+                )
+            });
+            let historical = match_historical_average(&averages, distance_km, cpwt.forecast_time);
+            // Rounded to 0.1 to hide f64 noise from the subtraction
+            let anomaly = |cur: Option<f64>, hist: Option<f64>| match (cur, hist) {
+                (Some(c), Some(h)) => Some(((c - h) * 10.0).round() / 10.0),
+                _ => None,
+            };
+
+            let current_temperature_c = current.map(|c| c.0);
+            let current_wind_speed_ms = current.map(|c| c.1);
+            let current_precipitation_mm = current.map(|c| c.2);
+            let historical_avg_temperature_c = historical.map(|h| h.avg_temperature_c);
+            let historical_avg_wind_speed_ms = historical.map(|h| h.avg_wind_speed_ms);
+            let historical_avg_precipitation_mm = historical.map(|h| h.avg_precipitation_mm);
+
+            HistoricalComparisonCheckpoint {
+                checkpoint_id: cp.id,
+                checkpoint: cp.name.clone(),
+                distance_km,
+                expected_time: cpwt.forecast_time,
+                current_temperature_c,
+                historical_avg_temperature_c,
+                temperature_anomaly_c: anomaly(current_temperature_c, historical_avg_temperature_c),
+                current_wind_speed_ms,
+                historical_avg_wind_speed_ms,
+                wind_speed_anomaly_ms: anomaly(current_wind_speed_ms, historical_avg_wind_speed_ms),
+                current_precipitation_mm,
+                historical_avg_precipitation_mm,
+                precipitation_anomaly_mm: anomaly(
+                    current_precipitation_mm,
+                    historical_avg_precipitation_mm,
+                ),
+            }
+        })
         .collect();
 
-    // Resolve all forecasts (parallel yr.no fetches per checkpoint)
-    let resolved =
-        resolve_race_forecasts(&state.pool, &state.yr_client, &checkpoints_with_times).await?;
+    Ok(Json(RaceHistoricalComparisonResponse {
+        race_id: race.id,
+        race_name: race.name,
+        target_duration_hours: params.target_duration_hours,
+        checkpoints,
+    }))
+}
 
-    let checkpoint_forecasts: Vec<RaceForecastCheckpoint> = checkpoints_with_times
+/// Forecast snowfall accumulation at each checkpoint over the race window.
+///
+/// The window covers every hour in which the field may pass the checkpoint,
+/// from the fastest to the slowest realistic pace (the same window the poller
+/// extracts). Intended for grooming crews, who need total snowfall rather
+/// than conditions at a single pass-through time.
+#[utoipa::path(
+    get,
+    path = "/api/v1/forecasts/race/{race_id}/precipitation-forecast",
+    tag = "Forecasts",
+    params(
+        ("race_id" = Uuid, Path, description = "Race UUID"),
+        RaceForecastQuery,
+    ),
+    responses(
+        (status = 200, description = "Snowfall accumulation per checkpoint", body = RacePrecipitationForecastResponse),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponseV2),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
+        (status = 502, description = "yr.no unavailable and no cached data", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_race_precipitation_forecast(
+    State(state): State<AppState>,
+    Path(race_id): Path<Uuid>,
+    Query(params): Query<RaceForecastQuery>,
+) -> Result<Json<RacePrecipitationForecastResponse>, AppError> {
+    validate_target_duration(params.target_duration_hours)?;
+
+    let race = queries::get_race_summary(&state.pool, race_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::RaceNotFound,
+                format!("Race {} not found", race_id),
+            )
+        })?;
+
+    let checkpoints_with_times = race_checkpoints_with_times(
+        &state.pool,
+        &race,
+        params.target_duration_hours,
+        &CheckpointRangeQuery::default(),
+        &state.pacing_params,
+        None,
+    )
+    .await?;
+
+    // One entry per (checkpoint, hourly slot), resolved in a single batch
+    let windows: Vec<Vec<DateTime<Utc>>> = checkpoints_with_times
         .iter()
-        .zip(resolved.iter())
-        .map(|(cpwt, res)| {
-            let weather = res.forecast.as_ref().map(Weather::simplified);
+        .map(|cpwt| {
+            compute_extraction_times(
+                race.start_time,
+                dec_to_f64(cpwt.checkpoint.distance_km),
+                &state.poller_config,
+            )
+        })
+        .collect();
+    let slots: Vec<CheckpointWithTime> = checkpoints_with_times
+        .iter()
+        .zip(&windows)
+        .flat_map(|(cpwt, times)| {
+            times.iter().map(|&forecast_time| CheckpointWithTime {
+                checkpoint: cpwt.checkpoint.clone(),
+                forecast_time,
+            })
+        })
+        .collect();
+    let resolved = resolve_race_forecasts(
+        &state.pool,
+        &state.yr_client,
+        &state.extraction_cache,
+        &slots,
+        state.slow_request_threshold_ms,
+    )
+    .await?;
+
+    let mut offset = 0;
+    let mut checkpoints = Vec::with_capacity(checkpoints_with_times.len());
+    for (cpwt, times) in checkpoints_with_times.iter().zip(&windows) {
+        let acc = accumulate_snowfall(&resolved[offset..offset + times.len()]);
+        offset += times.len();
+        let cp = &cpwt.checkpoint;
+        checkpoints.push(CheckpointSnowfall {
+            checkpoint_id: cp.id,
+            name: cp.name.clone(),
+            distance_km: dec_to_f64(cp.distance_km),
+            expected_time: cpwt.forecast_time,
+            window_start: times.first().copied().unwrap_or(cpwt.forecast_time),
+            window_end: times.last().copied().unwrap_or(cpwt.forecast_time),
+            total_expected_snowfall_mm: (acc.total_expected_snowfall_mm * 10.0).round() / 10.0,
+            max_hourly_intensity_mm: (acc.max_hourly_intensity_mm * 10.0).round() / 10.0,
+            hours_with_data: acc.hours_with_data,
+        });
+    }
 
-            RaceForecastCheckpoint {
+    Ok(Json(RacePrecipitationForecastResponse {
+        race_id: race.id,
+        target_duration_hours: params.target_duration_hours,
+        checkpoints,
+    }))
+}
+
+/// Get an hour-by-hour forecast timeline for a race.
+///
+/// Covers every full hour from race start to the expected finish (at most 72
+/// slots). Each slot shows the forecast at the checkpoint nearest the racer's
+/// elevation-adjusted expected position at that hour.
+#[utoipa::path(
+    get,
+    path = "/api/v1/forecasts/race/{race_id}/timeline",
+    tag = "Forecasts",
+    params(
+        ("race_id" = Uuid, Path, description = "Race UUID"),
+        RaceForecastQuery,
+    ),
+    responses(
+        (status = 200, description = "Hourly forecast timeline for the race", body = RaceTimelineResponse),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponseV2),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_race_timeline(
+    State(state): State<AppState>,
+    Path(race_id): Path<Uuid>,
+    Query(params): Query<RaceForecastQuery>,
+) -> Result<(HeaderMap, Json<RaceTimelineResponse>), AppError> {
+    validate_target_duration(params.target_duration_hours)?;
+
+    let race = queries::get_race_summary(&state.pool, race_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::RaceNotFound,
+                format!("Race {} not found", race_id),
+            )
+        })?;
+
+    let checkpoints_with_times = race_checkpoints_with_times(
+        &state.pool,
+        &race,
+        params.target_duration_hours,
+        &CheckpointRangeQuery::default(),
+        &state.pacing_params,
+        None,
+    )
+    .await?;
+    let slots = compute_timeline_slots(&checkpoints_with_times);
+    let truncated = checkpoints_with_times
+        .last()
+        .zip(slots.last())
+        .is_some_and(|(finish, slot)| slot.time < finish.forecast_time);
+
+    // Resolve the nearest checkpoint's forecast at each slot time
+    let slot_checkpoints: Vec<CheckpointWithTime> = slots
+        .iter()
+        .map(|slot| CheckpointWithTime {
+            checkpoint: checkpoints_with_times[slot.nearest_checkpoint]
+                .checkpoint
+                .clone(),
+            forecast_time: slot.time,
+        })
+        .collect();
+    let resolved = resolve_race_forecasts(
+        &state.pool,
+        &state.yr_client,
+        &state.extraction_cache,
+        &slot_checkpoints,
+        state.slow_request_threshold_ms,
+    )
+    .await?;
+
+    let timeline_slots = slots
+        .iter()
+        .zip(slot_checkpoints.iter().zip(resolved.iter()))
+        .map(|(slot, (cpwt, res))| {
+            let weather = res.forecast.as_ref().map(Weather::simplified);
+            RaceTimelineSlot {
+                time: slot.time.to_rfc3339(),
+                segment_from: checkpoints_with_times[slot.segment_start]
+                    .checkpoint
+                    .name
+                    .clone(),
+                segment_to: checkpoints_with_times[slot.segment_end]
+                    .checkpoint
+                    .name
+                    .clone(),
                 checkpoint_id: cpwt.checkpoint.id,
-                name: cpwt.checkpoint.name.clone(),
-                distance_km: dec_to_f64(cpwt.checkpoint.distance_km),
-                expected_time: cpwt.forecast_time.to_rfc3339(),
+                checkpoint_name: cpwt.checkpoint.name.clone(),
                 forecast_available: weather.is_some(),
                 weather,
             }
         })
         .collect();
 
+    let mut headers = HeaderMap::new();
+    if resolved.iter().any(|r| r.is_stale) {
+        headers.insert("X-Forecast-Stale", "true".parse().unwrap());
+    }
+
+    Ok((
+        headers,
+        Json(RaceTimelineResponse {
+            race_id: race.id,
+            race_name: race.name,
+            target_duration_hours: params.target_duration_hours,
+            truncated,
+            slots: timeline_slots,
+        }),
+    ))
+}
+
+/// Get weather forecasts for all checkpoints in a race.
+///
+/// Calculates expected pass-through times for each checkpoint using
+/// elevation-adjusted pacing based on the target duration, then returns
+/// the latest weather forecast for each checkpoint at its expected time.
+/// `pacing_model=fatigue` additionally slows late segments (see
+/// [`FatiguePacingModel`]). Long races default to 2-hour forecast slots,
+/// which keeps the number of distinct forecast times down.
+#[utoipa::path(
+    get,
+    path = "/api/v1/forecasts/race/{race_id}",
+    tag = "Forecasts",
+    params(
+        ("race_id" = Uuid, Path, description = "Race UUID"),
+        RaceForecastQuery,
+        CheckpointRangeQuery,
+        PacingModelQuery,
+        TimeSlotQuery,
+        StaleFilterQuery,
+        CoverageQuery,
+    ),
+    responses(
+        (status = 200, description = "Race forecast with weather at all checkpoints", body = RaceForecastResponse,
+         headers(
+             ("X-Forecast-Stale" = String, description = "Set to 'true' when serving cached data because yr.no is unreachable")
+         )),
+        (status = 400, description = "Invalid query parameters or checkpoint range", body = ErrorResponseV2),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_race_forecast(
+    State(state): State<AppState>,
+    Path(race_id): Path<Uuid>,
+    Query(params): Query<RaceForecastQuery>,
+    Query(range): Query<CheckpointRangeQuery>,
+    Query(pacing): Query<PacingModelQuery>,
+    Query(slots): Query<TimeSlotQuery>,
+    Query(stale_filter): Query<StaleFilterQuery>,
+    Query(coverage): Query<CoverageQuery>,
+) -> Result<(HeaderMap, Json<RaceForecastResponse>), AppError> {
+    let started = tokio::time::Instant::now();
+    validate_target_duration(params.target_duration_hours)?;
+    coverage.validate()?;
+    let time_slot_hours = slots.interval_hours(params.target_duration_hours)?;
+
+    // Use lightweight query — no GPX blob
+    let race = queries::get_race_summary(&state.pool, race_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::RaceNotFound,
+                format!("Race {} not found", race_id),
+            )
+        })?;
+
+    let options = RaceForecastOptions {
+        target_duration_hours: params.target_duration_hours,
+        range,
+        pacing_model: pacing.pacing_model,
+        time_slot_hours,
+        include_stale: stale_filter.include_stale,
+        minimum_coverage_pct: coverage.minimum_coverage_pct,
+    };
+    let (mut response, any_stale) = build_race_forecast(&state, race, &options).await?;
+    if state.debug_timing {
+        response.total_request_ms = Some(started.elapsed().as_millis() as u64);
+    }
+
+    let mut headers = HeaderMap::new();
+    if any_stale {
+        headers.insert("X-Forecast-Stale", "true".parse().unwrap());
+    }
+    Ok((headers, Json(response)))
+}
+
+/// Validated race forecast parameters (see [`get_race_forecast`]).
+#[derive(Debug)]
+pub(crate) struct RaceForecastOptions {
+    pub(crate) target_duration_hours: f64,
+    pub(crate) range: CheckpointRangeQuery,
+    pub(crate) pacing_model: PacingModel,
+    pub(crate) time_slot_hours: u32,
+    pub(crate) include_stale: bool,
+    pub(crate) minimum_coverage_pct: f64,
+}
+
+impl RaceForecastOptions {
+    /// Defaults of every optional query parameter for a target duration.
+    pub(crate) fn with_defaults(target_duration_hours: f64) -> Result<Self, AppError> {
+        Ok(Self {
+            target_duration_hours,
+            range: CheckpointRangeQuery::default(),
+            pacing_model: PacingModel::default(),
+            time_slot_hours: TimeSlotQuery {
+                time_slot_interval_hours: None,
+            }
+            .interval_hours(target_duration_hours)?,
+            include_stale: default_include_stale(),
+            minimum_coverage_pct: 0.0,
+        })
+    }
+}
+
+/// Lapse-rate check of the resolved race forecasts. Each checkpoint is
+/// compared with the start checkpoint's forecast at the same slot, looked up
+/// from the start's yr.no cache; if that lookup fails the check is skipped.
+async fn check_lapse_rate(
+    state: &AppState,
+    slots: &[CheckpointWithTime],
+    resolved: &[ResolvedForecast],
+) -> Option<LapseRateWarning> {
+    let start = slots.first()?;
+    resolved.first()?.forecast.as_ref()?;
+    let times: Vec<DateTime<Utc>> = slots.iter().map(|s| s.forecast_time).collect();
+    let start_at_times = match resolve_forecasts_at_times(
+        &state.pool,
+        &state.yr_client,
+        &start.checkpoint,
+        &times,
+        ForecastResolution::SixHourly,
+    )
+    .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::debug!("Lapse-rate check skipped: {}", e);
+            return None;
+        }
+    };
+    let samples: Vec<LapseRateSample> = slots
+        .iter()
+        .zip(resolved)
+        .zip(start_at_times)
+        .map(|((slot, res), start_res)| LapseRateSample {
+            elevation_m: slot.checkpoint.elevation_m,
+            temperature_c: res.forecast.as_ref().map(|f| f.temperature_c),
+            start_temperature_c: start_res.forecast.map(|f| f.temperature_c),
+        })
+        .collect();
+    check_temperature_lapse_consistency(&samples)
+}
+
+/// Race forecast for `race`, and whether any checkpoint fell back to stale
+/// cached data. Shared by the endpoint and the summary pre-computation.
+pub(crate) async fn build_race_forecast(
+    state: &AppState,
+    race: models::Race,
+    options: &RaceForecastOptions,
+) -> Result<(RaceForecastResponse, bool), AppError> {
+    let checkpoints_with_times = race_checkpoints_with_times(
+        &state.pool,
+        &race,
+        options.target_duration_hours,
+        &options.range,
+        &state.pacing_params,
+        match options.pacing_model {
+            PacingModel::Elevation => None,
+            PacingModel::Fatigue => Some(FatiguePacingModel::new(state.pacing_fatigue_factor)),
+        },
+    )
+    .await?;
+
+    // With 2-hour slots, forecasts are looked up at the nearest slot;
+    // `expected_time` stays the pass-through time
+    let time_slot_hours = options.time_slot_hours;
+    let forecast_slots: Vec<CheckpointWithTime> = checkpoints_with_times
+        .iter()
+        .map(|cpwt| CheckpointWithTime {
+            checkpoint: cpwt.checkpoint.clone(),
+            forecast_time: if time_slot_hours > 1 {
+                nearest_time_slot(race.start_time, cpwt.forecast_time, time_slot_hours)
+            } else {
+                cpwt.forecast_time
+            },
+        })
+        .collect();
+
+    // Resolve all forecasts (parallel yr.no fetches per checkpoint)
+    let resolved = resolve_race_forecasts(
+        &state.pool,
+        &state.yr_client,
+        &state.extraction_cache,
+        &forecast_slots,
+        state.slow_request_threshold_ms,
+    )
+    .await?;
+
+    let checkpoint_forecasts: Vec<RaceForecastCheckpoint> = checkpoints_with_times
+        .iter()
+        .zip(resolved.iter())
+        .map(|(cpwt, res)| RaceForecastCheckpoint::new(cpwt, res, options.include_stale))
+        .collect();
+
     // Find the oldest model run time across all checkpoints that have forecasts
     // (oldest = most conservative indicator of forecast freshness)
     let yr_model_run_at = resolved
@@ -552,21 +2239,417 @@ pub async fn get_race_forecast(
         .min()
         .map(|dt| dt.to_rfc3339());
 
-    let any_stale = resolved.iter().any(|r| r.is_stale);
-    let mut headers = HeaderMap::new();
-    if any_stale {
-        headers.insert("X-Forecast-Stale", "true".parse().unwrap());
+    let difficulty_rating = rate_race_difficulty(&resolved);
+
+    if let Some(w) = check_lapse_rate(state, &forecast_slots, &resolved).await {
+        tracing::debug!(
+            "Race {}: checkpoint {} forecast temperature {:+.1}°C vs. start at {:+.0} m \
+             differs from the lapse-rate expectation ({:+.1}°C); local inversion conditions \
+             may be affecting the forecast",
+            race.id,
+            w.checkpoint_index,
+            w.actual_temp_diff_c,
+            w.elevation_diff_m,
+            w.expected_temp_diff_c
+        );
     }
 
+    let snow_temps: Vec<Option<f64>> = checkpoint_forecasts
+        .iter()
+        .zip(resolved.iter())
+        .map(|(cp, res)| {
+            res.forecast
+                .as_ref()
+                .filter(|_| cp.forecast_available)
+                .and_then(|f| f.snow_temperature_c)
+                .map(dec_to_f64)
+        })
+        .collect();
+    let snow_spread = SnowTemperatureSpread::new(&snow_temps);
+    let forecast_coverage_pct = forecast_coverage_pct(&checkpoint_forecasts);
+
+    let any_stale = resolved.iter().any(|r| r.is_stale);
+
     Ok((
-        headers,
-        Json(RaceForecastResponse {
+        RaceForecastResponse {
             race_id: race.id,
             race_name: race.name,
-            target_duration_hours: params.target_duration_hours,
+            category: race.category,
+            target_duration_hours: options.target_duration_hours,
+            time_slot_hours,
             yr_model_run_at,
             forecast_horizon,
+            difficulty_rating,
+            difficulty_label: difficulty_label(difficulty_rating).to_string(),
+            coldest_checkpoint: RaceExtremeCheckpoint::coldest(&checkpoint_forecasts),
+            windiest_checkpoint: RaceExtremeCheckpoint::windiest(&checkpoint_forecasts),
+            most_likely_precipitation_type: most_likely_precipitation_type(&checkpoint_forecasts),
+            snow_temperature_gradient_c: snow_spread.gradient_c,
+            snow_temp_at_start_c: snow_spread.at_start_c,
+            snow_temp_at_finish_c: snow_spread.at_finish_c,
+            uniform_wax_possible: snow_spread.uniform_wax_possible,
+            forecast_coverage_pct,
+            coverage_warning: forecast_coverage_pct < options.minimum_coverage_pct,
+            effective_start_checkpoint: checkpoint_forecasts.first().map(|cp| cp.checkpoint_id),
+            total_request_ms: None,
             checkpoints: checkpoint_forecasts,
-        }),
+        },
+        any_stale,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::forecast::make_resolved;
+    use chrono::TimeZone;
+    use rust_decimal::Decimal;
+
+    fn forecast(feels_like_c: &str, wind_speed_ms: &str, precipitation: (&str, &str)) -> Weather {
+        Weather::simplified(&db_forecast(feels_like_c, wind_speed_ms, precipitation))
+    }
+
+    fn db_forecast(
+        feels_like_c: &str,
+        wind_speed_ms: &str,
+        precipitation: (&str, &str),
+    ) -> models::Forecast {
+        let mut f = make_resolved(
+            feels_like_c,
+            wind_speed_ms,
+            precipitation.0,
+            precipitation.1,
+        )
+        .forecast
+        .unwrap();
+        f.temperature_c = f.feels_like_c + Decimal::from(3);
+        f
+    }
+
+    fn checkpoint(
+        name: &str,
+        distance_km: f64,
+        weather: Option<Weather>,
+    ) -> RaceForecastCheckpoint {
+        RaceForecastCheckpoint {
+            checkpoint_id: Uuid::new_v4(),
+            name: name.to_string(),
+            distance_km,
+            expected_time: "2026-03-01T08:00:00+00:00".to_string(),
+            forecast_available: weather.is_some(),
+            stale: false,
+            weather,
+        }
+    }
+
+    fn checkpoint_with_time() -> CheckpointWithTime {
+        CheckpointWithTime {
+            checkpoint: models::Checkpoint {
+                distance_km: Decimal::from(62),
+                ..models::Checkpoint::fixture("Oxberg")
+            },
+            forecast_time: "2026-03-01T11:30:00Z".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_race_forecast_checkpoint_includes_stale_by_default() {
+        let resolved = ResolvedForecast {
+            is_stale: true,
+            ..make_resolved("-8.0", "2.0", "0.0", "none")
+        };
+        let cp = RaceForecastCheckpoint::new(&checkpoint_with_time(), &resolved, true);
+        assert!(cp.forecast_available);
+        assert!(cp.stale);
+        assert!(cp.weather.is_some());
+        assert_eq!(cp.distance_km, 62.0);
+    }
+
+    #[test]
+    fn test_race_forecast_checkpoint_withholds_stale_data() {
+        let stale = ResolvedForecast {
+            is_stale: true,
+            ..make_resolved("-8.0", "2.0", "0.0", "none")
+        };
+        let cp = RaceForecastCheckpoint::new(&checkpoint_with_time(), &stale, false);
+        assert!(!cp.forecast_available);
+        assert!(cp.stale);
+        assert!(cp.weather.is_none());
+
+        // Fresh data is unaffected by the filter
+        let fresh = ResolvedForecast {
+            is_stale: false,
+            ..stale
+        };
+        let cp = RaceForecastCheckpoint::new(&checkpoint_with_time(), &fresh, false);
+        assert!(cp.forecast_available);
+        assert!(!cp.stale);
+        assert!(cp.weather.is_some());
+    }
+
+    #[test]
+    fn test_race_forecast_extremes() {
+        let checkpoints = vec![
+            checkpoint("Berga", 0.0, Some(forecast("-8.0", "2.0", ("0.0", "none")))),
+            checkpoint(
+                "Risberg",
+                35.0,
+                Some(forecast("-14.5", "3.0", ("0.4", "snow"))),
+            ),
+            checkpoint(
+                "Evertsberg",
+                47.0,
+                Some(forecast("-11.0", "7.5", ("0.2", "sleet"))),
+            ),
+            checkpoint("Oxberg", 62.0, None),
+            checkpoint("Mora", 90.0, Some(forecast("-3.0", "4.0", ("1.1", "snow")))),
+        ];
+
+        let coldest = RaceExtremeCheckpoint::coldest(&checkpoints).unwrap();
+        assert_eq!(coldest.name, "Risberg");
+        assert_eq!(coldest.feels_like_c, -14.5);
+        assert_eq!(coldest.temperature_c, -11.5);
+        assert_eq!(coldest.distance_km, 35.0);
+
+        let windiest = RaceExtremeCheckpoint::windiest(&checkpoints).unwrap();
+        assert_eq!(windiest.name, "Evertsberg");
+        assert_eq!(windiest.wind_speed_ms, 7.5);
+
+        assert_eq!(most_likely_precipitation_type(&checkpoints), "snow");
+    }
+
+    #[test]
+    fn test_forecast_coverage_pct() {
+        let weather = || Some(forecast("-8.0", "2.0", ("0.0", "none")));
+        let none = [
+            checkpoint("Berga", 0.0, None),
+            checkpoint("Mora", 90.0, None),
+        ];
+        assert_eq!(forecast_coverage_pct(&none), 0.0);
+
+        let half = [
+            checkpoint("Berga", 0.0, weather()),
+            checkpoint("Mora", 90.0, None),
+        ];
+        assert_eq!(forecast_coverage_pct(&half), 50.0);
+
+        let full = [
+            checkpoint("Berga", 0.0, weather()),
+            checkpoint("Mora", 90.0, weather()),
+        ];
+        assert_eq!(forecast_coverage_pct(&full), 100.0);
+
+        assert_eq!(forecast_coverage_pct(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_minimum_coverage_pct_validation() {
+        let query = |pct| CoverageQuery {
+            minimum_coverage_pct: pct,
+        };
+        assert!(query(0.0).validate().is_ok());
+        assert!(query(100.0).validate().is_ok());
+        assert!(query(-1.0).validate().is_err());
+        assert!(query(100.5).validate().is_err());
+        assert!(query(f64::NAN).validate().is_err());
+    }
+
+    #[test]
+    fn test_snow_temperature_spread() {
+        let spread = SnowTemperatureSpread::new(&[Some(-6.2), None, Some(-3.1), Some(-4.0)]);
+        assert_eq!(spread.gradient_c, Some(3.1));
+        assert_eq!(spread.at_start_c, Some(-6.2));
+        assert_eq!(spread.at_finish_c, Some(-4.0));
+        assert!(!spread.uniform_wax_possible);
+
+        // Narrow range, no forecast at the finish
+        let spread = SnowTemperatureSpread::new(&[Some(-5.0), Some(-3.5), None]);
+        assert_eq!(spread.gradient_c, Some(1.5));
+        assert_eq!(spread.at_finish_c, None);
+        assert!(spread.uniform_wax_possible);
+
+        let empty = SnowTemperatureSpread::new(&[None, None]);
+        assert_eq!(empty.gradient_c, None);
+        assert_eq!(empty.at_start_c, None);
+        assert!(!empty.uniform_wax_possible);
+    }
+
+    #[test]
+    fn test_race_forecast_extremes_without_forecasts() {
+        let checkpoints = vec![checkpoint("Berga", 0.0, None)];
+        assert_eq!(RaceExtremeCheckpoint::coldest(&checkpoints), None);
+        assert_eq!(RaceExtremeCheckpoint::windiest(&[]), None);
+        assert_eq!(most_likely_precipitation_type(&checkpoints), "none");
+
+        // Dry course: precipitation type of zero-precipitation entries is ignored
+        let dry = vec![checkpoint(
+            "Berga",
+            0.0,
+            Some(forecast("-8.0", "2.0", ("0.0", "snow"))),
+        )];
+        assert_eq!(most_likely_precipitation_type(&dry), "none");
+    }
+
+    #[test]
+    fn test_time_slot_interval_defaults_by_duration() {
+        let query = |hours| TimeSlotQuery {
+            time_slot_interval_hours: hours,
+        };
+        assert_eq!(query(None).interval_hours(8.0).unwrap(), 1);
+        assert_eq!(query(None).interval_hours(10.0).unwrap(), 2);
+        assert_eq!(query(Some(1)).interval_hours(12.0).unwrap(), 1);
+        assert_eq!(query(Some(2)).interval_hours(5.0).unwrap(), 2);
+        assert!(matches!(
+            query(Some(3)).interval_hours(5.0),
+            Err(AppError::BadRequest(AppErrorCode::InvalidParameter, _))
+        ));
+    }
+
+    #[test]
+    fn test_forecast_export_range() {
+        let query = |from: &str, to: &str| ForecastExportQuery {
+            from: from.to_string(),
+            to: to.to_string(),
+            format: None,
+        };
+        let (from, to) = query("2026-02-28T06:00:00+01:00", "2026-03-01T00:00:00Z")
+            .range()
+            .unwrap();
+        assert_eq!(from.to_rfc3339(), "2026-02-28T05:00:00+00:00");
+        assert_eq!(to.to_rfc3339(), "2026-03-01T00:00:00+00:00");
+
+        // Both bounds are inclusive, so a single instant is a valid range
+        let (from, to) = query("2026-03-01T00:00:00Z", "2026-03-01T00:00:00Z")
+            .range()
+            .unwrap();
+        assert_eq!(from, to);
+
+        for (from, to) in [
+            ("2026-03-02T00:00:00Z", "2026-03-01T00:00:00Z"),
+            ("2026-03-01", "2026-03-02T00:00:00Z"),
+            ("2026-03-01T00:00:00Z", "tomorrow"),
+        ] {
+            assert!(matches!(
+                query(from, to).range(),
+                Err(AppError::BadRequest(AppErrorCode::InvalidDatetime, _))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_forecast_export_row_line() {
+        let mut f = db_forecast("-8", "3", ("0.4", "snow"));
+        f.forecast_time = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+        f.fetched_at = Utc.with_ymd_and_hms(2026, 2, 28, 18, 5, 0).unwrap();
+        f.data_resolution = Some("hourly".to_string());
+
+        let line = ForecastExportRow::from_forecast(&f).to_line().unwrap();
+        // Exactly one line, so rows can be split on newlines
+        assert_eq!(line.matches('\n').count(), 1);
+        assert!(line.ends_with('\n'));
+
+        let row: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(row["id"], f.id.to_string());
+        assert_eq!(row["checkpoint_id"], f.checkpoint_id.to_string());
+        assert_eq!(row["forecast_time"], "2026-03-01T09:00:00+00:00");
+        assert_eq!(row["fetched_at"], "2026-02-28T18:05:00+00:00");
+        assert_eq!(row["yr_model_run_at"], serde_json::Value::Null);
+        assert_eq!(row["source"], "yr.no");
+        assert_eq!(row["data_resolution"], "hourly");
+        // Weather fields are flattened into the row
+        assert_eq!(row["temperature_c"], -5.0);
+        assert_eq!(row["feels_like_c"], -8.0);
+        assert_eq!(row["precipitation_type"], "snow");
+        assert!(row.get("weather").is_none());
+    }
+
+    #[test]
+    fn test_parse_bound() {
+        assert_eq!(parse_bound("since", None).unwrap(), None);
+        assert_eq!(
+            parse_bound("since", Some("2026-02-28T07:00:00+01:00")).unwrap(),
+            Some(Utc.with_ymd_and_hms(2026, 2, 28, 6, 0, 0).unwrap())
+        );
+        match parse_bound("until", Some("yesterday")) {
+            Err(AppError::BadRequest(AppErrorCode::InvalidDatetime, msg)) => {
+                assert!(msg.starts_with("Invalid until: "), "{}", msg)
+            }
+            other => panic!("expected InvalidDatetime, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_history_bounds() {
+        let query = |since: Option<&str>, until: Option<&str>| ForecastQuery {
+            datetime: "2026-03-01T08:00:00Z".to_string(),
+            since: since.map(str::to_string),
+            until: until.map(str::to_string),
+            min_resolution: ForecastResolution::SixHourly,
+        };
+        let feb_27 = "2026-02-27T00:00:00Z";
+        let feb_28 = "2026-02-28T00:00:00Z";
+
+        assert_eq!(query(None, None).history_bounds().unwrap(), (None, None));
+        let (since, until) = query(Some(feb_27), Some(feb_28)).history_bounds().unwrap();
+        assert!(since.unwrap() < until.unwrap());
+        // One bound alone needs no ordering check
+        assert!(query(Some(feb_28), None).history_bounds().is_ok());
+
+        // since must be strictly before until
+        for (since, until) in [(feb_28, feb_27), (feb_28, feb_28)] {
+            assert!(matches!(
+                query(Some(since), Some(until)).history_bounds(),
+                Err(AppError::BadRequest(AppErrorCode::InvalidDatetime, _))
+            ));
+        }
+        assert!(query(Some("not a date"), Some(feb_28))
+            .history_bounds()
+            .is_err());
+    }
+
+    #[test]
+    fn test_forecast_history_response() {
+        let requested = Utc.with_ymd_and_hms(2026, 3, 1, 8, 20, 0).unwrap();
+        let slot = Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap();
+        let run = |hour: u32, model_run: bool| {
+            let mut f = db_forecast("-8", "3", ("0", "none"));
+            f.forecast_time = slot;
+            f.fetched_at = Utc.with_ymd_and_hms(2026, 2, 27, hour, 5, 0).unwrap();
+            f.yr_model_run_at =
+                model_run.then(|| Utc.with_ymd_and_hms(2026, 2, 27, hour, 0, 0).unwrap());
+            f
+        };
+        let checkpoint = models::Checkpoint::fixture("Mora");
+        let forecasts = [run(6, false), run(12, true)];
+
+        let response = ForecastHistoryResponse::new(checkpoint.clone(), requested, &forecasts, 3);
+        assert_eq!(response.checkpoint_id, checkpoint.id);
+        assert_eq!(response.checkpoint_name, "Mora");
+        // The matched yr.no slot, not the requested time
+        assert_eq!(response.forecast_time, "2026-03-01T08:00:00+00:00");
+        // Counted over all stored model runs, not just the returned entries
+        assert_eq!(response.model_run_count, 3);
+        assert_eq!(response.history.len(), 2);
+        // A legacy row without a model run is placed by its fetch time
+        assert_eq!(response.history[0].yr_model_run_at, None);
+        assert_eq!(
+            response.history[0].model_run_at,
+            "2026-02-27T06:05:00+00:00"
+        );
+        assert_eq!(
+            response.history[1].yr_model_run_at.as_deref(),
+            Some("2026-02-27T12:00:00+00:00")
+        );
+        assert_eq!(
+            response.history[1].model_run_at,
+            "2026-02-27T12:00:00+00:00"
+        );
+
+        // since/until can filter out every entry; the count still reflects
+        // the stored runs and the requested time is echoed
+        let response = ForecastHistoryResponse::new(checkpoint, requested, &[], 3);
+        assert!(response.history.is_empty());
+        assert_eq!(response.model_run_count, 3);
+        assert_eq!(response.forecast_time, "2026-03-01T08:20:00+00:00");
+    }
+}
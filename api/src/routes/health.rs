@@ -1,9 +1,23 @@
 use axum::extract::State;
 use axum::Json;
+use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
 use sqlx::PgPool;
 use utoipa::ToSchema;
 
+use crate::services::poller::{PollerState, SharedPollerState};
+use crate::services::yr::YrClient;
+
+/// State for the health check.
+#[derive(Clone)]
+pub(crate) struct HealthState {
+    pub(crate) pool: PgPool,
+    pub(crate) yr_client: YrClient,
+    pub(crate) poller_state: SharedPollerState,
+    /// Minutes without poller progress before `poller_healthy` is false
+    pub(crate) poller_liveness_threshold_minutes: i64,
+}
+
 /// Health check response.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct HealthResponse {
@@ -13,6 +27,35 @@ pub struct HealthResponse {
     pub version: String,
     /// Whether the database is reachable
     pub database: bool,
+    /// Whether yr.no calls are paused after repeated failures (forecasts are
+    /// served from cache only)
+    pub yr_circuit_breaker_open: bool,
+    /// Whether the background poller is making progress. Null when no races
+    /// are being polled or polling is paused.
+    pub poller_healthy: Option<bool>,
+}
+
+/// Judge poller liveness from its state.
+///
+/// The poller is considered stuck when neither its last completed cycle nor
+/// its scheduled wakeup lies within `threshold_minutes` of `now` — a poller
+/// sleeping until a future wakeup is alive, however long ago it last polled.
+/// Returns `None` when no checkpoints are being polled (no upcoming races) or
+/// polling is paused, since the poller is then idle by design.
+fn poller_liveness(
+    state: &PollerState,
+    now: DateTime<Utc>,
+    threshold_minutes: i64,
+) -> Option<bool> {
+    if state.checkpoints.is_empty() || state.paused {
+        return None;
+    }
+    let last_progress = match (state.last_poll_completed_at, state.next_wakeup_at) {
+        (Some(completed), Some(wakeup)) => completed.max(wakeup),
+        (Some(at), None) | (None, Some(at)) => at,
+        (None, None) => return Some(false),
+    };
+    Some(now - last_progress <= Duration::minutes(threshold_minutes))
 }
 
 /// Health check endpoint.
@@ -20,6 +63,8 @@ pub struct HealthResponse {
 /// Returns the API status and version. Verifies database connectivity
 /// with a simple query. Returns status "degraded" (still 200) if the
 /// DB is unreachable, so load balancers can distinguish partial failures.
+/// An open yr.no circuit breaker or a stuck poller is reported but does not
+/// degrade the status.
 #[utoipa::path(
     get,
     path = "/api/v1/health",
@@ -28,12 +73,24 @@ pub struct HealthResponse {
         (status = 200, description = "Service is healthy", body = HealthResponse),
     )
 )]
-pub async fn health_check(State(pool): State<PgPool>) -> Json<HealthResponse> {
+pub async fn health_check(State(state): State<HealthState>) -> Json<HealthResponse> {
     let db_ok = sqlx::query_scalar::<_, i32>("SELECT 1")
-        .fetch_one(&pool)
+        .fetch_one(&state.pool)
         .await
         .is_ok();
 
+    let poller_healthy = poller_liveness(
+        &*state.poller_state.read().await,
+        Utc::now(),
+        state.poller_liveness_threshold_minutes,
+    );
+    if poller_healthy == Some(false) {
+        tracing::warn!(
+            "Poller has made no progress for over {} minutes",
+            state.poller_liveness_threshold_minutes
+        );
+    }
+
     Json(HealthResponse {
         status: if db_ok {
             "ok".to_string()
@@ -42,6 +99,8 @@ pub async fn health_check(State(pool): State<PgPool>) -> Json<HealthResponse> {
         },
         version: env!("CARGO_PKG_VERSION").to_string(),
         database: db_ok,
+        yr_circuit_breaker_open: state.yr_client.circuit_breaker_open(),
+        poller_healthy,
     })
 }
 
@@ -54,5 +113,55 @@ mod tests {
     // The old unit test called `health_check()` directly, which was possible
     // when it had no dependencies. With the DB check added, a mock pool
     // would be needed — but per project rules we use unit tests with mock
-    // data only, not mock DB pools.
+    // data only, not mock DB pools. The poller liveness logic is pure and
+    // tested below.
+    use super::*;
+    use crate::services::poller::CheckpointPollStatus;
+
+    fn polling_state(now: DateTime<Utc>) -> PollerState {
+        let mut state = PollerState::new();
+        state.set_checkpoints(vec![CheckpointPollStatus {
+            checkpoint_id: uuid::Uuid::nil(),
+            checkpoint_name: "Smågan".to_string(),
+            race_name: "Vasaloppet".to_string(),
+            distance_km: 11.0,
+            poll_priority: 3,
+            expires_at: None,
+            last_fetched_at: None,
+            last_model_run_at: None,
+            last_poll_result: "new_data".to_string(),
+            extraction_count: 0,
+            model_run_age_minutes: None,
+            next_model_run_expected_at: None,
+            stale_warning: false,
+            model_run_overdue: false,
+            last_seen_at: now,
+        }]);
+        state.last_poll_completed_at = Some(now - Duration::minutes(5));
+        state.next_wakeup_at = Some(now + Duration::minutes(10));
+        state
+    }
+
+    #[test]
+    fn test_poller_liveness() {
+        let now = Utc::now();
+        assert_eq!(poller_liveness(&polling_state(now), now, 30), Some(true));
+
+        // Last poll long ago, wakeup long overdue: stuck
+        let mut stuck = polling_state(now);
+        stuck.last_poll_completed_at = Some(now - Duration::hours(2));
+        stuck.next_wakeup_at = Some(now - Duration::minutes(45));
+        assert_eq!(poller_liveness(&stuck, now, 30), Some(false));
+
+        // Last poll long ago but sleeping until a future wakeup: alive
+        let mut sleeping = stuck.clone();
+        sleeping.next_wakeup_at = Some(now + Duration::minutes(1));
+        assert_eq!(poller_liveness(&sleeping, now, 30), Some(true));
+
+        // Paused or nothing to poll: not judged
+        let mut paused = stuck.clone();
+        paused.paused = true;
+        assert_eq!(poller_liveness(&paused, now, 30), None);
+        assert_eq!(poller_liveness(&PollerState::new(), now, 30), None);
+    }
 }
@@ -0,0 +1,165 @@
+//! Static reference data for frontends.
+//!
+//! GET /api/v1/meta/symbol-codes       — all known yr.no `symbol_code` values.
+//! GET /api/v1/meta/symbol-codes/:code — one of them.
+//!
+//! The list lives in `symbols.json`, embedded in the binary. yr.no appends
+//! `_day`, `_night` or `_polartwilight` to codes that depend on the sun;
+//! those are listed once, by their base code, with `day_variant` /
+//! `night_variant` set.
+
+use std::sync::LazyLock;
+
+use axum::extract::Path;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::errors::{AppError, AppErrorCode, ErrorResponseV2};
+
+/// Base URL of the met.no weather icon set (`{code}.svg`).
+const ICON_BASE_URL: &str = "https://api.met.no/images/weathericons/svg";
+
+/// Suffixes yr.no appends to sun-dependent symbol codes.
+const VARIANT_SUFFIXES: [&str; 3] = ["_day", "_night", "_polartwilight"];
+
+/// A yr.no weather symbol code.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SymbolCode {
+    /// Base symbol code, e.g. "heavysnow" or "clearsky"
+    pub code: String,
+    /// "clear", "cloudy", "fog", "rain", "sleet" or "snow"
+    pub category: String,
+    /// English description, e.g. "Heavy snow"
+    pub description: String,
+    /// SVG icon; the `_day` variant's for codes that only come in variants
+    #[serde(default, skip_deserializing)]
+    pub icon_url: String,
+    /// Whether yr.no sends this code as `{code}_day`
+    pub day_variant: bool,
+    /// Whether yr.no sends this code as `{code}_night`
+    pub night_variant: bool,
+}
+
+static SYMBOL_CODES: LazyLock<Vec<SymbolCode>> = LazyLock::new(|| {
+    let mut codes: Vec<SymbolCode> =
+        serde_json::from_str(include_str!("symbols.json")).expect("symbols.json is valid");
+    for symbol in &mut codes {
+        let icon = if symbol.day_variant {
+            format!("{}_day", symbol.code)
+        } else {
+            symbol.code.clone()
+        };
+        symbol.icon_url = format!("{}/{}.svg", ICON_BASE_URL, icon);
+    }
+    codes
+});
+
+/// Look up a symbol code as yr.no sends it, with or without a variant
+/// suffix ("clearsky_day" finds "clearsky"; "cloudy_day" finds nothing).
+fn find_symbol_code(code: &str) -> Option<&'static SymbolCode> {
+    if let Some(symbol) = SYMBOL_CODES.iter().find(|s| s.code == code) {
+        return Some(symbol);
+    }
+    let base = VARIANT_SUFFIXES
+        .iter()
+        .find_map(|suffix| code.strip_suffix(suffix))?;
+    SYMBOL_CODES
+        .iter()
+        .find(|s| s.code == base && (s.day_variant || s.night_variant))
+}
+
+/// List all known yr.no symbol codes.
+///
+/// Static data for mapping `symbol_code` to icons; no authentication.
+#[utoipa::path(
+    get,
+    path = "/api/v1/meta/symbol-codes",
+    tag = "Meta",
+    responses(
+        (status = 200, description = "Known yr.no symbol codes", body = Vec<SymbolCode>),
+    )
+)]
+pub async fn list_symbol_codes() -> Json<&'static [SymbolCode]> {
+    Json(SYMBOL_CODES.as_slice())
+}
+
+/// Get one yr.no symbol code.
+///
+/// Accepts the base code or a variant as sent by yr.no (e.g. "clearsky_day").
+#[utoipa::path(
+    get,
+    path = "/api/v1/meta/symbol-codes/{code}",
+    tag = "Meta",
+    params(
+        ("code" = String, Path, description = "Symbol code, e.g. \"heavysnow\" or \"clearsky_day\""),
+    ),
+    responses(
+        (status = 200, description = "Symbol code metadata", body = SymbolCode),
+        (status = 404, description = "Unknown symbol code", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_symbol_code(
+    Path(code): Path<String>,
+) -> Result<Json<&'static SymbolCode>, AppError> {
+    find_symbol_code(&code).map(Json).ok_or_else(|| {
+        AppError::NotFound(
+            AppErrorCode::SymbolCodeNotFound,
+            format!("Unknown symbol code '{}'", code),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_symbol_codes_load_with_icons() {
+        let codes: HashSet<&str> = SYMBOL_CODES.iter().map(|s| s.code.as_str()).collect();
+        assert_eq!(codes.len(), SYMBOL_CODES.len(), "codes are unique");
+
+        let heavysnow = find_symbol_code("heavysnow").unwrap();
+        assert_eq!(heavysnow.category, "snow");
+        assert_eq!(heavysnow.description, "Heavy snow");
+        assert_eq!(
+            heavysnow.icon_url,
+            "https://api.met.no/images/weathericons/svg/heavysnow.svg"
+        );
+        assert!(!heavysnow.day_variant && !heavysnow.night_variant);
+
+        let clearsky = find_symbol_code("clearsky").unwrap();
+        assert!(clearsky.day_variant && clearsky.night_variant);
+        assert!(clearsky.icon_url.ends_with("/clearsky_day.svg"));
+    }
+
+    #[test]
+    fn test_find_symbol_code_variants() {
+        assert_eq!(
+            find_symbol_code("partlycloudy_night").map(|s| s.code.as_str()),
+            Some("partlycloudy")
+        );
+        assert_eq!(
+            find_symbol_code("heavyrainshowersandthunder_polartwilight").map(|s| s.code.as_str()),
+            Some("heavyrainshowersandthunder")
+        );
+        // Codes without variants don't take a suffix
+        assert!(find_symbol_code("cloudy_day").is_none());
+        assert!(find_symbol_code("hail").is_none());
+    }
+
+    #[test]
+    fn test_symbol_codes_cover_yr_test_data() {
+        // Codes used in yr.no responses elsewhere in the test suite
+        for code in [
+            "cloudy",
+            "lightsnow",
+            "partlycloudy_day",
+            "snow",
+            "heavyrainshowersandthunder_day",
+        ] {
+            assert!(find_symbol_code(code).is_some(), "{}", code);
+        }
+    }
+}
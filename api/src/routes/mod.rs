@@ -1,4 +1,11 @@
+pub mod accuracy;
+pub mod admin;
+pub mod checkpoints;
 pub mod forecasts;
 pub mod health;
+pub mod meta;
 pub mod poller;
 pub mod races;
+pub mod schema;
+pub mod sparse;
+pub mod summaries;
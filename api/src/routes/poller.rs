@@ -5,13 +5,26 @@
 
 use axum::extract::State;
 use axum::Json;
+use chrono::Utc;
 
 use crate::services::poller::{PollerState, SharedPollerState};
 
+/// Shared state for the poller status route.
+#[derive(Clone)]
+pub(crate) struct PollerStatusState {
+    pub poller_state: SharedPollerState,
+    /// Model run age (minutes) above which a checkpoint gets `stale_warning`
+    pub stale_warn_minutes: u32,
+}
+
 /// Get the current poller status.
 ///
 /// Returns per-checkpoint info (expires_at, last_fetched_at, last_model_run_at,
-/// last_poll_result) and global info (next_wakeup_at, last_poll_completed_at, active).
+/// last_poll_result, model_run_age_minutes, stale_warning) and global info
+/// (next_wakeup_at, next_wakeup_in_seconds, last_poll_completed_at, active,
+/// checkpoints_with_stale_warning, and the yr.no cache summary
+/// oldest_unpolled_checkpoint_age_seconds, checkpoints_with_valid_cache and
+/// checkpoints_total).
 #[utoipa::path(
     get,
     path = "/api/v1/poller/status",
@@ -20,7 +33,10 @@ use crate::services::poller::{PollerState, SharedPollerState};
         (status = 200, description = "Current poller status", body = PollerState),
     )
 )]
-pub async fn get_poller_status(State(state): State<SharedPollerState>) -> Json<PollerState> {
-    let s = state.read().await;
-    Json(s.clone())
+pub(crate) async fn get_poller_status(State(state): State<PollerStatusState>) -> Json<PollerState> {
+    let mut s = state.poller_state.read().await.clone();
+    let now = Utc::now();
+    s.annotate_staleness(now, state.stale_warn_minutes);
+    s.annotate_next_wakeup(now);
+    Json(s)
 }
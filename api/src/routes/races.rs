@@ -1,15 +1,23 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::db::{models, queries};
-use crate::errors::{AppError, ErrorResponse};
-use crate::helpers::dec_to_f64;
-use crate::services::forecast::compute_pacing_profile;
-use crate::services::gpx::{compute_track_profile, extract_track_points, CoursePoint};
+use crate::errors::{AppError, AppErrorCode, ErrorResponseV2};
+use crate::helpers::{dec_to_f64, opt_dec_to_f64};
+use crate::services::forecast::{compute_pacing_profile, PacingParams};
+use crate::services::gpx::{
+    compute_elevation_stats, compute_track_profile, extract_track_points, CoursePoint,
+    ElevationStats,
+};
+use crate::services::poller::{reextract_race_forecasts, PollerConfig};
+use crate::services::yr::parse_http_date;
 
 /// Response type for GET /api/v1/races (list, without GPX).
 #[derive(Debug, Serialize, ToSchema)]
@@ -24,6 +32,41 @@ pub struct RaceListItem {
     pub start_time: String,
     /// Total race distance in kilometres
     pub distance_km: f64,
+    /// Measured length of the GPX track in kilometres (null if the file has no track)
+    pub computed_track_length_km: Option<f64>,
+    /// Host country (from the GPX, if set)
+    pub country: Option<String>,
+    /// Race website (from the GPX, if set)
+    pub website: Option<String>,
+    /// Ski technique: "classic", "skate" or "freestyle" (from the GPX, if set)
+    pub discipline: Option<String>,
+    /// Race category, e.g. "classic", "skate", "biathlon" (from the GPX, if set)
+    pub category: Option<String>,
+    /// Total climb in metres (from the GPX, if set)
+    pub elevation_gain_m: Option<f64>,
+    /// When the race was first seeded (RFC 3339)
+    pub created_at: String,
+    /// When the race was last re-seeded or edited (RFC 3339)
+    pub updated_at: String,
+    /// Number of checkpoints along the course
+    pub checkpoint_count: usize,
+    /// Whether a checkpoint sits at 0 km
+    pub has_start_checkpoint: bool,
+    /// Whether a checkpoint sits at `distance_km`
+    pub has_finish_checkpoint: bool,
+    /// Whether cached yr.no forecasts already reach the race start.
+    /// Only in the race list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forecast_available: Option<bool>,
+    /// Days until the race start enters yr.no's ~10-day forecast range
+    /// (null when it already has, or the race is over). Only in the race list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<i32>)]
+    pub days_until_forecast_available: Option<Option<i32>>,
+    /// Distance from the search point to the race start in kilometres.
+    /// Only present when filtering with `near_lat`/`near_lon`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_distance_km: Option<f64>,
 }
 
 impl From<models::Race> for RaceListItem {
@@ -34,10 +77,204 @@ impl From<models::Race> for RaceListItem {
             year: r.year,
             start_time: r.start_time.to_rfc3339(),
             distance_km: dec_to_f64(r.distance_km),
+            computed_track_length_km: opt_dec_to_f64(r.computed_track_length_km),
+            country: r.country,
+            website: r.website,
+            discipline: r.discipline,
+            category: r.category,
+            elevation_gain_m: opt_dec_to_f64(r.elevation_gain_m),
+            created_at: r.created_at.to_rfc3339(),
+            updated_at: r.updated_at.to_rfc3339(),
+            checkpoint_count: r.checkpoint_count as usize,
+            has_start_checkpoint: r.has_start_checkpoint,
+            has_finish_checkpoint: r.has_finish_checkpoint,
+            forecast_available: None,
+            days_until_forecast_available: None,
+            search_distance_km: None,
         }
     }
 }
 
+impl From<queries::RaceListRow> for RaceListItem {
+    fn from(r: queries::RaceListRow) -> Self {
+        Self {
+            forecast_available: Some(r.forecast_available),
+            days_until_forecast_available: Some(r.days_until_forecast_available),
+            ..RaceListItem::from(r.race)
+        }
+    }
+}
+
+/// Default search radius for GET /api/v1/races?near_lat=..&near_lon=..
+const DEFAULT_SEARCH_RADIUS_KM: f64 = 50.0;
+
+/// Optional geographic filter for GET /api/v1/races.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RaceListQuery {
+    /// Latitude of the search point (WGS84). Requires `near_lon`.
+    pub near_lat: Option<f64>,
+    /// Longitude of the search point (WGS84). Requires `near_lat`.
+    pub near_lon: Option<f64>,
+    /// Search radius in km around the point (default 50)
+    pub radius_km: Option<f64>,
+    /// Only races of this category (case-insensitive, e.g. "classic")
+    pub category: Option<String>,
+}
+
+impl RaceListQuery {
+    /// Validated `(lat, lon, radius_km)`, or `None` when no geographic filter was given.
+    fn near(&self) -> Result<Option<(f64, f64, f64)>, AppError> {
+        let (lat, lon) = match (self.near_lat, self.near_lon) {
+            (None, None) if self.radius_km.is_none() => return Ok(None),
+            (Some(lat), Some(lon)) => (lat, lon),
+            _ => {
+                return Err(AppError::BadRequest(
+                    AppErrorCode::InvalidParameter,
+                    "near_lat and near_lon must be given together".to_string(),
+                ))
+            }
+        };
+        if !lat.is_finite() || !(-90.0..=90.0).contains(&lat) {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                "near_lat must be between -90 and 90".to_string(),
+            ));
+        }
+        if !lon.is_finite() || !(-180.0..=180.0).contains(&lon) {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                "near_lon must be between -180 and 180".to_string(),
+            ));
+        }
+        let radius_km = self.radius_km.unwrap_or(DEFAULT_SEARCH_RADIUS_KM);
+        // NaN fails the > 0 comparison
+        if !(radius_km > 0.0 && radius_km.is_finite()) {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                "radius_km must be a positive number".to_string(),
+            ));
+        }
+        Ok(Some((lat, lon, radius_km)))
+    }
+}
+
+/// State for PATCH /api/v1/races/:id, which re-extracts forecasts using the
+/// poller's pacing bounds.
+#[derive(Clone)]
+pub(crate) struct RaceUpdateState {
+    pub pool: PgPool,
+    pub poller_config: PollerConfig,
+}
+
+/// State for GET /api/v1/races/:id/course, which computes pacing time
+/// fractions with the configured gradient constants.
+#[derive(Clone)]
+pub(crate) struct RaceCourseState {
+    pub pool: PgPool,
+    pub pacing_params: PacingParams,
+}
+
+/// Request body for PATCH /api/v1/races/:id.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRaceRequest {
+    /// New race start time in ISO 8601 / RFC 3339 format
+    pub start_time: String,
+}
+
+/// Body of PATCH /api/v1/races/:id: a plain start time or a JSON Patch.
+enum RaceUpdate {
+    StartTime(DateTime<Utc>),
+    Patch(serde_json::Value),
+}
+
+/// Race fields a JSON Patch may change. Everything else is read-only.
+const PATCHABLE_RACE_FIELDS: [&str; 1] = ["start_time"];
+
+fn parse_start_time(start_time: &str) -> Result<DateTime<Utc>, AppError> {
+    start_time.parse().map_err(|e| {
+        AppError::BadRequest(
+            AppErrorCode::InvalidDatetime,
+            format!("Invalid start_time: {}", e),
+        )
+    })
+}
+
+/// Apply a JSON Patch (RFC 6902) to a race as returned by the API and return
+/// the patched start time.
+///
+/// 400 for a malformed patch, an invalid path, a change to a read-only field
+/// or a missing/invalid `start_time`; 422 when a `test` operation fails.
+fn apply_race_patch(
+    current: &RaceListItem,
+    patch: serde_json::Value,
+) -> Result<DateTime<Utc>, AppError> {
+    let bad_request = |msg: String| AppError::BadRequest(AppErrorCode::InvalidParameter, msg);
+    let patch: json_patch::Patch = serde_json::from_value(normalize_start_time_tests(patch))
+        .map_err(|e| bad_request(format!("Malformed JSON Patch: {}", e)))?;
+    let original = serde_json::to_value(current)
+        .map_err(|e| AppError::InternalError(format!("Race serialization: {}", e)))?;
+
+    let mut doc = original.clone();
+    json_patch::patch(&mut doc, &patch).map_err(|e| match e.kind {
+        json_patch::PatchErrorKind::TestFailed => AppError::PatchTestFailed(format!(
+            "Test operation {} failed: {} did not match",
+            e.operation, e.path
+        )),
+        _ => bad_request(format!("Patch operation {} failed: {}", e.operation, e)),
+    })?;
+
+    let (Some(original), Some(patched)) = (original.as_object(), doc.as_object()) else {
+        return Err(bad_request("Patched race must be an object".to_string()));
+    };
+    let changed = original.keys().chain(patched.keys()).find(|k| {
+        !PATCHABLE_RACE_FIELDS.contains(&k.as_str()) && original.get(*k) != patched.get(*k)
+    });
+    if let Some(field) = changed {
+        return Err(bad_request(format!("Field '{}' is read-only", field)));
+    }
+
+    let start_time = patched
+        .get("start_time")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| bad_request("start_time is required and must be a string".to_string()))?;
+    parse_start_time(start_time)
+}
+
+/// Rewrite the values of `test` operations on `/start_time` in the format
+/// races are serialized with, so that equal instants match however they are
+/// written (`Z` vs `+00:00`, other offsets). Unparseable values are kept and
+/// fail the test as usual.
+fn normalize_start_time_tests(mut patch: serde_json::Value) -> serde_json::Value {
+    let Some(ops) = patch.as_array_mut() else {
+        return patch;
+    };
+    for op in ops.iter_mut().filter_map(|op| op.as_object_mut()) {
+        if op.get("op").and_then(|v| v.as_str()) != Some("test")
+            || op.get("path").and_then(|v| v.as_str()) != Some("/start_time")
+        {
+            continue;
+        }
+        let parsed = op
+            .get("value")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<DateTime<Utc>>().ok());
+        if let Some(t) = parsed {
+            op.insert("value".to_string(), t.to_rfc3339().into());
+        }
+    }
+    patch
+}
+
+/// Allowed values for `poll_priority`.
+const POLL_PRIORITY_RANGE: std::ops::RangeInclusive<i32> = 1..=5;
+
+/// Request body for PATCH /api/v1/races/:id/checkpoints/:checkpoint_id.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateCheckpointRequest {
+    /// Poller refresh priority, 1 (lowest) to 5 (highest)
+    pub poll_priority: i32,
+}
+
 /// Response type for GET /api/v1/races/:id/checkpoints.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct CheckpointResponse {
@@ -55,103 +292,1000 @@ pub struct CheckpointResponse {
     pub elevation_m: f64,
     /// Display order along the course
     pub sort_order: i32,
+    /// Poller refresh priority, 1 (lowest) to 5 (highest)
+    pub poll_priority: i32,
+    /// Wind exposure from elevation and course context:
+    /// "sheltered", "moderate", "exposed", or "very_exposed"
+    pub wind_exposure: String,
+    /// When the checkpoint was first seeded (ISO 8601)
+    pub created_at: String,
+    /// When the checkpoint was last seeded from GPX or its poll priority
+    /// changed (ISO 8601)
+    pub updated_at: String,
 }
 
-impl From<models::Checkpoint> for CheckpointResponse {
-    fn from(c: models::Checkpoint) -> Self {
+impl CheckpointResponse {
+    /// Build a response, classifying wind exposure against the elevations of
+    /// the neighbouring checkpoints along the course.
+    fn new(
+        c: models::Checkpoint,
+        prev_elevation: Option<f64>,
+        next_elevation: Option<f64>,
+    ) -> Self {
+        let elevation_m = dec_to_f64(c.elevation_m);
         Self {
             id: c.id,
             name: c.name,
             distance_km: dec_to_f64(c.distance_km),
             latitude: dec_to_f64(c.latitude),
             longitude: dec_to_f64(c.longitude),
-            elevation_m: dec_to_f64(c.elevation_m),
+            elevation_m,
             sort_order: c.sort_order,
+            poll_priority: c.poll_priority,
+            wind_exposure: classify_wind_exposure(elevation_m, prev_elevation, next_elevation)
+                .to_string(),
+            created_at: c.created_at.to_rfc3339(),
+            updated_at: c.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Wind exposure categories, from least to most exposed.
+const WIND_EXPOSURE_CATEGORIES: [&str; 4] = ["sheltered", "moderate", "exposed", "very_exposed"];
+
+/// Classify a checkpoint's wind exposure from its elevation.
+///
+/// Below 400 m "sheltered", 400–700 m "moderate", 700–1000 m "exposed",
+/// above 1000 m "very_exposed". A local peak (higher than both neighbours)
+/// is one category worse; the first and last checkpoints are never peaks.
+fn classify_wind_exposure(
+    elevation_m: f64,
+    prev_elevation: Option<f64>,
+    next_elevation: Option<f64>,
+) -> &'static str {
+    let mut level = if elevation_m < 400.0 {
+        0
+    } else if elevation_m < 700.0 {
+        1
+    } else if elevation_m <= 1000.0 {
+        2
+    } else {
+        3
+    };
+    if let (Some(prev), Some(next)) = (prev_elevation, next_elevation) {
+        if elevation_m > prev && elevation_m > next {
+            level = (level + 1).min(WIND_EXPOSURE_CATEGORIES.len() - 1);
+        }
+    }
+    WIND_EXPOSURE_CATEGORIES[level]
+}
+
+/// Response type for GET /api/v1/races/:id/bounds.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RaceBounds {
+    /// Southernmost checkpoint latitude (WGS84)
+    pub min_lat: f64,
+    /// Northernmost checkpoint latitude (WGS84)
+    pub max_lat: f64,
+    /// Westernmost checkpoint longitude (WGS84)
+    pub min_lon: f64,
+    /// Easternmost checkpoint longitude (WGS84)
+    pub max_lon: f64,
+    /// Midpoint of min/max latitude
+    pub center_lat: f64,
+    /// Midpoint of min/max longitude
+    pub center_lon: f64,
+}
+
+impl RaceBounds {
+    fn new(min_lat: f64, max_lat: f64, min_lon: f64, max_lon: f64) -> Self {
+        Self {
+            min_lat,
+            max_lat,
+            min_lon,
+            max_lon,
+            center_lat: (min_lat + max_lat) / 2.0,
+            center_lon: (min_lon + max_lon) / 2.0,
         }
     }
 }
 
-/// List all available races.
+/// Whether a client holding a copy from `if_modified_since` is still up to
+/// date. HTTP dates have whole-second precision, so `latest` is truncated.
+fn not_modified_since(latest: Option<DateTime<Utc>>, if_modified_since: DateTime<Utc>) -> bool {
+    latest.is_some_and(|latest| latest.timestamp() <= if_modified_since.timestamp())
+}
+
+/// List all available races, optionally only those starting near a point.
+///
+/// With `near_lat`/`near_lon`, returns races whose first checkpoint is within
+/// `radius_km`, nearest first, each with `search_distance_km`.
+///
+/// Sends `Last-Modified` (newest race `updated_at`, or the newest yr.no
+/// fetch, passed cache expiry or day change that can move
+/// `forecast_available`/`days_until_forecast_available`) and answers 304
+/// when the request's `If-Modified-Since` is not older than that.
 #[utoipa::path(
     get,
     path = "/api/v1/races",
     tag = "Races",
+    params(
+        RaceListQuery,
+        ("If-Modified-Since" = Option<String>, Header, description = "HTTP date of the client's cached copy"),
+    ),
     responses(
-        (status = 200, description = "List of all races", body = Vec<RaceListItem>),
+        (status = 200, description = "List of races", body = Vec<RaceListItem>),
+        (status = 304, description = "No race changed since If-Modified-Since"),
+        (status = 400, description = "Invalid geographic filter", body = ErrorResponseV2),
     )
 )]
-pub async fn list_races(State(pool): State<PgPool>) -> Result<Json<Vec<RaceListItem>>, AppError> {
-    let races = queries::list_races(&pool).await?;
-    let items: Vec<RaceListItem> = races.into_iter().map(RaceListItem::from).collect();
-    Ok(Json(items))
+pub async fn list_races(
+    State(pool): State<PgPool>,
+    Query(params): Query<RaceListQuery>,
+    request_headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let near = params.near()?;
+    let category = params.category.as_deref().map(str::to_lowercase);
+
+    let latest = queries::latest_race_update(&pool).await?;
+    let if_modified_since = request_headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| parse_http_date(v).ok());
+
+    let mut headers = HeaderMap::new();
+    if let Some(latest) = latest {
+        let http_date = latest.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        if let Ok(value) = HeaderValue::from_str(&http_date) {
+            headers.insert(header::LAST_MODIFIED, value);
+        }
+    }
+
+    if let Some(ims) = if_modified_since {
+        if not_modified_since(latest, ims) {
+            return Ok((StatusCode::NOT_MODIFIED, headers).into_response());
+        }
+    }
+
+    let items: Vec<RaceListItem> = if let Some((lat, lon, radius_km)) = near {
+        queries::find_races_near(&pool, lat, lon, radius_km, category.as_deref())
+            .await?
+            .into_iter()
+            .map(|r| RaceListItem {
+                search_distance_km: Some(r.start_distance_km),
+                ..RaceListItem::from(r.race)
+            })
+            .collect()
+    } else {
+        queries::list_races(&pool, category.as_deref())
+            .await?
+            .into_iter()
+            .map(RaceListItem::from)
+            .collect()
+    };
+    Ok((headers, Json(items)).into_response())
+}
+
+/// Update a race's start time.
+///
+/// The body is either `{ "start_time": ... }` or a JSON Patch (RFC 6902)
+/// array applied to the race as returned by the API, e.g. a `test` on
+/// `/start_time` followed by a `replace` for a conditional update. Only
+/// `start_time` can change.
+///
+/// Existing yr.no caches stay valid (they are location-based), but pass-through
+/// times shift — forecasts for the new time slots are re-extracted in the
+/// background.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/races/{id}",
+    tag = "Races",
+    params(
+        ("id" = Uuid, Path, description = "Race UUID"),
+    ),
+    request_body(
+        content((UpdateRaceRequest = "application/json"), (json_patch::Patch = "application/json-patch+json"))
+    ),
+    responses(
+        (status = 200, description = "Updated race (unchanged for a no-op patch)", body = RaceListItem),
+        (status = 400, description = "Invalid start_time, malformed patch or read-only field changed", body = ErrorResponseV2),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
+        (status = 422, description = "A JSON Patch `test` operation failed", body = ErrorResponseV2),
+    ),
+    security(("api_key" = []))
+)]
+pub(crate) async fn update_race(
+    State(RaceUpdateState {
+        pool,
+        poller_config,
+    }): State<RaceUpdateState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<serde_json::Value>,
+) -> Result<Json<RaceListItem>, AppError> {
+    // A plain body is validated up front; a patch needs the current race
+    let update = if body.is_array() {
+        RaceUpdate::Patch(body)
+    } else {
+        let body: UpdateRaceRequest = serde_json::from_value(body).map_err(|e| {
+            AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!("Invalid request body: {}", e),
+            )
+        })?;
+        RaceUpdate::StartTime(parse_start_time(&body.start_time)?)
+    };
+
+    let old = queries::get_race_summary(&pool, id).await?.ok_or_else(|| {
+        AppError::NotFound(AppErrorCode::RaceNotFound, format!("Race {} not found", id))
+    })?;
+
+    let start_time = match update {
+        RaceUpdate::StartTime(start_time) => start_time,
+        RaceUpdate::Patch(patch) => apply_race_patch(&RaceListItem::from(old.clone()), patch)?,
+    };
+    if start_time == old.start_time {
+        return Ok(Json(RaceListItem::from(old)));
+    }
+
+    let race = queries::update_race_start_time(&pool, id, start_time)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(AppErrorCode::RaceNotFound, format!("Race {} not found", id))
+        })?;
+
+    tracing::info!(
+        "Race '{}' ({}) start time changed: {} → {}",
+        race.name,
+        race.year,
+        old.start_time.to_rfc3339(),
+        race.start_time.to_rfc3339()
+    );
+
+    if old.start_time != race.start_time {
+        tokio::spawn(reextract_race_forecasts(
+            pool.clone(),
+            race.id,
+            race.start_time,
+            poller_config,
+        ));
+    }
+
+    Ok(Json(RaceListItem::from(race)))
+}
+
+/// Optional filter for GET /api/v1/races/:id/course.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CourseQuery {
+    /// Only return points from this track segment (0-based `<trkseg>` index)
+    pub segment: Option<usize>,
 }
 
 /// Get race course as pre-parsed JSON coordinates, with cumulative distances
 /// and pacing time fractions.
+///
+/// Each point carries its `segment_index`; the `X-Segment-Count` header gives
+/// the number of track segments so clients can draw each as a separate line.
+/// Time fractions and distances are course-wide even when filtering by segment.
 #[utoipa::path(
     get,
     path = "/api/v1/races/{id}/course",
     tag = "Races",
     params(
         ("id" = Uuid, Path, description = "Race UUID"),
+        CourseQuery,
     ),
     responses(
-        (status = 200, description = "Course coordinates with cumulative distances and time fractions", body = Vec<CoursePoint>),
-        (status = 404, description = "Race not found", body = ErrorResponse),
+        (status = 200, description = "Course coordinates with cumulative distances and time fractions", body = Vec<CoursePoint>,
+            headers(("X-Segment-Count" = usize, description = "Number of track segments in the course"))),
+        (status = 400, description = "Segment index out of range", body = ErrorResponseV2),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
     )
 )]
 pub async fn get_race_course(
-    State(pool): State<PgPool>,
+    State(state): State<RaceCourseState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Vec<CoursePoint>>, AppError> {
-    let gpx = queries::get_race_course_gpx(&pool, id)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Race {} not found", id)))?;
-
-    // GPX parsing is CPU-bound — run on the blocking thread pool
-    let mut points = tokio::task::spawn_blocking(move || extract_track_points(&gpx))
-        .await
-        .map_err(|e| AppError::InternalError(format!("GPX parsing task failed: {}", e)))?
-        .map_err(|e| AppError::InternalError(format!("Failed to parse course GPX: {}", e)))?;
+    Query(params): Query<CourseQuery>,
+) -> Result<(HeaderMap, Json<Vec<CoursePoint>>), AppError> {
+    let mut points = load_course_points(&state.pool, id).await?;
 
     // Always compute pacing time fractions (elevation-based, duration-independent)
     let track = compute_track_profile(&points);
     if track.len() >= 2 {
-        let profile = compute_pacing_profile(&track, points.len());
+        let profile = compute_pacing_profile(&track, points.len(), &state.pacing_params);
         for (pt, (_d, frac)) in points.iter_mut().zip(profile.into_iter()) {
             pt.time_fraction = frac;
         }
     }
 
-    Ok(Json(points))
+    let segment_count = segment_count(&points);
+    if let Some(segment) = params.segment {
+        if segment >= segment_count {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!(
+                    "segment {} out of range (course has {} segment(s))",
+                    segment, segment_count
+                ),
+            ));
+        }
+        points.retain(|p| p.segment_index == segment);
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Segment-Count", segment_count.into());
+    Ok((headers, Json(points)))
+}
+
+/// Load a race's track points, preferring those cached at seed time.
+async fn load_course_points(pool: &PgPool, id: Uuid) -> Result<Vec<CoursePoint>, AppError> {
+    if let Some(points) = queries::get_race_course_points(pool, id).await? {
+        return Ok(points);
+    }
+    let gpx = queries::get_race_course_gpx(pool, id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(AppErrorCode::RaceNotFound, format!("Race {} not found", id))
+        })?;
+
+    // GPX parsing is CPU-bound — run on the blocking thread pool
+    tokio::task::spawn_blocking(move || extract_track_points(&gpx))
+        .await
+        .map_err(|e| AppError::InternalError(format!("GPX parsing task failed: {}", e)))?
+        .map_err(|e| AppError::InternalError(format!("Failed to parse course GPX: {}", e)))
+}
+
+/// Response type for GET /api/v1/races/:id/stats.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RaceStatsResponse {
+    #[serde(flatten)]
+    pub elevation: ElevationStats,
+    /// Elevation change in metres from each checkpoint to the next, in
+    /// course order (negative when the leg ends lower than it starts)
+    pub checkpoint_elevation_gains: Vec<f64>,
 }
 
-/// Get all checkpoints for a race, ordered by distance from start.
+/// Get elevation statistics for a race course.
+///
+/// Gain, loss and gradients come from the GPX track after 5-point smoothing;
+/// `checkpoint_elevation_gains` compares consecutive checkpoint elevations.
+#[utoipa::path(
+    get,
+    path = "/api/v1/races/{id}/stats",
+    tag = "Races",
+    params(
+        ("id" = Uuid, Path, description = "Race UUID"),
+    ),
+    responses(
+        (status = 200, description = "Course elevation statistics", body = RaceStatsResponse),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_race_stats(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RaceStatsResponse>, AppError> {
+    let points = load_course_points(&pool, id).await?;
+    let checkpoints = queries::get_checkpoints(&pool, id).await?;
+    Ok(Json(RaceStatsResponse {
+        elevation: compute_elevation_stats(&points),
+        checkpoint_elevation_gains: checkpoint_elevation_gains(&checkpoints),
+    }))
+}
+
+/// Elevation difference between consecutive checkpoints (sorted by `sort_order`).
+fn checkpoint_elevation_gains(checkpoints: &[models::Checkpoint]) -> Vec<f64> {
+    checkpoints
+        .windows(2)
+        .map(|pair| dec_to_f64(pair[1].elevation_m) - dec_to_f64(pair[0].elevation_m))
+        .collect()
+}
+
+/// Number of track segments (highest `segment_index` + 1, or 0 without points).
+fn segment_count(points: &[CoursePoint]) -> usize {
+    points
+        .iter()
+        .map(|p| p.segment_index + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Checkpoint list order: course order (`sort_order`) unless a client asks
+/// for distance or name order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointSort {
+    /// Course order (default)
+    #[default]
+    SortOrder,
+    DistanceAsc,
+    DistanceDesc,
+    NameAsc,
+    NameDesc,
+}
+
+impl CheckpointSort {
+    /// `ORDER BY` clause; ties fall back to course order.
+    fn order_by(self) -> &'static str {
+        match self {
+            CheckpointSort::SortOrder => "sort_order ASC",
+            CheckpointSort::DistanceAsc => "distance_km ASC, sort_order ASC",
+            CheckpointSort::DistanceDesc => "distance_km DESC, sort_order ASC",
+            CheckpointSort::NameAsc => "name ASC, sort_order ASC",
+            CheckpointSort::NameDesc => "name DESC, sort_order ASC",
+        }
+    }
+}
+
+/// Ordering for GET /api/v1/races/:id/checkpoints.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CheckpointSortQuery {
+    /// `sort_order` (course order, default), `distance_asc`, `distance_desc`,
+    /// `name_asc` or `name_desc`
+    #[param(value_type = Option<CheckpointSort>)]
+    pub sort: Option<String>,
+}
+
+impl CheckpointSortQuery {
+    /// The requested order; an unknown value is a 400.
+    fn sort(&self) -> Result<CheckpointSort, AppError> {
+        let Some(sort) = self.sort.as_deref() else {
+            return Ok(CheckpointSort::default());
+        };
+        CheckpointSort::deserialize(
+            serde::de::value::StrDeserializer::<serde::de::value::Error>::new(sort),
+        )
+        .map_err(|_| {
+            AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!(
+                    "Invalid sort '{}': expected sort_order, distance_asc, distance_desc, \
+                     name_asc or name_desc",
+                    sort
+                ),
+            )
+        })
+    }
+}
+
+/// Get all checkpoints for a race, in course order unless `?sort=` says
+/// otherwise.
+///
+/// The measured GPX track length is returned in the `X-Computed-Track-Length-Km`
+/// header (omitted when the race has no track) so it can be compared with the
+/// checkpoint distances without changing the array response.
 #[utoipa::path(
     get,
     path = "/api/v1/races/{id}/checkpoints",
     tag = "Races",
     params(
         ("id" = Uuid, Path, description = "Race UUID"),
+        CheckpointSortQuery,
     ),
     responses(
-        (status = 200, description = "List of checkpoints along the course", body = Vec<CheckpointResponse>),
-        (status = 404, description = "Race not found", body = ErrorResponse),
+        (status = 200, description = "List of checkpoints along the course", body = Vec<CheckpointResponse>,
+            headers(("X-Computed-Track-Length-Km" = f64, description = "Measured GPX track length in km"))),
+        (status = 400, description = "Unknown sort value", body = ErrorResponseV2),
+        (status = 404, description = "Race not found", body = ErrorResponseV2),
     )
 )]
 pub async fn get_checkpoints(
     State(pool): State<PgPool>,
     Path(race_id): Path<Uuid>,
-) -> Result<Json<Vec<CheckpointResponse>>, AppError> {
+    Query(params): Query<CheckpointSortQuery>,
+) -> Result<(HeaderMap, Json<Vec<CheckpointResponse>>), AppError> {
+    let sort = params.sort()?;
+
     // Verify the race exists first (lightweight — no GPX blob)
-    let _race = queries::get_race_summary(&pool, race_id)
+    let race = queries::get_race_summary(&pool, race_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::RaceNotFound,
+                format!("Race {} not found", race_id),
+            )
+        })?;
+
+    let checkpoints = queries::get_checkpoints_ordered(&pool, race_id, sort.order_by()).await?;
+    let items = checkpoint_responses(checkpoints);
+
+    let mut headers = HeaderMap::new();
+    if let Some(length) = opt_dec_to_f64(race.computed_track_length_km) {
+        headers.insert(
+            "X-Computed-Track-Length-Km",
+            format!("{:.3}", length).parse().unwrap(),
+        );
+    }
+    Ok((headers, Json(items)))
+}
+
+/// Convert checkpoints to responses (keeping their order), passing each one
+/// its course neighbours' elevations for wind exposure. Neighbours are by
+/// `sort_order`, so any list order works.
+fn checkpoint_responses(checkpoints: Vec<models::Checkpoint>) -> Vec<CheckpointResponse> {
+    let mut course: Vec<(i32, f64)> = checkpoints
+        .iter()
+        .map(|c| (c.sort_order, dec_to_f64(c.elevation_m)))
+        .collect();
+    course.sort_by_key(|(sort_order, _)| *sort_order);
+    checkpoints
+        .into_iter()
+        .map(|c| {
+            let i = course
+                .binary_search_by_key(&c.sort_order, |(sort_order, _)| *sort_order)
+                .unwrap_or_default();
+            let prev = i.checked_sub(1).map(|p| course[p].1);
+            let next = course.get(i + 1).map(|(_, e)| *e);
+            CheckpointResponse::new(c, prev, next)
+        })
+        .collect()
+}
+
+/// Set a checkpoint's poll priority.
+///
+/// The background poller refreshes higher-priority checkpoints first. The
+/// value survives GPX re-seeding.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/races/{id}/checkpoints/{checkpoint_id}",
+    tag = "Races",
+    security(("api_key" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Race UUID"),
+        ("checkpoint_id" = Uuid, Path, description = "Checkpoint UUID"),
+    ),
+    request_body = UpdateCheckpointRequest,
+    responses(
+        (status = 200, description = "Updated checkpoint", body = CheckpointResponse),
+        (status = 400, description = "poll_priority outside 1–5", body = ErrorResponseV2),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponseV2),
+        (status = 404, description = "Checkpoint not found in this race", body = ErrorResponseV2),
+    )
+)]
+pub async fn update_checkpoint(
+    State(pool): State<PgPool>,
+    Path((race_id, checkpoint_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<UpdateCheckpointRequest>,
+) -> Result<Json<CheckpointResponse>, AppError> {
+    if !POLL_PRIORITY_RANGE.contains(&body.poll_priority) {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            format!(
+                "poll_priority must be between {} and {}",
+                POLL_PRIORITY_RANGE.start(),
+                POLL_PRIORITY_RANGE.end()
+            ),
+        ));
+    }
+
+    let not_found = || {
+        AppError::NotFound(
+            AppErrorCode::CheckpointNotFound,
+            format!("Checkpoint {} not found in race {}", checkpoint_id, race_id),
+        )
+    };
+    if !queries::update_checkpoint_poll_priority(&pool, race_id, checkpoint_id, body.poll_priority)
         .await?
-        .ok_or_else(|| AppError::NotFound(format!("Race {} not found", race_id)))?;
+    {
+        return Err(not_found());
+    }
+
+    tracing::info!(
+        "Checkpoint {} poll priority set to {}",
+        checkpoint_id,
+        body.poll_priority
+    );
 
     let checkpoints = queries::get_checkpoints(&pool, race_id).await?;
-    let items: Vec<CheckpointResponse> = checkpoints
+    checkpoint_responses(checkpoints)
         .into_iter()
-        .map(CheckpointResponse::from)
-        .collect();
-    Ok(Json(items))
+        .find(|c| c.id == checkpoint_id)
+        .map(Json)
+        .ok_or_else(not_found)
+}
+
+/// Get the bounding box of all checkpoints in a race (for map initialization).
+#[utoipa::path(
+    get,
+    path = "/api/v1/races/{id}/bounds",
+    tag = "Races",
+    params(
+        ("id" = Uuid, Path, description = "Race UUID"),
+    ),
+    responses(
+        (status = 200, description = "Bounding box of the race checkpoints", body = RaceBounds),
+        (status = 404, description = "Race not found or has no checkpoints", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_race_bounds(
+    State(pool): State<PgPool>,
+    Path(race_id): Path<Uuid>,
+) -> Result<Json<RaceBounds>, AppError> {
+    let (min_lat, max_lat, min_lon, max_lon) = queries::get_race_checkpoint_bounds(&pool, race_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::RaceNotFound,
+                format!("Race {} not found or has no checkpoints", race_id),
+            )
+        })?;
+
+    Ok(Json(RaceBounds::new(
+        dec_to_f64(min_lat),
+        dec_to_f64(max_lat),
+        dec_to_f64(min_lon),
+        dec_to_f64(max_lon),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::api_key::{require_api_key, AdminApiKey, API_KEY_HEADER};
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use axum::routing::patch;
+    use axum::Router;
+    use chrono::TimeZone;
+    use tower::ServiceExt;
+
+    /// PATCH /api/v1/races/:id as wired in `main`, on a pool that never
+    /// connects (requests below are rejected before any query).
+    async fn patch_race(api_key: Option<&str>, body: &str) -> StatusCode {
+        let app = Router::new()
+            .route("/api/v1/races/:id", patch(update_race))
+            .route_layer(axum::middleware::from_fn_with_state(
+                AdminApiKey::new(Some("s3cret".to_string())),
+                require_api_key,
+            ))
+            .with_state(RaceUpdateState {
+                pool: PgPool::connect_lazy("postgres://localhost/unused").unwrap(),
+                poller_config: PollerConfig::defaults(),
+            });
+        let mut request = Request::patch(format!("/api/v1/races/{}", Uuid::new_v4()))
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(key) = api_key {
+            request = request.header(API_KEY_HEADER, key);
+        }
+        app.oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_update_race_requires_api_key() {
+        let body = r#"{"start_time":"2026-03-01T09:00:00+01:00"}"#;
+        assert_eq!(patch_race(None, body).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            patch_race(Some("wrong"), body).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_race_rejects_invalid_body() {
+        let key = Some("s3cret");
+        assert_eq!(
+            patch_race(key, r#"{"start_time":"next tuesday"}"#).await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            patch_race(key, r#"{"name":"Vasaloppet"}"#).await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            patch_race(key, r#"{"start_time":42}"#).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[test]
+    fn test_not_modified_since() {
+        let latest = Utc.with_ymd_and_hms(2026, 2, 14, 12, 0, 0).unwrap()
+            + chrono::Duration::milliseconds(400);
+        let same_second = Utc.with_ymd_and_hms(2026, 2, 14, 12, 0, 0).unwrap();
+        assert!(not_modified_since(Some(latest), same_second));
+        assert!(not_modified_since(
+            Some(latest),
+            same_second + chrono::Duration::hours(1)
+        ));
+        assert!(!not_modified_since(
+            Some(latest),
+            same_second - chrono::Duration::seconds(1)
+        ));
+        // No races yet: nothing to be "not modified" against
+        assert!(!not_modified_since(None, same_second));
+    }
+
+    fn race_item(start_time: DateTime<Utc>) -> RaceListItem {
+        RaceListItem::from(race_row(start_time))
+    }
+
+    fn race_row(start_time: DateTime<Utc>) -> models::Race {
+        models::Race {
+            id: Uuid::nil(),
+            name: "Vasaloppet".to_string(),
+            year: 2026,
+            start_time,
+            distance_km: rust_decimal::Decimal::from(90),
+            computed_track_length_km: None,
+            country: None,
+            website: None,
+            discipline: None,
+            category: None,
+            elevation_gain_m: None,
+            created_at: start_time,
+            updated_at: start_time,
+            checkpoint_count: 9,
+            has_start_checkpoint: true,
+            has_finish_checkpoint: true,
+        }
+    }
+
+    #[test]
+    fn test_forecast_coverage_only_in_race_list() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 7, 0, 0).unwrap();
+        let race = serde_json::to_value(race_item(start)).unwrap();
+        assert!(race.get("forecast_available").is_none());
+        assert!(race.get("days_until_forecast_available").is_none());
+
+        let listed = |forecast_available, days_until_forecast_available| {
+            serde_json::to_value(RaceListItem::from(queries::RaceListRow {
+                race: race_row(start),
+                forecast_available,
+                days_until_forecast_available,
+            }))
+            .unwrap()
+        };
+        let in_range = listed(true, None);
+        assert_eq!(in_range["forecast_available"], true);
+        assert!(in_range["days_until_forecast_available"].is_null());
+        assert!(in_range
+            .as_object()
+            .unwrap()
+            .contains_key("days_until_forecast_available"));
+        let later = listed(false, Some(12));
+        assert_eq!(later["forecast_available"], false);
+        assert_eq!(later["days_until_forecast_available"], 12);
+    }
+
+    #[test]
+    fn test_race_patch_conditional_replace() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 7, 0, 0).unwrap();
+        let patch = serde_json::json!([
+            { "op": "test", "path": "/start_time", "value": "2026-03-01T07:00:00+00:00" },
+            { "op": "replace", "path": "/start_time", "value": "2026-03-01T09:00:00+01:00" }
+        ]);
+        assert_eq!(
+            apply_race_patch(&race_item(start), patch).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_race_patch_failing_test_is_422() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 7, 0, 0).unwrap();
+        let patch = serde_json::json!([
+            { "op": "test", "path": "/start_time", "value": "2026-03-01T06:00:00+00:00" },
+            { "op": "replace", "path": "/start_time", "value": "2026-03-01T09:00:00+00:00" }
+        ]);
+        let err = apply_race_patch(&race_item(start), patch).unwrap_err();
+        assert!(matches!(err, AppError::PatchTestFailed(_)));
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[test]
+    fn test_race_patch_test_compares_instants() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 7, 0, 0).unwrap();
+        for written in [
+            "2026-03-01T07:00:00Z",
+            "2026-03-01T07:00:00+00:00",
+            "2026-03-01T08:00:00+01:00",
+        ] {
+            let patch = serde_json::json!([
+                { "op": "test", "path": "/start_time", "value": written },
+                { "op": "replace", "path": "/start_time", "value": "2026-03-01T09:00:00Z" }
+            ]);
+            assert_eq!(
+                apply_race_patch(&race_item(start), patch).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap(),
+                "{}",
+                written
+            );
+        }
+        // A different instant, or garbage, still fails the test
+        for written in ["2026-03-01T07:00:00+01:00", "7 o'clock"] {
+            let patch = serde_json::json!([
+                { "op": "test", "path": "/start_time", "value": written }
+            ]);
+            assert!(matches!(
+                apply_race_patch(&race_item(start), patch),
+                Err(AppError::PatchTestFailed(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_race_patch_noop_keeps_start_time() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 7, 0, 0).unwrap();
+        assert_eq!(
+            apply_race_patch(&race_item(start), serde_json::json!([])).unwrap(),
+            start
+        );
+        let same = serde_json::json!([
+            { "op": "test", "path": "/name", "value": "Vasaloppet" }
+        ]);
+        assert_eq!(apply_race_patch(&race_item(start), same).unwrap(), start);
+    }
+
+    #[test]
+    fn test_race_patch_rejects_malformed_and_read_only() {
+        let item = race_item(Utc.with_ymd_and_hms(2026, 3, 1, 7, 0, 0).unwrap());
+        let bad = |patch: serde_json::Value| {
+            matches!(
+                apply_race_patch(&item, patch),
+                Err(AppError::BadRequest(AppErrorCode::InvalidParameter, _))
+            )
+        };
+        assert!(bad(serde_json::json!([{ "op": "replace" }])));
+        assert!(bad(
+            serde_json::json!([{ "op": "frobnicate", "path": "/name" }])
+        ));
+        assert!(bad(
+            serde_json::json!([{ "op": "replace", "path": "/missing", "value": 1 }])
+        ));
+        assert!(bad(
+            serde_json::json!([{ "op": "replace", "path": "/name", "value": "Mora" }])
+        ));
+        assert!(bad(
+            serde_json::json!([{ "op": "remove", "path": "/start_time" }])
+        ));
+    }
+
+    #[test]
+    fn test_race_bounds_center_is_midpoint() {
+        let b = RaceBounds::new(60.9, 61.2, 13.9, 14.6);
+        assert!((b.center_lat - 61.05).abs() < 1e-10);
+        assert!((b.center_lon - 14.25).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_wind_exposure_sheltered_below_400m() {
+        assert_eq!(classify_wind_exposure(0.0, None, None), "sheltered");
+        assert_eq!(classify_wind_exposure(399.9, None, None), "sheltered");
+    }
+
+    #[test]
+    fn test_wind_exposure_moderate_400_to_700m() {
+        assert_eq!(classify_wind_exposure(400.0, None, None), "moderate");
+        assert_eq!(classify_wind_exposure(699.9, None, None), "moderate");
+    }
+
+    #[test]
+    fn test_wind_exposure_exposed_700_to_1000m() {
+        assert_eq!(classify_wind_exposure(700.0, None, None), "exposed");
+        assert_eq!(classify_wind_exposure(1000.0, None, None), "exposed");
+    }
+
+    #[test]
+    fn test_wind_exposure_very_exposed_above_1000m() {
+        assert_eq!(classify_wind_exposure(1000.1, None, None), "very_exposed");
+    }
+
+    #[test]
+    fn test_wind_exposure_local_peak_one_category_worse() {
+        assert_eq!(
+            classify_wind_exposure(450.0, Some(350.0), Some(420.0)),
+            "exposed"
+        );
+        // Already the worst category — stays there
+        assert_eq!(
+            classify_wind_exposure(1200.0, Some(900.0), Some(1100.0)),
+            "very_exposed"
+        );
+    }
+
+    #[test]
+    fn test_wind_exposure_not_a_peak() {
+        // Higher than only one neighbour, or at an end of the course
+        assert_eq!(
+            classify_wind_exposure(450.0, Some(350.0), Some(500.0)),
+            "moderate"
+        );
+        assert_eq!(
+            classify_wind_exposure(450.0, Some(450.0), Some(300.0)),
+            "moderate"
+        );
+        assert_eq!(classify_wind_exposure(450.0, None, Some(300.0)), "moderate");
+    }
+
+    fn sort_query(sort: Option<&str>) -> CheckpointSortQuery {
+        CheckpointSortQuery {
+            sort: sort.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_sort_order_by() {
+        let order_by = |sort| sort_query(sort).sort().unwrap().order_by();
+        assert_eq!(order_by(None), "sort_order ASC");
+        assert_eq!(order_by(Some("sort_order")), "sort_order ASC");
+        assert_eq!(
+            order_by(Some("distance_asc")),
+            "distance_km ASC, sort_order ASC"
+        );
+        assert_eq!(
+            order_by(Some("distance_desc")),
+            "distance_km DESC, sort_order ASC"
+        );
+        assert_eq!(order_by(Some("name_asc")), "name ASC, sort_order ASC");
+        assert_eq!(order_by(Some("name_desc")), "name DESC, sort_order ASC");
+    }
+
+    #[test]
+    fn test_checkpoint_sort_rejects_unknown_values() {
+        for sort in ["elevation_asc", "NAME_ASC", ""] {
+            assert!(matches!(
+                sort_query(Some(sort)).sort(),
+                Err(AppError::BadRequest(AppErrorCode::InvalidParameter, _))
+            ));
+        }
+    }
+
+    fn near_query(lat: Option<f64>, lon: Option<f64>, radius: Option<f64>) -> RaceListQuery {
+        RaceListQuery {
+            near_lat: lat,
+            near_lon: lon,
+            radius_km: radius,
+            category: None,
+        }
+    }
+
+    #[test]
+    fn test_near_query_absent_and_defaults() {
+        assert_eq!(near_query(None, None, None).near().unwrap(), None);
+        assert_eq!(
+            near_query(Some(61.0), Some(13.5), None).near().unwrap(),
+            Some((61.0, 13.5, DEFAULT_SEARCH_RADIUS_KM))
+        );
+    }
+
+    #[test]
+    fn test_near_query_rejects_invalid() {
+        assert!(near_query(Some(61.0), None, None).near().is_err());
+        assert!(near_query(None, None, Some(10.0)).near().is_err());
+        assert!(near_query(Some(91.0), Some(13.5), None).near().is_err());
+        assert!(near_query(Some(61.0), Some(13.5), Some(0.0))
+            .near()
+            .is_err());
+        assert!(near_query(Some(61.0), Some(13.5), Some(f64::NAN))
+            .near()
+            .is_err());
+    }
+
+    #[test]
+    fn test_segment_count() {
+        let point = |segment_index| CoursePoint {
+            lat: 61.0,
+            lon: 14.0,
+            ele: 0.0,
+            distance_km: 0.0,
+            time_fraction: 0.0,
+            segment_index,
+        };
+        assert_eq!(segment_count(&[]), 0);
+        assert_eq!(segment_count(&[point(0), point(0)]), 1);
+        assert_eq!(segment_count(&[point(0), point(1), point(1)]), 2);
+    }
+
+    #[test]
+    fn test_race_bounds_single_point() {
+        let b = RaceBounds::new(61.0, 61.0, 14.0, 14.0);
+        assert_eq!(b.center_lat, 61.0);
+        assert_eq!(b.center_lon, 14.0);
+    }
 }
@@ -0,0 +1,165 @@
+//! Versioned OpenAPI schema endpoint.
+//!
+//! GET /api/v1/schema — the same document as `/api-docs/openapi.json`, tagged
+//! with `x-api-version` / `x-crate-version` extensions, as JSON (default) or
+//! YAML (`?format=yaml`).
+
+use axum::extract::Query;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use utoipa::openapi::extensions::ExtensionsBuilder;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::errors::{AppError, AppErrorCode, ErrorResponseV2};
+use crate::ApiDoc;
+
+/// API version advertised in the `x-api-version` extension.
+const API_VERSION: &str = "0.1.0";
+
+/// Serialization format for the schema endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+/// Query parameters for GET /api/v1/schema.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SchemaQuery {
+    /// `json` (default) or `yaml`
+    #[param(value_type = Option<SchemaFormat>)]
+    pub format: Option<String>,
+}
+
+impl SchemaQuery {
+    /// The requested format; an unknown value is a 400.
+    fn format(&self) -> Result<SchemaFormat, AppError> {
+        let Some(format) = self.format.as_deref() else {
+            return Ok(SchemaFormat::default());
+        };
+        SchemaFormat::deserialize(
+            serde::de::value::StrDeserializer::<serde::de::value::Error>::new(format),
+        )
+        .map_err(|_| {
+            AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!("Invalid format '{}': expected json or yaml", format),
+            )
+        })
+    }
+}
+
+/// The API's OpenAPI document with version extensions.
+fn versioned_openapi() -> utoipa::openapi::OpenApi {
+    let mut doc = ApiDoc::openapi();
+    let extensions = ExtensionsBuilder::new()
+        .add("x-api-version", API_VERSION)
+        .add("x-crate-version", env!("CARGO_PKG_VERSION"))
+        .build();
+    match doc.extensions.as_mut() {
+        Some(existing) => existing.merge(extensions),
+        None => doc.extensions = Some(extensions),
+    }
+    doc
+}
+
+/// Get the OpenAPI schema.
+///
+/// Returns the same document as `/api-docs/openapi.json`, plus
+/// `x-api-version` and `x-crate-version` extensions.
+#[utoipa::path(
+    get,
+    path = "/api/v1/schema",
+    tag = "Health",
+    params(SchemaQuery),
+    responses(
+        (status = 200, description = "OpenAPI schema as JSON (default) or YAML",
+            content((Object = "application/json"), (String = "application/yaml"))),
+        (status = 400, description = "Invalid format", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_schema(Query(params): Query<SchemaQuery>) -> Result<Response, AppError> {
+    let format = params.format()?;
+    let doc = versioned_openapi();
+    let (content_type, body) = match format {
+        SchemaFormat::Json => (
+            "application/json",
+            serde_json::to_string(&doc)
+                .map_err(|e| AppError::InternalError(format!("Schema serialization: {}", e)))?,
+        ),
+        SchemaFormat::Yaml => (
+            "application/yaml",
+            serde_yaml::to_string(&doc)
+                .map_err(|e| AppError::InternalError(format!("Schema serialization: {}", e)))?,
+        ),
+    };
+    Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    async fn fetch(uri: &str) -> (StatusCode, String, String) {
+        let response = Router::new()
+            .route("/api/v1/schema", get(get_schema))
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map(|v| v.to_str().unwrap().to_string())
+            .unwrap_or_default();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            content_type,
+            String::from_utf8(bytes.to_vec()).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_schema_json() {
+        let (status, content_type, body) = fetch("/api/v1/schema").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type, "application/json");
+
+        let json = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(json["x-api-version"], "0.1.0");
+        assert_eq!(json["x-crate-version"], env!("CARGO_PKG_VERSION"));
+        assert!(json["paths"]["/api/v1/schema"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_schema_yaml() {
+        let (status, content_type, body) = fetch("/api/v1/schema?format=yaml").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type, "application/yaml");
+
+        let yaml = serde_yaml::from_str::<serde_yaml::Value>(&body).unwrap();
+        assert_eq!(yaml["x-api-version"].as_str(), Some("0.1.0"));
+    }
+
+    #[tokio::test]
+    async fn test_schema_unknown_format() {
+        let (status, content_type, body) = fetch("/api/v1/schema?format=xml").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(content_type, "application/json");
+
+        let json = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(json["error_code"], "INVALID_PARAMETER");
+        assert!(json["error"].as_str().unwrap().contains("'xml'"));
+    }
+}
@@ -0,0 +1,230 @@
+//! Sparse fieldsets (`?fields=a,b,c`) for forecast responses.
+//!
+//! The selected names may be `ForecastResponse` top-level fields or `Weather`
+//! fields. [`Sparse`] serializes the wrapped value through `serde_json::Value`
+//! and drops every key that was not requested:
+//!
+//! - top level: requested top-level fields, plus `weather` when any weather
+//!   field (or `weather` itself) is requested
+//! - `weather`: requested weather fields; all of them when `weather` is requested
+//!
+//! Arrays (the multi-datetime endpoint) are filtered element by element.
+
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use utoipa::IntoParams;
+
+use crate::errors::{AppError, AppErrorCode};
+
+/// Top-level `ForecastResponse` fields accepted by `fields`.
+pub(crate) const FORECAST_RESPONSE_FIELDS: &[&str] = &[
+    "checkpoint_id",
+    "checkpoint_name",
+    "forecast_time",
+    "forecast_available",
+    "fetched_at",
+    "yr_model_run_at",
+    "model_run_age_minutes",
+    "next_model_run_expected_at",
+    "model_run_overdue",
+    "source",
+    "stale",
+    "forecast_horizon",
+    "data_resolution",
+    "weather",
+    "dawn_minutes_before_race",
+];
+
+/// `Weather` fields accepted by `fields`.
+pub(crate) const WEATHER_FIELDS: &[&str] = &[
+    "temperature_c",
+    "temperature_percentile_10_c",
+    "temperature_percentile_90_c",
+    "feels_like_c",
+    "snow_temperature_c",
+    "wind_speed_ms",
+    "wind_speed_percentile_10_ms",
+    "wind_speed_percentile_90_ms",
+    "temperature_uncertainty_c",
+    "wind_uncertainty_ms",
+    "forecast_confidence",
+    "wind_direction_deg",
+    "wind_gust_ms",
+    "precipitation_mm",
+    "precipitation_min_mm",
+    "precipitation_max_mm",
+    "precipitation_type",
+    "precipitation_intensity",
+    "blowing_snow_risk",
+    "humidity_pct",
+    "dew_point_c",
+    "cloud_cover_pct",
+    "cloud_cover_low_pct",
+    "cloud_cover_medium_pct",
+    "cloud_cover_high_pct",
+    "snow_depth_cm",
+    "uv_index",
+    "symbol_code",
+    "thunder_probability_pct",
+    "lightning_risk",
+    "sunrise",
+    "sunset",
+];
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct FieldsQuery {
+    /// Comma-separated fields to return (sparse fieldset). Top-level fields:
+    /// checkpoint_id, checkpoint_name, forecast_time, forecast_available,
+    /// fetched_at, yr_model_run_at, model_run_age_minutes,
+    /// next_model_run_expected_at, model_run_overdue, source, stale,
+    /// forecast_horizon, data_resolution, weather, dawn_minutes_before_race.
+    /// Weather fields: temperature_c, temperature_percentile_10_c,
+    /// temperature_percentile_90_c, feels_like_c, snow_temperature_c,
+    /// wind_speed_ms, wind_speed_percentile_10_ms, wind_speed_percentile_90_ms,
+    /// temperature_uncertainty_c, wind_uncertainty_ms, forecast_confidence,
+    /// wind_direction_deg, wind_gust_ms, precipitation_mm, precipitation_min_mm,
+    /// precipitation_max_mm, precipitation_type, precipitation_intensity,
+    /// blowing_snow_risk, humidity_pct, dew_point_c, cloud_cover_pct,
+    /// cloud_cover_low_pct, cloud_cover_medium_pct, cloud_cover_high_pct,
+    /// snow_depth_cm, uv_index, symbol_code, thunder_probability_pct,
+    /// lightning_risk, sunrise, sunset.
+    /// Omit to return all fields; unknown names are rejected with 400.
+    pub fields: Option<String>,
+}
+
+impl FieldsQuery {
+    /// Parse and validate `fields`; `None` when absent or empty.
+    pub(crate) fn selection(&self) -> Result<Option<BTreeSet<String>>, AppError> {
+        let Some(raw) = self.fields.as_deref() else {
+            return Ok(None);
+        };
+        let fields: BTreeSet<String> = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(str::to_string)
+            .collect();
+        if fields.is_empty() {
+            return Ok(None);
+        }
+        let unknown: Vec<&str> = fields
+            .iter()
+            .map(String::as_str)
+            .filter(|f| !FORECAST_RESPONSE_FIELDS.contains(f) && !WEATHER_FIELDS.contains(f))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!("Unknown field(s) in fields: {}", unknown.join(", ")),
+            ));
+        }
+        Ok(Some(fields))
+    }
+}
+
+/// A response that serializes only the selected fields (all when `None`).
+#[derive(Debug)]
+pub struct Sparse<T> {
+    value: T,
+    fields: Option<BTreeSet<String>>,
+}
+
+impl<T> Sparse<T> {
+    pub fn new(value: T, fields: Option<BTreeSet<String>>) -> Self {
+        Self { value, fields }
+    }
+}
+
+impl<T: Serialize> Serialize for Sparse<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(fields) = &self.fields else {
+            return self.value.serialize(serializer);
+        };
+        let mut value = serde_json::to_value(&self.value).map_err(serde::ser::Error::custom)?;
+        filter_value(&mut value, fields);
+        value.serialize(serializer)
+    }
+}
+
+fn filter_value(value: &mut Value, fields: &BTreeSet<String>) {
+    match value {
+        Value::Array(items) => items.iter_mut().for_each(|v| filter_value(v, fields)),
+        Value::Object(map) => filter_response(map, fields),
+        _ => {}
+    }
+}
+
+fn filter_response(map: &mut Map<String, Value>, fields: &BTreeSet<String>) {
+    let full_weather = fields.contains("weather");
+    let any_weather = full_weather || fields.iter().any(|f| WEATHER_FIELDS.contains(&f.as_str()));
+    map.retain(|key, _| fields.contains(key) || (key == "weather" && any_weather));
+    if !full_weather {
+        if let Some(Value::Object(weather)) = map.get_mut("weather") {
+            weather.retain(|key, _| fields.contains(key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::forecasts::{ForecastResponse, Weather};
+    use serde_json::json;
+    use utoipa::openapi::{RefOr, Schema};
+    use utoipa::PartialSchema;
+
+    fn query(fields: &str) -> FieldsQuery {
+        FieldsQuery {
+            fields: Some(fields.to_string()),
+        }
+    }
+
+    fn schema_fields(schema: RefOr<Schema>) -> BTreeSet<String> {
+        match schema {
+            RefOr::T(Schema::Object(obj)) => obj.properties.into_keys().collect(),
+            _ => panic!("expected an object schema"),
+        }
+    }
+
+    #[test]
+    fn test_field_lists_match_response_schemas() {
+        let as_set = |names: &[&str]| names.iter().map(|s| s.to_string()).collect();
+        assert_eq!(schema_fields(Weather::schema()), as_set(WEATHER_FIELDS));
+        assert_eq!(
+            schema_fields(ForecastResponse::schema()),
+            as_set(FORECAST_RESPONSE_FIELDS)
+        );
+    }
+
+    #[test]
+    fn test_unknown_field_rejected() {
+        assert!(matches!(
+            query("temperature_c,bogus").selection(),
+            Err(AppError::BadRequest(AppErrorCode::InvalidParameter, msg)) if msg.contains("bogus")
+        ));
+        assert!(query(" , ").selection().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_sparse_filters_top_level_and_weather() {
+        let value = json!([{
+            "checkpoint_id": "x",
+            "stale": false,
+            "weather": { "temperature_c": -4.0, "wind_speed_ms": 3.2 }
+        }]);
+        let fields = query("checkpoint_id,temperature_c").selection().unwrap();
+        let out = serde_json::to_value(Sparse::new(value.clone(), fields)).unwrap();
+        assert_eq!(
+            out,
+            json!([{ "checkpoint_id": "x", "weather": { "temperature_c": -4.0 } }])
+        );
+
+        let fields = query("weather").selection().unwrap();
+        let out = serde_json::to_value(Sparse::new(value, fields)).unwrap();
+        assert_eq!(
+            out,
+            json!([{ "weather": { "temperature_c": -4.0, "wind_speed_ms": 3.2 } }])
+        );
+    }
+}
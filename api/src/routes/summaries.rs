@@ -0,0 +1,98 @@
+//! Pre-computed race forecast summaries.
+//!
+//! GET /api/v1/forecasts/race/:id/cached-summary serves the race forecasts
+//! stored by [`run_forecast_summaries`] after each poll cycle, without any
+//! yr.no or pacing work. `GET /api/v1/forecasts/race/:id` is unaffected.
+//!
+//! [`run_forecast_summaries`]: crate::services::summaries::run_forecast_summaries
+
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderValue};
+use axum::Json;
+use uuid::Uuid;
+
+use crate::db::{models, queries};
+use crate::errors::{AppError, AppErrorCode, ErrorResponseV2};
+use crate::routes::forecasts::{
+    build_race_forecast, validate_target_duration, AppState, RaceForecastOptions, RaceForecastQuery,
+};
+use crate::services::summaries::duration_key;
+
+/// Serialized race forecast of `race` for `hours` with default options.
+pub(crate) async fn compute_summary(
+    state: &AppState,
+    race: models::Race,
+    hours: f64,
+) -> Result<serde_json::Value, AppError> {
+    let options = RaceForecastOptions::with_defaults(hours)?;
+    let (response, _) = build_race_forecast(state, race, &options).await?;
+    serde_json::to_value(response)
+        .map_err(|e| AppError::InternalError(format!("Summary serialization: {}", e)))
+}
+
+/// Get a pre-computed race forecast.
+///
+/// Returns the race forecast (as `GET /api/v1/forecasts/race/{race_id}` with
+/// default options) stored after the latest poll cycle. Only the pinned
+/// durations 6, 8 and 10 hours are pre-computed.
+#[utoipa::path(
+    get,
+    path = "/api/v1/forecasts/race/{race_id}/cached-summary",
+    tag = "Forecasts",
+    params(
+        ("race_id" = Uuid, Path, description = "Race UUID"),
+        RaceForecastQuery,
+    ),
+    responses(
+        (status = 200, description = "Pre-computed race forecast", body = crate::routes::forecasts::RaceForecastResponse,
+         headers(
+             ("X-Summary-Computed-At" = String, description = "When the summary was computed (RFC 3339)")
+         )),
+        (status = 400, description = "Invalid target_duration_hours", body = ErrorResponseV2),
+        (status = 404, description = "No pre-computed summary for this race and duration", body = ErrorResponseV2),
+    )
+)]
+pub async fn get_cached_race_summary(
+    State(state): State<AppState>,
+    Path(race_id): Path<Uuid>,
+    Query(params): Query<RaceForecastQuery>,
+) -> Result<(HeaderMap, Json<serde_json::Value>), AppError> {
+    validate_target_duration(params.target_duration_hours)?;
+    let summary = queries::get_forecast_summary(
+        &state.pool,
+        race_id,
+        duration_key(params.target_duration_hours),
+    )
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(
+            AppErrorCode::CacheNotFound,
+            format!(
+                "No pre-computed summary for race {} at {} hours",
+                race_id, params.target_duration_hours
+            ),
+        )
+    })?;
+
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&summary.computed_at.to_rfc3339()) {
+        headers.insert("X-Summary-Computed-At", value);
+    }
+    Ok((headers, Json(summary.summary_json)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::summaries::PINNED_SUMMARY_DURATIONS_HOURS;
+
+    #[test]
+    fn test_pinned_durations_are_valid_options() {
+        for hours in PINNED_SUMMARY_DURATIONS_HOURS {
+            assert!(validate_target_duration(hours).is_ok());
+            let options = RaceForecastOptions::with_defaults(hours).unwrap();
+            assert_eq!(options.time_slot_hours, if hours >= 10.0 { 2 } else { 1 });
+            assert!(options.include_stale);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Historical weather averages per race checkpoint, for comparing the current
+//! forecast against a "normal" race day.
+//!
+//! Loaded at startup from `HISTORICAL_AVERAGES_FILE` (default
+//! `./data/historical_averages.csv`) and upserted into `historical_averages`.
+//! The file is a plain CSV with a header row; blank lines and lines starting
+//! with `#` are ignored. Race names must not contain commas.
+//!
+//! ```text
+//! race_name,checkpoint_distance_km,month,day,hour,avg_temperature_c,avg_wind_speed_ms,avg_precipitation_mm
+//! Vasaloppet,0.0,3,2,7,-9.4,2.1,0.1
+//! ```
+//!
+//! `month`/`day`/`hour` are UTC. `avg_precipitation_mm` is per hour.
+
+use chrono::{DateTime, Datelike, DurationRound, TimeDelta, Timelike, Utc};
+use std::path::Path;
+use thiserror::Error;
+
+/// Expected CSV header, in column order.
+const CSV_HEADER: [&str; 8] = [
+    "race_name",
+    "checkpoint_distance_km",
+    "month",
+    "day",
+    "hour",
+    "avg_temperature_c",
+    "avg_wind_speed_ms",
+    "avg_precipitation_mm",
+];
+
+/// Maximum distance (km) between a checkpoint and a historical row's
+/// `checkpoint_distance_km` for the row to count as the same checkpoint.
+/// Absorbs small course changes between years.
+pub const HISTORICAL_DISTANCE_TOLERANCE_KM: f64 = 1.0;
+
+/// Errors that can occur while loading the historical averages file.
+#[derive(Debug, Error)]
+pub enum ClimateError {
+    #[error("IO error reading historical averages file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid historical averages file, line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// Average conditions at a checkpoint for one UTC hour of the year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoricalAverage {
+    pub race_name: String,
+    pub checkpoint_distance_km: f64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub avg_temperature_c: f64,
+    pub avg_wind_speed_ms: f64,
+    pub avg_precipitation_mm: f64,
+}
+
+/// Load the historical averages file. A missing file yields an empty list
+/// (with a warning).
+pub fn load_historical_averages(path: &Path) -> Result<Vec<HistoricalAverage>, ClimateError> {
+    if !path.exists() {
+        tracing::warn!(
+            "Historical averages file does not exist: {}",
+            path.display()
+        );
+        return Ok(Vec::new());
+    }
+    parse_historical_csv(&std::fs::read_to_string(path)?)
+}
+
+fn parse_historical_csv(csv: &str) -> Result<Vec<HistoricalAverage>, ClimateError> {
+    let mut rows = Vec::new();
+    let mut header_seen = false;
+
+    for (idx, raw) in csv.lines().enumerate() {
+        let line = idx + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = trimmed.split(',').map(str::trim).collect();
+
+        if !header_seen {
+            if fields != CSV_HEADER {
+                return Err(ClimateError::Parse {
+                    line,
+                    message: format!("expected header '{}'", CSV_HEADER.join(",")),
+                });
+            }
+            header_seen = true;
+            continue;
+        }
+
+        rows.push(parse_row(&fields).map_err(|message| ClimateError::Parse { line, message })?);
+    }
+
+    Ok(rows)
+}
+
+fn parse_row(fields: &[&str]) -> Result<HistoricalAverage, String> {
+    if fields.len() != CSV_HEADER.len() {
+        return Err(format!(
+            "expected {} columns, found {}",
+            CSV_HEADER.len(),
+            fields.len()
+        ));
+    }
+    if fields[0].is_empty() {
+        return Err("race_name is empty".to_string());
+    }
+
+    fn num<T: std::str::FromStr>(value: &str, column: &str) -> Result<T, String> {
+        value
+            .parse()
+            .map_err(|_| format!("invalid {}: '{}'", column, value))
+    }
+
+    let row = HistoricalAverage {
+        race_name: fields[0].to_string(),
+        checkpoint_distance_km: num(fields[1], CSV_HEADER[1])?,
+        month: num(fields[2], CSV_HEADER[2])?,
+        day: num(fields[3], CSV_HEADER[3])?,
+        hour: num(fields[4], CSV_HEADER[4])?,
+        avg_temperature_c: num(fields[5], CSV_HEADER[5])?,
+        avg_wind_speed_ms: num(fields[6], CSV_HEADER[6])?,
+        avg_precipitation_mm: num(fields[7], CSV_HEADER[7])?,
+    };
+
+    if !(1..=12).contains(&row.month) || !(1..=31).contains(&row.day) || row.hour > 23 {
+        return Err(format!(
+            "invalid date/hour {}-{} {}:00",
+            row.month, row.day, row.hour
+        ));
+    }
+    Ok(row)
+}
+
+/// Find the historical average for a checkpoint pass-through time.
+///
+/// The time is rounded to the nearest full hour and matched on UTC
+/// month/day/hour; among matching rows the one nearest in distance (within
+/// [`HISTORICAL_DISTANCE_TOLERANCE_KM`]) wins.
+pub fn match_historical_average(
+    averages: &[HistoricalAverage],
+    distance_km: f64,
+    at: DateTime<Utc>,
+) -> Option<&HistoricalAverage> {
+    let at = at.duration_round(TimeDelta::hours(1)).unwrap_or(at);
+    averages
+        .iter()
+        .filter(|a| a.month == at.month() && a.day == at.day() && a.hour == at.hour())
+        .map(|a| ((a.checkpoint_distance_km - distance_km).abs(), a))
+        .filter(|(diff, _)| *diff <= HISTORICAL_DISTANCE_TOLERANCE_KM)
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, a)| a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn avg(distance_km: f64, hour: u32, temp: f64) -> HistoricalAverage {
+        HistoricalAverage {
+            race_name: "Vasaloppet".to_string(),
+            checkpoint_distance_km: distance_km,
+            month: 3,
+            day: 2,
+            hour,
+            avg_temperature_c: temp,
+            avg_wind_speed_ms: 2.0,
+            avg_precipitation_mm: 0.1,
+        }
+    }
+
+    #[test]
+    fn test_parse_historical_csv() {
+        let csv = "# comment\n\
+                   race_name,checkpoint_distance_km,month,day,hour,avg_temperature_c,avg_wind_speed_ms,avg_precipitation_mm\n\
+                   \n\
+                   Vasaloppet, 0.0, 3, 2, 7, -9.4, 2.1, 0.1\n\
+                   Vasaloppet,11.0,3,2,8,-7.5,3.0,0.0\n";
+        let rows = parse_historical_csv(csv).unwrap();
+        assert_eq!(rows.len(), 2);
+        let mut expected = avg(0.0, 7, -9.4);
+        expected.avg_wind_speed_ms = 2.1;
+        assert_eq!(rows[0], expected);
+        assert_eq!(rows[1].checkpoint_distance_km, 11.0);
+
+        let err = parse_historical_csv("race,km\n").unwrap_err();
+        assert!(matches!(err, ClimateError::Parse { line: 1, .. }));
+
+        let bad_hour = format!("{}\nVasaloppet,0,3,2,24,-9,2,0\n", CSV_HEADER.join(","));
+        let err = parse_historical_csv(&bad_hour).unwrap_err();
+        assert!(matches!(err, ClimateError::Parse { line: 2, .. }));
+
+        assert!(parse_historical_csv("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_match_historical_average() {
+        let averages = vec![avg(0.0, 7, -9.0), avg(11.0, 8, -7.0), avg(11.6, 8, -6.0)];
+
+        // 07:40 rounds to 08:00; 11.2 km is nearest to the 11.0 km row
+        let at = Utc.with_ymd_and_hms(2026, 3, 2, 7, 40, 0).unwrap();
+        let hit = match_historical_average(&averages, 11.2, at).unwrap();
+        assert_eq!(hit.avg_temperature_c, -7.0);
+
+        // Outside the distance tolerance
+        assert!(match_historical_average(&averages, 15.0, at).is_none());
+
+        // Different day
+        let other_day = Utc.with_ymd_and_hms(2026, 3, 3, 8, 0, 0).unwrap();
+        assert!(match_historical_average(&averages, 11.0, other_day).is_none());
+    }
+}
@@ -0,0 +1,240 @@
+//! Checkpoint elevation lookup from OpenTopoData.
+//!
+//! Some GPX files omit `<ele>` for checkpoints (parsed as 0 m) or give
+//! `<ele>0</ele>`. With `ENRICH_ELEVATIONS=true`, seeding looks those
+//! checkpoints up at `ELEVATION_API_URL` (default: the public SRTM 30 m
+//! dataset, falling back to ASTER 30 m north of 60°N where SRTM has no data)
+//! and stores the result instead. A location no dataset covers comes back as
+//! null and is treated as a failed lookup. Lookups are cached indefinitely
+//! in `elevation_cache`, keyed by coordinates rounded to 4 decimal places —
+//! terrain doesn't change. A failed lookup is logged and the GPX value kept.
+//! See: https://www.opentopodata.org/api/
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::db::queries;
+use crate::helpers::{dec_to_f64, f64_to_decimal_full};
+use crate::services::gpx::GpxRace;
+
+/// Timeout for one elevation request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pause between uncached lookups (the public API allows one call per second).
+const REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Errors that can occur while looking up an elevation.
+#[derive(Debug, Error)]
+pub enum ElevationError {
+    #[error("HTTP error fetching elevation: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Elevation API error: {0}")]
+    Api(String),
+    #[error("No elevation data at {lat:.4}, {lon:.4}")]
+    NoData { lat: f64, lon: f64 },
+}
+
+/// Client for an OpenTopoData dataset endpoint, e.g.
+/// `https://api.opentopodata.org/v1/srtm30m,aster30m`.
+#[derive(Debug, Clone)]
+pub struct ElevationClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenTopoDataResponse {
+    status: String,
+    #[serde(default)]
+    results: Vec<OpenTopoDataResult>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenTopoDataResult {
+    /// Null outside the datasets' coverage (SRTM ends at 60°N)
+    elevation: Option<f64>,
+}
+
+/// Elevation from an OpenTopoData response body for a single location.
+/// Errors come back as JSON with a non-"OK" status as well.
+fn parse_elevation(
+    http_status: StatusCode,
+    body: &str,
+    lat: f64,
+    lon: f64,
+) -> Result<f64, ElevationError> {
+    let body: OpenTopoDataResponse = serde_json::from_str(body).map_err(|e| {
+        ElevationError::Api(format!("HTTP {}: invalid response: {}", http_status, e))
+    })?;
+    if body.status != "OK" {
+        return Err(ElevationError::Api(body.error.unwrap_or_else(|| {
+            format!("HTTP {} ({})", http_status, body.status)
+        })));
+    }
+    body.results
+        .first()
+        .and_then(|r| r.elevation)
+        .ok_or(ElevationError::NoData { lat, lon })
+}
+
+impl ElevationClient {
+    pub fn new(base_url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to build HTTP client");
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Terrain elevation in metres at a location.
+    pub async fn fetch_elevation(&self, lat: f64, lon: f64) -> Result<f64, ElevationError> {
+        let url = format!("{}?locations={:.4},{:.4}", self.base_url, lat, lon);
+        let response = self.client.get(&url).send().await?;
+        let http_status = response.status();
+        let body = response.text().await?;
+        parse_elevation(http_status, &body, lat, lon)
+    }
+
+    /// Elevation of each checkpoint of `race`, in order, with 0 m values
+    /// replaced by a cached or fetched lookup. Lookup failures keep the GPX
+    /// value.
+    pub async fn checkpoint_elevations(&self, pool: &PgPool, race: &GpxRace) -> Vec<f64> {
+        let mut elevations = Vec::with_capacity(race.checkpoints.len());
+        let mut fetched = false;
+
+        for cp in &race.checkpoints {
+            if cp.elevation_m != 0.0 {
+                elevations.push(cp.elevation_m);
+                continue;
+            }
+
+            let lat_key = f64_to_decimal_full(cp.latitude).round_dp(4);
+            let lon_key = f64_to_decimal_full(cp.longitude).round_dp(4);
+            let cached = queries::get_cached_elevation(pool, lat_key, lon_key)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to read elevation cache: {}", e);
+                    None
+                });
+
+            let elevation = match cached {
+                Some(elevation) => Some(dec_to_f64(elevation)),
+                None => {
+                    if fetched {
+                        tokio::time::sleep(REQUEST_INTERVAL).await;
+                    }
+                    fetched = true;
+                    match self.fetch_elevation(cp.latitude, cp.longitude).await {
+                        Ok(elevation) => {
+                            if let Err(e) = queries::insert_elevation_cache(
+                                pool,
+                                lat_key,
+                                lon_key,
+                                f64_to_decimal_full(elevation),
+                            )
+                            .await
+                            {
+                                tracing::warn!("Failed to cache elevation: {}", e);
+                            }
+                            Some(elevation)
+                        }
+                        Err(e) => {
+                            tracing::warn!(
+                                "Elevation lookup for checkpoint '{}' of race '{}' ({}) failed: {}",
+                                cp.name,
+                                race.name,
+                                race.year,
+                                e
+                            );
+                            None
+                        }
+                    }
+                }
+            };
+
+            match elevation {
+                Some(elevation) => {
+                    tracing::info!(
+                        "Enriched checkpoint '{}' of race '{}' ({}): elevation 0 → {:.0} m",
+                        cp.name,
+                        race.name,
+                        race.year,
+                        elevation
+                    );
+                    elevations.push(elevation);
+                }
+                None => elevations.push(cp.elevation_m),
+            }
+        }
+        elevations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OK: StatusCode = StatusCode::OK;
+
+    #[test]
+    fn test_parse_elevation() {
+        let body = r#"{"status":"OK","results":[{"dataset":"srtm30m","elevation":815.5,"location":{"lat":46.56,"lng":7.9}}]}"#;
+        assert_eq!(parse_elevation(OK, body, 46.56, 7.9).unwrap(), 815.5);
+
+        // With a fallback dataset, the result names the dataset that answered
+        let body = r#"{"status":"OK","results":[{"dataset":"aster30m","elevation":164.0,"location":{"lat":61.16,"lng":13.26}}]}"#;
+        assert_eq!(parse_elevation(OK, body, 61.16, 13.26).unwrap(), 164.0);
+    }
+
+    #[test]
+    fn test_parse_elevation_null_is_no_data() {
+        // What srtm30m alone returns north of 60°N
+        let body = r#"{"status":"OK","results":[{"dataset":"srtm30m","elevation":null,"location":{"lat":61.16,"lng":13.26}}]}"#;
+        assert!(matches!(
+            parse_elevation(OK, body, 61.16, 13.26),
+            Err(ElevationError::NoData { .. })
+        ));
+        assert!(matches!(
+            parse_elevation(OK, r#"{"status":"OK","results":[]}"#, 61.16, 13.26),
+            Err(ElevationError::NoData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_elevation_errors() {
+        let body = r#"{"status":"INVALID_REQUEST","error":"Invalid locations"}"#;
+        match parse_elevation(StatusCode::BAD_REQUEST, body, 0.0, 13.3) {
+            Err(ElevationError::Api(message)) => assert_eq!(message, "Invalid locations"),
+            other => panic!("expected API error, got {:?}", other),
+        }
+
+        match parse_elevation(
+            StatusCode::TOO_MANY_REQUESTS,
+            r#"{"status":"ERROR"}"#,
+            0.0,
+            0.0,
+        ) {
+            Err(ElevationError::Api(message)) => {
+                assert_eq!(message, "HTTP 429 Too Many Requests (ERROR)")
+            }
+            other => panic!("expected API error, got {:?}", other),
+        }
+
+        assert!(matches!(
+            parse_elevation(
+                StatusCode::BAD_GATEWAY,
+                "<html>Bad gateway</html>",
+                0.0,
+                0.0
+            ),
+            Err(ElevationError::Api(_))
+        ));
+    }
+}
@@ -0,0 +1,202 @@
+//! In-memory LRU of extracted yr.no forecasts.
+//!
+//! `extract_forecasts_at_times` deserializes the full cached yr.no JSON on
+//! every call. Race endpoints hit the same checkpoints at the same pass-through
+//! times over and over between model runs, so the extraction result is cached
+//! per `(checkpoint_id, yr_model_run_at, requested times)`. A new model run
+//! changes the key, so stale entries simply age out.
+
+use chrono::{DateTime, Utc};
+use lru::LruCache;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::services::yr::{
+    extract_forecasts_at_times, extract_model_run_at, ExtractionResult, ForecastResolution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ExtractionKey {
+    checkpoint_id: Uuid,
+    yr_model_run_at: DateTime<Utc>,
+    times_hash: u64,
+}
+
+/// Thread-safe LRU of `ExtractionResult`s with hit/miss counters.
+pub struct ExtractionCache {
+    entries: Mutex<LruCache<ExtractionKey, ExtractionResult>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ExtractionCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Extract forecasts for a checkpoint, reusing a cached result for the
+    /// same model run and times. Responses without `meta.updated_at` cannot
+    /// be told apart across fetches and are never cached.
+    pub fn extract(
+        &self,
+        checkpoint_id: Uuid,
+        raw_json: &serde_json::Value,
+        forecast_times: &[DateTime<Utc>],
+    ) -> Result<ExtractionResult, AppError> {
+        let Some(yr_model_run_at) = extract_model_run_at(raw_json) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return extract_forecasts_at_times(
+                raw_json.clone(),
+                forecast_times,
+                ForecastResolution::SixHourly,
+            );
+        };
+        let key = ExtractionKey {
+            checkpoint_id,
+            yr_model_run_at,
+            times_hash: hash_times(forecast_times),
+        };
+
+        if let Some(hit) = self.lock().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(hit.clone());
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let result = extract_forecasts_at_times(
+            raw_json.clone(),
+            forecast_times,
+            ForecastResolution::SixHourly,
+        )?;
+        self.lock().put(key, result.clone());
+        Ok(result)
+    }
+
+    /// Cumulative `(hits, misses)` since startup.
+    pub fn stats(&self) -> (u64, u64) {
+        (
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+        )
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<ExtractionKey, ExtractionResult>> {
+        // A panic while holding the lock cannot leave the LRU inconsistent
+        // for our purposes, so recover rather than propagate the poison.
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Hash of the requested times. Order matters: `ExtractionResult.forecasts`
+/// is positional, so the same times in another order are a different entry.
+fn hash_times(forecast_times: &[DateTime<Utc>]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    forecast_times.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yr_json(updated_at: Option<&str>) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "properties": {
+                "timeseries": [{
+                    "time": "2026-03-01T07:00:00Z",
+                    "data": {
+                        "instant": {
+                            "details": {
+                                "air_temperature": -5.0,
+                                "wind_speed": 3.0,
+                                "wind_from_direction": 180.0,
+                                "relative_humidity": 80.0,
+                                "dew_point_temperature": -8.0,
+                                "cloud_area_fraction": 50.0
+                            }
+                        },
+                        "next_1_hours": {
+                            "summary": { "symbol_code": "cloudy" },
+                            "details": { "precipitation_amount": 0.0 }
+                        }
+                    }
+                }]
+            }
+        });
+        if let Some(t) = updated_at {
+            json["properties"]["meta"] = serde_json::json!({ "updated_at": t });
+        }
+        json
+    }
+
+    fn cache() -> ExtractionCache {
+        ExtractionCache::new(NonZeroUsize::new(10).unwrap())
+    }
+
+    #[test]
+    fn test_same_model_run_and_times_hits() {
+        let cache = cache();
+        let id = Uuid::new_v4();
+        let json = yr_json(Some("2026-03-01T06:00:00Z"));
+        let times = ["2026-03-01T07:00:00Z".parse().unwrap()];
+
+        let first = cache.extract(id, &json, &times).unwrap();
+        let second = cache.extract(id, &json, &times).unwrap();
+        assert_eq!(cache.stats(), (1, 1));
+        assert_eq!(
+            first.forecasts[0].as_ref().unwrap().temperature_c,
+            second.forecasts[0].as_ref().unwrap().temperature_c
+        );
+    }
+
+    #[test]
+    fn test_new_model_run_or_checkpoint_misses() {
+        let cache = cache();
+        let id = Uuid::new_v4();
+        let times = ["2026-03-01T07:00:00Z".parse().unwrap()];
+
+        cache
+            .extract(id, &yr_json(Some("2026-03-01T06:00:00Z")), &times)
+            .unwrap();
+        cache
+            .extract(id, &yr_json(Some("2026-03-01T12:00:00Z")), &times)
+            .unwrap();
+        cache
+            .extract(
+                Uuid::new_v4(),
+                &yr_json(Some("2026-03-01T12:00:00Z")),
+                &times,
+            )
+            .unwrap();
+        assert_eq!(cache.stats(), (0, 3));
+    }
+
+    #[test]
+    fn test_response_without_model_run_is_not_cached() {
+        let cache = cache();
+        let id = Uuid::new_v4();
+        let json = yr_json(None);
+        let times = ["2026-03-01T07:00:00Z".parse().unwrap()];
+
+        cache.extract(id, &json, &times).unwrap();
+        cache.extract(id, &json, &times).unwrap();
+        assert_eq!(cache.stats(), (0, 2));
+    }
+
+    #[test]
+    fn test_times_hash_respects_order() {
+        let a: DateTime<Utc> = "2026-03-01T07:00:00Z".parse().unwrap();
+        let b: DateTime<Utc> = "2026-03-01T08:00:00Z".parse().unwrap();
+        assert_eq!(hash_times(&[a, b]), hash_times(&[a, b]));
+        assert_ne!(hash_times(&[a, b]), hash_times(&[b, a]));
+    }
+}
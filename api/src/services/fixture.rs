@@ -0,0 +1,240 @@
+//! Synthetic race fixtures for testing.
+//!
+//! When `TEST_RACE_DATA_DIR` is set, races are seeded from YAML (or JSON)
+//! files in that directory instead of GPX files in `DATA_DIR`, so test
+//! environments can inject races with known coordinates. Each file holds one
+//! race and mirrors `GpxRace`:
+//!
+//! ```yaml
+//! name: Synthetic Loop                     # required
+//! year: 2026                               # required
+//! start_time: "2026-03-01T08:00:00+01:00"  # required, RFC 3339 with offset
+//! distance_km: 20.0                        # required, declared race distance
+//! country: SE                              # optional
+//! website: https://example.com             # optional
+//! discipline: skate                        # optional: classic, skate, freestyle
+//! category: skate                          # optional, e.g. classic, skate, biathlon
+//! elevation_gain_m: 150.0                  # optional
+//! checkpoints:                             # required, at least one, in course order
+//!   - name: Start
+//!     latitude: 61.0
+//!     longitude: 14.0
+//!     elevation_m: 400.0
+//!     distance_km: 0.0                     # distance from start
+//! ```
+//!
+//! The course geometry is a straight-line track through the checkpoints.
+
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::services::gpx::{normalize_category, GpxCheckpoint, GpxRace, DISCIPLINES};
+
+/// Errors that can occur while loading fixture files.
+#[derive(Debug, Error)]
+pub enum FixtureError {
+    #[error("IO error reading fixture: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid fixture {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+    #[error("Invalid fixture {path}: {message}")]
+    InvalidValue { path: PathBuf, message: String },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FixtureRace {
+    name: String,
+    year: i32,
+    start_time: DateTime<FixedOffset>,
+    distance_km: f64,
+    country: Option<String>,
+    website: Option<String>,
+    discipline: Option<String>,
+    category: Option<String>,
+    elevation_gain_m: Option<f64>,
+    checkpoints: Vec<FixtureCheckpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FixtureCheckpoint {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    elevation_m: f64,
+    distance_km: f64,
+}
+
+/// Load every `*.yaml`, `*.yml` and `*.json` fixture in `dir`, sorted by
+/// file name. Unlike GPX seeding, any invalid file fails the whole load.
+pub fn load_races_from_yaml(dir: &Path) -> Result<Vec<GpxRace>, FixtureError> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    paths.retain(|p| {
+        p.extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml" || ext == "json")
+    });
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            tracing::info!("Loading race fixture: {}", path.display());
+            let text = std::fs::read_to_string(path)?;
+            parse_fixture(&text, path)
+        })
+        .collect()
+}
+
+/// Parse one fixture file into a `GpxRace`.
+fn parse_fixture(text: &str, path: &Path) -> Result<GpxRace, FixtureError> {
+    let fixture: FixtureRace =
+        serde_yaml::from_str(text).map_err(|source| FixtureError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    let invalid = |message: String| FixtureError::InvalidValue {
+        path: path.to_path_buf(),
+        message,
+    };
+
+    if fixture.checkpoints.is_empty() {
+        return Err(invalid("at least one checkpoint is required".to_string()));
+    }
+    let discipline = match fixture.discipline {
+        Some(d) => {
+            let d = d.to_lowercase();
+            if !DISCIPLINES.contains(&d.as_str()) {
+                return Err(invalid(format!(
+                    "discipline must be one of {}: '{}'",
+                    DISCIPLINES.join(", "),
+                    d
+                )));
+            }
+            Some(d)
+        }
+        None => None,
+    };
+    let category = fixture
+        .category
+        .map(|c| normalize_category(&c).map_err(|e| invalid(format!("category {}", e))))
+        .transpose()?;
+
+    let checkpoints: Vec<GpxCheckpoint> = fixture
+        .checkpoints
+        .into_iter()
+        .map(|c| GpxCheckpoint {
+            name: c.name,
+            latitude: c.latitude,
+            longitude: c.longitude,
+            elevation_m: c.elevation_m,
+            distance_km: c.distance_km,
+        })
+        .collect();
+    let gpx_xml = track_gpx(&fixture.name, &checkpoints);
+
+    Ok(GpxRace {
+        name: fixture.name,
+        year: fixture.year,
+        start_time: fixture.start_time,
+        distance_km: fixture.distance_km,
+        checkpoints,
+        gpx_xml,
+        computed_track_length_km: None,
+        country: fixture.country,
+        website: fixture.website,
+        discipline,
+        category,
+        elevation_gain_m: fixture.elevation_gain_m,
+    })
+}
+
+/// Minimal GPX document with a single track through the checkpoints, stored
+/// as the race's `course_gpx`.
+fn track_gpx(name: &str, checkpoints: &[GpxCheckpoint]) -> String {
+    let points: String = checkpoints
+        .iter()
+        .map(|c| {
+            format!(
+                "<trkpt lat=\"{}\" lon=\"{}\"><ele>{}</ele></trkpt>",
+                c.latitude, c.longitude, c.elevation_m
+            )
+        })
+        .collect();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+         <gpx xmlns=\"http://www.topografix.com/GPX/1/1\" version=\"1.1\" creator=\"fixture\">\
+         <trk><name>{}</name><trkseg>{}</trkseg></trk></gpx>",
+        xml_escape(name),
+        points
+    )
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Races from the fixtures bundled under `api/tests/fixtures`.
+#[cfg(test)]
+pub(crate) fn load_test_fixture_races() -> Vec<GpxRace> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    load_races_from_yaml(&dir).expect("bundled test fixtures are valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::gpx::extract_track_points;
+
+    #[test]
+    fn test_load_bundled_fixture() {
+        let races = load_test_fixture_races();
+        let race = races
+            .iter()
+            .find(|r| r.name == "Synthetic Loop")
+            .expect("fixture present");
+        assert_eq!(race.year, 2026);
+        assert_eq!(race.discipline.as_deref(), Some("skate"));
+        assert_eq!(race.checkpoints.len(), 3);
+        assert_eq!(race.checkpoints[1].latitude, 61.09);
+
+        // The generated course passes through every checkpoint
+        let points = extract_track_points(&race.gpx_xml).unwrap();
+        assert_eq!(points.len(), 3);
+    }
+
+    #[test]
+    fn test_json_fixture_is_accepted() {
+        let json = r#"{"name": "J", "year": 2026, "start_time": "2026-03-01T08:00:00Z",
+            "distance_km": 5.0, "checkpoints": [{"name": "S", "latitude": 1.0,
+            "longitude": 2.0, "elevation_m": 3.0, "distance_km": 0.0}]}"#;
+        let race = parse_fixture(json, Path::new("j.json")).unwrap();
+        assert_eq!(race.checkpoints[0].longitude, 2.0);
+    }
+
+    #[test]
+    fn test_invalid_fixtures_rejected() {
+        let no_checkpoints = "name: X\nyear: 2026\nstart_time: \"2026-03-01T08:00:00Z\"\n\
+                              distance_km: 5.0\ncheckpoints: []\n";
+        assert!(matches!(
+            parse_fixture(no_checkpoints, Path::new("x.yaml")),
+            Err(FixtureError::InvalidValue { .. })
+        ));
+
+        let missing_year =
+            "name: X\nstart_time: \"2026-03-01T08:00:00Z\"\ndistance_km: 5.0\ncheckpoints: []\n";
+        assert!(matches!(
+            parse_fixture(missing_year, Path::new("x.yaml")),
+            Err(FixtureError::Parse { .. })
+        ));
+    }
+}
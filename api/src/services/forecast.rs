@@ -11,19 +11,23 @@
 //! cache row per checkpoint. yr.no's Expires header controls freshness,
 //! If-Modified-Since enables conditional requests.
 
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Duration, Timelike, Utc};
 use futures::stream::{self, StreamExt};
+use rust_decimal::Decimal;
 use sqlx::PgPool;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use crate::db::models::{Checkpoint, Forecast};
 use crate::db::queries::{self, InsertForecastParams};
-use crate::errors::AppError;
+use crate::errors::{AppError, AppErrorCode};
 use crate::helpers::{dec_to_f64, f64_to_decimal_1dp};
-use crate::services::gpx::TrackPoint;
+use crate::services::extraction_cache::ExtractionCache;
+use crate::services::gpx::{haversine_distance_km, TrackPoint};
 use crate::services::yr::{
-    extract_forecasts_at_times, parse_expires_header, ExtractionResult, YrClient, YrParsedForecast,
-    YrTimeseriesResult,
+    extract_forecasts_at_times, parse_expires_header, ExtractionResult, ForecastResolution,
+    YrClient, YrParsedForecast, YrTimeseriesResult,
 };
 
 /// Calculate the "feels like" temperature using the North American Wind Chill Index.
@@ -44,6 +48,10 @@ pub fn calculate_feels_like(temperature_c: f64, wind_speed_ms: f64) -> f64 {
     13.12 + 0.6215 * temperature_c - 11.37 * v016 + 0.3965 * temperature_c * v016
 }
 
+/// Relative effectiveness of medium/high cloud (vs. low cloud) at blocking
+/// radiative cooling of the snow surface.
+const UPPER_CLOUD_RADIATIVE_WEIGHT: f64 = 0.5;
+
 /// Estimate snow surface temperature for cross-country skiing wax selection.
 ///
 /// Uses a dew-point-based approach grounded in published research:
@@ -62,22 +70,88 @@ pub fn calculate_feels_like(temperature_c: f64, wind_speed_ms: f64) -> f64 {
 /// - Overcast skies and wind reduce the offset
 /// - Result is clamped to ≤ 0°C (snow cannot exceed its melting point)
 ///
+/// Low cloud is warm and optically thick, so it blocks longwave cooling almost
+/// completely; thin, cold medium/high cloud blocks much less. When the low-cloud
+/// fraction is known, cloud above it only counts at `UPPER_CLOUD_RADIATIVE_WEIGHT`.
+///
 /// Formula: T_snow = min(T_base − offset, 0.0)
 ///   where T_base = min(T_air, T_dew)
 ///         offset = (1 − cloud_fraction) × 3.0 × 1/(1 + wind/5)
+///         cloud_fraction = low + max(total − low, 0) × 0.5   (if low known)
+///                        = total                              (otherwise)
 pub fn calculate_snow_temperature(
     temperature_c: f64,
     dew_point_c: f64,
     cloud_cover_pct: f64,
+    cloud_cover_low_pct: Option<f64>,
     wind_speed_ms: f64,
 ) -> f64 {
     let t_base = temperature_c.min(dew_point_c);
-    let cloud_factor = 1.0 - (cloud_cover_pct / 100.0).clamp(0.0, 1.0);
+    let effective_cover_pct = match cloud_cover_low_pct {
+        Some(low) => low + (cloud_cover_pct - low).max(0.0) * UPPER_CLOUD_RADIATIVE_WEIGHT,
+        None => cloud_cover_pct,
+    };
+    let cloud_factor = 1.0 - (effective_cover_pct / 100.0).clamp(0.0, 1.0);
     let wind_damping = 1.0 / (1.0 + wind_speed_ms / 5.0);
     let radiative_offset = cloud_factor * 3.0 * wind_damping;
     (t_base - radiative_offset).min(0.0)
 }
 
+/// Albedo of the snow surface in the heat-balance snow temperature model.
+const SNOW_ALBEDO: f64 = 0.8;
+
+/// Longwave cooling (°C) of the snow surface under a clear sky in the
+/// heat-balance model; cloud cover reduces it proportionally.
+const CLEAR_SKY_LONGWAVE_COOLING_C: f64 = 3.0;
+
+/// Weight of [`calculate_snow_temperature`] in the snow temperature
+/// ensemble; the heat-balance model gets the rest.
+const SNOW_TEMP_PHYSICS_WEIGHT: f64 = 0.6;
+
+/// Snow surface temperature from a simple sensible-heat balance.
+///
+/// Formula: T_snow = min(T_air × (1 − albedo) × solar_factor − longwave_cooling, 0.0)
+///   where solar_factor     = uv_index / 10
+///         longwave_cooling = (1 − cloud_fraction) × 3.0
+fn calculate_snow_temperature_heat_balance(
+    temperature_c: f64,
+    cloud_cover_pct: f64,
+    uv_index: f64,
+) -> f64 {
+    let solar_factor = uv_index / 10.0;
+    let cloud_factor = 1.0 - (cloud_cover_pct / 100.0).clamp(0.0, 1.0);
+    let longwave_cooling = cloud_factor * CLEAR_SKY_LONGWAVE_COOLING_C;
+    (temperature_c * (1.0 - SNOW_ALBEDO) * solar_factor - longwave_cooling).min(0.0)
+}
+
+/// Snow surface temperature blending [`calculate_snow_temperature`] (60%)
+/// with the sensible-heat-balance model (40%) when the UV index is known.
+/// Without a UV index this is [`calculate_snow_temperature`].
+pub fn calculate_snow_temperature_ensemble(
+    temperature_c: f64,
+    dew_point_c: f64,
+    cloud_cover_pct: f64,
+    cloud_cover_low_pct: Option<f64>,
+    wind_speed_ms: f64,
+    uv_index: Option<f64>,
+) -> f64 {
+    let physics = calculate_snow_temperature(
+        temperature_c,
+        dew_point_c,
+        cloud_cover_pct,
+        cloud_cover_low_pct,
+        wind_speed_ms,
+    );
+    match uv_index {
+        Some(uv) => {
+            let heat_balance =
+                calculate_snow_temperature_heat_balance(temperature_c, cloud_cover_pct, uv);
+            SNOW_TEMP_PHYSICS_WEIGHT * physics + (1.0 - SNOW_TEMP_PHYSICS_WEIGHT) * heat_balance
+        }
+        None => physics,
+    }
+}
+
 /// Infer precipitation type from yr.no symbol_code and temperature.
 ///
 /// Primary: parse from symbol_code string (contains "snow", "rain", "sleet").
@@ -114,6 +188,88 @@ pub fn infer_precipitation_type(
     }
 }
 
+/// Classify an hourly precipitation rate into a WMO intensity class.
+///
+/// - "none":     0 mm/h
+/// - "trace":    0–0.1 mm/h
+/// - "light":    0.1–2.5 mm/h
+/// - "moderate": 2.5–7.5 mm/h
+/// - "heavy":    > 7.5 mm/h
+///
+/// Callers with 6-hour accumulations must divide by 6 first
+/// (see `precipitation_rate_mm_per_hour`).
+pub fn classify_precipitation_intensity(mm_per_hour: f64) -> &'static str {
+    if mm_per_hour <= 0.0 || mm_per_hour.is_nan() {
+        "none"
+    } else if mm_per_hour < 0.1 {
+        "trace"
+    } else if mm_per_hour < 2.5 {
+        "light"
+    } else if mm_per_hour <= 7.5 {
+        "moderate"
+    } else {
+        "heavy"
+    }
+}
+
+/// Classify a yr.no thunder probability (%) into a lightning risk level.
+///
+/// - "none":     < 5%
+/// - "low":      5–25%
+/// - "moderate": 25–60%
+/// - "high":     > 60%
+pub fn classify_lightning_risk(thunder_probability_pct: f64) -> &'static str {
+    if thunder_probability_pct.is_nan() || thunder_probability_pct < 5.0 {
+        "none"
+    } else if thunder_probability_pct < 25.0 {
+        "low"
+    } else if thunder_probability_pct <= 60.0 {
+        "moderate"
+    } else {
+        "high"
+    }
+}
+
+/// Convert a yr.no precipitation amount to an hourly rate.
+///
+/// Hourly entries carry a 1-hour accumulation (`next_1_hours`); 6-hourly
+/// entries carry a 6-hour accumulation (`next_6_hours`).
+fn precipitation_rate_mm_per_hour(precipitation_mm: f64, resolution: ForecastResolution) -> f64 {
+    match resolution {
+        ForecastResolution::Hourly => precipitation_mm,
+        ForecastResolution::SixHourly => precipitation_mm / 6.0,
+    }
+}
+
+/// Assess blowing snow / ground drift risk from wind and precipitation.
+///
+/// - Falling snow: "possible" above 5 m/s, "likely" above 8 m/s, "severe" above 12 m/s
+/// - No precipitation but below -5°C with wind above 10 m/s: "possible"
+///   (old, loose snow being redistributed)
+/// - Otherwise "none"
+pub fn assess_blowing_snow(
+    wind_ms: f64,
+    temp_c: f64,
+    precip_mm: f64,
+    precip_type: &str,
+) -> &'static str {
+    if precip_type == "snow" {
+        if wind_ms > 12.0 {
+            return "severe";
+        }
+        if wind_ms > 8.0 {
+            return "likely";
+        }
+        if wind_ms > 5.0 {
+            return "possible";
+        }
+    }
+    if precip_mm <= 0.0 && temp_c < -5.0 && wind_ms > 10.0 {
+        return "possible";
+    }
+    "none"
+}
+
 /// Calculate the expected pass-through time for a checkpoint using even pacing.
 ///
 /// pass_time = start_time + duration * (checkpoint.distance_km / race.distance_km)
@@ -138,21 +294,120 @@ fn calculate_pass_time(
 // which accounts for gradient. Uphill segments get more time, downhill less,
 // while the total duration stays exactly the same as the user's target.
 
-/// Uphill cost multiplier per unit gradient (m/m).
+/// Default uphill cost multiplier per unit gradient (m/m).
 /// A 5% uphill grade → cost factor 1.6× per km.
-const K_UP: f64 = 12.0;
+pub const DEFAULT_K_UP: f64 = 12.0;
 
-/// Downhill cost multiplier per unit gradient (m/m).
+/// Default downhill cost multiplier per unit gradient (m/m).
 /// A 5% downhill grade → cost factor 0.8× per km.
-const K_DOWN: f64 = 4.0;
+pub const DEFAULT_K_DOWN: f64 = 4.0;
+
+/// Default minimum cost factor per km (floor). Even steep downhill isn't free
+/// in XC skiing.
+pub const DEFAULT_MIN_COST_FACTOR: f64 = 0.5;
+
+/// Gradient cost constants for elevation-adjusted pacing
+/// (`PACING_K_UP`, `PACING_K_DOWN`, `PACING_MIN_COST_FACTOR`).
+///
+/// The defaults are calibrated for average cross-country skiers; elite
+/// fields lose less time on climbs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PacingParams {
+    /// Uphill cost multiplier per unit gradient (m/m)
+    pub k_up: f64,
+    /// Downhill cost multiplier per unit gradient (m/m)
+    pub k_down: f64,
+    /// Minimum cost factor per km
+    pub min_cost_factor: f64,
+}
+
+impl Default for PacingParams {
+    fn default() -> Self {
+        Self {
+            k_up: DEFAULT_K_UP,
+            k_down: DEFAULT_K_DOWN,
+            min_cost_factor: DEFAULT_MIN_COST_FACTOR,
+        }
+    }
+}
 
-/// Minimum cost factor per km (floor). Even steep downhill isn't free in XC skiing.
-const MIN_COST_FACTOR: f64 = 0.5;
+impl PacingParams {
+    pub fn from_config(config: &crate::config::AppConfig) -> Self {
+        Self {
+            k_up: config.pacing_k_up,
+            k_down: config.pacing_k_down,
+            min_cost_factor: config.pacing_min_cost_factor,
+        }
+    }
+
+    /// Effort cost per km for a gradient (m/m).
+    fn cost_factor(&self, gradient: f64) -> f64 {
+        if gradient >= 0.0 {
+            // Uphill: penalise
+            (1.0 + self.k_up * gradient).max(self.min_cost_factor)
+        } else {
+            // Downhill: bonus (gradient is negative, k_down is positive)
+            (1.0 - self.k_down * gradient.abs()).max(self.min_cost_factor)
+        }
+    }
+}
 
 /// Input for elevation-adjusted pacing calculation.
+#[derive(Debug, Clone, Default)]
 pub struct PacingCheckpoint {
     pub distance_km: f64,
     pub elevation_m: f64,
+    /// Checkpoint coordinates (WGS84), for [`enrich_pacing_checkpoints_with_haversine`]
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Replace declared (often rounded) cumulative distances with the Haversine
+/// distance along the course, given as the coordinates of consecutive track
+/// points.
+///
+/// Each checkpoint gets the cumulative distance of the track point nearest
+/// to it. Points are matched in course order — the search for a checkpoint
+/// starts at the previous checkpoint's point — so loop and multi-lap courses
+/// that pass the same place twice keep increasing distances. Gaps between
+/// coordinates count as course distance, so pass a single track segment.
+/// Without coordinates the declared distances are kept.
+pub fn enrich_pacing_checkpoints_with_haversine(
+    checkpoints: &mut [PacingCheckpoint],
+    latitudes: &[f64],
+    longitudes: &[f64],
+) {
+    let n = latitudes.len().min(longitudes.len());
+    if n == 0 {
+        return;
+    }
+    let mut cumulative = Vec::with_capacity(n);
+    cumulative.push(0.0);
+    for i in 1..n {
+        cumulative.push(
+            cumulative[i - 1]
+                + haversine_distance_km(
+                    latitudes[i - 1],
+                    longitudes[i - 1],
+                    latitudes[i],
+                    longitudes[i],
+                ),
+        );
+    }
+
+    let mut from = 0;
+    for cp in checkpoints.iter_mut() {
+        let distance_to = |i: usize| {
+            haversine_distance_km(cp.latitude, cp.longitude, latitudes[i], longitudes[i])
+        };
+        // The first nearest point wins, so a later pass of the same place
+        // is left for a later checkpoint
+        let nearest = (from..n)
+            .min_by(|&a, &b| distance_to(a).total_cmp(&distance_to(b)))
+            .unwrap_or(from);
+        cp.distance_km = cumulative[nearest];
+        from = nearest;
+    }
 }
 
 /// Compute cumulative time fractions for each checkpoint based on elevation profile.
@@ -164,7 +419,10 @@ pub struct PacingCheckpoint {
 ///
 /// If there are fewer than 2 checkpoints, returns trivial fractions.
 /// Falls back to even (distance-based) pacing if total distance is zero.
-pub fn calculate_pass_time_fractions(checkpoints: &[PacingCheckpoint]) -> Vec<f64> {
+pub fn calculate_pass_time_fractions(
+    checkpoints: &[PacingCheckpoint],
+    params: &PacingParams,
+) -> Vec<f64> {
     let n = checkpoints.len();
     if n == 0 {
         return vec![];
@@ -187,15 +445,7 @@ pub fn calculate_pass_time_fractions(checkpoints: &[PacingCheckpoint]) -> Vec<f6
         // gradient in m/m (rise over run)
         let gradient = ele_delta / (dist_delta * 1000.0);
 
-        let cost_factor = if gradient >= 0.0 {
-            // Uphill: penalise
-            (1.0 + K_UP * gradient).max(MIN_COST_FACTOR)
-        } else {
-            // Downhill: bonus (gradient is negative, K_DOWN is positive)
-            (1.0 - K_DOWN * gradient.abs()).max(MIN_COST_FACTOR)
-        };
-
-        segment_costs.push(cost_factor * dist_delta);
+        segment_costs.push(params.cost_factor(gradient) * dist_delta);
     }
 
     let total_cost: f64 = segment_costs.iter().sum();
@@ -254,6 +504,7 @@ pub fn calculate_pass_time_fractions(checkpoints: &[PacingCheckpoint]) -> Vec<f6
 pub fn calculate_pass_time_fractions_with_track(
     checkpoints: &[PacingCheckpoint],
     track: &[TrackPoint],
+    params: &PacingParams,
 ) -> Vec<f64> {
     let n = checkpoints.len();
     if n == 0 {
@@ -265,7 +516,7 @@ pub fn calculate_pass_time_fractions_with_track(
 
     // If track is empty, fall back to the simple function
     if track.is_empty() {
-        return calculate_pass_time_fractions(checkpoints);
+        return calculate_pass_time_fractions(checkpoints, params);
     }
 
     // Compute cost for each segment between consecutive checkpoints
@@ -321,13 +572,7 @@ pub fn calculate_pass_time_fractions_with_track(
             let ele_delta = e1 - e0;
             let gradient = ele_delta / (dist_delta * 1000.0);
 
-            let cost_factor = if gradient >= 0.0 {
-                (1.0 + K_UP * gradient).max(MIN_COST_FACTOR)
-            } else {
-                (1.0 - K_DOWN * gradient.abs()).max(MIN_COST_FACTOR)
-            };
-
-            segment_cost += cost_factor * dist_delta;
+            segment_cost += params.cost_factor(gradient) * dist_delta;
         }
 
         segment_costs.push(segment_cost);
@@ -376,7 +621,11 @@ pub fn calculate_pass_time_fractions_with_track(
 /// # Returns
 /// A `Vec<(f64, f64)>` of `(distance_km, time_fraction)` pairs, sorted by
 /// distance. Empty if track has fewer than 2 points.
-pub fn compute_pacing_profile(track: &[TrackPoint], max_points: usize) -> Vec<(f64, f64)> {
+pub fn compute_pacing_profile(
+    track: &[TrackPoint],
+    max_points: usize,
+    params: &PacingParams,
+) -> Vec<(f64, f64)> {
     if track.len() < 2 {
         return track.iter().map(|tp| (tp.distance_km, 0.0)).collect();
     }
@@ -395,13 +644,7 @@ pub fn compute_pacing_profile(track: &[TrackPoint], max_points: usize) -> Vec<(f
         let ele_delta = track[i].elevation_m - track[i - 1].elevation_m;
         let gradient = ele_delta / (dist_delta * 1000.0);
 
-        let cost_factor = if gradient >= 0.0 {
-            (1.0 + K_UP * gradient).max(MIN_COST_FACTOR)
-        } else {
-            (1.0 - K_DOWN * gradient.abs()).max(MIN_COST_FACTOR)
-        };
-
-        total_cost += cost_factor * dist_delta;
+        total_cost += params.cost_factor(gradient) * dist_delta;
         cumulative_costs.push(total_cost);
     }
 
@@ -542,6 +785,188 @@ pub fn calculate_pass_time_weighted(
     start_time + Duration::seconds(duration_secs)
 }
 
+/// Resolve optional start/end checkpoint IDs to an inclusive index range into
+/// `checkpoints` (sorted by sort_order). A missing start means the race start,
+/// a missing end the finish.
+///
+/// Errors with `BadRequest` if an ID doesn't belong to the race or the start
+/// comes after the end.
+pub fn checkpoint_range(
+    checkpoints: &[Checkpoint],
+    start_checkpoint_id: Option<Uuid>,
+    end_checkpoint_id: Option<Uuid>,
+) -> Result<(usize, usize), AppError> {
+    if checkpoints.is_empty() {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            "Race has no checkpoints".to_string(),
+        ));
+    }
+    let position = |id: Uuid, param: &str| {
+        checkpoints
+            .iter()
+            .position(|cp| cp.id == id)
+            .ok_or_else(|| {
+                AppError::BadRequest(
+                    AppErrorCode::InvalidParameter,
+                    format!("{} {} does not belong to this race", param, id),
+                )
+            })
+    };
+    let start = match start_checkpoint_id {
+        Some(id) => position(id, "start_checkpoint_id")?,
+        None => 0,
+    };
+    let end = match end_checkpoint_id {
+        Some(id) => position(id, "end_checkpoint_id")?,
+        None => checkpoints.len().saturating_sub(1),
+    };
+    if checkpoints[start].sort_order > checkpoints[end].sort_order {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            format!(
+                "start checkpoint '{}' comes after end checkpoint '{}'",
+                checkpoints[start].name, checkpoints[end].name
+            ),
+        ));
+    }
+    Ok((start, end))
+}
+
+/// Index of the `start_at_checkpoint` into `checkpoints` (sorted by
+/// sort_order), for entrants who start part-way along the course.
+///
+/// Errors with `BadRequest` if the checkpoint doesn't belong to the race or
+/// is the race start (sort_order 0).
+pub fn start_at_checkpoint_index(checkpoints: &[Checkpoint], id: Uuid) -> Result<usize, AppError> {
+    let index = checkpoints
+        .iter()
+        .position(|cp| cp.id == id)
+        .ok_or_else(|| {
+            AppError::BadRequest(
+                AppErrorCode::InvalidParameter,
+                format!("start_at_checkpoint {} does not belong to this race", id),
+            )
+        })?;
+    if checkpoints[index].sort_order == 0 {
+        return Err(AppError::BadRequest(
+            AppErrorCode::InvalidParameter,
+            format!(
+                "start_at_checkpoint '{}' is already the race start",
+                checkpoints[index].name
+            ),
+        ));
+    }
+    Ok(index)
+}
+
+/// Rescale cumulative time fractions so the first is 0.0 and the last 1.0,
+/// e.g. fractions interpolated from a full-course profile for a course that
+/// starts part-way along. Returned unchanged when they span nothing.
+pub fn rebase_fractions(fractions: &[f64]) -> Vec<f64> {
+    let (Some(&first), Some(&last)) = (fractions.first(), fractions.last()) else {
+        return Vec::new();
+    };
+    let span = last - first;
+    if span <= 0.0 {
+        return fractions.to_vec();
+    }
+    fractions.iter().map(|f| (f - first) / span).collect()
+}
+
+/// Pass-through times for a partial course `[start, end]` skied in
+/// `leg_duration_hours`.
+///
+/// `fractions` are the full-course time fractions. Renormalising them to
+/// 0.0 at `start` and 1.0 at `end` is the same as recomputing the
+/// elevation-adjusted pacing over the subset, since both are cumulative
+/// effort. The leg start time assumes the earlier part of the race is skied
+/// at the same relative pace: `race_start + leg_duration × f_start / (f_end − f_start)`.
+/// A zero-length range (start == end) is treated as a full-course duration.
+pub fn partial_pass_times(
+    fractions: &[f64],
+    start: usize,
+    end: usize,
+    race_start: DateTime<Utc>,
+    leg_duration_hours: f64,
+) -> Vec<DateTime<Utc>> {
+    let (f_start, f_end) = (fractions[start], fractions[end]);
+    let span = f_end - f_start;
+    if span <= 0.0 {
+        return fractions[start..=end]
+            .iter()
+            .map(|&f| calculate_pass_time_weighted(race_start, f, leg_duration_hours))
+            .collect();
+    }
+    let leg_start = calculate_pass_time_weighted(race_start, f_start / span, leg_duration_hours);
+    fractions[start..=end]
+        .iter()
+        .map(|&f| calculate_pass_time_weighted(leg_start, (f - f_start) / span, leg_duration_hours))
+        .collect()
+}
+
+/// Fill the yr.no cache for every checkpoint of the given races, until
+/// `shutdown` is cancelled.
+///
+/// Checkpoints are fetched one at a time, not in parallel, to stay well
+/// within yr.no rate limits; failures are logged and skipped.
+pub async fn preheat_yr_caches(
+    pool: PgPool,
+    yr_client: YrClient,
+    race_ids: Vec<Uuid>,
+    shutdown: CancellationToken,
+) {
+    for race_id in race_ids {
+        let checkpoints = tokio::select! {
+            biased;
+            _ = shutdown.cancelled() => {
+                tracing::info!("Cache preheat stopped by shutdown");
+                return;
+            }
+            result = queries::get_checkpoints(&pool, race_id) => result,
+        };
+        let checkpoints = match checkpoints {
+            Ok(checkpoints) => checkpoints,
+            Err(e) => {
+                tracing::warn!(
+                    "Cache preheat: failed to load checkpoints for {}: {}",
+                    race_id,
+                    e
+                );
+                continue;
+            }
+        };
+        let total = checkpoints.len();
+        for (i, checkpoint) in checkpoints.iter().enumerate() {
+            let result = tokio::select! {
+                biased;
+                _ = shutdown.cancelled() => {
+                    tracing::info!("Cache preheat stopped by shutdown");
+                    return;
+                }
+                result = ensure_yr_cache_fresh(&pool, &yr_client, checkpoint) => result,
+            };
+            match result {
+                Ok(_) => tracing::info!(
+                    "Cache preheat: race {} checkpoint {}/{} '{}' cached",
+                    race_id,
+                    i + 1,
+                    total,
+                    checkpoint.name
+                ),
+                Err(e) => tracing::warn!(
+                    "Cache preheat: race {} checkpoint {}/{} '{}' failed: {}",
+                    race_id,
+                    i + 1,
+                    total,
+                    checkpoint.name,
+                    e
+                ),
+            }
+        }
+    }
+}
+
 /// Ensure the yr.no cache is fresh for a given checkpoint. Does NOT extract forecasts.
 ///
 /// Returns the cached raw_response JSON (either still-valid cache or just-fetched).
@@ -571,7 +996,7 @@ pub(crate) async fn ensure_yr_cache_fresh(
     let alt = dec_to_f64(checkpoint.elevation_m);
 
     match yr_client
-        .fetch_timeseries(lat, lon, alt, if_modified_since)
+        .fetch_timeseries_deduplicated(checkpoint_id, lat, lon, alt, if_modified_since)
         .await?
     {
         YrTimeseriesResult::NewData {
@@ -643,9 +1068,23 @@ pub(crate) fn build_single_insert_params(
 
     let cloud_pct = dec_to_f64(parsed.cloud_cover_pct);
     let dew_point = dec_to_f64(parsed.dew_point_c);
-    let snow_temp = calculate_snow_temperature(temp_c, dew_point, cloud_pct, wind_ms);
+    let cloud_low_pct = parsed.cloud_cover_low_pct.map(dec_to_f64);
+    let snow_temp = calculate_snow_temperature_ensemble(
+        temp_c,
+        dew_point,
+        cloud_pct,
+        cloud_low_pct,
+        wind_ms,
+        parsed.uv_index.map(dec_to_f64),
+    );
     let snow_temp_dec = f64_to_decimal_1dp(snow_temp);
 
+    let precip_intensity = classify_precipitation_intensity(precipitation_rate_mm_per_hour(
+        precip_mm,
+        parsed.resolution,
+    ));
+    let blowing_snow = assess_blowing_snow(wind_ms, temp_c, precip_mm, precip_type);
+
     InsertForecastParams {
         checkpoint_id,
         forecast_time: parsed.forecast_time,
@@ -665,11 +1104,19 @@ pub(crate) fn build_single_insert_params(
         humidity_pct: parsed.humidity_pct,
         dew_point_c: parsed.dew_point_c,
         cloud_cover_pct: parsed.cloud_cover_pct,
+        cloud_cover_low_pct: parsed.cloud_cover_low_pct,
+        cloud_cover_medium_pct: parsed.cloud_cover_medium_pct,
+        cloud_cover_high_pct: parsed.cloud_cover_high_pct,
+        snow_depth_cm: parsed.snow_depth_cm,
         uv_index: parsed.uv_index,
         symbol_code: parsed.symbol_code.clone(),
         feels_like_c: feels_like_dec,
         precipitation_type: precip_type.to_string(),
         snow_temperature_c: snow_temp_dec,
+        precipitation_intensity: precip_intensity.to_string(),
+        blowing_snow_risk: blowing_snow.to_string(),
+        data_resolution: parsed.resolution.as_str().to_string(),
+        thunder_probability_pct: parsed.thunder_probability_pct,
         yr_model_run_at: parsed.yr_model_run_at,
     }
 }
@@ -682,20 +1129,26 @@ pub(crate) fn build_single_insert_params(
 /// 4. Re-queries the DB for the canonical forecast row.
 ///
 /// Returns `(Some(forecast), is_stale, Some(horizon))` when a forecast is available,
-/// `(None, false, Some(horizon))` when yr.no doesn't cover the requested time but
-/// the cache is available, or `(None, false, None)` on yr.no failure with no cache.
+/// `(None, false, Some(horizon))` when yr.no doesn't cover the requested time (or
+/// only below `min_resolution`) but the cache is available, or
+/// `(None, false, None)` on yr.no failure with no cache.
 pub async fn resolve_forecast(
     pool: &PgPool,
     yr_client: &YrClient,
     checkpoint: &Checkpoint,
     forecast_time: DateTime<Utc>,
+    min_resolution: ForecastResolution,
 ) -> Result<(Option<Forecast>, bool, Option<DateTime<Utc>>), AppError> {
     // Step 1: Try to get fresh yr.no data
     let raw_json = match ensure_yr_cache_fresh(pool, yr_client, checkpoint).await {
         Ok(json) => json,
         Err(e) => {
             // yr.no failed — fall back to cached forecast from DB
-            let cached = queries::get_latest_forecast(pool, checkpoint.id, forecast_time).await?;
+            let cached = queries::get_latest_forecast(pool, checkpoint.id, forecast_time)
+                .await?
+                .filter(|f| {
+                    ForecastResolution::stored_meets(f.data_resolution.as_deref(), min_resolution)
+                });
             if let Some(forecast) = cached {
                 tracing::warn!("yr.no unavailable, returning stale data: {}", e);
                 return Ok((Some(forecast), true, None));
@@ -711,7 +1164,7 @@ pub async fn resolve_forecast(
     let ExtractionResult {
         forecasts: parsed,
         forecast_horizon,
-    } = extract_forecasts_at_times(raw_json, &[forecast_time])?;
+    } = extract_forecasts_at_times(raw_json, &[forecast_time], min_resolution)?;
     let maybe_parsed = parsed.into_iter().next().flatten();
 
     match maybe_parsed {
@@ -731,6 +1184,115 @@ pub async fn resolve_forecast(
     }
 }
 
+/// Resolve forecasts for one checkpoint at several times.
+///
+/// Like [`resolve_forecast`], but the yr.no cache is checked (and fetched at
+/// most once) for the checkpoint and all times are extracted from the same
+/// JSON in a single pass. Results are in the order of `forecast_times`.
+///
+/// When yr.no is unavailable, each time falls back to the latest stored
+/// forecast; errors only if none of the times has one.
+pub async fn resolve_forecasts_at_times(
+    pool: &PgPool,
+    yr_client: &YrClient,
+    checkpoint: &Checkpoint,
+    forecast_times: &[DateTime<Utc>],
+    min_resolution: ForecastResolution,
+) -> Result<Vec<ResolvedForecast>, AppError> {
+    let raw_json = match ensure_yr_cache_fresh(pool, yr_client, checkpoint).await {
+        Ok(json) => json,
+        Err(e) => {
+            let pairs: Vec<(Uuid, DateTime<Utc>)> =
+                forecast_times.iter().map(|&t| (checkpoint.id, t)).collect();
+            let cached: Vec<Option<Forecast>> = queries::get_latest_forecasts_batch(pool, &pairs)
+                .await?
+                .into_iter()
+                .map(|f| {
+                    f.filter(|f| {
+                        ForecastResolution::stored_meets(
+                            f.data_resolution.as_deref(),
+                            min_resolution,
+                        )
+                    })
+                })
+                .collect();
+            if cached.iter().all(Option::is_none) {
+                return Err(AppError::ExternalServiceError(format!(
+                    "yr.no unavailable and no cached data: {}",
+                    e
+                )));
+            }
+            tracing::warn!("yr.no unavailable, returning stale data: {}", e);
+            return Ok(cached
+                .into_iter()
+                .map(|forecast| ResolvedForecast {
+                    forecast,
+                    is_stale: true,
+                    forecast_horizon: None,
+                })
+                .collect());
+        }
+    };
+
+    let (params, forecast_horizon) = build_insert_params_at_times(
+        checkpoint.id,
+        raw_json,
+        forecast_times,
+        min_resolution,
+        Utc::now(),
+    )?;
+
+    // Times beyond the horizon are resolved now; the rest are re-queried after insert
+    let results: Vec<Option<ResolvedForecast>> = params
+        .iter()
+        .map(|p| {
+            p.is_none().then_some(ResolvedForecast {
+                forecast: None,
+                is_stale: false,
+                forecast_horizon: Some(forecast_horizon),
+            })
+        })
+        .collect();
+    let horizons = vec![Some(forecast_horizon); forecast_times.len()];
+    batch_insert_forecasts(pool, params.into_iter().flatten().collect()).await?;
+
+    let checkpoints: Vec<CheckpointWithTime> = forecast_times
+        .iter()
+        .map(|&forecast_time| CheckpointWithTime {
+            checkpoint: checkpoint.clone(),
+            forecast_time,
+        })
+        .collect();
+    fill_requeried_forecasts(pool, &checkpoints, results, &horizons).await
+}
+
+/// Extract all `forecast_times` from one cached yr.no response and build the
+/// insert params for each (`None` where yr.no doesn't cover the time, or only
+/// below `min_resolution`).
+///
+/// Returns the params in input order along with the forecast horizon.
+fn build_insert_params_at_times(
+    checkpoint_id: Uuid,
+    raw_json: serde_json::Value,
+    forecast_times: &[DateTime<Utc>],
+    min_resolution: ForecastResolution,
+    fetched_at: DateTime<Utc>,
+) -> Result<(Vec<Option<InsertForecastParams>>, DateTime<Utc>), AppError> {
+    let ExtractionResult {
+        forecasts,
+        forecast_horizon,
+    } = extract_forecasts_at_times(raw_json, forecast_times, min_resolution)?;
+    let params = forecasts
+        .iter()
+        .map(|parsed| {
+            parsed
+                .as_ref()
+                .map(|p| build_single_insert_params(checkpoint_id, p, fetched_at))
+        })
+        .collect();
+    Ok((params, forecast_horizon))
+}
+
 /// Checkpoint with its expected pass-through time (for batch resolution).
 pub struct CheckpointWithTime {
     pub checkpoint: Checkpoint,
@@ -750,40 +1312,595 @@ pub struct ResolvedForecast {
     pub forecast_horizon: Option<DateTime<Utc>>,
 }
 
+/// Test forecast resolved fresh for "now" (cloudy, 80% humidity), with
+/// `feels_like_c` also used as the air temperature. Override fields with
+/// struct update syntax.
+#[cfg(test)]
+pub(crate) fn make_resolved(
+    feels_like_c: &str,
+    wind_speed_ms: &str,
+    precipitation_mm: &str,
+    precipitation_type: &str,
+) -> ResolvedForecast {
+    let now = Utc::now();
+    ResolvedForecast {
+        forecast: Some(Forecast {
+            id: Uuid::new_v4(),
+            checkpoint_id: Uuid::new_v4(),
+            forecast_time: now,
+            fetched_at: now,
+            source: "yr.no".to_string(),
+            temperature_c: feels_like_c.parse().unwrap(),
+            temperature_percentile_10_c: None,
+            temperature_percentile_90_c: None,
+            wind_speed_ms: wind_speed_ms.parse().unwrap(),
+            wind_speed_percentile_10_ms: None,
+            wind_speed_percentile_90_ms: None,
+            wind_direction_deg: Decimal::ZERO,
+            wind_gust_ms: None,
+            precipitation_mm: precipitation_mm.parse().unwrap(),
+            precipitation_min_mm: None,
+            precipitation_max_mm: None,
+            humidity_pct: Decimal::from(80),
+            dew_point_c: Decimal::ZERO,
+            cloud_cover_pct: Decimal::from(50),
+            uv_index: None,
+            symbol_code: "cloudy".to_string(),
+            feels_like_c: feels_like_c.parse().unwrap(),
+            precipitation_type: precipitation_type.to_string(),
+            snow_temperature_c: None,
+            precipitation_intensity: None,
+            cloud_cover_low_pct: None,
+            cloud_cover_medium_pct: None,
+            cloud_cover_high_pct: None,
+            snow_depth_cm: None,
+            blowing_snow_risk: None,
+            data_resolution: None,
+            thunder_probability_pct: None,
+            yr_model_run_at: None,
+            created_at: now,
+        }),
+        is_stale: false,
+        forecast_horizon: None,
+    }
+}
+
+/// Neutral starting point for the race difficulty rating (0–10 scale).
+const DIFFICULTY_BASE: f64 = 5.0;
+
+/// Rate how hard the weather makes a race on a 0–10 scale.
+///
+/// Starts from a neutral 5.0 and adjusts using forecasts that are available:
+/// - −0.5 per °C of average feels-like above 0°C (warm = easier)
+/// - +0.3 per m/s of average wind speed
+/// - +1.0 if any checkpoint has precipitation
+/// - +2.0 if any checkpoint has snow (slower skiing)
+/// - −0.5 if every checkpoint is below −10°C feels-like
+///
+/// Returns the neutral base when no forecasts are available.
+pub fn rate_race_difficulty(forecasts: &[ResolvedForecast]) -> f64 {
+    let available: Vec<&Forecast> = forecasts
+        .iter()
+        .filter_map(|r| r.forecast.as_ref())
+        .collect();
+    if available.is_empty() {
+        return DIFFICULTY_BASE;
+    }
+
+    let n = available.len() as f64;
+    let feels_like: Vec<f64> = available
+        .iter()
+        .map(|f| dec_to_f64(f.feels_like_c))
+        .collect();
+    let avg_feels_like = feels_like.iter().sum::<f64>() / n;
+    let avg_wind = available
+        .iter()
+        .map(|f| dec_to_f64(f.wind_speed_ms))
+        .sum::<f64>()
+        / n;
+
+    let mut rating = DIFFICULTY_BASE;
+    rating -= 0.5 * avg_feels_like.max(0.0);
+    rating += 0.3 * avg_wind;
+    if available
+        .iter()
+        .any(|f| dec_to_f64(f.precipitation_mm) > 0.0)
+    {
+        rating += 1.0;
+    }
+    if available.iter().any(|f| f.precipitation_type == "snow") {
+        rating += 2.0;
+    }
+    if feels_like.iter().all(|&t| t < -10.0) {
+        rating -= 0.5;
+    }
+
+    rating.clamp(0.0, 10.0)
+}
+
+/// Standard environmental lapse rate (°C per km of altitude gain).
+const STANDARD_LAPSE_RATE_C_PER_KM: f64 = -6.5;
+
+/// Deviation (°C) from the standard lapse rate above which a temperature
+/// difference between checkpoints is flagged.
+const LAPSE_RATE_TOLERANCE_C: f64 = 3.0;
+
+/// A checkpoint's forecast temperature at its pass time, and the start
+/// checkpoint's forecast temperature at the same time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LapseRateSample {
+    pub elevation_m: Decimal,
+    /// None when the checkpoint has no forecast
+    pub temperature_c: Option<Decimal>,
+    /// None when the start checkpoint has no forecast at that time
+    pub start_temperature_c: Option<Decimal>,
+}
+
+/// A checkpoint whose forecast temperature relative to the start departs
+/// from the standard lapse rate, e.g. because of a valley inversion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LapseRateWarning {
+    /// Index of the checkpoint in the input slice, counting checkpoints
+    /// without a forecast
+    pub checkpoint_index: usize,
+    /// Elevation relative to the start checkpoint (m)
+    pub elevation_diff_m: f64,
+    /// Temperature difference the standard lapse rate predicts (°C)
+    pub expected_temp_diff_c: f64,
+    /// Forecast temperature difference to the start checkpoint at the same
+    /// time (°C)
+    pub actual_temp_diff_c: f64,
+}
+
+/// Check forecast temperatures against the standard lapse rate (−6.5 °C/km).
+///
+/// Takes one sample per checkpoint in course order, start first, and
+/// compares every later checkpoint with the start's forecast for the same
+/// time, so the diurnal cycle between pass times isn't mistaken for an
+/// inversion. yr.no already corrects for altitude, so a difference of more
+/// than 3 °C from the lapse-rate expectation hints at local inversion
+/// conditions. Returns the largest such deviation, or `None` when all
+/// checkpoints are consistent or the start has no forecast. Checkpoints
+/// missing either temperature are skipped.
+pub fn check_temperature_lapse_consistency(
+    samples: &[LapseRateSample],
+) -> Option<LapseRateWarning> {
+    let (start, rest) = samples.split_first()?;
+    start.temperature_c?;
+    let start_ele = dec_to_f64(start.elevation_m);
+
+    rest.iter()
+        .enumerate()
+        .filter_map(|(i, sample)| {
+            let (temp, start_temp) = (sample.temperature_c?, sample.start_temperature_c?);
+            let elevation_diff_m = dec_to_f64(sample.elevation_m) - start_ele;
+            Some(LapseRateWarning {
+                checkpoint_index: i + 1,
+                elevation_diff_m,
+                expected_temp_diff_c: STANDARD_LAPSE_RATE_C_PER_KM * elevation_diff_m / 1000.0,
+                actual_temp_diff_c: dec_to_f64(temp) - dec_to_f64(start_temp),
+            })
+        })
+        .map(|w| ((w.actual_temp_diff_c - w.expected_temp_diff_c).abs(), w))
+        .filter(|(deviation, _)| *deviation > LAPSE_RATE_TOLERANCE_C)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, w)| w)
+}
+
+/// Map a difficulty rating to a label: "easy" (0–3), "moderate" (3–5),
+/// "hard" (5–8), or "extreme" (8–10).
+pub fn difficulty_label(rating: f64) -> &'static str {
+    if rating < 3.0 {
+        "easy"
+    } else if rating < 5.0 {
+        "moderate"
+    } else if rating < 8.0 {
+        "hard"
+    } else {
+        "extreme"
+    }
+}
+
+/// P90 − P10 spread, or `None` unless both percentiles are known.
+pub fn percentile_spread(p10: Option<Decimal>, p90: Option<Decimal>) -> Option<f64> {
+    Some(dec_to_f64(p90?) - dec_to_f64(p10?))
+}
+
+/// Classify forecast confidence from the temperature percentile spread:
+/// "high" (< 2°C), "medium" (2–5°C), or "low" (> 5°C).
+///
+/// Returns `None` unless both percentiles are known (long-range forecasts
+/// often lack them).
+pub fn assess_forecast_confidence(
+    p10: Option<Decimal>,
+    p90: Option<Decimal>,
+) -> Option<&'static str> {
+    let spread = percentile_spread(p10, p90)?;
+    Some(if spread < 2.0 {
+        "high"
+    } else if spread <= 5.0 {
+        "medium"
+    } else {
+        "low"
+    })
+}
+
+/// UV exposure estimate over a race.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UvExposureReport {
+    /// Sum of UV index × segment hours (UV-hours)
+    pub total_uv_exposure: f64,
+    /// Highest UV index at any checkpoint pass-through time
+    pub max_uv_index: f64,
+    /// WHO risk category for `max_uv_index`
+    pub uv_risk_category: &'static str,
+    /// Sun protection advice for the risk category
+    pub recommendation: &'static str,
+    /// Number of checkpoints with a UV forecast
+    pub checkpoints_with_uv_data: usize,
+}
+
+/// Estimate UV exposure along a race.
+///
+/// Each segment between consecutive checkpoints contributes its duration in
+/// hours × the average UV index at its two ends (or the one end that has a
+/// UV forecast). Segments with no UV data at either end contribute nothing —
+/// yr.no only provides UV for roughly the first 60 hours.
+///
+/// `checkpoints` and `forecasts` must be in the same (course) order.
+pub fn calculate_uv_exposure(
+    checkpoints: &[CheckpointWithTime],
+    forecasts: &[ResolvedForecast],
+) -> UvExposureReport {
+    let uv: Vec<Option<f64>> = forecasts
+        .iter()
+        .map(|r| r.forecast.as_ref().and_then(|f| f.uv_index).map(dec_to_f64))
+        .collect();
+
+    let mut total = 0.0;
+    for (i, pair) in checkpoints.windows(2).enumerate() {
+        let hours = (pair[1].forecast_time - pair[0].forecast_time).num_seconds() as f64 / 3600.0;
+        let segment_uv = match (
+            uv.get(i).copied().flatten(),
+            uv.get(i + 1).copied().flatten(),
+        ) {
+            (Some(a), Some(b)) => (a + b) / 2.0,
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => 0.0,
+        };
+        total += segment_uv * hours.max(0.0);
+    }
+
+    let max_uv_index = uv.iter().flatten().copied().fold(0.0, f64::max);
+    let uv_risk_category = uv_risk_category(max_uv_index);
+
+    UvExposureReport {
+        total_uv_exposure: total,
+        max_uv_index,
+        uv_risk_category,
+        recommendation: uv_recommendation(uv_risk_category),
+        checkpoints_with_uv_data: uv.iter().flatten().count(),
+    }
+}
+
+/// Map a UV index to a WHO risk category: "low" (<3), "moderate" (3–6),
+/// "high" (6–8), or "very high" (8+).
+pub fn uv_risk_category(uv_index: f64) -> &'static str {
+    if uv_index < 3.0 {
+        "low"
+    } else if uv_index < 6.0 {
+        "moderate"
+    } else if uv_index < 8.0 {
+        "high"
+    } else {
+        "very high"
+    }
+}
+
+/// Sun protection advice for a UV risk category.
+fn uv_recommendation(category: &str) -> &'static str {
+    match category {
+        "low" => "No sun protection needed",
+        "moderate" => "SPF 15+ recommended",
+        "high" => "SPF 30+ recommended",
+        _ => "SPF 50+ recommended; protect lips and eyes (snow reflects UV)",
+    }
+}
+
+/// Snowfall expected at one checkpoint over a window of hourly slots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnowfallAccumulation {
+    /// Sum of hourly precipitation × snow fraction over the window (mm water equivalent)
+    pub total_expected_snowfall_mm: f64,
+    /// Highest snow-weighted hourly rate in the window (mm/h)
+    pub max_hourly_intensity_mm: f64,
+    /// Hourly slots with a forecast (slots beyond the yr.no horizon are skipped)
+    pub hours_with_data: usize,
+}
+
+/// Share of precipitation expected to fall as snow for a precipitation type:
+/// 1.0 for snow, 0.5 for sleet, 0.0 otherwise.
+pub fn snow_fraction(precipitation_type: &str) -> f64 {
+    match precipitation_type {
+        "snow" => 1.0,
+        "sleet" => 0.5,
+        _ => 0.0,
+    }
+}
+
+/// Accumulate snowfall over consecutive hourly slots at one checkpoint.
+///
+/// Each slot contributes its hourly precipitation rate (6-hourly amounts are
+/// divided by 6, so several hourly slots matched to the same 6-hour entry
+/// add up to its total) weighted by [`snow_fraction`].
+pub fn accumulate_snowfall(slots: &[ResolvedForecast]) -> SnowfallAccumulation {
+    let mut total = 0.0;
+    let mut max_rate: f64 = 0.0;
+    let mut hours_with_data = 0;
+    for forecast in slots.iter().filter_map(|r| r.forecast.as_ref()) {
+        let resolution = match forecast.data_resolution.as_deref() {
+            Some("six_hourly") => ForecastResolution::SixHourly,
+            _ => ForecastResolution::Hourly,
+        };
+        let rate =
+            precipitation_rate_mm_per_hour(dec_to_f64(forecast.precipitation_mm), resolution)
+                * snow_fraction(&forecast.precipitation_type);
+        total += rate;
+        max_rate = max_rate.max(rate);
+        hours_with_data += 1;
+    }
+    SnowfallAccumulation {
+        total_expected_snowfall_mm: total,
+        max_hourly_intensity_mm: max_rate,
+        hours_with_data,
+    }
+}
+
+/// Number of wind rose sectors (22.5° each, sector 0 centred on north).
+pub const WIND_ROSE_SECTORS: usize = 16;
+
+/// Compass labels for the wind rose sectors, clockwise from north.
+pub const WIND_ROSE_LABELS: [&str; WIND_ROSE_SECTORS] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+/// Wind statistics for one wind rose direction sector.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WindSector {
+    /// Forecasts with the wind blowing from this sector
+    pub count: u32,
+    /// Mean wind speed of those forecasts (m/s), 0 when empty
+    pub mean_speed_ms: f64,
+    /// Highest wind speed of those forecasts (m/s), 0 when empty
+    pub max_speed_ms: f64,
+}
+
+/// Wind rose sector for a meteorological wind direction ("from", degrees).
+/// Sector `i` covers `i × 22.5° ± 11.25°`, so 350° and 10° are both north.
+pub fn wind_rose_sector(direction_deg: f64) -> usize {
+    let width = 360.0 / WIND_ROSE_SECTORS as f64;
+    ((direction_deg.rem_euclid(360.0) + width / 2.0) / width) as usize % WIND_ROSE_SECTORS
+}
+
+/// Aggregate forecast wind direction and speed into 16 wind rose sectors.
+pub fn compute_wind_rose(forecasts: &[Forecast]) -> [WindSector; WIND_ROSE_SECTORS] {
+    let mut sectors = [WindSector::default(); WIND_ROSE_SECTORS];
+    let mut speed_sums = [0.0; WIND_ROSE_SECTORS];
+    for forecast in forecasts {
+        let speed = dec_to_f64(forecast.wind_speed_ms);
+        let i = wind_rose_sector(dec_to_f64(forecast.wind_direction_deg));
+        sectors[i].count += 1;
+        sectors[i].max_speed_ms = sectors[i].max_speed_ms.max(speed);
+        speed_sums[i] += speed;
+    }
+    for (sector, sum) in sectors.iter_mut().zip(speed_sums) {
+        if sector.count > 0 {
+            sector.mean_speed_ms = sum / sector.count as f64;
+        }
+    }
+    sectors
+}
+
+/// Floor a datetime to the start of its hour.
+pub(crate) fn floor_to_hour(dt: DateTime<Utc>) -> DateTime<Utc> {
+    dt.date_naive()
+        .and_hms_opt(dt.time().hour(), 0, 0)
+        .map(|naive| DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc))
+        .unwrap_or(dt)
+}
+
+/// Ceil a datetime to the next hour (or same if already on the hour).
+pub(crate) fn ceil_to_hour(dt: DateTime<Utc>) -> DateTime<Utc> {
+    if dt.time().minute() == 0 && dt.time().second() == 0 && dt.time().nanosecond() == 0 {
+        dt
+    } else {
+        floor_to_hour(dt) + Duration::hours(1)
+    }
+}
+
+/// Every full hour from `from` (floored) to `to` (ceiled), inclusive.
+///
+/// Used by the race timeline.
+pub fn hourly_slots(from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let last = ceil_to_hour(to);
+    let mut slots = Vec::new();
+    let mut current = floor_to_hour(from);
+    while current <= last {
+        slots.push(current);
+        current += Duration::hours(1);
+    }
+    slots
+}
+
+/// Maximum number of hourly slots in a race timeline.
+pub const MAX_TIMELINE_SLOTS: usize = 72;
+
+/// Where the racer is expected to be at one hour of the race timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineSlot {
+    /// Full-hour UTC time of the slot
+    pub time: DateTime<Utc>,
+    /// Index of the checkpoint starting the current segment
+    pub segment_start: usize,
+    /// Index of the checkpoint ending the current segment
+    pub segment_end: usize,
+    /// Index of the checkpoint closest to the racer's expected position,
+    /// whose forecast represents this hour
+    pub nearest_checkpoint: usize,
+}
+
+/// Build hourly timeline slots covering the race window.
+///
+/// `checkpoints` carry elevation-adjusted pass-through times in course order.
+/// For each hour between the first and last pass-through time, finds the
+/// segment the racer is in and which end of it they are closer to (by time,
+/// which is linear in distance within a segment). Hours before the start or
+/// after the finish snap to the first or last checkpoint. Returns at most
+/// [`MAX_TIMELINE_SLOTS`] slots; empty if there are no checkpoints.
+pub fn compute_timeline_slots(checkpoints: &[CheckpointWithTime]) -> Vec<TimelineSlot> {
+    let (Some(first), Some(last)) = (checkpoints.first(), checkpoints.last()) else {
+        return Vec::new();
+    };
+    let last_idx = checkpoints.len() - 1;
+
+    hourly_slots(first.forecast_time, last.forecast_time)
+        .into_iter()
+        .take(MAX_TIMELINE_SLOTS)
+        .map(|time| {
+            // First checkpoint whose pass-through time is after this slot
+            let next = checkpoints.partition_point(|cp| cp.forecast_time <= time);
+            let (segment_start, segment_end, nearest_checkpoint) = if next == 0 {
+                (0, last_idx.min(1), 0)
+            } else if next > last_idx {
+                (last_idx.saturating_sub(1), last_idx, last_idx)
+            } else {
+                let a = &checkpoints[next - 1];
+                let b = &checkpoints[next];
+                let span = (b.forecast_time - a.forecast_time).num_seconds() as f64;
+                let elapsed = (time - a.forecast_time).num_seconds() as f64;
+                let nearest = if span <= 0.0 || elapsed / span < 0.5 {
+                    next - 1
+                } else {
+                    next
+                };
+                (next - 1, next, nearest)
+            };
+            TimelineSlot {
+                time,
+                segment_start,
+                segment_end,
+                nearest_checkpoint,
+            }
+        })
+        .collect()
+}
+
 /// Resolve forecasts for multiple checkpoints in a race — extract-on-read.
 ///
 /// 1. `ensure_yr_cache_fresh` for each checkpoint (parallel)
-/// 2. Extract forecasts from cached JSON in-memory for all checkpoints
+/// 2. Extract forecasts from cached JSON in-memory for all checkpoints,
+///    reusing `extraction_cache` results from the same model run
 /// 3. Write to forecasts table for history (ON CONFLICT DO NOTHING)
 /// 4. Re-query DB for canonical Forecast rows (batch)
 ///
 /// Each checkpoint has its own yr_responses row (keyed by checkpoint_id FK),
 /// so there is no location-based grouping.
+///
+/// Resolutions slower than `slow_request_threshold_ms` log a per-step
+/// timing breakdown (see [`ResolveTimings`]).
 pub async fn resolve_race_forecasts(
     pool: &PgPool,
     yr_client: &YrClient,
+    extraction_cache: &ExtractionCache,
     checkpoints: &[CheckpointWithTime],
+    slow_request_threshold_ms: u64,
 ) -> Result<Vec<ResolvedForecast>, AppError> {
     let n = checkpoints.len();
+    let mut timings = ResolveTimings {
+        checkpoint_count: n,
+        ..Default::default()
+    };
 
     // ── Step 1: Ensure yr.no cache fresh for each checkpoint (bounded parallel) ──
+    let step = Instant::now();
     let fetch_results = fetch_yr_caches(pool, yr_client, checkpoints).await;
+    timings.yr_fetch_ms = elapsed_ms(step);
 
     // ── Step 2: Handle results, falling back to DB cache on error ──
+    let step = Instant::now();
     let pairs: Vec<(Uuid, DateTime<Utc>)> = checkpoints
         .iter()
         .map(|cpwt| (cpwt.checkpoint.id, cpwt.forecast_time))
         .collect();
     let cached_forecasts = queries::get_latest_forecasts_batch(pool, &pairs).await?;
-
-    let (results, horizons, insert_params) =
-        process_fetch_results(&fetch_results, checkpoints, &cached_forecasts, n)?;
+    timings.requery_ms = elapsed_ms(step);
+
+    let step = Instant::now();
+    let (results, horizons, insert_params) = process_fetch_results(
+        &fetch_results,
+        checkpoints,
+        &cached_forecasts,
+        extraction_cache,
+        n,
+    )?;
+    timings.extraction_ms = elapsed_ms(step);
+    let (hits, misses) = extraction_cache.stats();
+    tracing::debug!(
+        "Extraction cache: {} hits, {} misses since startup",
+        hits,
+        misses
+    );
 
     // ── Step 2b: Batch-insert all forecast params concurrently ──
+    let step = Instant::now();
     batch_insert_forecasts(pool, insert_params).await?;
+    timings.insert_ms = elapsed_ms(step);
 
     // ── Step 3: Batch re-query DB for canonical Forecast rows ──
-    fill_requeried_forecasts(pool, checkpoints, results, &horizons).await
+    let step = Instant::now();
+    let resolved = fill_requeried_forecasts(pool, checkpoints, results, &horizons).await;
+    timings.requery_ms += elapsed_ms(step);
+
+    if timings.is_slow(slow_request_threshold_ms) {
+        tracing::warn!(
+            checkpoint_count = timings.checkpoint_count,
+            yr_fetch_ms = timings.yr_fetch_ms,
+            extraction_ms = timings.extraction_ms,
+            insert_ms = timings.insert_ms,
+            requery_ms = timings.requery_ms,
+            "Slow race forecast resolution: {}ms (threshold {}ms)",
+            timings.total_ms(),
+            slow_request_threshold_ms
+        );
+    }
+    resolved
+}
+
+/// Time spent in each step of [`resolve_race_forecasts`]. `requery_ms`
+/// covers both batch DB reads (stale fallback and canonical rows).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResolveTimings {
+    pub checkpoint_count: usize,
+    pub yr_fetch_ms: u64,
+    pub extraction_ms: u64,
+    pub insert_ms: u64,
+    pub requery_ms: u64,
+}
+
+impl ResolveTimings {
+    pub fn total_ms(&self) -> u64 {
+        self.yr_fetch_ms + self.extraction_ms + self.insert_ms + self.requery_ms
+    }
+
+    /// Whether the steps took longer than `threshold_ms` in total.
+    pub fn is_slow(&self, threshold_ms: u64) -> bool {
+        self.total_ms() > threshold_ms
+    }
+}
+
+fn elapsed_ms(since: Instant) -> u64 {
+    since.elapsed().as_millis() as u64
 }
 
 /// Fetch yr.no caches for all checkpoints with bounded concurrency.
@@ -819,6 +1936,7 @@ fn process_fetch_results(
     fetch_results: &[Result<serde_json::Value, AppError>],
     checkpoints: &[CheckpointWithTime],
     cached_forecasts: &[Option<Forecast>],
+    extraction_cache: &ExtractionCache,
     n: usize,
 ) -> Result<
     (
@@ -839,7 +1957,11 @@ fn process_fetch_results(
                 let ExtractionResult {
                     forecasts: parsed,
                     forecast_horizon,
-                } = extract_forecasts_at_times(raw_json.clone(), &[forecast_time])?;
+                } = extraction_cache.extract(
+                    checkpoints[idx].checkpoint.id,
+                    raw_json,
+                    &[forecast_time],
+                )?;
                 let maybe_parsed = parsed.into_iter().next().flatten();
 
                 match maybe_parsed {
@@ -953,7 +2075,12 @@ async fn fill_requeried_forecasts(
 pub async fn get_checkpoint(pool: &PgPool, checkpoint_id: Uuid) -> Result<Checkpoint, AppError> {
     queries::get_checkpoint(pool, checkpoint_id)
         .await?
-        .ok_or_else(|| AppError::NotFound(format!("Checkpoint {} not found", checkpoint_id)))
+        .ok_or_else(|| {
+            AppError::NotFound(
+                AppErrorCode::CheckpointNotFound,
+                format!("Checkpoint {} not found", checkpoint_id),
+            )
+        })
 }
 
 #[cfg(test)]
@@ -1025,6 +2152,98 @@ mod tests {
         assert_eq!(infer_precipitation_type("cloudy", 1.0, 1.0), "sleet");
     }
 
+    #[test]
+    fn test_precip_intensity_none() {
+        assert_eq!(classify_precipitation_intensity(0.0), "none");
+        assert_eq!(classify_precipitation_intensity(-0.1), "none");
+    }
+
+    #[test]
+    fn test_precip_intensity_trace() {
+        assert_eq!(classify_precipitation_intensity(0.01), "trace");
+        assert_eq!(classify_precipitation_intensity(0.09), "trace");
+    }
+
+    #[test]
+    fn test_precip_intensity_light() {
+        assert_eq!(classify_precipitation_intensity(0.1), "light");
+        assert_eq!(classify_precipitation_intensity(2.4), "light");
+    }
+
+    #[test]
+    fn test_precip_intensity_moderate() {
+        assert_eq!(classify_precipitation_intensity(2.5), "moderate");
+        assert_eq!(classify_precipitation_intensity(7.5), "moderate");
+    }
+
+    #[test]
+    fn test_precip_intensity_heavy() {
+        assert_eq!(classify_precipitation_intensity(7.6), "heavy");
+        assert_eq!(classify_precipitation_intensity(30.0), "heavy");
+    }
+
+    #[test]
+    fn test_precip_intensity_six_hourly_divided_by_six() {
+        // 12 mm over 6 hours = 2 mm/h → light (would be heavy if treated as hourly)
+        let rate = precipitation_rate_mm_per_hour(12.0, ForecastResolution::SixHourly);
+        assert!((rate - 2.0).abs() < 1e-10);
+        assert_eq!(classify_precipitation_intensity(rate), "light");
+        let hourly = precipitation_rate_mm_per_hour(12.0, ForecastResolution::Hourly);
+        assert_eq!(classify_precipitation_intensity(hourly), "heavy");
+    }
+
+    #[test]
+    fn test_lightning_risk_boundaries() {
+        assert_eq!(classify_lightning_risk(0.0), "none");
+        assert_eq!(classify_lightning_risk(4.9), "none");
+        assert_eq!(classify_lightning_risk(5.0), "low");
+        assert_eq!(classify_lightning_risk(25.0), "moderate");
+        assert_eq!(classify_lightning_risk(60.0), "moderate");
+        assert_eq!(classify_lightning_risk(60.1), "high");
+    }
+
+    #[test]
+    fn test_blowing_snow_none_in_calm_snowfall() {
+        assert_eq!(assess_blowing_snow(3.0, -4.0, 1.0, "snow"), "none");
+        assert_eq!(assess_blowing_snow(5.0, -4.0, 1.0, "snow"), "none");
+    }
+
+    #[test]
+    fn test_blowing_snow_possible_above_5_ms() {
+        assert_eq!(assess_blowing_snow(5.1, -4.0, 1.0, "snow"), "possible");
+        assert_eq!(assess_blowing_snow(8.0, -4.0, 1.0, "snow"), "possible");
+    }
+
+    #[test]
+    fn test_blowing_snow_likely_above_8_ms() {
+        assert_eq!(assess_blowing_snow(8.1, -4.0, 1.0, "snow"), "likely");
+        assert_eq!(assess_blowing_snow(12.0, -4.0, 1.0, "snow"), "likely");
+    }
+
+    #[test]
+    fn test_blowing_snow_severe_above_12_ms() {
+        assert_eq!(assess_blowing_snow(12.1, -4.0, 1.0, "snow"), "severe");
+        assert_eq!(assess_blowing_snow(20.0, -15.0, 3.0, "snow"), "severe");
+    }
+
+    #[test]
+    fn test_blowing_snow_ignores_rain_and_sleet() {
+        assert_eq!(assess_blowing_snow(15.0, 3.0, 2.0, "rain"), "none");
+        assert_eq!(assess_blowing_snow(15.0, 1.0, 2.0, "sleet"), "none");
+    }
+
+    #[test]
+    fn test_blowing_snow_old_snow_redistribution() {
+        // Dry, cold and windy: loose old snow drifts without active snowfall
+        assert_eq!(assess_blowing_snow(10.5, -8.0, 0.0, "none"), "possible");
+    }
+
+    #[test]
+    fn test_blowing_snow_old_snow_needs_cold_and_strong_wind() {
+        assert_eq!(assess_blowing_snow(10.5, -5.0, 0.0, "none"), "none");
+        assert_eq!(assess_blowing_snow(10.0, -8.0, 0.0, "none"), "none");
+    }
+
     #[test]
     fn test_pacing_start() {
         let start = DateTime::parse_from_rfc3339("2026-03-01T07:00:00Z")
@@ -1063,21 +2282,25 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 30.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 60.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 4);
         assert!((fractions[0] - 0.0).abs() < 1e-10);
         assert!((fractions[1] - 1.0 / 3.0).abs() < 1e-10);
@@ -1092,17 +2315,20 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 0.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 45.0,
                 elevation_m: 500.0,
+                ..Default::default()
             }, // +500m over 45km
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 500.0,
+                ..Default::default()
             }, // flat
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 3);
         assert!((fractions[0] - 0.0).abs() < 1e-10);
         // Midpoint should be > 0.5 (uphill first half takes more time)
@@ -1111,7 +2337,49 @@ mod tests {
             "Uphill half should take more than 50% of time, got {}",
             fractions[1]
         );
-        assert!((fractions[2] - 1.0).abs() < 1e-10);
+        assert!((fractions[2] - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_elevation_fractions_k_up_scales_climb_share() {
+        // Uphill first half, flat second half
+        let checkpoints = vec![
+            PacingCheckpoint {
+                distance_km: 0.0,
+                elevation_m: 0.0,
+                ..Default::default()
+            },
+            PacingCheckpoint {
+                distance_km: 45.0,
+                elevation_m: 500.0,
+                ..Default::default()
+            },
+            PacingCheckpoint {
+                distance_km: 90.0,
+                elevation_m: 500.0,
+                ..Default::default()
+            },
+        ];
+        let default = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
+        let steep = calculate_pass_time_fractions(
+            &checkpoints,
+            &PacingParams {
+                k_up: 24.0,
+                ..Default::default()
+            },
+        );
+        assert!(steep[1] > default[1]);
+
+        // Without an uphill penalty the course paces like a flat one
+        let even = calculate_pass_time_fractions(
+            &checkpoints,
+            &PacingParams {
+                k_up: 0.0,
+                ..Default::default()
+            },
+        );
+        assert!((even[1] - 0.5).abs() < 1e-10);
+        assert!((even[2] - 1.0).abs() < 1e-10);
     }
 
     #[test]
@@ -1121,17 +2389,20 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 500.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 45.0,
                 elevation_m: 500.0,
+                ..Default::default()
             }, // flat
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 0.0,
+                ..Default::default()
             }, // -500m over 45km
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 3);
         // Midpoint should be > 0.5 (downhill second half takes less time,
         // so more of the time is spent in the flat first half)
@@ -1150,41 +2421,50 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 349.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 11.0,
                 elevation_m: 502.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 24.0,
                 elevation_m: 390.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 35.0,
                 elevation_m: 396.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 47.0,
                 elevation_m: 419.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 62.0,
                 elevation_m: 231.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 71.0,
                 elevation_m: 247.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 81.0,
                 elevation_m: 206.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 168.0,
+                ..Default::default()
             },
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 9);
         assert!((fractions[0] - 0.0).abs() < 1e-10, "Start should be 0.0");
         assert!((fractions[8] - 1.0).abs() < 1e-10, "Finish should be 1.0");
@@ -1210,41 +2490,50 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 349.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 11.0,
                 elevation_m: 502.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 24.0,
                 elevation_m: 390.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 35.0,
                 elevation_m: 396.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 47.0,
                 elevation_m: 419.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 62.0,
                 elevation_m: 231.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 71.0,
                 elevation_m: 247.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 81.0,
                 elevation_m: 206.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 168.0,
+                ..Default::default()
             },
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         let even_fraction = 11.0 / 90.0;
         assert!(
             fractions[1] > even_fraction,
@@ -1274,16 +2563,20 @@ mod tests {
 
     #[test]
     fn test_elevation_fractions_empty() {
-        let fractions = calculate_pass_time_fractions(&[]);
+        let fractions = calculate_pass_time_fractions(&[], &PacingParams::default());
         assert!(fractions.is_empty());
     }
 
     #[test]
     fn test_elevation_fractions_single() {
-        let fractions = calculate_pass_time_fractions(&[PacingCheckpoint {
-            distance_km: 0.0,
-            elevation_m: 100.0,
-        }]);
+        let fractions = calculate_pass_time_fractions(
+            &[PacingCheckpoint {
+                distance_km: 0.0,
+                elevation_m: 100.0,
+                ..Default::default()
+            }],
+            &PacingParams::default(),
+        );
         assert_eq!(fractions.len(), 1);
         assert!((fractions[0] - 0.0).abs() < 1e-10);
     }
@@ -1318,18 +2611,22 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 45.0,
                 elevation_m: 300.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
         ];
-        let simple = calculate_pass_time_fractions(&checkpoints);
-        let track_aware = calculate_pass_time_fractions_with_track(&checkpoints, &[]);
+        let simple = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
+        let track_aware =
+            calculate_pass_time_fractions_with_track(&checkpoints, &[], &PacingParams::default());
         assert_eq!(simple.len(), track_aware.len());
         for (s, t) in simple.iter().zip(track_aware.iter()) {
             assert!(
@@ -1343,7 +2640,8 @@ mod tests {
 
     #[test]
     fn test_track_pacing_empty_checkpoints() {
-        let fractions = calculate_pass_time_fractions_with_track(&[], &[]);
+        let fractions =
+            calculate_pass_time_fractions_with_track(&[], &[], &PacingParams::default());
         assert!(fractions.is_empty());
     }
 
@@ -1352,6 +2650,7 @@ mod tests {
         let checkpoints = vec![PacingCheckpoint {
             distance_km: 0.0,
             elevation_m: 100.0,
+            ..Default::default()
         }];
         let fractions = calculate_pass_time_fractions_with_track(
             &checkpoints,
@@ -1359,6 +2658,7 @@ mod tests {
                 distance_km: 0.0,
                 elevation_m: 100.0,
             }],
+            &PacingParams::default(),
         );
         assert_eq!(fractions.len(), 1);
         assert!((fractions[0] - 0.0).abs() < 1e-10);
@@ -1371,23 +2671,31 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 30.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 60.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
         ];
         let track = make_linear_track(0.0, 100.0, 90.0, 100.0, 100);
-        let simple = calculate_pass_time_fractions(&checkpoints);
-        let track_aware = calculate_pass_time_fractions_with_track(&checkpoints, &track);
+        let simple = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
+        let track_aware = calculate_pass_time_fractions_with_track(
+            &checkpoints,
+            &track,
+            &PacingParams::default(),
+        );
         assert_eq!(simple.len(), track_aware.len());
         for (s, t) in simple.iter().zip(track_aware.iter()) {
             assert!(
@@ -1407,19 +2715,26 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 45.0,
                 elevation_m: 350.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 600.0,
+                ..Default::default()
             },
         ];
         let track = make_linear_track(0.0, 100.0, 90.0, 600.0, 200);
-        let simple = calculate_pass_time_fractions(&checkpoints);
-        let track_aware = calculate_pass_time_fractions_with_track(&checkpoints, &track);
+        let simple = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
+        let track_aware = calculate_pass_time_fractions_with_track(
+            &checkpoints,
+            &track,
+            &PacingParams::default(),
+        );
         assert_eq!(simple.len(), track_aware.len());
         for (s, t) in simple.iter().zip(track_aware.iter()) {
             assert!(
@@ -1441,14 +2756,17 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 45.0,
                 elevation_m: 100.0, // net elevation = 0
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
         ];
 
@@ -1477,8 +2795,12 @@ mod tests {
             });
         }
 
-        let simple = calculate_pass_time_fractions(&checkpoints);
-        let track_aware = calculate_pass_time_fractions_with_track(&checkpoints, &track);
+        let simple = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
+        let track_aware = calculate_pass_time_fractions_with_track(
+            &checkpoints,
+            &track,
+            &PacingParams::default(),
+        );
 
         // Simple sees both segments as flat → 50/50 split
         assert!(
@@ -1510,14 +2832,17 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 300.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 45.0,
                 elevation_m: 300.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 300.0,
+                ..Default::default()
             },
         ];
 
@@ -1545,7 +2870,11 @@ mod tests {
             });
         }
 
-        let track_aware = calculate_pass_time_fractions_with_track(&checkpoints, &track);
+        let track_aware = calculate_pass_time_fractions_with_track(
+            &checkpoints,
+            &track,
+            &PacingParams::default(),
+        );
 
         // First segment has hidden valley → more costly
         assert!(
@@ -1562,38 +2891,47 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 349.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 11.0,
                 elevation_m: 502.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 24.0,
                 elevation_m: 390.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 35.0,
                 elevation_m: 396.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 47.0,
                 elevation_m: 419.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 62.0,
                 elevation_m: 231.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 71.0,
                 elevation_m: 247.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 81.0,
                 elevation_m: 206.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 168.0,
+                ..Default::default()
             },
         ];
 
@@ -1616,7 +2954,11 @@ mod tests {
             elevation_m: 168.0,
         });
 
-        let fractions = calculate_pass_time_fractions_with_track(&checkpoints, &track);
+        let fractions = calculate_pass_time_fractions_with_track(
+            &checkpoints,
+            &track,
+            &PacingParams::default(),
+        );
         assert_eq!(fractions.len(), 9);
         assert!((fractions[0] - 0.0).abs() < 1e-10, "Start should be 0.0");
         assert!((fractions[8] - 1.0).abs() < 1e-10, "Finish should be 1.0");
@@ -1646,43 +2988,56 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 349.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 11.0,
                 elevation_m: 502.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 24.0,
                 elevation_m: 390.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 35.0,
                 elevation_m: 396.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 47.0,
                 elevation_m: 419.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 62.0,
                 elevation_m: 231.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 71.0,
                 elevation_m: 247.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 81.0,
                 elevation_m: 206.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 168.0,
+                ..Default::default()
             },
         ];
 
-        let simple = calculate_pass_time_fractions(&checkpoints);
-        let track_aware = calculate_pass_time_fractions_with_track(&checkpoints, &track);
+        let simple = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
+        let track_aware = calculate_pass_time_fractions_with_track(
+            &checkpoints,
+            &track,
+            &PacingParams::default(),
+        );
 
         assert_eq!(track_aware.len(), 9);
         assert!((track_aware[0] - 0.0).abs() < 1e-10);
@@ -1732,18 +3087,22 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 30.0,
                 elevation_m: 200.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 60.0,
                 elevation_m: 150.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
         ];
 
@@ -1763,7 +3122,11 @@ mod tests {
             },
         ];
 
-        let fractions = calculate_pass_time_fractions_with_track(&checkpoints, &track);
+        let fractions = calculate_pass_time_fractions_with_track(
+            &checkpoints,
+            &track,
+            &PacingParams::default(),
+        );
         assert_eq!(fractions.len(), 4);
         assert!((fractions[0] - 0.0).abs() < 1e-10);
         assert!((fractions[3] - 1.0).abs() < 1e-10);
@@ -1837,8 +3200,13 @@ mod tests {
             humidity_pct: Decimal::from_str("75.0").unwrap(),
             dew_point_c: Decimal::from_str("-8.5").unwrap(),
             cloud_cover_pct: Decimal::from_str("50.0").unwrap(),
+            cloud_cover_low_pct: None,
+            cloud_cover_medium_pct: None,
+            cloud_cover_high_pct: None,
+            snow_depth_cm: None,
             uv_index: None,
             symbol_code: "lightsnow".to_string(),
+            thunder_probability_pct: None,
             yr_model_run_at: Some(model_run),
             resolution: ForecastResolution::Hourly,
         };
@@ -1895,8 +3263,13 @@ mod tests {
             humidity_pct: Decimal::from_str("60.0").unwrap(),
             dew_point_c: Decimal::from_str("-2.0").unwrap(),
             cloud_cover_pct: Decimal::from_str("0.0").unwrap(),
+            cloud_cover_low_pct: None,
+            cloud_cover_medium_pct: None,
+            cloud_cover_high_pct: None,
+            snow_depth_cm: None,
             uv_index: None,
             symbol_code: "clearsky_day".to_string(),
+            thunder_probability_pct: None,
             yr_model_run_at: None,
             resolution: ForecastResolution::SixHourly,
         };
@@ -1913,6 +3286,7 @@ mod tests {
         assert!(params.precipitation_max_mm.is_none());
         assert!(params.uv_index.is_none());
         assert!(params.yr_model_run_at.is_none());
+        assert_eq!(params.data_resolution, "six_hourly");
 
         // Zero precip -> "none"
         assert_eq!(params.precipitation_type, "none");
@@ -1961,8 +3335,13 @@ mod tests {
             humidity_pct: Decimal::from_str("90.0").unwrap(),
             dew_point_c: Decimal::from_str("-9.5").unwrap(),
             cloud_cover_pct: Decimal::from_str("100.0").unwrap(),
+            cloud_cover_low_pct: None,
+            cloud_cover_medium_pct: None,
+            cloud_cover_high_pct: None,
+            snow_depth_cm: None,
             uv_index: Some(Decimal::from_str("0.5").unwrap()),
             symbol_code: "heavysnow".to_string(),
+            thunder_probability_pct: None,
             yr_model_run_at: Some(model_run),
             resolution: ForecastResolution::Hourly,
         };
@@ -2014,10 +3393,12 @@ mod tests {
         // Snow temperature: -8°C air, -9.5°C dew point, 100% cloud, 5 m/s wind
         // T_base = min(-8, -9.5) = -9.5, cloud_factor = 0.0 → offset = 0.0
         // T_snow = min(-9.5, 0) = -9.5
+        // UV 0.5 → heat balance: -8 × 0.2 × 0.05 - 0.0 = -0.08
+        // Ensemble: 0.6 × -9.5 + 0.4 × -0.08 = -5.73
         let snow_temp_f64 = dec_to_f64(params.snow_temperature_c);
         assert!(
-            (snow_temp_f64 - (-9.5)).abs() < 0.1,
-            "100% cloud: snow temp should ≈ T_base (dew point), got {}",
+            (snow_temp_f64 - (-5.73)).abs() < 0.1,
+            "100% cloud, UV 0.5: snow temp should ≈ ensemble, got {}",
             snow_temp_f64
         );
     }
@@ -2043,8 +3424,13 @@ mod tests {
             humidity_pct: Decimal::from_str("50.0").unwrap(),
             dew_point_c: Decimal::from_str("-10.0").unwrap(),
             cloud_cover_pct: Decimal::from_str("80.0").unwrap(),
+            cloud_cover_low_pct: None,
+            cloud_cover_medium_pct: None,
+            cloud_cover_high_pct: None,
+            snow_depth_cm: None,
             uv_index: None,
             symbol_code: "lightsnow".to_string(),
+            thunder_probability_pct: None,
             yr_model_run_at: None,
             resolution: ForecastResolution::Hourly,
         };
@@ -2053,6 +3439,116 @@ mod tests {
         assert_eq!(params.precipitation_type, "none");
     }
 
+    #[test]
+    fn test_build_insert_params_at_times_one_response_serves_all() {
+        // One cached yr.no response (06:00–19:00 hourly) serves 12 requested times
+        let start: DateTime<Utc> = "2026-03-01T06:00:00Z".parse().unwrap();
+        let timeseries: Vec<serde_json::Value> = (0..14)
+            .map(|h| {
+                serde_json::json!({
+                    "time": (start + Duration::hours(h)).to_rfc3339(),
+                    "data": {
+                        "instant": { "details": {
+                            "air_temperature": -10.0 + h as f64,
+                            "wind_speed": 3.0,
+                            "wind_from_direction": 180.0,
+                            "relative_humidity": 75.0,
+                            "dew_point_temperature": -12.0,
+                            "cloud_area_fraction": 50.0
+                        }},
+                        "next_1_hours": {
+                            "summary": { "symbol_code": "cloudy" },
+                            "details": { "precipitation_amount": 0.0 }
+                        }
+                    }
+                })
+            })
+            .collect();
+        let json = serde_json::json!({
+            "type": "Feature",
+            "properties": { "timeseries": timeseries }
+        });
+
+        // Requested out of chronological order to check input order is kept
+        let times: Vec<DateTime<Utc>> = [5, 1, 12, 3, 7, 2, 9, 4, 11, 6, 8, 10]
+            .iter()
+            .map(|&h| start + Duration::hours(h))
+            .collect();
+        let checkpoint_id = Uuid::new_v4();
+        let (params, horizon) = build_insert_params_at_times(
+            checkpoint_id,
+            json,
+            &times,
+            ForecastResolution::SixHourly,
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert_eq!(params.len(), 12);
+        assert_eq!(horizon, start + Duration::hours(13));
+        for (p, t) in params.iter().zip(&times) {
+            let p = p.as_ref().expect("time within the cached timeseries");
+            assert_eq!(p.checkpoint_id, checkpoint_id);
+            assert_eq!(p.forecast_time, *t);
+        }
+    }
+
+    #[test]
+    fn test_haversine_enrichment_vasaloppet() {
+        let gpx = include_str!("../../../data/vasaloppet-2026.gpx");
+        let race = crate::services::gpx::parse_gpx(gpx, Default::default()).unwrap();
+        let mut checkpoints: Vec<PacingCheckpoint> = race
+            .checkpoints
+            .iter()
+            .map(|cp| PacingCheckpoint {
+                distance_km: cp.distance_km,
+                elevation_m: cp.elevation_m,
+                latitude: cp.latitude,
+                longitude: cp.longitude,
+            })
+            .collect();
+        let track = crate::services::gpx::extract_track_points(gpx).unwrap();
+        let lats: Vec<f64> = track.iter().map(|p| p.lat).collect();
+        let lons: Vec<f64> = track.iter().map(|p| p.lon).collect();
+        assert_eq!(checkpoints.last().unwrap().distance_km, 90.0);
+
+        enrich_pacing_checkpoints_with_haversine(&mut checkpoints, &lats, &lons);
+
+        assert_eq!(checkpoints[0].distance_km, 0.0);
+        // Measured along the track, in course order
+        for i in 1..checkpoints.len() {
+            assert!(checkpoints[i].distance_km > checkpoints[i - 1].distance_km);
+        }
+        let total = checkpoints.last().unwrap().distance_km;
+        assert!((total - 90.0).abs() <= 90.0 * 0.02, "total = {}", total);
+    }
+
+    #[test]
+    fn test_haversine_enrichment_loop_course() {
+        // Out along the equator and back: 0.1° of longitude is ~11.1 km
+        let lons = [0.0, 0.05, 0.1, 0.05, 0.0];
+        let lats = [0.0; 5];
+        let at = |lon: f64| PacingCheckpoint {
+            distance_km: -1.0,
+            longitude: lon,
+            ..Default::default()
+        };
+        // Start, turnaround, back at the midpoint, finish at the start
+        let mut checkpoints = vec![at(0.0), at(0.1), at(0.05), at(0.0)];
+
+        enrich_pacing_checkpoints_with_haversine(&mut checkpoints, &lats, &lons);
+
+        let distances: Vec<f64> = checkpoints.iter().map(|c| c.distance_km).collect();
+        let leg = haversine_distance_km(0.0, 0.0, 0.0, 0.05);
+        for (measured, legs) in distances.iter().zip([0.0, 2.0, 3.0, 4.0]) {
+            assert!((measured - leg * legs).abs() < 1e-9, "{:?}", distances);
+        }
+
+        // Without track coordinates the declared distances stay
+        enrich_pacing_checkpoints_with_haversine(&mut checkpoints[..1], &[], &[]);
+        assert_eq!(checkpoints[0].distance_km, 0.0);
+    }
+
     // --- calculate_pass_time_fractions edge cases ---
 
     #[test]
@@ -2062,13 +3558,15 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 300.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 160.0,
+                ..Default::default()
             },
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 2);
         assert!((fractions[0] - 0.0).abs() < 1e-10);
         assert!((fractions[1] - 1.0).abs() < 1e-10);
@@ -2081,21 +3579,25 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 300.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 45.0,
                 elevation_m: 500.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 45.0,
                 elevation_m: 500.0,
+                ..Default::default()
             }, // duplicate distance
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 160.0,
+                ..Default::default()
             },
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 4);
         assert!((fractions[0] - 0.0).abs() < 1e-10);
         assert!((fractions[3] - 1.0).abs() < 1e-10);
@@ -2122,17 +3624,20 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 200.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 300.0,
+                ..Default::default()
             },
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 3);
         // Falls back to evenly spaced: 0.0, 0.5, 1.0
         assert!((fractions[0] - 0.0).abs() < 1e-10);
@@ -2148,17 +3653,20 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 1000.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 1.0,
                 elevation_m: 0.0,
+                ..Default::default()
             }, // steep downhill
             PacingCheckpoint {
                 distance_km: 2.0,
                 elevation_m: 0.0,
+                ..Default::default()
             }, // flat
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 3);
         assert!((fractions[0] - 0.0).abs() < 1e-10);
         assert!((fractions[2] - 1.0).abs() < 1e-10);
@@ -2189,17 +3697,20 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 0.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 1.0,
                 elevation_m: 500.0,
+                ..Default::default()
             }, // steep uphill
             PacingCheckpoint {
                 distance_km: 2.0,
                 elevation_m: 500.0,
+                ..Default::default()
             }, // flat
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 3);
 
         // Uphill cost = 7.0*1km=7.0, flat cost = 1.0*1km=1.0, total = 8.0
@@ -2217,7 +3728,7 @@ mod tests {
     fn test_snow_temp_overcast_windy() {
         // 100% cloud, 5 m/s wind → minimal offset, snow ≈ air temp
         // T_base = min(-5, -5) = -5, offset = 0 (cloud_factor=0), T_snow = -5.0
-        let result = calculate_snow_temperature(-5.0, -5.0, 100.0, 5.0);
+        let result = calculate_snow_temperature(-5.0, -5.0, 100.0, None, 5.0);
         assert!(
             (result - (-5.0)).abs() < 0.01,
             "Overcast + windy: snow temp should ≈ air temp, got {}",
@@ -2229,7 +3740,7 @@ mod tests {
     fn test_snow_temp_clear_calm() {
         // 0% cloud, 0 m/s wind → maximum offset of 3°C
         // T_base = min(-5, -5) = -5, offset = 3.0, T_snow = -8.0
-        let result = calculate_snow_temperature(-5.0, -5.0, 0.0, 0.0);
+        let result = calculate_snow_temperature(-5.0, -5.0, 0.0, None, 0.0);
         assert!(
             (result - (-8.0)).abs() < 0.01,
             "Clear + calm: snow temp should be T_base - 3, got {}",
@@ -2241,7 +3752,7 @@ mod tests {
     fn test_snow_temp_clear_windy() {
         // 0% cloud, 10 m/s wind → wind damps the offset
         // T_base = min(-5, -5) = -5, offset = 1.0 * 3.0 * 1/(1+10/5) = 3.0 * 1/3 = 1.0
-        let result = calculate_snow_temperature(-5.0, -5.0, 0.0, 10.0);
+        let result = calculate_snow_temperature(-5.0, -5.0, 0.0, None, 10.0);
         let expected = -5.0 - 1.0;
         assert!(
             (result - expected).abs() < 0.01,
@@ -2254,7 +3765,7 @@ mod tests {
     #[test]
     fn test_snow_temp_warm_air_clamped() {
         // Air temp 5°C, dew point 5°C → result clamped to 0°C
-        let result = calculate_snow_temperature(5.0, 5.0, 50.0, 2.0);
+        let result = calculate_snow_temperature(5.0, 5.0, 50.0, None, 2.0);
         assert!(
             (result - 0.0).abs() < 0.01,
             "Warm air: snow temp should be clamped to 0, got {}",
@@ -2265,7 +3776,7 @@ mod tests {
     #[test]
     fn test_snow_temp_very_cold() {
         // -20°C, clear, calm → T_base - 3.0 = -23°C
-        let result = calculate_snow_temperature(-20.0, -20.0, 0.0, 0.0);
+        let result = calculate_snow_temperature(-20.0, -20.0, 0.0, None, 0.0);
         assert!(
             (result - (-23.0)).abs() < 0.01,
             "Very cold + clear + calm: expected -23, got {}",
@@ -2276,7 +3787,7 @@ mod tests {
     #[test]
     fn test_snow_temp_partial_cloud() {
         // -10°C, 50% cloud, 0 m/s wind → offset = 0.5 * 3.0 * 1.0 = 1.5
-        let result = calculate_snow_temperature(-10.0, -10.0, 50.0, 0.0);
+        let result = calculate_snow_temperature(-10.0, -10.0, 50.0, None, 0.0);
         assert!(
             (result - (-11.5)).abs() < 0.01,
             "Partial cloud: expected -11.5, got {}",
@@ -2289,7 +3800,7 @@ mod tests {
         // T_air = -5°C, T_dew = -10°C (dry air → lower dew point → colder base)
         // T_base = min(-5, -10) = -10, offset = 0.5 * 3.0 * 1/(1+2/5) = 1.5 * 1/1.4 ≈ 1.0714
         // T_snow = -10 - 1.0714 ≈ -11.07
-        let result = calculate_snow_temperature(-5.0, -10.0, 50.0, 2.0);
+        let result = calculate_snow_temperature(-5.0, -10.0, 50.0, None, 2.0);
         let expected = -10.0 - (0.5 * 3.0 / 1.4);
         assert!(
             (result - expected).abs() < 0.01,
@@ -2299,11 +3810,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_snow_temp_low_cloud_blocks_more_than_high_cloud() {
+        // Same 60% total cover: all-low vs. all-high
+        let all_low = calculate_snow_temperature(-10.0, -10.0, 60.0, Some(60.0), 0.0);
+        let all_high = calculate_snow_temperature(-10.0, -10.0, 60.0, Some(0.0), 0.0);
+        // all low: effective 60% → offset 1.2; all high: effective 30% → offset 2.1
+        assert!((all_low - (-11.2)).abs() < 0.01, "got {}", all_low);
+        assert!((all_high - (-12.1)).abs() < 0.01, "got {}", all_high);
+        assert!(all_high < all_low, "High cloud should allow more cooling");
+    }
+
+    #[test]
+    fn test_snow_temp_without_low_cloud_matches_total_cover() {
+        let with_none = calculate_snow_temperature(-10.0, -10.0, 50.0, None, 0.0);
+        let with_full_low = calculate_snow_temperature(-10.0, -10.0, 50.0, Some(50.0), 0.0);
+        assert!((with_none - with_full_low).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_snow_temp_heat_balance() {
+        // 0.2 × 0.5 × -10 = -1.0; 50% cloud → longwave cooling 1.5
+        let result = calculate_snow_temperature_heat_balance(-10.0, 50.0, 5.0);
+        assert!((result - (-2.5)).abs() < 0.01, "got {}", result);
+        // Clamped to the melting point
+        assert_eq!(
+            calculate_snow_temperature_heat_balance(5.0, 100.0, 8.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_snow_temp_ensemble_between_models() {
+        for (temp, dew, cloud, wind, uv) in [
+            (-10.0, -12.0, 20.0, 1.0, 2.0),
+            (-3.0, -3.0, 80.0, 6.0, 5.0),
+            (-20.0, -22.0, 0.0, 0.0, 0.5),
+        ] {
+            let physics = calculate_snow_temperature(temp, dew, cloud, None, wind);
+            let heat_balance = calculate_snow_temperature_heat_balance(temp, cloud, uv);
+            let ensemble =
+                calculate_snow_temperature_ensemble(temp, dew, cloud, None, wind, Some(uv));
+            let (lo, hi) = (physics.min(heat_balance), physics.max(heat_balance));
+            assert!(
+                (lo..=hi).contains(&ensemble),
+                "ensemble {} not between {} and {}",
+                ensemble,
+                physics,
+                heat_balance
+            );
+            assert!((ensemble - (0.6 * physics + 0.4 * heat_balance)).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_snow_temp_ensemble_without_uv_is_physics_model() {
+        let physics = calculate_snow_temperature(-8.0, -9.0, 40.0, Some(10.0), 3.0);
+        let ensemble = calculate_snow_temperature_ensemble(-8.0, -9.0, 40.0, Some(10.0), 3.0, None);
+        assert_eq!(ensemble, physics);
+    }
+
     // --- compute_pacing_profile tests ---
 
     #[test]
     fn test_pacing_profile_empty_track() {
-        let profile = compute_pacing_profile(&[], 500);
+        let profile = compute_pacing_profile(&[], 500, &PacingParams::default());
         assert!(profile.is_empty());
     }
 
@@ -2313,7 +3884,7 @@ mod tests {
             distance_km: 0.0,
             elevation_m: 100.0,
         }];
-        let profile = compute_pacing_profile(&track, 500);
+        let profile = compute_pacing_profile(&track, 500, &PacingParams::default());
         assert_eq!(profile.len(), 1);
         assert!((profile[0].0 - 0.0).abs() < 1e-10);
         assert!((profile[0].1 - 0.0).abs() < 1e-10);
@@ -2323,7 +3894,7 @@ mod tests {
     fn test_pacing_profile_flat_track_even_fractions() {
         // Flat track → fractions should be proportional to distance
         let track = make_linear_track(0.0, 100.0, 90.0, 100.0, 10);
-        let profile = compute_pacing_profile(&track, 500);
+        let profile = compute_pacing_profile(&track, 500, &PacingParams::default());
 
         assert_eq!(profile.len(), 11);
         assert!((profile[0].0 - 0.0).abs() < 1e-10);
@@ -2349,7 +3920,7 @@ mod tests {
         // Uphill track → time fraction should be ahead of distance fraction
         // (i.e., at 50% distance you've used >50% of time because uphill is costly)
         let track = make_linear_track(0.0, 100.0, 90.0, 600.0, 20);
-        let profile = compute_pacing_profile(&track, 500);
+        let profile = compute_pacing_profile(&track, 500, &PacingParams::default());
 
         assert_eq!(profile.len(), 21);
         assert!((profile[0].1 - 0.0).abs() < 1e-10);
@@ -2395,7 +3966,7 @@ mod tests {
             });
         }
 
-        let profile = compute_pacing_profile(&track, 500);
+        let profile = compute_pacing_profile(&track, 500, &PacingParams::default());
         assert_eq!(profile.len(), 101);
         assert!((profile[0].1 - 0.0).abs() < 1e-10);
         assert!((profile[100].1 - 1.0).abs() < 1e-6);
@@ -2422,7 +3993,7 @@ mod tests {
         let track = make_linear_track(0.0, 100.0, 90.0, 100.0, 1000);
         assert_eq!(track.len(), 1001);
 
-        let profile = compute_pacing_profile(&track, 100);
+        let profile = compute_pacing_profile(&track, 100, &PacingParams::default());
         assert_eq!(profile.len(), 100);
 
         // First and last should be correct
@@ -2435,7 +4006,7 @@ mod tests {
     #[test]
     fn test_pacing_profile_no_downsampling_when_under_limit() {
         let track = make_linear_track(0.0, 100.0, 90.0, 100.0, 10);
-        let profile = compute_pacing_profile(&track, 500);
+        let profile = compute_pacing_profile(&track, 500, &PacingParams::default());
         // 11 points < 500 max → no downsampling
         assert_eq!(profile.len(), 11);
     }
@@ -2448,7 +4019,7 @@ mod tests {
         let course_points = extract_track_points(gpx).unwrap();
         let track = compute_track_profile(&course_points);
 
-        let profile = compute_pacing_profile(&track, 500);
+        let profile = compute_pacing_profile(&track, 500, &PacingParams::default());
 
         // Track has fewer points than max_points, so no downsampling
         assert_eq!(profile.len(), track.len());
@@ -2547,7 +4118,7 @@ mod tests {
                 elevation_m: 300.0,
             },
         ];
-        let profile = compute_pacing_profile(&track, 500);
+        let profile = compute_pacing_profile(&track, 500, &PacingParams::default());
 
         // Verify interpolation at exact track point distances returns exact values
         for &(dist, frac) in &profile {
@@ -2569,21 +4140,25 @@ mod tests {
             PacingCheckpoint {
                 distance_km: 0.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 50.0,
                 elevation_m: 200.0,
+                ..Default::default()
             },
             PacingCheckpoint {
                 distance_km: 30.0,
                 elevation_m: 150.0,
+                ..Default::default()
             }, // backwards!
             PacingCheckpoint {
                 distance_km: 90.0,
                 elevation_m: 100.0,
+                ..Default::default()
             },
         ];
-        let fractions = calculate_pass_time_fractions(&checkpoints);
+        let fractions = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
         assert_eq!(fractions.len(), 4);
         assert!((fractions[0] - 0.0).abs() < 1e-10);
         assert!((fractions[3] - 1.0).abs() < 1e-10);
@@ -2593,4 +4168,540 @@ mod tests {
             "Negative-distance segment should have zero cost"
         );
     }
+
+    #[test]
+    fn test_wind_rose_sectors() {
+        assert_eq!(wind_rose_sector(0.0), 0);
+        assert_eq!(wind_rose_sector(350.0), 0);
+        assert_eq!(wind_rose_sector(11.24), 0);
+        assert_eq!(wind_rose_sector(11.25), 1);
+        assert_eq!(wind_rose_sector(90.0), 4);
+        assert_eq!(wind_rose_sector(360.0), 0);
+        assert_eq!(WIND_ROSE_LABELS[wind_rose_sector(225.0)], "SW");
+
+        let wind = |direction: i64, speed: &str| {
+            let mut f = make_resolved("-5.0", speed, "0.0", "none")
+                .forecast
+                .unwrap();
+            f.wind_direction_deg = Decimal::from(direction);
+            f
+        };
+        let rose = compute_wind_rose(&[wind(355, "2.0"), wind(5, "4.0"), wind(180, "6.5")]);
+        assert_eq!(rose.len(), 16);
+        assert_eq!(rose[0].count, 2);
+        assert!((rose[0].mean_speed_ms - 3.0).abs() < 1e-10);
+        assert!((rose[0].max_speed_ms - 4.0).abs() < 1e-10);
+        assert_eq!(rose[8].count, 1);
+        assert_eq!(rose[4], WindSector::default());
+        assert_eq!(rose.iter().map(|s| s.count).sum::<u32>(), 3);
+    }
+
+    #[test]
+    fn test_difficulty_no_forecasts_is_neutral() {
+        let unavailable = ResolvedForecast {
+            forecast: None,
+            is_stale: false,
+            forecast_horizon: None,
+        };
+        assert_eq!(rate_race_difficulty(&[]), 5.0);
+        assert_eq!(rate_race_difficulty(&[unavailable]), 5.0);
+    }
+
+    #[test]
+    fn test_difficulty_calm_dry_freezing() {
+        // 0°C feels-like, no wind, no precipitation → neutral
+        let r = rate_race_difficulty(&[make_resolved("0.0", "0.0", "0.0", "none")]);
+        assert!((r - 5.0).abs() < 1e-10);
+        assert_eq!(difficulty_label(r), "hard");
+    }
+
+    #[test]
+    fn test_difficulty_warm_is_easier() {
+        // +6°C feels-like → 5.0 − 3.0 = 2.0
+        let r = rate_race_difficulty(&[make_resolved("6.0", "0.0", "0.0", "none")]);
+        assert!((r - 2.0).abs() < 1e-10);
+        assert_eq!(difficulty_label(r), "easy");
+    }
+
+    #[test]
+    fn test_difficulty_wind_and_snow() {
+        // −5°C, avg wind 5 m/s (+1.5), precipitation (+1.0), snow (+2.0) → 9.5
+        let r = rate_race_difficulty(&[
+            make_resolved("-5.0", "4.0", "0.5", "snow"),
+            make_resolved("-5.0", "6.0", "0.0", "none"),
+        ]);
+        assert!((r - 9.5).abs() < 1e-10, "got {}", r);
+        assert_eq!(difficulty_label(r), "extreme");
+    }
+
+    #[test]
+    fn test_difficulty_all_very_cold_reduction() {
+        // All below −10°C feels-like → −0.5
+        let r = rate_race_difficulty(&[
+            make_resolved("-12.0", "0.0", "0.0", "none"),
+            make_resolved("-15.0", "0.0", "0.0", "none"),
+        ]);
+        assert!((r - 4.5).abs() < 1e-10);
+        assert_eq!(difficulty_label(r), "moderate");
+    }
+
+    #[test]
+    fn test_difficulty_clamped_to_range() {
+        let r = rate_race_difficulty(&[make_resolved("-5.0", "30.0", "5.0", "snow")]);
+        assert_eq!(r, 10.0);
+        let r = rate_race_difficulty(&[make_resolved("25.0", "0.0", "0.0", "none")]);
+        assert_eq!(r, 0.0);
+    }
+
+    fn make_uv_checkpoint(hours_after_start: i64) -> CheckpointWithTime {
+        CheckpointWithTime {
+            checkpoint: Checkpoint::fixture(&format!("CP{}", hours_after_start)),
+            forecast_time: Utc::now() + chrono::Duration::hours(hours_after_start),
+        }
+    }
+
+    fn make_uv_resolved(uv_index: Option<&str>) -> ResolvedForecast {
+        let mut r = make_resolved("0.0", "0.0", "0.0", "none");
+        if let Some(f) = r.forecast.as_mut() {
+            f.uv_index = uv_index.map(|u| Decimal::from_str(u).unwrap());
+        }
+        r
+    }
+
+    #[test]
+    fn test_uv_exposure_weights_segments_by_duration() {
+        // 0→2h at avg UV 3.0, 2→3h at avg UV 5.0 → 6 + 5 = 11 UV-hours
+        let cps = [
+            make_uv_checkpoint(0),
+            make_uv_checkpoint(2),
+            make_uv_checkpoint(3),
+        ];
+        let fcs = [
+            make_uv_resolved(Some("2.0")),
+            make_uv_resolved(Some("4.0")),
+            make_uv_resolved(Some("6.0")),
+        ];
+        let report = calculate_uv_exposure(&cps, &fcs);
+        assert!((report.total_uv_exposure - 11.0).abs() < 1e-9);
+        assert_eq!(report.max_uv_index, 6.0);
+        assert_eq!(report.uv_risk_category, "high");
+        assert_eq!(report.recommendation, "SPF 30+ recommended");
+        assert_eq!(report.checkpoints_with_uv_data, 3);
+    }
+
+    #[test]
+    fn test_uv_exposure_missing_uv_uses_available_end() {
+        // Both segments have UV 2.0 at the middle checkpoint only → 2 + 2
+        let cps = [
+            make_uv_checkpoint(0),
+            make_uv_checkpoint(1),
+            make_uv_checkpoint(2),
+        ];
+        let fcs = [
+            make_uv_resolved(None),
+            make_uv_resolved(Some("2.0")),
+            ResolvedForecast {
+                forecast: None,
+                is_stale: false,
+                forecast_horizon: None,
+            },
+        ];
+        let report = calculate_uv_exposure(&cps, &fcs);
+        assert!((report.total_uv_exposure - 4.0).abs() < 1e-9);
+        assert_eq!(report.checkpoints_with_uv_data, 1);
+        assert_eq!(report.uv_risk_category, "low");
+    }
+
+    #[test]
+    fn test_accumulate_snowfall_weights_by_type_and_resolution() {
+        let mut six_hourly = make_resolved("-4.0", "0.0", "3.0", "snow");
+        if let Some(f) = six_hourly.forecast.as_mut() {
+            f.data_resolution = Some("six_hourly".to_string());
+        }
+        let slots = [
+            make_resolved("-2.0", "0.0", "1.2", "snow"),
+            make_resolved("1.0", "0.0", "2.0", "sleet"),
+            make_resolved("4.0", "0.0", "5.0", "rain"),
+            six_hourly,
+            ResolvedForecast {
+                forecast: None,
+                is_stale: false,
+                forecast_horizon: None,
+            },
+        ];
+        // 1.2 + 2.0 × 0.5 + 0 + 3.0 / 6
+        let acc = accumulate_snowfall(&slots);
+        assert!((acc.total_expected_snowfall_mm - 2.7).abs() < 1e-9);
+        assert!((acc.max_hourly_intensity_mm - 1.2).abs() < 1e-9);
+        assert_eq!(acc.hours_with_data, 4);
+
+        assert_eq!(accumulate_snowfall(&[]).total_expected_snowfall_mm, 0.0);
+    }
+
+    #[test]
+    fn test_uv_risk_category_boundaries() {
+        assert_eq!(uv_risk_category(0.0), "low");
+        assert_eq!(uv_risk_category(3.0), "moderate");
+        assert_eq!(uv_risk_category(6.0), "high");
+        assert_eq!(uv_risk_category(8.0), "very high");
+    }
+
+    #[test]
+    fn test_floor_to_hour() {
+        let dt = "2026-03-01T07:45:30Z".parse::<DateTime<Utc>>().unwrap();
+        let floored = floor_to_hour(dt);
+        assert_eq!(
+            floored,
+            "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_floor_to_hour_exact() {
+        let dt = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let floored = floor_to_hour(dt);
+        assert_eq!(floored, dt);
+    }
+
+    #[test]
+    fn test_ceil_to_hour() {
+        let dt = "2026-03-01T07:00:01Z".parse::<DateTime<Utc>>().unwrap();
+        let ceiled = ceil_to_hour(dt);
+        assert_eq!(
+            ceiled,
+            "2026-03-01T08:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ceil_to_hour_exact() {
+        let dt = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let ceiled = ceil_to_hour(dt);
+        assert_eq!(ceiled, dt, "Exact hour should not be rounded up");
+    }
+
+    #[test]
+    fn test_hourly_slots_floor_and_ceil() {
+        let from = "2026-03-01T07:15:00Z".parse::<DateTime<Utc>>().unwrap();
+        let to = "2026-03-01T09:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        let slots = hourly_slots(from, to);
+        assert_eq!(slots.len(), 4); // 07, 08, 09, 10
+        assert_eq!(slots[0], floor_to_hour(from));
+        assert_eq!(slots[3], ceil_to_hour(to));
+    }
+
+    #[test]
+    fn test_timeline_slots_segments_and_nearest() {
+        // Checkpoints pass at +0h, +1h, +4h from a 08:00 start
+        let mut cps = [
+            make_uv_checkpoint(0),
+            make_uv_checkpoint(1),
+            make_uv_checkpoint(4),
+        ];
+        let start = "2026-03-01T08:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        for (cp, h) in cps.iter_mut().zip([0, 1, 4]) {
+            cp.forecast_time = start + chrono::Duration::hours(h);
+        }
+
+        let slots = compute_timeline_slots(&cps);
+        assert_eq!(slots.len(), 5); // 08..=12
+        assert_eq!(
+            (
+                slots[0].segment_start,
+                slots[0].segment_end,
+                slots[0].nearest_checkpoint
+            ),
+            (0, 1, 0)
+        );
+        // 10:00 is 1/3 into the 09→12 segment → nearer its start
+        assert_eq!(
+            (
+                slots[2].segment_start,
+                slots[2].segment_end,
+                slots[2].nearest_checkpoint
+            ),
+            (1, 2, 1)
+        );
+        // 11:00 is 2/3 in → nearer the finish
+        assert_eq!(slots[3].nearest_checkpoint, 2);
+        // Finish hour snaps to the last segment
+        assert_eq!(
+            (
+                slots[4].segment_start,
+                slots[4].segment_end,
+                slots[4].nearest_checkpoint
+            ),
+            (1, 2, 2)
+        );
+    }
+
+    #[test]
+    fn test_timeline_slots_capped() {
+        let cps = [make_uv_checkpoint(0), make_uv_checkpoint(100)];
+        assert_eq!(compute_timeline_slots(&cps).len(), MAX_TIMELINE_SLOTS);
+        assert!(compute_timeline_slots(&[]).is_empty());
+    }
+
+    fn dec(s: &str) -> Option<Decimal> {
+        Some(Decimal::from_str(s).unwrap())
+    }
+
+    #[test]
+    fn test_confidence_both_percentiles() {
+        assert_eq!(
+            assess_forecast_confidence(dec("-6.0"), dec("-4.5")),
+            Some("high")
+        );
+        assert_eq!(
+            assess_forecast_confidence(dec("-6.0"), dec("-4.0")),
+            Some("medium")
+        );
+        assert_eq!(
+            assess_forecast_confidence(dec("-6.0"), dec("-1.0")),
+            Some("medium")
+        );
+        assert_eq!(
+            assess_forecast_confidence(dec("-6.0"), dec("-0.5")),
+            Some("low")
+        );
+        assert_eq!(percentile_spread(dec("-6.0"), dec("-0.5")), Some(5.5));
+    }
+
+    #[test]
+    fn test_confidence_missing_p10() {
+        assert_eq!(assess_forecast_confidence(None, dec("-4.0")), None);
+        assert_eq!(percentile_spread(None, dec("-4.0")), None);
+    }
+
+    #[test]
+    fn test_confidence_missing_p90() {
+        assert_eq!(assess_forecast_confidence(dec("-6.0"), None), None);
+        assert_eq!(percentile_spread(dec("-6.0"), None), None);
+    }
+
+    #[test]
+    fn test_confidence_no_percentiles() {
+        assert_eq!(assess_forecast_confidence(None, None), None);
+        assert_eq!(percentile_spread(None, None), None);
+    }
+
+    fn range_checkpoints() -> Vec<Checkpoint> {
+        (0..4)
+            .map(|i| Checkpoint {
+                sort_order: i,
+                ..make_uv_checkpoint(i as i64).checkpoint
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_checkpoint_range_defaults_to_full_course() {
+        let cps = range_checkpoints();
+        assert_eq!(checkpoint_range(&cps, None, None).unwrap(), (0, 3));
+        assert_eq!(
+            checkpoint_range(&cps, Some(cps[1].id), None).unwrap(),
+            (1, 3)
+        );
+        assert_eq!(
+            checkpoint_range(&cps, None, Some(cps[2].id)).unwrap(),
+            (0, 2)
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_range_rejects_invalid() {
+        let cps = range_checkpoints();
+        assert!(checkpoint_range(&cps, Some(cps[3].id), Some(cps[1].id)).is_err());
+        assert!(checkpoint_range(&cps, Some(Uuid::new_v4()), None).is_err());
+        assert!(checkpoint_range(&cps, None, Some(Uuid::new_v4())).is_err());
+    }
+
+    fn three_checkpoint_course() -> Vec<Checkpoint> {
+        [(0.0, 0, "Start"), (30.0, 1, "Middle"), (60.0, 2, "Finish")]
+            .into_iter()
+            .map(|(distance_km, sort_order, name)| Checkpoint {
+                name: name.to_string(),
+                distance_km: Decimal::try_from(distance_km).unwrap(),
+                sort_order,
+                ..make_uv_checkpoint(0).checkpoint
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_start_at_second_checkpoint_excludes_first() {
+        let mut cps = three_checkpoint_course();
+        let start = start_at_checkpoint_index(&cps, cps[1].id).unwrap();
+        assert_eq!(start, 1);
+        cps.drain(..start);
+        assert_eq!(cps.len(), 2);
+        assert_eq!(cps[0].name, "Middle");
+
+        let inputs: Vec<PacingCheckpoint> = cps
+            .iter()
+            .map(|cp| PacingCheckpoint {
+                distance_km: dec_to_f64(cp.distance_km),
+                ..Default::default()
+            })
+            .collect();
+        let fractions = calculate_pass_time_fractions(&inputs, &PacingParams::default());
+        assert_eq!(fractions, vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_start_at_checkpoint_rejects_invalid() {
+        let cps = three_checkpoint_course();
+        // The race start and checkpoints of other races are rejected
+        assert!(start_at_checkpoint_index(&cps, cps[0].id).is_err());
+        assert!(start_at_checkpoint_index(&cps, Uuid::new_v4()).is_err());
+    }
+
+    #[test]
+    fn test_rebase_fractions() {
+        assert_eq!(
+            rebase_fractions(&[0.25, 0.5, 1.0]),
+            vec![0.0, 1.0 / 3.0, 1.0]
+        );
+        assert_eq!(rebase_fractions(&[0.0, 0.4, 1.0]), vec![0.0, 0.4, 1.0]);
+        assert_eq!(rebase_fractions(&[0.5]), vec![0.5]);
+        assert!(rebase_fractions(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_partial_pass_times_rescaled_to_leg() {
+        let start = "2026-03-01T08:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let fractions = [0.0, 0.25, 0.5, 1.0];
+        // Leg from checkpoint 1 to 2 (a quarter of the race) in 2h:
+        // leg starts after 2h × 0.25 / 0.25 = 2h, ends 2h later
+        let times = partial_pass_times(&fractions, 1, 2, start, 2.0);
+        assert_eq!(times.len(), 2);
+        assert_eq!(times[0], start + chrono::Duration::hours(2));
+        assert_eq!(times[1], start + chrono::Duration::hours(4));
+
+        // Full range is identical to regular pacing
+        let full = partial_pass_times(&fractions, 0, 3, start, 8.0);
+        for (t, f) in full.iter().zip(fractions) {
+            assert_eq!(*t, calculate_pass_time_weighted(start, f, 8.0));
+        }
+    }
+
+    /// Lapse-rate sample at `elevation_m`; temperatures None when missing.
+    fn lapse_sample(
+        elevation_m: f64,
+        temp: Option<f64>,
+        start_temp: Option<f64>,
+    ) -> LapseRateSample {
+        let d = |v: f64| Decimal::try_from(v).unwrap();
+        LapseRateSample {
+            elevation_m: d(elevation_m),
+            temperature_c: temp.map(d),
+            start_temperature_c: start_temp.map(d),
+        }
+    }
+
+    #[test]
+    fn test_check_temperature_lapse_consistency() {
+        // Start at 349 m, 500 m checkpoint ~1 °C colder: consistent
+        let consistent = [
+            lapse_sample(349.0, Some(-5.0), Some(-5.0)),
+            lapse_sample(500.0, Some(-6.0), Some(-5.0)),
+        ];
+        assert!(check_temperature_lapse_consistency(&consistent).is_none());
+
+        // Higher checkpoint 4 °C warmer than the start: inversion
+        let inversion = [
+            lapse_sample(349.0, Some(-12.0), Some(-12.0)),
+            lapse_sample(500.0, Some(-8.0), Some(-12.0)),
+            lapse_sample(420.0, Some(-11.0), Some(-12.0)),
+        ];
+        let warning = check_temperature_lapse_consistency(&inversion).unwrap();
+        assert_eq!(warning.checkpoint_index, 1);
+        assert!((warning.elevation_diff_m - 151.0).abs() < 1e-9);
+        assert!((warning.expected_temp_diff_c - -0.9815).abs() < 1e-9);
+        assert!((warning.actual_temp_diff_c - 4.0).abs() < 1e-9);
+
+        assert!(check_temperature_lapse_consistency(&[]).is_none());
+        assert!(check_temperature_lapse_consistency(&[lapse_sample(
+            349.0,
+            Some(-5.0),
+            Some(-5.0)
+        )])
+        .is_none());
+    }
+
+    #[test]
+    fn test_lapse_rate_compares_with_start_at_the_same_time() {
+        // The day warms by 6 °C between the start and the later pass time;
+        // at that time the start is just as warm, so there is no inversion
+        let diurnal = [
+            lapse_sample(349.0, Some(-12.0), Some(-12.0)),
+            lapse_sample(500.0, Some(-7.0), Some(-6.0)),
+        ];
+        assert!(check_temperature_lapse_consistency(&diurnal).is_none());
+    }
+
+    #[test]
+    fn test_lapse_rate_missing_forecasts() {
+        // Without the start's forecast there is nothing to compare with,
+        // even though the next checkpoint would look like an inversion
+        let no_start = [
+            lapse_sample(349.0, None, None),
+            lapse_sample(500.0, Some(-8.0), Some(-12.0)),
+            lapse_sample(420.0, Some(-11.0), Some(-12.0)),
+        ];
+        assert!(check_temperature_lapse_consistency(&no_start).is_none());
+
+        // A checkpoint without a forecast is skipped but keeps its index
+        let gap = [
+            lapse_sample(349.0, Some(-12.0), Some(-12.0)),
+            lapse_sample(420.0, None, Some(-12.0)),
+            lapse_sample(500.0, Some(-8.0), Some(-12.0)),
+        ];
+        assert_eq!(
+            check_temperature_lapse_consistency(&gap)
+                .unwrap()
+                .checkpoint_index,
+            2
+        );
+    }
+
+    #[test]
+    fn test_difficulty_label_boundaries() {
+        assert_eq!(difficulty_label(0.0), "easy");
+        assert_eq!(difficulty_label(3.0), "moderate");
+        assert_eq!(difficulty_label(5.0), "hard");
+        assert_eq!(difficulty_label(8.0), "extreme");
+        assert_eq!(difficulty_label(10.0), "extreme");
+    }
+
+    #[test]
+    fn test_resolve_timings_slow_threshold() {
+        let timings = ResolveTimings {
+            checkpoint_count: 12,
+            yr_fetch_ms: 1500,
+            extraction_ms: 200,
+            insert_ms: 250,
+            requery_ms: 50,
+        };
+        assert_eq!(timings.total_ms(), 2000);
+        assert!(!timings.is_slow(2000));
+        assert!(timings.is_slow(1999));
+        assert!(!ResolveTimings::default().is_slow(0));
+    }
+
+    #[tokio::test]
+    async fn test_preheat_yr_caches_stops_on_shutdown() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let yr_client = YrClient::with_client(reqwest::Client::new(), "test", "http://localhost:9");
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+
+        // Returns before loading checkpoints or calling yr.no
+        tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            preheat_yr_caches(pool, yr_client, vec![Uuid::new_v4()], shutdown),
+        )
+        .await
+        .expect("preheat should stop when shutdown is cancelled");
+    }
 }
@@ -1,15 +1,20 @@
 //! GPX file parser for race and checkpoint data.
 //!
 //! Reads GPX files with Weather Bingo extensions (`wb:` namespace) to extract:
-//! - Race metadata: name, year, start_time, distance_km
-//! - Checkpoints: waypoints with `<type>checkpoint</type>` and `<wb:distance_km>`
+//! - Race metadata: name, year, start_time, distance_km, and optionally
+//!   country, website, discipline, category, elevation_gain_m
+//! - Checkpoints: waypoints with `<type>checkpoint</type>` (or
+//!   `<sym>Checkpoint</sym>`, see [`CheckpointIdentifierMode`]) and
+//!   `<wb:distance_km>`
 //! - Full GPX XML for storage in the database
 
 use chrono::{DateTime, FixedOffset};
 use quick_xml::events::Event;
 use quick_xml::Reader;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 use std::path::Path;
+use std::time::Duration;
 use thiserror::Error;
 use utoipa::ToSchema;
 
@@ -24,8 +29,169 @@ pub enum GpxError {
     MissingField(String),
     #[error("Invalid field value for '{field}': {message}")]
     InvalidValue { field: String, message: String },
+    #[error("HTTP error fetching GPX: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Invalid GPX URL '{url}': {message}")]
+    InvalidUrl { url: String, message: String },
+    /// Every field error found in [`GpxParseMode::CollectAll`] mode.
+    #[error("{} GPX errors: {}", .0.len(), join_errors(.0))]
+    Multiple(Vec<GpxError>),
 }
 
+fn join_errors(errors: &[GpxError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// How the parser reacts to missing or invalid fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpxParseMode {
+    /// Return the first field error (used when loading races).
+    FailFast,
+    /// Keep parsing and return all field errors as [`GpxError::Multiple`].
+    /// XML syntax errors still stop the parser.
+    CollectAll,
+}
+
+/// Which waypoint tags mark a checkpoint (`CHECKPOINT_IDENTIFIER_MODE`).
+///
+/// Some GPX authoring tools write `<sym>Checkpoint</sym>` instead of
+/// `<type>checkpoint</type>`. The `<sym>` match is case-insensitive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CheckpointIdentifierMode {
+    /// `<type>checkpoint</type>` only (default)
+    #[default]
+    TypeOnly,
+    /// `<sym>Checkpoint</sym>` only
+    SymOnly,
+    /// Either tag
+    Either,
+}
+
+impl CheckpointIdentifierMode {
+    /// Whether a waypoint with these `<type>` / `<sym>` values is a checkpoint.
+    fn is_checkpoint(self, wpt_type: Option<&str>, wpt_sym: Option<&str>) -> bool {
+        let by_type = wpt_type == Some("checkpoint");
+        let by_sym = wpt_sym.is_some_and(|s| s.eq_ignore_ascii_case("checkpoint"));
+        match self {
+            Self::TypeOnly => by_type,
+            Self::SymOnly => by_sym,
+            Self::Either => by_type || by_sym,
+        }
+    }
+}
+
+impl std::str::FromStr for CheckpointIdentifierMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "type_only" => Ok(Self::TypeOnly),
+            "sym_only" => Ok(Self::SymOnly),
+            "either" => Ok(Self::Either),
+            other => Err(format!(
+                "expected 'type_only', 'sym_only' or 'either', got '{}'",
+                other
+            )),
+        }
+    }
+}
+
+/// Field errors and warnings collected while parsing.
+#[derive(Debug)]
+struct GpxIssues {
+    mode: GpxParseMode,
+    errors: Vec<GpxError>,
+    warnings: Vec<GpxValidationIssue>,
+}
+
+impl GpxIssues {
+    fn new(mode: GpxParseMode) -> Self {
+        Self {
+            mode,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Fail with `error` in fail-fast mode, otherwise record it and continue.
+    fn record(&mut self, error: GpxError) -> Result<(), GpxError> {
+        match self.mode {
+            GpxParseMode::FailFast => Err(error),
+            GpxParseMode::CollectAll => {
+                self.errors.push(error);
+                Ok(())
+            }
+        }
+    }
+
+    /// Record a missing required field, unless its value was already
+    /// reported as invalid.
+    fn require<T>(&mut self, value: Option<T>, field: &str) -> Result<Option<T>, GpxError> {
+        let reported = self
+            .errors
+            .iter()
+            .any(|e| matches!(e, GpxError::InvalidValue { field: f, .. } if f == field));
+        if value.is_none() && !reported {
+            self.record(GpxError::MissingField(field.to_string()))?;
+        }
+        Ok(value)
+    }
+
+    fn warn(&mut self, field: &str, message: String) {
+        self.warnings.push(GpxValidationIssue {
+            field: field.to_string(),
+            message,
+        });
+    }
+
+    /// The collected errors as one [`GpxError::Multiple`].
+    fn take_error(&mut self) -> GpxError {
+        GpxError::Multiple(std::mem::take(&mut self.errors))
+    }
+}
+
+/// Checkpoint coordinates with fewer decimal places than this get a
+/// validation warning (4 places is ~11 m).
+const MIN_COORDINATE_DECIMALS: usize = 4;
+
+/// Plausible elevation range in metres (below the Dead Sea to above Everest).
+const ELEVATION_RANGE_M: RangeInclusive<f64> = -500.0..=9000.0;
+
+/// Check that a point's coordinates and elevation are physically plausible,
+/// e.g. to catch `lat="610.1"`. `point` names the point in the message.
+fn validate_coordinates(point: &str, lat: f64, lon: f64, ele: f64) -> Result<(), GpxError> {
+    let checks = [
+        ("lat", "latitude", lat, -90.0..=90.0),
+        ("lon", "longitude", lon, -180.0..=180.0),
+        ("ele", "elevation", ele, ELEVATION_RANGE_M),
+    ];
+    for (field, label, value, range) in checks {
+        if !range.contains(&value) {
+            return Err(GpxError::InvalidValue {
+                field: field.to_string(),
+                message: format!(
+                    "{} {} {} is outside [{}, {}]",
+                    point,
+                    label,
+                    value,
+                    range.start(),
+                    range.end()
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Timeout for fetching one GPX file from `RACE_DATA_URLS`.
+const URL_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+/// Delay before the single retry of a failed GPX URL fetch.
+const URL_FETCH_RETRY_DELAY: Duration = Duration::from_secs(5);
+
 /// Parsed race data from a GPX file.
 #[derive(Debug, Clone)]
 pub struct GpxRace {
@@ -41,6 +207,66 @@ pub struct GpxRace {
     pub checkpoints: Vec<GpxCheckpoint>,
     /// The full GPX XML content (for storage in DB)
     pub gpx_xml: String,
+    /// Haversine length of the `<trk>` in km, set by [`parse_gpx_file`].
+    /// `None` when the file has no track points.
+    pub computed_track_length_km: Option<f64>,
+    /// Host country from `<wb:country>` (optional)
+    pub country: Option<String>,
+    /// Race website from `<wb:website>` (optional)
+    pub website: Option<String>,
+    /// Ski technique from `<wb:discipline>`: "classic", "skate" or "freestyle" (optional)
+    pub discipline: Option<String>,
+    /// Race category from `<wb:category>`, lowercased, e.g. "classic",
+    /// "skate", "biathlon" or "pursuit" (optional)
+    pub category: Option<String>,
+    /// Total climb in metres from `<wb:elevation_gain_m>` (optional)
+    pub elevation_gain_m: Option<f64>,
+}
+
+impl GpxRace {
+    /// Whether a checkpoint sits at 0 km.
+    pub fn has_start_checkpoint(&self) -> bool {
+        self.checkpoints.iter().any(|cp| cp.distance_km == 0.0)
+    }
+
+    /// Whether a checkpoint sits at the declared `distance_km`.
+    pub fn has_finish_checkpoint(&self) -> bool {
+        self.checkpoints
+            .iter()
+            .any(|cp| cp.distance_km == self.distance_km)
+    }
+}
+
+/// Allowed values for `<wb:discipline>`.
+pub(crate) const DISCIPLINES: [&str; 3] = ["classic", "skate", "freestyle"];
+
+/// Longest `<wb:category>` (the `races.category` column width).
+const MAX_CATEGORY_LEN: usize = 50;
+
+/// Trimmed, lowercased race category, or an error message when it is empty
+/// or longer than [`MAX_CATEGORY_LEN`] characters.
+pub(crate) fn normalize_category(text: &str) -> Result<String, String> {
+    let category = text.trim().to_lowercase();
+    if category.is_empty() {
+        return Err("must not be empty".to_string());
+    }
+    if category.chars().count() > MAX_CATEGORY_LEN {
+        return Err(format!(
+            "at most {} characters: '{}'",
+            MAX_CATEGORY_LEN, text
+        ));
+    }
+    Ok(category)
+}
+
+/// Optional `<wb:race>` fields collected while parsing.
+#[derive(Debug, Default)]
+struct OptionalRaceFields {
+    country: Option<String>,
+    website: Option<String>,
+    discipline: Option<String>,
+    category: Option<String>,
+    elevation_gain_m: Option<f64>,
 }
 
 /// A checkpoint parsed from a GPX waypoint.
@@ -59,19 +285,89 @@ pub struct GpxCheckpoint {
 }
 
 /// Parse a GPX file from disk and extract race + checkpoint data.
-pub fn parse_gpx_file(path: &Path) -> Result<GpxRace, GpxError> {
+///
+/// Also measures the track and warns when it differs from the declared
+/// `<wb:distance_km>` by more than `distance_tolerance_km`.
+pub fn parse_gpx_file(
+    path: &Path,
+    distance_tolerance_km: f64,
+    identifier: CheckpointIdentifierMode,
+) -> Result<GpxRace, GpxError> {
     let gpx_xml = std::fs::read_to_string(path)?;
-    parse_gpx(&gpx_xml)
+    parse_gpx_measured(&gpx_xml, distance_tolerance_km, identifier)
+}
+
+/// Parse GPX XML and measure its track (see [`parse_gpx_file`]).
+fn parse_gpx_measured(
+    gpx_xml: &str,
+    distance_tolerance_km: f64,
+    identifier: CheckpointIdentifierMode,
+) -> Result<GpxRace, GpxError> {
+    let mut race = parse_gpx(gpx_xml, identifier)?;
+    race.computed_track_length_km = track_length_km(&extract_track_points(gpx_xml)?);
+    if let Some(deviation) = track_length_deviation_km(&race, distance_tolerance_km) {
+        tracing::warn!(
+            "Race '{}' ({}) declares {:.1} km but its track measures {:.1} km ({:+.1} km)",
+            race.name,
+            race.year,
+            race.distance_km,
+            race.computed_track_length_km.unwrap_or_default(),
+            deviation
+        );
+    }
+    if !race.has_start_checkpoint() {
+        tracing::warn!(
+            "Race '{}' ({}) has no start checkpoint at 0 km",
+            race.name,
+            race.year
+        );
+    }
+    if !race.has_finish_checkpoint() {
+        tracing::warn!(
+            "Race '{}' ({}) has no finish checkpoint at {} km",
+            race.name,
+            race.year,
+            race.distance_km
+        );
+    }
+    Ok(race)
+}
+
+/// Total track length: the cumulative distance of the last point.
+fn track_length_km(points: &[CoursePoint]) -> Option<f64> {
+    points.last().map(|p| p.distance_km)
+}
+
+/// Computed minus declared length, if it exceeds `tolerance_km` in either direction.
+fn track_length_deviation_km(race: &GpxRace, tolerance_km: f64) -> Option<f64> {
+    let deviation = race.computed_track_length_km? - race.distance_km;
+    (deviation.abs() > tolerance_km).then_some(deviation)
+}
+
+/// Parse GPX XML content and extract race + checkpoint data, recognising
+/// checkpoints by `identifier`.
+pub fn parse_gpx(gpx_xml: &str, identifier: CheckpointIdentifierMode) -> Result<GpxRace, GpxError> {
+    parse_gpx_with(
+        gpx_xml,
+        identifier,
+        &mut GpxIssues::new(GpxParseMode::FailFast),
+    )
 }
 
-/// Parse GPX XML content and extract race + checkpoint data.
-pub fn parse_gpx(gpx_xml: &str) -> Result<GpxRace, GpxError> {
+/// Parser shared by [`parse_gpx`] and [`validate_gpx`]. `issues` decides
+/// whether field errors stop parsing, and collects warnings.
+fn parse_gpx_with(
+    gpx_xml: &str,
+    identifier: CheckpointIdentifierMode,
+    issues: &mut GpxIssues,
+) -> Result<GpxRace, GpxError> {
     let mut reader = Reader::from_str(gpx_xml);
 
     let mut race_name: Option<String> = None;
     let mut race_year: Option<i32> = None;
     let mut race_start_time: Option<DateTime<FixedOffset>> = None;
     let mut race_distance_km: Option<f64> = None;
+    let mut race_optional = OptionalRaceFields::default();
 
     let mut checkpoints: Vec<GpxCheckpoint> = Vec::new();
 
@@ -79,9 +375,11 @@ pub fn parse_gpx(gpx_xml: &str) -> Result<GpxRace, GpxError> {
     let mut in_wpt = false;
     let mut wpt_lat: f64 = 0.0;
     let mut wpt_lon: f64 = 0.0;
+    let mut wpt_coord_decimals: usize = 0;
     let mut wpt_name: Option<String> = None;
     let mut wpt_ele: Option<f64> = None;
     let mut wpt_type: Option<String> = None;
+    let mut wpt_sym: Option<String> = None;
     let mut wpt_distance_km: Option<f64> = None;
 
     // Track nesting context
@@ -131,13 +429,29 @@ pub fn parse_gpx(gpx_xml: &str) -> Result<GpxRace, GpxError> {
                     "distance_km" if in_wb_race => {
                         current_element = Some("wb_distance_km".to_string());
                     }
+                    "country" if in_wb_race => {
+                        current_element = Some("wb_country".to_string());
+                    }
+                    "website" if in_wb_race => {
+                        current_element = Some("wb_website".to_string());
+                    }
+                    "discipline" if in_wb_race => {
+                        current_element = Some("wb_discipline".to_string());
+                    }
+                    "category" if in_wb_race => {
+                        current_element = Some("wb_category".to_string());
+                    }
+                    "elevation_gain_m" if in_wb_race => {
+                        current_element = Some("wb_elevation_gain_m".to_string());
+                    }
                     "wpt" => {
                         in_wpt = true;
                         wpt_name = None;
                         wpt_ele = None;
                         wpt_type = None;
+                        wpt_sym = None;
                         wpt_distance_km = None;
-                        parse_wpt_attrs(e, &mut wpt_lat, &mut wpt_lon);
+                        wpt_coord_decimals = parse_wpt_attrs(e, &mut wpt_lat, &mut wpt_lon);
                     }
                     "name" if in_wpt && !in_wpt_extensions => {
                         current_element = Some("wpt_name".to_string());
@@ -148,6 +462,9 @@ pub fn parse_gpx(gpx_xml: &str) -> Result<GpxRace, GpxError> {
                     "type" if in_wpt && !in_wpt_extensions => {
                         current_element = Some("wpt_type".to_string());
                     }
+                    "sym" if in_wpt && !in_wpt_extensions => {
+                        current_element = Some("wpt_sym".to_string());
+                    }
                     "distance_km" if in_wpt_extensions => {
                         current_element = Some("wpt_distance_km".to_string());
                     }
@@ -165,11 +482,14 @@ pub fn parse_gpx(gpx_xml: &str) -> Result<GpxRace, GpxError> {
                             &mut race_year,
                             &mut race_start_time,
                             &mut race_distance_km,
+                            &mut race_optional,
                             &mut wpt_name,
                             &mut wpt_ele,
                             &mut wpt_type,
+                            &mut wpt_sym,
                             &mut wpt_distance_km,
-                        )?;
+                        )
+                        .or_else(|e| issues.record(e))?;
                     }
                 }
             }
@@ -195,13 +515,15 @@ pub fn parse_gpx(gpx_xml: &str) -> Result<GpxRace, GpxError> {
                     }
                     "wpt" => {
                         finalize_waypoint(
-                            &mut wpt_type,
+                            identifier.is_checkpoint(wpt_type.as_deref(), wpt_sym.as_deref()),
                             &mut wpt_name,
                             wpt_lat,
                             wpt_lon,
                             wpt_ele,
                             wpt_distance_km,
+                            wpt_coord_decimals,
                             &mut checkpoints,
+                            issues,
                         )?;
                         in_wpt = false;
                     }
@@ -220,16 +542,24 @@ pub fn parse_gpx(gpx_xml: &str) -> Result<GpxRace, GpxError> {
         race_year,
         race_start_time,
         race_distance_km,
+        race_optional,
         checkpoints,
         gpx_xml,
+        issues,
     )
 }
 
 /// Parse `lat` and `lon` attributes from a `<wpt>` element.
-fn parse_wpt_attrs(e: &quick_xml::events::BytesStart<'_>, lat: &mut f64, lon: &mut f64) {
+///
+/// Returns the smaller number of decimal places of the two values.
+fn parse_wpt_attrs(e: &quick_xml::events::BytesStart<'_>, lat: &mut f64, lon: &mut f64) -> usize {
+    let mut decimals = usize::MAX;
     for attr in e.attributes().flatten() {
         let key = std::str::from_utf8(attr.key.as_ref()).unwrap_or("");
         let val = std::str::from_utf8(&attr.value).unwrap_or("");
+        if key == "lat" || key == "lon" {
+            decimals = decimals.min(val.split_once('.').map_or(0, |(_, frac)| frac.len()));
+        }
         match key {
             "lat" => {
                 *lat = val.parse().unwrap_or_else(|e| {
@@ -246,6 +576,11 @@ fn parse_wpt_attrs(e: &quick_xml::events::BytesStart<'_>, lat: &mut f64, lon: &m
             _ => {}
         }
     }
+    if decimals == usize::MAX {
+        0
+    } else {
+        decimals
+    }
 }
 
 /// Dispatch a captured text value to the appropriate metadata or waypoint field.
@@ -257,9 +592,11 @@ fn apply_text_value(
     race_year: &mut Option<i32>,
     race_start_time: &mut Option<DateTime<FixedOffset>>,
     race_distance_km: &mut Option<f64>,
+    race_optional: &mut OptionalRaceFields,
     wpt_name: &mut Option<String>,
     wpt_ele: &mut Option<f64>,
     wpt_type: &mut Option<String>,
+    wpt_sym: &mut Option<String>,
     wpt_distance_km: &mut Option<f64>,
 ) -> Result<(), GpxError> {
     match elem {
@@ -285,9 +622,38 @@ fn apply_text_value(
                 message: format!("not a valid number: '{}'", text),
             })?);
         }
+        "wb_country" => race_optional.country = Some(text.to_string()),
+        "wb_website" => race_optional.website = Some(text.to_string()),
+        "wb_discipline" => {
+            let discipline = text.to_lowercase();
+            if !DISCIPLINES.contains(&discipline.as_str()) {
+                return Err(GpxError::InvalidValue {
+                    field: "wb:discipline".to_string(),
+                    message: format!("expected one of {}: '{}'", DISCIPLINES.join(", "), text),
+                });
+            }
+            race_optional.discipline = Some(discipline);
+        }
+        "wb_category" => {
+            race_optional.category =
+                Some(
+                    normalize_category(text).map_err(|message| GpxError::InvalidValue {
+                        field: "wb:category".to_string(),
+                        message,
+                    })?,
+                );
+        }
+        "wb_elevation_gain_m" => {
+            race_optional.elevation_gain_m =
+                Some(text.parse().map_err(|_| GpxError::InvalidValue {
+                    field: "wb:elevation_gain_m".to_string(),
+                    message: format!("not a valid number: '{}'", text),
+                })?);
+        }
         "wpt_name" => *wpt_name = Some(text.to_string()),
         "wpt_ele" => *wpt_ele = Some(text.parse().unwrap_or(0.0)),
         "wpt_type" => *wpt_type = Some(text.to_string()),
+        "wpt_sym" => *wpt_sym = Some(text.to_string()),
         "wpt_distance_km" => {
             *wpt_distance_km = Some(text.parse().map_err(|_| GpxError::InvalidValue {
                 field: "wb:distance_km (waypoint)".to_string(),
@@ -300,22 +666,45 @@ fn apply_text_value(
 }
 
 /// If the completed waypoint is a checkpoint, validate and push it.
+#[allow(clippy::too_many_arguments)]
 fn finalize_waypoint(
-    wpt_type: &mut Option<String>,
+    is_checkpoint: bool,
     wpt_name: &mut Option<String>,
     wpt_lat: f64,
     wpt_lon: f64,
     wpt_ele: Option<f64>,
     wpt_distance_km: Option<f64>,
+    wpt_coord_decimals: usize,
     checkpoints: &mut Vec<GpxCheckpoint>,
+    issues: &mut GpxIssues,
 ) -> Result<(), GpxError> {
-    if wpt_type.as_deref() == Some("checkpoint") {
-        let name = wpt_name
-            .take()
-            .ok_or_else(|| GpxError::MissingField("waypoint <name> for checkpoint".to_string()))?;
-        let distance_km = wpt_distance_km.ok_or_else(|| {
-            GpxError::MissingField(format!("wb:distance_km for checkpoint '{}'", name))
-        })?;
+    if is_checkpoint {
+        let Some(name) = wpt_name.take() else {
+            return issues.record(GpxError::MissingField(
+                "waypoint <name> for checkpoint".to_string(),
+            ));
+        };
+        let Some(distance_km) = wpt_distance_km else {
+            return issues.record(GpxError::MissingField(format!(
+                "wb:distance_km for checkpoint '{}'",
+                name
+            )));
+        };
+        if wpt_ele.is_none() {
+            issues.warn(
+                "ele",
+                format!("checkpoint '{}' has no <ele>; assuming 0 m", name),
+            );
+        }
+        if wpt_coord_decimals < MIN_COORDINATE_DECIMALS {
+            issues.warn(
+                "lat/lon",
+                format!(
+                    "checkpoint '{}' coordinates have fewer than {} decimal places",
+                    name, MIN_COORDINATE_DECIMALS
+                ),
+            );
+        }
         checkpoints.push(GpxCheckpoint {
             name,
             latitude: wpt_lat,
@@ -328,25 +717,45 @@ fn finalize_waypoint(
 }
 
 /// Validate required fields and build the final `GpxRace`.
+#[allow(clippy::too_many_arguments)]
 fn build_gpx_race(
     race_name: Option<String>,
     race_year: Option<i32>,
     race_start_time: Option<DateTime<FixedOffset>>,
     race_distance_km: Option<f64>,
+    race_optional: OptionalRaceFields,
     checkpoints: Vec<GpxCheckpoint>,
     gpx_xml: &str,
+    issues: &mut GpxIssues,
 ) -> Result<GpxRace, GpxError> {
-    let name = race_name.ok_or_else(|| GpxError::MissingField("metadata/name".to_string()))?;
-    let year = race_year.ok_or_else(|| GpxError::MissingField("wb:year".to_string()))?;
-    let start_time =
-        race_start_time.ok_or_else(|| GpxError::MissingField("wb:start_time".to_string()))?;
-    let distance_km =
-        race_distance_km.ok_or_else(|| GpxError::MissingField("wb:distance_km".to_string()))?;
+    let name = issues.require(race_name, "metadata/name")?;
+    let year = issues.require(race_year, "wb:year")?;
+    let start_time = issues.require(race_start_time, "wb:start_time")?;
+    let distance_km = issues.require(race_distance_km, "wb:distance_km")?;
 
     if checkpoints.is_empty() {
-        return Err(GpxError::MissingField(
+        issues.record(GpxError::MissingField(
             "at least one waypoint with <type>checkpoint</type>".to_string(),
-        ));
+        ))?;
+    }
+    for cp in &checkpoints {
+        if let Err(e) = validate_coordinates(
+            &format!("checkpoint '{}'", cp.name),
+            cp.latitude,
+            cp.longitude,
+            cp.elevation_m,
+        ) {
+            issues.record(e)?;
+        }
+    }
+
+    let (Some(name), Some(year), Some(start_time), Some(distance_km)) =
+        (name, year, start_time, distance_km)
+    else {
+        return Err(issues.take_error());
+    };
+    if !issues.errors.is_empty() {
+        return Err(issues.take_error());
     }
 
     Ok(GpxRace {
@@ -356,9 +765,107 @@ fn build_gpx_race(
         distance_km,
         checkpoints,
         gpx_xml: gpx_xml.to_string(),
+        computed_track_length_km: None,
+        country: race_optional.country,
+        website: race_optional.website,
+        discipline: race_optional.discipline,
+        category: race_optional.category,
+        elevation_gain_m: race_optional.elevation_gain_m,
     })
 }
 
+/// One error or warning in a [`GpxValidationReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct GpxValidationIssue {
+    /// GPX element or field concerned, e.g. "wb:year"
+    pub field: String,
+    /// What is wrong
+    pub message: String,
+}
+
+impl From<GpxError> for GpxValidationIssue {
+    fn from(error: GpxError) -> Self {
+        let (field, message) = match error {
+            GpxError::MissingField(field) => (field, "missing required field".to_string()),
+            GpxError::InvalidValue { field, message } => (field, message),
+            GpxError::Xml(e) => ("xml".to_string(), e.to_string()),
+            other => ("gpx".to_string(), other.to_string()),
+        };
+        Self { field, message }
+    }
+}
+
+/// Summary of a GPX file that parsed successfully.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct GpxParsedSummary {
+    pub name: String,
+    pub year: i32,
+    pub checkpoints_count: usize,
+    /// Declared `<wb:distance_km>`
+    pub distance_km: f64,
+    /// Haversine length of the `<trk>`, if it has track points
+    pub computed_track_length_km: Option<f64>,
+}
+
+/// Result of [`validate_gpx`].
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct GpxValidationReport {
+    /// True when the file has no errors (warnings are allowed)
+    pub valid: bool,
+    pub errors: Vec<GpxValidationIssue>,
+    pub warnings: Vec<GpxValidationIssue>,
+    /// Parsed race; `null` when the file has errors
+    pub parsed: Option<GpxParsedSummary>,
+}
+
+/// Validate GPX XML without loading it, reporting every problem at once.
+///
+/// Errors are what [`parse_gpx`] would reject (collected with
+/// [`GpxParseMode::CollectAll`]). Warnings flag checkpoints without `<ele>`,
+/// checkpoint coordinates with fewer than 4 decimal places, and files
+/// without track points.
+pub fn validate_gpx(content: &str, identifier: CheckpointIdentifierMode) -> GpxValidationReport {
+    let mut issues = GpxIssues::new(GpxParseMode::CollectAll);
+    let result = parse_gpx_with(content, identifier, &mut issues);
+
+    // An XML error stops the parser, so earlier field errors are still here
+    let mut errors: Vec<GpxValidationIssue> = issues.errors.drain(..).map(Into::into).collect();
+    let race = match result {
+        Ok(race) => Some(race),
+        Err(GpxError::Multiple(all)) => {
+            errors.extend(all.into_iter().map(Into::into));
+            None
+        }
+        Err(e) => {
+            errors.push(e.into());
+            None
+        }
+    };
+
+    // XML errors in the track are already reported by the parser
+    let track_points = extract_track_points(content).unwrap_or_default();
+    if track_points.is_empty() {
+        issues.warn(
+            "trk",
+            "no track points; the course profile and track-aware pacing are unavailable"
+                .to_string(),
+        );
+    }
+
+    GpxValidationReport {
+        valid: errors.is_empty(),
+        errors,
+        warnings: issues.warnings,
+        parsed: race.map(|race| GpxParsedSummary {
+            name: race.name,
+            year: race.year,
+            checkpoints_count: race.checkpoints.len(),
+            distance_km: race.distance_km,
+            computed_track_length_km: track_length_km(&track_points),
+        }),
+    }
+}
+
 /// Earth's mean radius in kilometres (WGS84 volumetric mean).
 const EARTH_RADIUS_KM: f64 = 6371.0;
 
@@ -375,6 +882,70 @@ pub fn haversine_distance_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64
     EARTH_RADIUS_KM * c
 }
 
+/// Checkpoints within this distance of each other are treated as the same location.
+const ROUTE_OVERLAP_MATCH_KM: f64 = 0.1;
+
+/// Fraction of matching checkpoints above which a differently named race is
+/// flagged as a possible duplicate.
+const DUPLICATE_SIMILARITY_THRESHOLD: f64 = 0.9;
+
+/// Result of comparing a race's checkpoints against previously loaded races.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteOverlapReport {
+    /// The most similar existing race, e.g. "Vasaloppet (2025)"
+    pub route_similar_to: String,
+    /// Fraction of the new race's checkpoints within 100 m of one of its checkpoints
+    pub similarity: f64,
+    /// True when the similar race has a different name (likely a duplicate upload)
+    pub possible_duplicate: bool,
+}
+
+/// Compare a race's checkpoints against already loaded races.
+///
+/// Another year of the same race is reported when any checkpoint matches
+/// (within 100 m). A race with a different name is reported as a possible
+/// duplicate only when more than 90% of checkpoints match. Same name and
+/// year is an update of the same race and is ignored. Returns the most
+/// similar match, or `None`.
+pub fn detect_route_overlap(existing: &[GpxRace], new: &GpxRace) -> Option<RouteOverlapReport> {
+    existing
+        .iter()
+        .filter(|other| !(other.name == new.name && other.year == new.year))
+        .filter_map(|other| {
+            let similarity = checkpoint_similarity(&new.checkpoints, &other.checkpoints);
+            let same_name = other.name == new.name;
+            let relevant = if same_name {
+                similarity > 0.0
+            } else {
+                similarity > DUPLICATE_SIMILARITY_THRESHOLD
+            };
+            relevant.then(|| RouteOverlapReport {
+                route_similar_to: format!("{} ({})", other.name, other.year),
+                similarity,
+                possible_duplicate: !same_name,
+            })
+        })
+        .max_by(|a, b| a.similarity.total_cmp(&b.similarity))
+}
+
+/// Fraction of checkpoints in `a` that lie within [`ROUTE_OVERLAP_MATCH_KM`]
+/// of some checkpoint in `b`. 0.0 when `a` is empty.
+fn checkpoint_similarity(a: &[GpxCheckpoint], b: &[GpxCheckpoint]) -> f64 {
+    if a.is_empty() {
+        return 0.0;
+    }
+    let matched = a
+        .iter()
+        .filter(|cp| {
+            b.iter().any(|other| {
+                haversine_distance_km(cp.latitude, cp.longitude, other.latitude, other.longitude)
+                    <= ROUTE_OVERLAP_MATCH_KM
+            })
+        })
+        .count();
+    matched as f64 / a.len() as f64
+}
+
 /// A point along the race course with cumulative distance and elevation.
 ///
 /// Produced by [`compute_track_profile`] from raw [`CoursePoint`] data.
@@ -407,7 +978,10 @@ pub fn compute_track_profile(points: &[CoursePoint]) -> Vec<TrackPoint> {
     for i in 1..points.len() {
         let prev = &points[i - 1];
         let curr = &points[i];
-        cumulative += haversine_distance_km(prev.lat, prev.lon, curr.lat, curr.lon);
+        // Gaps between track segments are not part of the course
+        if prev.segment_index == curr.segment_index {
+            cumulative += haversine_distance_km(prev.lat, prev.lon, curr.lat, curr.lon);
+        }
         result.push(TrackPoint {
             distance_km: cumulative,
             elevation_m: curr.ele,
@@ -417,8 +991,95 @@ pub fn compute_track_profile(points: &[CoursePoint]) -> Vec<TrackPoint> {
     result
 }
 
+/// Points in the centred moving average applied before summing gain/loss.
+const ELEVATION_SMOOTHING_WINDOW: usize = 5;
+
+/// Horizontal stretch over which the steepest gradients are measured (km).
+const GRADIENT_STRETCH_KM: f64 = 0.1;
+
+/// Elevation statistics of a race course.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+pub struct ElevationStats {
+    /// Total climb in metres (smoothed track)
+    pub total_elevation_gain_m: f64,
+    /// Total descent in metres, as a positive number (smoothed track)
+    pub total_elevation_loss_m: f64,
+    /// Highest track point in metres
+    pub max_elevation_m: f64,
+    /// Lowest track point in metres
+    pub min_elevation_m: f64,
+    /// Steepest average climb over any 100 m horizontal stretch, in %
+    pub steepest_climb_gradient_pct: f64,
+    /// Steepest average descent over any 100 m horizontal stretch, in %
+    /// (positive number)
+    pub steepest_descent_gradient_pct: f64,
+}
+
+/// Compute elevation statistics from a course track.
+///
+/// Elevations are smoothed with a 5-point centred moving average to suppress
+/// GPS noise before gain, loss and gradients are computed; min/max use the
+/// raw elevations. Gradients are measured from each point to the first point
+/// at least 100 m further along, so courses shorter than that report 0.
+/// Returns all zeros for an empty track.
+pub fn compute_elevation_stats(points: &[CoursePoint]) -> ElevationStats {
+    if points.is_empty() {
+        return ElevationStats::default();
+    }
+    let smoothed = moving_average(points, ELEVATION_SMOOTHING_WINDOW);
+
+    let mut stats = ElevationStats {
+        max_elevation_m: f64::MIN,
+        min_elevation_m: f64::MAX,
+        ..ElevationStats::default()
+    };
+    for p in points {
+        stats.max_elevation_m = stats.max_elevation_m.max(p.ele);
+        stats.min_elevation_m = stats.min_elevation_m.min(p.ele);
+    }
+    for pair in smoothed.windows(2) {
+        let delta = pair[1] - pair[0];
+        if delta > 0.0 {
+            stats.total_elevation_gain_m += delta;
+        } else {
+            stats.total_elevation_loss_m -= delta;
+        }
+    }
+
+    let mut j = 0;
+    for i in 0..points.len() {
+        j = j.max(i + 1);
+        while j < points.len()
+            && points[j].distance_km - points[i].distance_km < GRADIENT_STRETCH_KM
+        {
+            j += 1;
+        }
+        if j == points.len() {
+            break;
+        }
+        let run_m = (points[j].distance_km - points[i].distance_km) * 1000.0;
+        let gradient_pct = (smoothed[j] - smoothed[i]) / run_m * 100.0;
+        stats.steepest_climb_gradient_pct = stats.steepest_climb_gradient_pct.max(gradient_pct);
+        stats.steepest_descent_gradient_pct =
+            stats.steepest_descent_gradient_pct.max(-gradient_pct);
+    }
+    stats
+}
+
+/// Centred moving average of point elevations. The window shrinks
+/// symmetrically near the ends so the first and last elevations are kept.
+fn moving_average(points: &[CoursePoint], window: usize) -> Vec<f64> {
+    (0..points.len())
+        .map(|i| {
+            let half = (window / 2).min(i).min(points.len() - 1 - i);
+            let (lo, hi) = (i - half, i + half + 1);
+            points[lo..hi].iter().map(|p| p.ele).sum::<f64>() / (hi - lo) as f64
+        })
+        .collect()
+}
+
 /// A single coordinate point along the race course.
-#[derive(Debug, Clone, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CoursePoint {
     /// Latitude (WGS84)
     pub lat: f64,
@@ -431,16 +1092,26 @@ pub struct CoursePoint {
     /// Cumulative time fraction (0.0 at start, 1.0 at finish).
     /// Based on elevation-adjusted pacing model (duration-independent).
     pub time_fraction: f64,
+    /// Index of the `<trkseg>` this point belongs to (0 for single-segment
+    /// courses and for points cached before segments were tracked)
+    #[serde(default)]
+    pub segment_index: usize,
 }
 
 /// Extract track points from GPX XML as `[{lat, lon, ele}]` coordinates.
 ///
 /// Reads `<trkpt>` elements from `<trkseg>` sections, extracting the `lat`/`lon`
 /// attributes and nested `<ele>` element. Points without elevation default to 0.
+/// Each point records the index of its `<trkseg>` (counted across all `<trk>`s).
+/// Cumulative distance runs across segments but does not include the gap
+/// between the end of one segment and the start of the next.
 pub fn extract_track_points(gpx_xml: &str) -> Result<Vec<CoursePoint>, GpxError> {
     let mut reader = Reader::from_str(gpx_xml);
     let mut points = Vec::new();
 
+    let mut segments_seen: usize = 0;
+    let mut segment_index: usize = 0;
+
     let mut in_trkpt = false;
     let mut trkpt_lat: f64 = 0.0;
     let mut trkpt_lon: f64 = 0.0;
@@ -454,6 +1125,10 @@ pub fn extract_track_points(gpx_xml: &str) -> Result<Vec<CoursePoint>, GpxError>
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let local = local_name_str(e.name().as_ref());
                 match local.as_str() {
+                    "trkseg" => {
+                        segment_index = segments_seen;
+                        segments_seen += 1;
+                    }
                     "trkpt" => {
                         in_trkpt = true;
                         trkpt_ele = None;
@@ -506,12 +1181,19 @@ pub fn extract_track_points(gpx_xml: &str) -> Result<Vec<CoursePoint>, GpxError>
                         reading_ele = false;
                     }
                     "trkpt" => {
+                        validate_coordinates(
+                            &format!("track point {}", points.len()),
+                            trkpt_lat,
+                            trkpt_lon,
+                            trkpt_ele.unwrap_or(0.0),
+                        )?;
                         points.push(CoursePoint {
                             lat: trkpt_lat,
                             lon: trkpt_lon,
                             ele: trkpt_ele.unwrap_or(0.0),
                             distance_km: 0.0,   // filled in below
                             time_fraction: 0.0, // overwritten by course handler with elevation-based pacing
+                            segment_index,
                         });
                         in_trkpt = false;
                     }
@@ -530,7 +1212,10 @@ pub fn extract_track_points(gpx_xml: &str) -> Result<Vec<CoursePoint>, GpxError>
         let mut cumulative = 0.0;
         for i in 1..points.len() {
             let prev = &points[i - 1];
-            cumulative += haversine_distance_km(prev.lat, prev.lon, points[i].lat, points[i].lon);
+            if prev.segment_index == points[i].segment_index {
+                cumulative +=
+                    haversine_distance_km(prev.lat, prev.lon, points[i].lat, points[i].lon);
+            }
             points[i].distance_km = cumulative;
         }
     }
@@ -554,7 +1239,11 @@ fn local_name_str(full: &[u8]) -> String {
 }
 
 /// Scan a directory for `*.gpx` files and parse each one.
-pub fn load_races_from_dir(dir: &Path) -> Result<Vec<GpxRace>, GpxError> {
+pub fn load_races_from_dir(
+    dir: &Path,
+    distance_tolerance_km: f64,
+    identifier: CheckpointIdentifierMode,
+) -> Result<Vec<GpxRace>, GpxError> {
     let mut races = Vec::new();
     if !dir.exists() {
         tracing::warn!("Data directory does not exist: {}", dir.display());
@@ -565,7 +1254,7 @@ pub fn load_races_from_dir(dir: &Path) -> Result<Vec<GpxRace>, GpxError> {
         let path = entry.path();
         if path.extension().is_some_and(|ext| ext == "gpx") {
             tracing::info!("Loading race from GPX: {}", path.display());
-            match parse_gpx_file(&path) {
+            match parse_gpx_file(&path, distance_tolerance_km, identifier) {
                 Ok(race) => {
                     tracing::info!(
                         "  Parsed race '{}' ({}) with {} checkpoints",
@@ -584,6 +1273,101 @@ pub fn load_races_from_dir(dir: &Path) -> Result<Vec<GpxRace>, GpxError> {
     Ok(races)
 }
 
+/// Check that a `RACE_DATA_URLS` entry is an absolute HTTPS URL.
+fn validate_race_data_url(url: &str) -> Result<reqwest::Url, GpxError> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| GpxError::InvalidUrl {
+        url: url.to_string(),
+        message: e.to_string(),
+    })?;
+    if parsed.scheme() != "https" {
+        return Err(GpxError::InvalidUrl {
+            url: url.to_string(),
+            message: format!("scheme must be https, got '{}'", parsed.scheme()),
+        });
+    }
+    Ok(parsed)
+}
+
+/// Fetch one GPX URL and parse the body.
+async fn fetch_gpx_url(
+    client: &reqwest::Client,
+    url: reqwest::Url,
+    distance_tolerance_km: f64,
+    identifier: CheckpointIdentifierMode,
+) -> Result<GpxRace, GpxError> {
+    let gpx_xml = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    parse_gpx_measured(&gpx_xml, distance_tolerance_km, identifier)
+}
+
+/// Fetch and parse GPX files from HTTPS URLs (`RACE_DATA_URLS`).
+///
+/// Each failed fetch is retried once after 5 seconds. URLs that are not
+/// HTTPS, or that still fail, are logged and skipped.
+pub async fn load_races_from_urls(
+    urls: &[String],
+    distance_tolerance_km: f64,
+    identifier: CheckpointIdentifierMode,
+) -> Vec<GpxRace> {
+    let mut races = Vec::new();
+    if urls.is_empty() {
+        return races;
+    }
+    let client = match reqwest::Client::builder()
+        .timeout(URL_FETCH_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!("Failed to build HTTP client for RACE_DATA_URLS: {}", e);
+            return races;
+        }
+    };
+
+    for url in urls {
+        let parsed = match validate_race_data_url(url) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                tracing::error!("Skipping race URL: {}", e);
+                continue;
+            }
+        };
+        tracing::info!("Loading race from URL: {}", url);
+        let mut result =
+            fetch_gpx_url(&client, parsed.clone(), distance_tolerance_km, identifier).await;
+        if let Err(e) = &result {
+            tracing::warn!(
+                "  Fetching {} failed ({}); retrying in {}s",
+                url,
+                e,
+                URL_FETCH_RETRY_DELAY.as_secs()
+            );
+            tokio::time::sleep(URL_FETCH_RETRY_DELAY).await;
+            result = fetch_gpx_url(&client, parsed, distance_tolerance_km, identifier).await;
+        }
+        match result {
+            Ok(race) => {
+                tracing::info!(
+                    "  Parsed race '{}' ({}) with {} checkpoints",
+                    race.name,
+                    race.year,
+                    race.checkpoints.len()
+                );
+                races.push(race);
+            }
+            Err(e) => {
+                tracing::error!("  Failed to load {}: {}", url, e);
+            }
+        }
+    }
+    races
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -631,7 +1415,7 @@ mod tests {
 
     #[test]
     fn test_parse_race_metadata() {
-        let race = parse_gpx(MINIMAL_GPX).unwrap();
+        let race = parse_gpx(MINIMAL_GPX, CheckpointIdentifierMode::TypeOnly).unwrap();
         assert_eq!(race.name, "Test Race");
         assert_eq!(race.year, 2026);
         assert_eq!(race.distance_km, 50.0);
@@ -640,7 +1424,7 @@ mod tests {
 
     #[test]
     fn test_parse_checkpoints() {
-        let race = parse_gpx(MINIMAL_GPX).unwrap();
+        let race = parse_gpx(MINIMAL_GPX, CheckpointIdentifierMode::TypeOnly).unwrap();
         assert_eq!(race.checkpoints.len(), 2); // POI waypoint excluded
         assert_eq!(race.checkpoints[0].name, "Start");
         assert_eq!(race.checkpoints[0].latitude, 61.1);
@@ -653,7 +1437,7 @@ mod tests {
 
     #[test]
     fn test_non_checkpoint_waypoints_excluded() {
-        let race = parse_gpx(MINIMAL_GPX).unwrap();
+        let race = parse_gpx(MINIMAL_GPX, CheckpointIdentifierMode::TypeOnly).unwrap();
         // "Scenic Viewpoint" has type "poi", should not be included
         assert!(!race
             .checkpoints
@@ -661,9 +1445,51 @@ mod tests {
             .any(|c| c.name == "Scenic Viewpoint"));
     }
 
+    /// MINIMAL_GPX with the start marked by `<sym>` and the finish by `<type>`.
+    fn sym_gpx() -> String {
+        MINIMAL_GPX.replacen("<type>checkpoint</type>", "<sym>Checkpoint</sym>", 1)
+    }
+
+    #[test]
+    fn test_sym_checkpoints_in_either_mode() {
+        let race = parse_gpx(&sym_gpx(), CheckpointIdentifierMode::Either).unwrap();
+        let names: Vec<&str> = race.checkpoints.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Start", "Finish"]);
+        assert_eq!(race.checkpoints[0].elevation_m, 350.0);
+
+        // <sym> matches case-insensitively
+        let lowercase = sym_gpx().replace("<sym>Checkpoint</sym>", "<sym>checkpoint</sym>");
+        let race = parse_gpx(&lowercase, CheckpointIdentifierMode::Either).unwrap();
+        assert_eq!(race.checkpoints.len(), 2);
+    }
+
+    #[test]
+    fn test_checkpoint_identifier_modes() {
+        let names = |mode| {
+            parse_gpx(&sym_gpx(), mode)
+                .unwrap()
+                .checkpoints
+                .into_iter()
+                .map(|c| c.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(CheckpointIdentifierMode::TypeOnly), ["Finish"]);
+        assert_eq!(names(CheckpointIdentifierMode::SymOnly), ["Start"]);
+
+        assert_eq!(
+            "either".parse::<CheckpointIdentifierMode>(),
+            Ok(CheckpointIdentifierMode::Either)
+        );
+        assert_eq!(
+            "sym_only".parse::<CheckpointIdentifierMode>(),
+            Ok(CheckpointIdentifierMode::SymOnly)
+        );
+        assert!("sym".parse::<CheckpointIdentifierMode>().is_err());
+    }
+
     #[test]
     fn test_gpx_xml_preserved() {
-        let race = parse_gpx(MINIMAL_GPX).unwrap();
+        let race = parse_gpx(MINIMAL_GPX, CheckpointIdentifierMode::TypeOnly).unwrap();
         assert!(race.gpx_xml.contains("<gpx"));
         assert!(race.gpx_xml.contains("Test Race"));
     }
@@ -690,7 +1516,7 @@ mod tests {
     <extensions><wb:distance_km>0</wb:distance_km></extensions>
   </wpt>
 </gpx>"#;
-        let result = parse_gpx(gpx);
+        let result = parse_gpx(gpx, CheckpointIdentifierMode::TypeOnly);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("metadata/name"));
     }
@@ -717,7 +1543,7 @@ mod tests {
     <extensions><wb:distance_km>0</wb:distance_km></extensions>
   </wpt>
 </gpx>"#;
-        let result = parse_gpx(gpx);
+        let result = parse_gpx(gpx, CheckpointIdentifierMode::TypeOnly);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("wb:year"));
     }
@@ -739,15 +1565,83 @@ mod tests {
     </extensions>
   </metadata>
 </gpx>"#;
-        let result = parse_gpx(gpx);
+        let result = parse_gpx(gpx, CheckpointIdentifierMode::TypeOnly);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("checkpoint"));
     }
 
+    #[test]
+    fn test_start_and_finish_checkpoints() {
+        let mut race = parse_gpx(MINIMAL_GPX, CheckpointIdentifierMode::TypeOnly).unwrap();
+        assert!(race.has_start_checkpoint());
+        assert!(race.has_finish_checkpoint());
+
+        race.distance_km = 52.0;
+        race.checkpoints[0].distance_km = 0.5;
+        assert!(!race.has_start_checkpoint());
+        assert!(!race.has_finish_checkpoint());
+    }
+
+    #[test]
+    fn test_validate_gpx_valid_with_warnings() {
+        let report = validate_gpx(MINIMAL_GPX, CheckpointIdentifierMode::TypeOnly);
+        assert!(report.valid);
+        assert!(report.errors.is_empty());
+        let parsed = report.parsed.unwrap();
+        assert_eq!(parsed.name, "Test Race");
+        assert_eq!(parsed.checkpoints_count, 2);
+        assert!(parsed.computed_track_length_km.unwrap() > 0.0);
+        // "61.1"/"13.3" have a single decimal place
+        assert_eq!(report.warnings.len(), 2);
+        assert!(report.warnings.iter().all(|w| w.field == "lat/lon"));
+
+        // Missing <ele> and no track
+        let gpx = MINIMAL_GPX.replace("<ele>350</ele>", "").replace(
+            "lat=\"61.0\" lon=\"14.5\"",
+            "lat=\"61.0000\" lon=\"14.5000\"",
+        );
+        let gpx = format!("{}</gpx>", &gpx[..gpx.find("<trk>").unwrap()]);
+        let report = validate_gpx(&gpx, CheckpointIdentifierMode::TypeOnly);
+        assert!(report.valid);
+        let fields: Vec<&str> = report.warnings.iter().map(|w| w.field.as_str()).collect();
+        assert_eq!(fields, ["ele", "lat/lon", "trk"]);
+        assert_eq!(report.parsed.unwrap().computed_track_length_km, None);
+    }
+
+    #[test]
+    fn test_validate_gpx_collects_all_errors() {
+        let gpx = MINIMAL_GPX
+            .replace("<name>Test Race</name>", "")
+            .replace("<wb:year>2026</wb:year>", "<wb:year>next</wb:year>")
+            .replace("<wb:distance_km>0</wb:distance_km>", "");
+        // parse_gpx stops at the first error...
+        assert!(matches!(
+            parse_gpx(&gpx, CheckpointIdentifierMode::TypeOnly),
+            Err(GpxError::InvalidValue { ref field, .. }) if field == "wb:year"
+        ));
+        // ...validate_gpx reports all of them, without a duplicate for wb:year
+        let report = validate_gpx(&gpx, CheckpointIdentifierMode::TypeOnly);
+        assert!(!report.valid);
+        assert!(report.parsed.is_none());
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "wb:year",
+                "wb:distance_km for checkpoint 'Start'",
+                "metadata/name"
+            ]
+        );
+
+        let report = validate_gpx("<gpx><metadata></gpx>", CheckpointIdentifierMode::TypeOnly);
+        assert!(!report.valid);
+        assert_eq!(report.errors[0].field, "xml");
+    }
+
     #[test]
     fn test_parse_vasaloppet_gpx() {
         let gpx = include_str!("../../../data/vasaloppet-2026.gpx");
-        let race = parse_gpx(gpx).unwrap();
+        let race = parse_gpx(gpx, CheckpointIdentifierMode::TypeOnly).unwrap();
         assert_eq!(race.name, "Vasaloppet");
         assert_eq!(race.year, 2026);
         assert_eq!(race.distance_km, 90.0);
@@ -756,6 +1650,48 @@ mod tests {
         assert_eq!(race.checkpoints[0].distance_km, 0.0);
         assert_eq!(race.checkpoints[8].name, "Mora (Finish)");
         assert_eq!(race.checkpoints[8].distance_km, 90.0);
+        assert_eq!(race.country.as_deref(), Some("SE"));
+        assert_eq!(race.discipline.as_deref(), Some("classic"));
+        assert_eq!(race.category.as_deref(), Some("classic"));
+    }
+
+    #[test]
+    fn test_optional_race_fields() {
+        let race = parse_gpx(MINIMAL_GPX, CheckpointIdentifierMode::TypeOnly).unwrap();
+        assert_eq!(race.country, None);
+        assert_eq!(race.website, None);
+        assert_eq!(race.discipline, None);
+        assert_eq!(race.category, None);
+        assert_eq!(race.elevation_gain_m, None);
+
+        let gpx = MINIMAL_GPX.replace(
+            "<wb:distance_km>50</wb:distance_km>\n      </wb:race>",
+            "<wb:distance_km>50</wb:distance_km>
+        <wb:country>NO</wb:country>
+        <wb:website>https://example.com/race</wb:website>
+        <wb:discipline>Skate</wb:discipline>
+        <wb:category>Pursuit</wb:category>
+        <wb:elevation_gain_m>1250.5</wb:elevation_gain_m>
+      </wb:race>",
+        );
+        let race = parse_gpx(&gpx, CheckpointIdentifierMode::TypeOnly).unwrap();
+        assert_eq!(race.country.as_deref(), Some("NO"));
+        assert_eq!(race.website.as_deref(), Some("https://example.com/race"));
+        assert_eq!(race.discipline.as_deref(), Some("skate"));
+        assert_eq!(race.category.as_deref(), Some("pursuit"));
+        assert_eq!(race.elevation_gain_m, Some(1250.5));
+
+        let bad = gpx.replace("Skate", "biathlon");
+        let err = parse_gpx(&bad, CheckpointIdentifierMode::TypeOnly)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("wb:discipline"), "{}", err);
+
+        let long = gpx.replace("Pursuit", &"x".repeat(51));
+        let err = parse_gpx(&long, CheckpointIdentifierMode::TypeOnly)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("wb:category"), "{}", err);
     }
 
     #[test]
@@ -788,6 +1724,62 @@ mod tests {
         assert!(last.lat > 60.0 && last.lat < 62.0);
     }
 
+    #[test]
+    fn test_course_points_json_roundtrip() {
+        // course_points are cached as JSONB — they must deserialize back unchanged
+        let points = extract_track_points(MINIMAL_GPX).unwrap();
+        let json = serde_json::to_value(&points).unwrap();
+        let restored: Vec<CoursePoint> = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.len(), points.len());
+        assert_eq!(restored[1].lat, points[1].lat);
+        assert_eq!(restored[1].ele, points[1].ele);
+        assert_eq!(restored[1].distance_km, points[1].distance_km);
+    }
+
+    const TWO_SEGMENT_GPX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx xmlns="http://www.topografix.com/GPX/1/1" version="1.1" creator="test">
+  <trk><name>Out and back</name>
+    <trkseg>
+      <trkpt lat="61.00" lon="14.0"><ele>100</ele></trkpt>
+      <trkpt lat="61.01" lon="14.0"><ele>120</ele></trkpt>
+    </trkseg>
+    <trkseg>
+      <trkpt lat="61.05" lon="14.0"><ele>130</ele></trkpt>
+      <trkpt lat="61.04" lon="14.0"><ele>110</ele></trkpt>
+      <trkpt lat="61.03" lon="14.0"><ele>100</ele></trkpt>
+    </trkseg>
+  </trk>
+</gpx>"#;
+
+    #[test]
+    fn test_extract_track_points_segment_index() {
+        let points = extract_track_points(TWO_SEGMENT_GPX).unwrap();
+        let segments: Vec<usize> = points.iter().map(|p| p.segment_index).collect();
+        assert_eq!(segments, vec![0, 0, 1, 1, 1]);
+        // Single-segment files stay on segment 0
+        let single = extract_track_points(MINIMAL_GPX).unwrap();
+        assert!(single.iter().all(|p| p.segment_index == 0));
+    }
+
+    #[test]
+    fn test_extract_track_points_skips_segment_gap() {
+        let points = extract_track_points(TWO_SEGMENT_GPX).unwrap();
+        let step = haversine_distance_km(61.00, 14.0, 61.01, 14.0);
+        // The 61.01 → 61.05 jump between segments is not counted
+        assert!((points[2].distance_km - step).abs() < 1e-9);
+        assert!((points[4].distance_km - 3.0 * step).abs() < 1e-6);
+        let profile = compute_track_profile(&points);
+        assert!((profile[4].distance_km - points[4].distance_km).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_course_point_segment_index_defaults_when_missing() {
+        // Cached course_points JSON from before segments were tracked
+        let json = r#"[{"lat":61.0,"lon":14.0,"ele":100.0,"distance_km":0.0,"time_fraction":0.0}]"#;
+        let points: Vec<CoursePoint> = serde_json::from_str(json).unwrap();
+        assert_eq!(points[0].segment_index, 0);
+    }
+
     #[test]
     fn test_extract_track_points_no_tracks() {
         let gpx = r#"<?xml version="1.0"?>
@@ -840,6 +1832,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_track_length_deviation() {
+        let mut race = parse_gpx(MINIMAL_GPX, CheckpointIdentifierMode::TypeOnly).unwrap();
+        assert_eq!(race.computed_track_length_km, None);
+        assert_eq!(track_length_deviation_km(&race, 2.0), None);
+
+        // Declared 50 km; the two-point track is ~67 km
+        let points = extract_track_points(MINIMAL_GPX).unwrap();
+        race.computed_track_length_km = track_length_km(&points);
+        let deviation = track_length_deviation_km(&race, 2.0).unwrap();
+        assert!(deviation > 2.0, "got {}", deviation);
+
+        race.computed_track_length_km = Some(51.5);
+        assert_eq!(track_length_deviation_km(&race, 2.0), None);
+        race.computed_track_length_km = Some(47.0);
+        assert_eq!(track_length_deviation_km(&race, 2.0), Some(-3.0));
+    }
+
+    // --- detect_route_overlap tests ---
+
+    fn race_at(name: &str, year: i32, coords: &[(f64, f64)]) -> GpxRace {
+        let mut race = parse_gpx(MINIMAL_GPX, CheckpointIdentifierMode::TypeOnly).unwrap();
+        race.name = name.to_string();
+        race.year = year;
+        race.checkpoints = coords
+            .iter()
+            .map(|&(latitude, longitude)| GpxCheckpoint {
+                name: format!("{},{}", latitude, longitude),
+                latitude,
+                longitude,
+                elevation_m: 0.0,
+                distance_km: 0.0,
+            })
+            .collect();
+        race
+    }
+
+    #[test]
+    fn test_route_overlap_same_race_other_year() {
+        let existing = [race_at("Vasaloppet", 2025, &[(61.1, 13.3), (61.0, 14.5)])];
+        // Start moved ~50 m north, finish moved elsewhere
+        let new = race_at("Vasaloppet", 2026, &[(61.1005, 13.3), (60.5, 15.0)]);
+        let report = detect_route_overlap(&existing, &new).unwrap();
+        assert_eq!(report.route_similar_to, "Vasaloppet (2025)");
+        assert_eq!(report.similarity, 0.5);
+        assert!(!report.possible_duplicate);
+    }
+
+    #[test]
+    fn test_route_overlap_different_name_needs_high_similarity() {
+        let coords = [(61.1, 13.3), (61.0, 14.5)];
+        let existing = [race_at("Vasaloppet", 2025, &coords)];
+        let dup = race_at("Vasa", 2025, &coords);
+        let report = detect_route_overlap(&existing, &dup).unwrap();
+        assert!(report.possible_duplicate);
+        assert_eq!(report.similarity, 1.0);
+
+        let partial = race_at("Other Race", 2025, &[(61.1, 13.3), (60.5, 15.0)]);
+        assert_eq!(detect_route_overlap(&existing, &partial), None);
+    }
+
+    #[test]
+    fn test_route_overlap_ignores_same_race_and_year() {
+        let coords = [(61.1, 13.3), (61.0, 14.5)];
+        let existing = [race_at("Vasaloppet", 2026, &coords)];
+        let new = race_at("Vasaloppet", 2026, &coords);
+        assert_eq!(detect_route_overlap(&existing, &new), None);
+    }
+
     // --- compute_track_profile tests ---
 
     #[test]
@@ -856,6 +1917,7 @@ mod tests {
             ele: 350.0,
             distance_km: 0.0,
             time_fraction: 0.0,
+            segment_index: 0,
         }];
         let profile = compute_track_profile(&points);
         assert_eq!(profile.len(), 1);
@@ -872,6 +1934,7 @@ mod tests {
                 ele: 100.0,
                 distance_km: 0.0,
                 time_fraction: 0.0,
+                segment_index: 0,
             },
             CoursePoint {
                 lat: 61.01,
@@ -879,6 +1942,7 @@ mod tests {
                 ele: 150.0,
                 distance_km: 0.0,
                 time_fraction: 0.0,
+                segment_index: 0,
             },
             CoursePoint {
                 lat: 61.02,
@@ -886,6 +1950,7 @@ mod tests {
                 ele: 200.0,
                 distance_km: 0.0,
                 time_fraction: 0.0,
+                segment_index: 0,
             },
         ];
         let profile = compute_track_profile(&points);
@@ -933,4 +1998,159 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_race_data_url_must_be_https() {
+        assert!(validate_race_data_url("https://raw.githubusercontent.com/o/r/main/a.gpx").is_ok());
+        assert!(matches!(
+            validate_race_data_url("http://example.com/a.gpx"),
+            Err(GpxError::InvalidUrl { .. })
+        ));
+        assert!(matches!(
+            validate_race_data_url("data/a.gpx"),
+            Err(GpxError::InvalidUrl { .. })
+        ));
+    }
+
+    /// Points every 10 m along a straight line with the given elevations.
+    fn profile(elevations: &[f64]) -> Vec<CoursePoint> {
+        elevations
+            .iter()
+            .enumerate()
+            .map(|(i, &ele)| CoursePoint {
+                lat: 0.0,
+                lon: 0.0,
+                ele,
+                distance_km: i as f64 * 0.01,
+                time_fraction: 0.0,
+                segment_index: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_elevation_stats_steady_climb() {
+        // 1 km at a constant 10% gradient
+        let elevations: Vec<f64> = (0..=100).map(|i| i as f64).collect();
+        let stats = compute_elevation_stats(&profile(&elevations));
+        assert!((stats.total_elevation_gain_m - 100.0).abs() < 2.0);
+        assert!(stats.total_elevation_loss_m < 1e-9);
+        assert_eq!(stats.max_elevation_m, 100.0);
+        assert_eq!(stats.min_elevation_m, 0.0);
+        assert!((stats.steepest_climb_gradient_pct - 10.0).abs() < 0.5);
+        assert!(stats.steepest_descent_gradient_pct < 1e-9);
+    }
+
+    #[test]
+    fn test_elevation_stats_smoothing_suppresses_noise() {
+        // Flat course with ±1 m jitter: raw gain would be ~100 m
+        let elevations: Vec<f64> = (0..=100)
+            .map(|i| if i % 2 == 0 { 500.0 } else { 501.0 })
+            .collect();
+        let stats = compute_elevation_stats(&profile(&elevations));
+        assert!(stats.total_elevation_gain_m < 25.0);
+        assert_eq!(stats.max_elevation_m, 501.0);
+        assert_eq!(stats.min_elevation_m, 500.0);
+    }
+
+    #[test]
+    fn test_elevation_stats_descent_and_empty() {
+        let elevations: Vec<f64> = (0..=50).map(|i| 300.0 - 2.0 * i as f64).collect();
+        let stats = compute_elevation_stats(&profile(&elevations));
+        assert!((stats.total_elevation_loss_m - 100.0).abs() < 4.0);
+        assert!((stats.steepest_descent_gradient_pct - 20.0).abs() < 1.0);
+        assert_eq!(compute_elevation_stats(&[]), ElevationStats::default());
+    }
+
+    #[test]
+    fn test_validate_coordinates_accepts_boundaries() {
+        for (lat, lon, ele) in [
+            (90.0, 180.0, 9000.0),
+            (-90.0, -180.0, -500.0),
+            (0.0, 0.0, 0.0),
+        ] {
+            assert!(validate_coordinates("point", lat, lon, ele).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_validate_coordinates_rejects_out_of_range() {
+        let field = |lat, lon, ele| match validate_coordinates("point", lat, lon, ele) {
+            Err(GpxError::InvalidValue { field, .. }) => field,
+            other => panic!("expected InvalidValue, got {:?}", other),
+        };
+        assert_eq!(field(90.01, 0.0, 0.0), "lat");
+        assert_eq!(field(-90.01, 0.0, 0.0), "lat");
+        assert_eq!(field(0.0, 180.5, 0.0), "lon");
+        assert_eq!(field(0.0, -181.0, 0.0), "lon");
+        assert_eq!(field(0.0, 0.0, 9000.1), "ele");
+        assert_eq!(field(0.0, 0.0, -500.1), "ele");
+        assert_eq!(field(f64::NAN, 0.0, 0.0), "lat");
+    }
+
+    #[test]
+    fn test_parse_gpx_rejects_implausible_checkpoint_latitude() {
+        // 10× typo of 61.1
+        let gpx = MINIMAL_GPX.replace(
+            r#"<wpt lat="61.1" lon="13.3">"#,
+            r#"<wpt lat="610.1" lon="13.3">"#,
+        );
+        match parse_gpx(&gpx, CheckpointIdentifierMode::TypeOnly) {
+            Err(GpxError::InvalidValue { field, message }) => {
+                assert_eq!(field, "lat");
+                assert!(message.contains("checkpoint 'Start'"), "{}", message);
+                assert!(message.contains("610.1"), "{}", message);
+            }
+            other => panic!("expected InvalidValue, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_gpx_rejects_implausible_checkpoint_elevation() {
+        let gpx = MINIMAL_GPX.replacen("<ele>165</ele>", "<ele>16500</ele>", 1);
+        assert!(matches!(
+            parse_gpx(&gpx, CheckpointIdentifierMode::TypeOnly),
+            Err(GpxError::InvalidValue { field, .. }) if field == "ele"
+        ));
+        // Non-checkpoint waypoints are not stored and not checked
+        let poi = MINIMAL_GPX.replace(
+            r#"<wpt lat="61.05" lon="13.9">"#,
+            r#"<wpt lat="61.05" lon="139">"#,
+        );
+        assert!(parse_gpx(&poi, CheckpointIdentifierMode::TypeOnly).is_ok());
+    }
+
+    #[test]
+    fn test_extract_track_points_rejects_implausible_coordinates() {
+        let gpx =
+            TWO_SEGMENT_GPX.replace(r#"lat="61.04" lon="14.0""#, r#"lat="61.04" lon="-190.0""#);
+        match extract_track_points(&gpx) {
+            Err(GpxError::InvalidValue { field, message }) => {
+                assert_eq!(field, "lon");
+                assert!(message.contains("track point 3"), "{}", message);
+            }
+            other => panic!("expected InvalidValue, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_extract_track_points_elevation_bounds() {
+        let gpx = |ele: &str| {
+            format!(
+                r#"<?xml version="1.0"?>
+<gpx xmlns="http://www.topografix.com/GPX/1/1" version="1.1" creator="test">
+  <trk><trkseg>
+    <trkpt lat="31.5" lon="35.5"><ele>{}</ele></trkpt>
+  </trkseg></trk>
+</gpx>"#,
+                ele
+            )
+        };
+        assert_eq!(extract_track_points(&gpx("-500")).unwrap()[0].ele, -500.0);
+        assert!(extract_track_points(&gpx("9000")).is_ok());
+        assert!(matches!(
+            extract_track_points(&gpx("-501")),
+            Err(GpxError::InvalidValue { field, .. }) if field == "ele"
+        ));
+    }
 }
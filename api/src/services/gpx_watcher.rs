@@ -0,0 +1,216 @@
+//! Development helper: reseed races when GPX files in `DATA_DIR` change.
+//!
+//! Enabled with `WATCH_DATA_DIR=true`. A `notify` watcher forwards filesystem
+//! events to this task. Editors emit several events per save, so events are
+//! collected until the directory has been quiet for [`DEBOUNCE`]; then every
+//! created or modified `*.gpx` file is re-parsed once and upserted (with
+//! elevation enrichment when `ENRICH_ELEVATIONS=true`).
+//! Parse errors (e.g. a half-saved file) are logged and the
+//! watcher keeps running. Stops when the shutdown token is cancelled.
+
+use notify::event::EventKind;
+use notify::{Event, RecursiveMode, Watcher};
+use sqlx::PgPool;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::db::queries;
+use crate::services::elevation::ElevationClient;
+use crate::services::gpx::{parse_gpx_file, CheckpointIdentifierMode};
+
+/// Quiet period after the last event before changed files are reseeded.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watch `dir` and reseed changed GPX files until `shutdown` is cancelled.
+pub async fn run_gpx_watcher(
+    pool: PgPool,
+    dir: PathBuf,
+    distance_tolerance_km: f64,
+    identifier: CheckpointIdentifierMode,
+    elevation_client: Option<ElevationClient>,
+    shutdown: CancellationToken,
+) {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher = match notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    }) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            tracing::error!("Failed to create GPX watcher: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+        tracing::error!("Failed to watch {}: {}", dir.display(), e);
+        return;
+    }
+    tracing::info!("Watching {} for GPX changes", dir.display());
+
+    loop {
+        let paths = tokio::select! {
+            _ = shutdown.cancelled() => {
+                tracing::info!("GPX watcher shutting down");
+                return;
+            }
+            paths = next_changed_batch(&mut rx) => match paths {
+                Some(paths) => paths,
+                None => return,
+            },
+        };
+        for path in paths {
+            reseed_file(
+                &pool,
+                &path,
+                distance_tolerance_km,
+                identifier,
+                elevation_client.as_ref(),
+            )
+            .await;
+        }
+    }
+}
+
+/// Wait for an event, then collect events until none arrives for
+/// [`DEBOUNCE`]. Returns the distinct changed `*.gpx` paths (possibly none),
+/// or None once the watcher is gone.
+async fn next_changed_batch(
+    rx: &mut mpsc::UnboundedReceiver<notify::Result<Event>>,
+) -> Option<BTreeSet<PathBuf>> {
+    let mut paths = BTreeSet::new();
+    let mut res = rx.recv().await?;
+    loop {
+        match res {
+            Ok(event) => paths.extend(changed_gpx_paths(&event)),
+            Err(e) => tracing::warn!("GPX watcher error: {}", e),
+        }
+        res = match tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+            Ok(Some(res)) => res,
+            // Quiet for DEBOUNCE, or the watcher is gone: flush what we have
+            Ok(None) | Err(_) => return Some(paths),
+        };
+    }
+}
+
+/// `*.gpx` paths touched by a create or modify event.
+fn changed_gpx_paths(event: &Event) -> Vec<PathBuf> {
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return Vec::new();
+    }
+    let mut paths: Vec<PathBuf> = event
+        .paths
+        .iter()
+        .filter(|p| p.extension().is_some_and(|ext| ext == "gpx"))
+        .cloned()
+        .collect();
+    paths.dedup();
+    paths
+}
+
+/// Re-parse one GPX file and upsert it, logging the outcome.
+async fn reseed_file(
+    pool: &PgPool,
+    path: &Path,
+    distance_tolerance_km: f64,
+    identifier: CheckpointIdentifierMode,
+    elevation_client: Option<&ElevationClient>,
+) {
+    tracing::info!("GPX file changed: {}", path.display());
+    // Rename-over-save and deletes both surface as events on a missing path
+    if !path.exists() {
+        return;
+    }
+    let race = match parse_gpx_file(path, distance_tolerance_km, identifier) {
+        Ok(race) => race,
+        Err(e) => {
+            tracing::error!("  Failed to parse {}: {}", path.display(), e);
+            return;
+        }
+    };
+    match queries::upsert_race_from_gpx(pool, &race, elevation_client).await {
+        Ok((race_id, _)) => tracing::info!(
+            "  Reseeded race '{}' ({}) with {} checkpoints → id={}",
+            race.name,
+            race.year,
+            race.checkpoints.len(),
+            race_id
+        ),
+        Err(e) => tracing::error!(
+            "  Failed to reseed race '{}' ({}): {}",
+            race.name,
+            race.year,
+            e
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind, RemoveKind};
+
+    fn event(kind: EventKind, paths: &[&str]) -> Event {
+        paths
+            .iter()
+            .fold(Event::new(kind), |e, p| e.add_path(PathBuf::from(p)))
+    }
+
+    #[test]
+    fn test_changed_gpx_paths_filters_kind_and_extension() {
+        let modified = event(
+            EventKind::Modify(ModifyKind::Any),
+            &["data/a.gpx", "data/a.gpx.swp", "data/notes.txt"],
+        );
+        assert_eq!(
+            changed_gpx_paths(&modified),
+            vec![PathBuf::from("data/a.gpx")]
+        );
+
+        let created = event(EventKind::Create(CreateKind::File), &["data/b.gpx"]);
+        assert_eq!(
+            changed_gpx_paths(&created),
+            vec![PathBuf::from("data/b.gpx")]
+        );
+
+        let removed = event(EventKind::Remove(RemoveKind::File), &["data/a.gpx"]);
+        assert!(changed_gpx_paths(&removed).is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_next_changed_batch_coalesces_events() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let modified = |path| Ok(event(EventKind::Modify(ModifyKind::Any), &[path]));
+
+        // One save: several events within the debounce window
+        tx.send(modified("data/a.gpx")).unwrap();
+        tx.send(Ok(event(
+            EventKind::Create(CreateKind::File),
+            &["data/b.gpx"],
+        )))
+        .unwrap();
+        let sender = tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(DEBOUNCE / 2).await;
+            sender.send(modified("data/a.gpx")).unwrap();
+            // After the window: belongs to the next batch
+            tokio::time::sleep(DEBOUNCE * 2).await;
+            sender.send(modified("data/c.gpx")).unwrap();
+        });
+
+        let batch = next_changed_batch(&mut rx).await.unwrap();
+        assert_eq!(
+            batch.into_iter().collect::<Vec<_>>(),
+            vec![PathBuf::from("data/a.gpx"), PathBuf::from("data/b.gpx")]
+        );
+        let batch = next_changed_batch(&mut rx).await.unwrap();
+        assert_eq!(
+            batch.into_iter().collect::<Vec<_>>(),
+            vec![PathBuf::from("data/c.gpx")]
+        );
+
+        drop(tx);
+        assert!(next_changed_batch(&mut rx).await.is_none());
+    }
+}
@@ -0,0 +1,232 @@
+//! Periodic database maintenance.
+//!
+//! The poller inserts forecast rows and rewrites `yr_responses` all day, so
+//! dead tuples and stale planner statistics accumulate quickly. Every
+//! `DB_MAINTENANCE_INTERVAL_HOURS` this task runs `VACUUM ANALYZE forecasts`
+//! and `ANALYZE` on `checkpoints` and `yr_responses`, and records the
+//! completion time in the poller state (`last_db_maintenance_at`). Failures
+//! are logged and retried at the next interval. Stops when the shutdown token
+//! is cancelled.
+//!
+//! A second task purges forecast rows older than `FORECAST_RETENTION_DAYS`
+//! once a day, so the `forecasts` table doesn't grow indefinitely. Forecasts
+//! of checkpoints with recorded actual conditions or observations are kept
+//! for the forecast accuracy comparison.
+//!
+//! With several instances behind a load balancer, both tasks run only on the
+//! poll leader (the instance that held the poller advisory lock in its last
+//! cycle), so the database is vacuumed and purged once rather than once per
+//! replica.
+
+use chrono::Utc;
+use sqlx::PgPool;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::db::queries;
+use crate::services::poller::SharedPollerState;
+
+/// How often old forecast rows are purged.
+const FORECAST_RETENTION_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+
+/// How often leader-only tasks check whether this instance is the poll leader.
+const LEADER_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Call `task` at most once per `interval` while this instance is the poll
+/// leader, until `shutdown` is cancelled. Leadership is re-checked every
+/// [`LEADER_CHECK_INTERVAL`]. With `run_at_start`, the first run happens as
+/// soon as the instance is the leader; otherwise one interval after startup.
+async fn run_on_poll_leader<F, Fut>(
+    interval: Duration,
+    run_at_start: bool,
+    poller_state: &SharedPollerState,
+    shutdown: &CancellationToken,
+    mut task: F,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut last_run = (!run_at_start).then(tokio::time::Instant::now);
+    loop {
+        let due = last_run.is_none_or(|at| at.elapsed() >= interval);
+        if due && poller_state.read().await.acquired_poll_lock {
+            task().await;
+            last_run = Some(tokio::time::Instant::now());
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            _ = tokio::time::sleep(LEADER_CHECK_INTERVAL) => {}
+        }
+    }
+}
+
+/// Run database maintenance every `interval` while this instance is the poll
+/// leader, until `shutdown` is cancelled. The first run happens one interval
+/// after startup.
+pub async fn run_db_maintenance(
+    pool: PgPool,
+    interval: Duration,
+    poller_state: SharedPollerState,
+    shutdown: CancellationToken,
+) {
+    tracing::info!(
+        "Database maintenance scheduled every {}h on the poll leader",
+        interval.as_secs() / 3600
+    );
+    run_on_poll_leader(interval, false, &poller_state, &shutdown, || async {
+        let started_at = Utc::now();
+        let timer = Instant::now();
+        tracing::info!(
+            "Database maintenance started at {}: {}",
+            started_at.to_rfc3339(),
+            queries::DB_MAINTENANCE_STATEMENTS.join("; ")
+        );
+        match queries::run_db_maintenance(&pool).await {
+            Ok(()) => {
+                let completed_at = Utc::now();
+                tracing::info!(
+                    "Database maintenance completed at {} in {} ms",
+                    completed_at.to_rfc3339(),
+                    timer.elapsed().as_millis()
+                );
+                poller_state.write().await.last_db_maintenance_at = Some(completed_at);
+            }
+            Err(e) => tracing::error!(
+                "Database maintenance failed after {} ms: {}",
+                timer.elapsed().as_millis(),
+                e
+            ),
+        }
+    })
+    .await;
+    tracing::info!("Database maintenance task shutting down");
+}
+
+/// Delete forecast rows older than `retention_days` once this instance is
+/// the poll leader and then every 24 hours, until `shutdown` is cancelled.
+pub async fn run_forecast_retention(
+    pool: PgPool,
+    retention_days: i32,
+    poller_state: SharedPollerState,
+    shutdown: CancellationToken,
+) {
+    tracing::info!(
+        "Forecast retention: purging forecasts older than {} days every 24h on the poll leader",
+        retention_days
+    );
+    run_on_poll_leader(
+        FORECAST_RETENTION_INTERVAL,
+        true,
+        &poller_state,
+        &shutdown,
+        || async {
+            match queries::delete_old_forecasts(&pool, retention_days).await {
+                Ok(deleted) => tracing::info!(
+                    "Forecast retention: deleted {} forecasts older than {} days",
+                    deleted,
+                    retention_days
+                ),
+                Err(e) => tracing::error!("Forecast retention failed: {}", e),
+            }
+        },
+    )
+    .await;
+    tracing::info!("Forecast retention task shutting down");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::poller::PollerState;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    /// Spawn `run_on_poll_leader` with a task that counts its runs.
+    fn spawn_counting(
+        interval: Duration,
+        run_at_start: bool,
+        state: &SharedPollerState,
+        shutdown: &CancellationToken,
+    ) -> (Arc<AtomicUsize>, tokio::task::JoinHandle<()>) {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let (state, shutdown, counter) = (state.clone(), shutdown.clone(), runs.clone());
+        let handle = tokio::spawn(async move {
+            run_on_poll_leader(interval, run_at_start, &state, &shutdown, || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async {}
+            })
+            .await
+        });
+        (runs, handle)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_leader_task_skipped_on_standby_instances() {
+        let state: SharedPollerState = Arc::new(RwLock::new(PollerState::new()));
+        let shutdown = CancellationToken::new();
+        let (runs, handle) = spawn_counting(FORECAST_RETENTION_INTERVAL, true, &state, &shutdown);
+
+        // Another instance holds the poll lock: nothing runs
+        tokio::time::sleep(Duration::from_secs(3 * 3600)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+        // Taking over the lock runs the overdue task at the next check
+        state.write().await.acquired_poll_lock = true;
+        tokio::time::sleep(LEADER_CHECK_INTERVAL * 2).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // Losing it again stops further runs
+        state.write().await.acquired_poll_lock = false;
+        tokio::time::sleep(FORECAST_RETENTION_INTERVAL * 2).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        shutdown.cancel();
+        handle.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_leader_task_runs_every_interval_after_startup() {
+        // Database maintenance: first run one interval after startup
+        let interval = Duration::from_secs(12 * 3600);
+        let state: SharedPollerState = Arc::new(RwLock::new(PollerState::new()));
+        state.write().await.acquired_poll_lock = true;
+        let shutdown = CancellationToken::new();
+        let (runs, handle) = spawn_counting(interval, false, &state, &shutdown);
+
+        tokio::time::sleep(interval - LEADER_CHECK_INTERVAL / 2).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        tokio::time::sleep(LEADER_CHECK_INTERVAL).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // Then once per interval, not once per leadership check
+        tokio::time::sleep(interval - LEADER_CHECK_INTERVAL).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        tokio::time::sleep(LEADER_CHECK_INTERVAL).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        shutdown.cancel();
+        handle.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_leader_task_stops_on_shutdown() {
+        let interval = Duration::from_secs(3600);
+        let state: SharedPollerState = Arc::new(RwLock::new(PollerState::new()));
+        state.write().await.acquired_poll_lock = true;
+        let shutdown = CancellationToken::new();
+        let (runs, handle) = spawn_counting(interval, true, &state, &shutdown);
+
+        tokio::time::sleep(LEADER_CHECK_INTERVAL / 2).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // Cancelling ends the task mid-wait instead of at the next check
+        shutdown.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("task should stop when shutdown is cancelled")
+            .unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,4 +1,14 @@
+pub mod climate;
+pub mod elevation;
+pub mod extraction_cache;
+pub mod fixture;
 pub mod forecast;
 pub mod gpx;
+pub mod gpx_watcher;
+pub mod maintenance;
+pub mod pacing;
 pub mod poller;
+pub mod resort;
+pub mod summaries;
+pub mod sunrise;
 pub mod yr;
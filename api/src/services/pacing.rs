@@ -0,0 +1,170 @@
+//! Alternative pacing models layered on top of the elevation-adjusted model
+//! in [`forecast`](crate::services::forecast).
+//!
+//! The elevation model prices each km by gradient only, so a flat km costs
+//! the same at the start and the finish. [`FatiguePacingModel`] adds
+//! cumulative fatigue: every segment's cost is scaled by
+//! `1 + fatigue_factor * cumulative_cost / total_cost`, where
+//! `cumulative_cost` is the elevation cost spent up to the segment's
+//! midpoint. Late segments — even flat ones — therefore take longer, while the
+//! total duration stays the user's target.
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::services::forecast::{calculate_pass_time_fractions, PacingCheckpoint, PacingParams};
+
+/// Default fatigue factor: the last km costs ~20% more than the first.
+pub const DEFAULT_FATIGUE_FACTOR: f64 = 0.2;
+
+/// Pacing model selectable via `?pacing_model=` on the race forecast.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PacingModel {
+    /// Gradient-weighted effort only (default)
+    #[default]
+    Elevation,
+    /// Gradient-weighted effort plus cumulative fatigue
+    Fatigue,
+}
+
+/// Cumulative-fatigue pacing on top of elevation-adjusted effort costs.
+#[derive(Debug, Clone, Copy)]
+pub struct FatiguePacingModel {
+    /// Extra cost at the finish relative to the start (0 = no fatigue).
+    pub fatigue_factor: f64,
+}
+
+impl Default for FatiguePacingModel {
+    fn default() -> Self {
+        Self {
+            fatigue_factor: DEFAULT_FATIGUE_FACTOR,
+        }
+    }
+}
+
+impl FatiguePacingModel {
+    pub fn new(fatigue_factor: f64) -> Self {
+        Self { fatigue_factor }
+    }
+
+    /// Re-weight cumulative effort fractions (0.0 at the start, 1.0 at the
+    /// finish) for fatigue.
+    ///
+    /// Each segment's share of the elevation cost is `fractions[i + 1] -
+    /// fractions[i]`, so the input fractions are already
+    /// `cumulative_cost / total_cost`. This works for both the checkpoint
+    /// model and fractions interpolated from the track-aware profile.
+    pub fn apply(&self, fractions: &[f64]) -> Vec<f64> {
+        if fractions.len() < 2 {
+            return fractions.to_vec();
+        }
+
+        let costs: Vec<f64> = fractions
+            .windows(2)
+            .map(|w| {
+                let share = (w[1] - w[0]).max(0.0);
+                let midpoint = (w[0] + w[1]) / 2.0;
+                share * (1.0 + self.fatigue_factor * midpoint)
+            })
+            .collect();
+
+        let total: f64 = costs.iter().sum();
+        if total <= 0.0 {
+            return fractions.to_vec();
+        }
+
+        let mut result = Vec::with_capacity(fractions.len());
+        result.push(0.0);
+        let mut cumulative = 0.0;
+        for cost in &costs {
+            cumulative += cost;
+            result.push(cumulative / total);
+        }
+
+        // Ensure last fraction is exactly 1.0 (avoid floating-point drift)
+        if let Some(last) = result.last_mut() {
+            *last = 1.0;
+        }
+        result
+    }
+}
+
+/// Like [`calculate_pass_time_fractions`], but with cumulative fatigue (see
+/// [`FatiguePacingModel`]).
+pub fn calculate_pass_time_fractions_with_fatigue(
+    checkpoints: &[PacingCheckpoint],
+    params: &PacingParams,
+    fatigue_factor: f64,
+) -> Vec<f64> {
+    FatiguePacingModel::new(fatigue_factor)
+        .apply(&calculate_pass_time_fractions(checkpoints, params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cp(distance_km: f64, elevation_m: f64) -> PacingCheckpoint {
+        PacingCheckpoint {
+            distance_km,
+            elevation_m,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fatigue_slows_late_segments() {
+        // Climb, then two flat 10 km segments
+        let checkpoints = vec![
+            cp(0.0, 200.0),
+            cp(10.0, 400.0),
+            cp(20.0, 400.0),
+            cp(30.0, 400.0),
+        ];
+        let elevation = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
+        let fatigue =
+            calculate_pass_time_fractions_with_fatigue(&checkpoints, &PacingParams::default(), 0.2);
+
+        assert_eq!(fatigue.len(), checkpoints.len());
+        assert_eq!(fatigue[0], 0.0);
+        assert_eq!(fatigue[3], 1.0);
+
+        // The last segment takes a bigger share of the race than the
+        // elevation model alone predicts...
+        let last_elevation = elevation[3] - elevation[2];
+        let last_fatigue = fatigue[3] - fatigue[2];
+        assert!(last_fatigue > last_elevation);
+
+        // ...and longer than the identical flat segment before it
+        let second_to_last = fatigue[2] - fatigue[1];
+        assert!(last_fatigue > second_to_last);
+
+        // The early climb correspondingly takes a smaller share
+        assert!(fatigue[1] < elevation[1]);
+    }
+
+    #[test]
+    fn test_zero_fatigue_matches_elevation_model() {
+        let checkpoints = vec![cp(0.0, 100.0), cp(5.0, 150.0), cp(12.0, 90.0)];
+        let elevation = calculate_pass_time_fractions(&checkpoints, &PacingParams::default());
+        let fatigue =
+            calculate_pass_time_fractions_with_fatigue(&checkpoints, &PacingParams::default(), 0.0);
+        for (a, b) in elevation.iter().zip(&fatigue) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        assert!(
+            calculate_pass_time_fractions_with_fatigue(&[], &PacingParams::default(), 0.2)
+                .is_empty()
+        );
+        assert_eq!(
+            calculate_pass_time_fractions_with_fatigue(
+                &[cp(0.0, 0.0)],
+                &PacingParams::default(),
+                0.2
+            ),
+            vec![0.0]
+        );
+    }
+}
@@ -11,50 +11,99 @@
 //! - State is in-memory (`Arc<RwLock<PollerState>>`); on restart, schedule
 //!   reconstructs from `yr_responses.expires_at`
 
-use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
 use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, watch, RwLock};
+use tokio_util::sync::CancellationToken;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::config::AppConfig;
 use crate::db::models::Checkpoint;
 use crate::db::queries;
-use crate::helpers::dec_to_f64;
-use crate::services::forecast::{build_single_insert_params, ensure_yr_cache_fresh};
-use crate::services::yr::{extract_forecasts_at_times, YrClient};
+use crate::errors::AppError;
+use crate::helpers::{dec_to_f64, estimate_next_model_run};
+use crate::services::forecast::{build_single_insert_params, ensure_yr_cache_fresh, floor_to_hour};
+use crate::services::yr::{
+    extract_forecasts_at_times, extract_model_run_at, ForecastResolution, YrClient,
+};
 
 // ---------------------------------------------------------------------------
 // Constants
 // ---------------------------------------------------------------------------
 
-/// Slowest realistic pace for cross-country skiing (km/h).
-const POLLER_MIN_SPEED_KMH: f64 = 10.0;
-
-/// Fastest realistic pace for cross-country skiing (km/h).
-const POLLER_MAX_SPEED_KMH: f64 = 30.0;
-
-/// How far ahead to look for upcoming races (days).
-const POLLER_LOOKAHEAD_DAYS: i64 = 10;
-
-/// Buffer added after the earliest `expires_at` before waking (seconds).
-const POLLER_WAKEUP_BUFFER_SECS: u64 = 30;
-
-/// Minimum sleep duration between poll cycles (seconds).
-const POLLER_MIN_SLEEP_SECS: u64 = 60;
+/// Maximum retries when yr.no keeps returning 304 after expiry.
+const POLLER_MAX_RETRIES: u32 = 5;
 
-/// Maximum sleep duration between poll cycles (seconds).
-const POLLER_MAX_SLEEP_SECS: u64 = 1800;
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
 
-/// Delay between retries when yr.no returns 304 (seconds).
-const POLLER_RETRY_DELAY_SECS: u64 = 120;
+/// Poller tuning, taken from `AppConfig` at startup.
+#[derive(Debug, Clone, Copy)]
+pub struct PollerConfig {
+    /// How far ahead to look for upcoming races (days).
+    pub lookahead_days: i64,
+    /// Slowest realistic pace for cross-country skiing (km/h).
+    pub min_speed_kmh: f64,
+    /// Fastest realistic pace for cross-country skiing (km/h).
+    pub max_speed_kmh: f64,
+    /// Buffer added after the earliest `expires_at` before waking (seconds).
+    pub wakeup_buffer_secs: u64,
+    /// Minimum sleep duration between poll cycles (seconds).
+    pub min_sleep_secs: u64,
+    /// Maximum sleep duration between poll cycles (seconds).
+    pub max_sleep_secs: u64,
+    /// Delay between retries when yr.no returns 304 (seconds).
+    pub retry_delay_secs: u64,
+    /// Fallback sleep when no upcoming races exist (seconds).
+    pub no_races_sleep_secs: u64,
+    /// Model run age (hours) after which a checkpoint is flagged as overdue.
+    pub model_run_overdue_hours: i64,
+    /// Hours after which a status the current cycle hasn't refreshed yet is dropped.
+    pub checkpoint_status_retention_hours: i64,
+}
 
-/// Maximum retries when yr.no keeps returning 304 after expiry.
-const POLLER_MAX_RETRIES: u32 = 5;
+impl PollerConfig {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            lookahead_days: config.poller_lookahead_days,
+            min_speed_kmh: config.poller_min_speed_kmh,
+            max_speed_kmh: config.poller_max_speed_kmh,
+            wakeup_buffer_secs: config.poller_wakeup_buffer_secs,
+            min_sleep_secs: config.poller_min_sleep_secs,
+            max_sleep_secs: config.poller_max_sleep_secs,
+            retry_delay_secs: config.poller_retry_delay_secs,
+            no_races_sleep_secs: config.poller_no_races_sleep_secs,
+            model_run_overdue_hours: config.model_run_overdue_hours,
+            checkpoint_status_retention_hours: config.checkpoint_status_retention_hours,
+        }
+    }
+}
 
-/// Fallback sleep when no upcoming races exist (seconds).
-const POLLER_NO_RACES_SLEEP_SECS: u64 = 3600;
+#[cfg(test)]
+impl PollerConfig {
+    /// The `AppConfig` defaults.
+    pub(crate) fn defaults() -> Self {
+        Self {
+            lookahead_days: 10,
+            min_speed_kmh: 10.0,
+            max_speed_kmh: 30.0,
+            wakeup_buffer_secs: 30,
+            min_sleep_secs: 60,
+            max_sleep_secs: 1800,
+            retry_delay_secs: 120,
+            no_races_sleep_secs: 3600,
+            model_run_overdue_hours: 12,
+            checkpoint_status_retention_hours: 48,
+        }
+    }
+}
 
 // ---------------------------------------------------------------------------
 // Poller state (in-memory, shared via Arc<RwLock<>>)
@@ -67,22 +116,66 @@ pub struct CheckpointPollStatus {
     pub checkpoint_name: String,
     pub race_name: String,
     pub distance_km: f64,
+    /// Refresh priority, 1 (lowest) to 5 (highest); higher is polled first
+    pub poll_priority: i32,
     pub expires_at: Option<DateTime<Utc>>,
     pub last_fetched_at: Option<DateTime<Utc>>,
     pub last_model_run_at: Option<DateTime<Utc>>,
     /// "new_data", "not_modified", "error", or "pending"
     pub last_poll_result: String,
     pub extraction_count: usize,
+    /// Minutes since `last_model_run_at`, computed when the status is read
+    pub model_run_age_minutes: Option<u32>,
+    /// When yr.no's next model run is expected (6 h after `last_model_run_at`,
+    /// snapped to 00/06/12/18 UTC), computed when the status is read
+    pub next_model_run_expected_at: Option<DateTime<Utc>>,
+    /// True when the model run is older than `FORECAST_STALE_WARN_MINUTES`
+    pub stale_warning: bool,
+    /// True when the model run is older than `MODEL_RUN_OVERDUE_HOURS`,
+    /// i.e. yr.no hasn't published a new run in an unusually long time
+    pub model_run_overdue: bool,
+    /// When the poller last polled this checkpoint. Checkpoints not seen for
+    /// `CHECKPOINT_STATUS_RETENTION_HOURS` (ended or deleted races) are dropped.
+    pub last_seen_at: DateTime<Utc>,
 }
 
+/// `checkpoint_id → (fetched_at, expires_at)` of cached yr.no responses.
+pub(crate) type YrCacheTimes = HashMap<Uuid, (DateTime<Utc>, DateTime<Utc>)>;
+
 /// Global poller state, exposed via the status endpoint.
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct PollerState {
     pub active: bool,
     pub next_wakeup_at: Option<DateTime<Utc>>,
+    /// Seconds until `next_wakeup_at` (0 if already due), computed on read
+    pub next_wakeup_in_seconds: Option<i64>,
     pub last_poll_completed_at: Option<DateTime<Utc>>,
     pub last_poll_duration_ms: Option<u64>,
     pub total_polls: u64,
+    /// Whether polling is paused via the admin API
+    pub paused: bool,
+    /// When the poller was paused (null while running)
+    pub paused_since: Option<DateTime<Utc>>,
+    /// Number of checkpoints with `stale_warning` set
+    pub checkpoints_with_stale_warning: usize,
+    /// Number of checkpoints with `model_run_overdue` set
+    pub overdue_checkpoints_count: usize,
+    /// Whether this instance held the poller advisory lock in its last cycle.
+    /// With several instances, only the lock holder polls yr.no.
+    pub acquired_poll_lock: bool,
+    /// When the periodic `VACUUM ANALYZE` last completed (null before the
+    /// first run or when disabled)
+    pub last_db_maintenance_at: Option<DateTime<Utc>>,
+    /// Seconds since the least recently fetched checkpoint of an upcoming
+    /// race was fetched from yr.no, as of the last cycle. A never-fetched
+    /// checkpoint counts from its race's start (0 before the start).
+    /// Null before the first cycle or without upcoming races.
+    pub oldest_unpolled_checkpoint_age_seconds: Option<i64>,
+    /// Checkpoints of upcoming races whose cached yr.no response had not
+    /// expired at the end of the last cycle
+    pub checkpoints_with_valid_cache: usize,
+    /// Checkpoints of upcoming races polled in the last cycle
+    pub checkpoints_total: usize,
     pub checkpoints: Vec<CheckpointPollStatus>,
 }
 
@@ -91,17 +184,217 @@ impl PollerState {
         Self {
             active: true,
             next_wakeup_at: None,
+            next_wakeup_in_seconds: None,
             last_poll_completed_at: None,
             last_poll_duration_ms: None,
             total_polls: 0,
+            paused: false,
+            paused_since: None,
+            checkpoints_with_stale_warning: 0,
+            overdue_checkpoints_count: 0,
+            acquired_poll_lock: false,
+            last_db_maintenance_at: None,
+            oldest_unpolled_checkpoint_age_seconds: None,
+            checkpoints_with_valid_cache: 0,
+            checkpoints_total: 0,
             checkpoints: Vec::new(),
         }
     }
+
+    /// Set the cache freshness summary from the upcoming checkpoints
+    /// (`(checkpoint, race_name, race_start)`) and their yr.no cache times.
+    pub(crate) fn set_cache_freshness(
+        &mut self,
+        checkpoints: &[(Checkpoint, String, DateTime<Utc>)],
+        cache_times: &YrCacheTimes,
+        now: DateTime<Utc>,
+    ) {
+        self.checkpoints_total = checkpoints.len();
+        self.checkpoints_with_valid_cache = checkpoints
+            .iter()
+            .filter(|(cp, _, _)| cache_times.get(&cp.id).is_some_and(|(_, exp)| *exp > now))
+            .count();
+        self.oldest_unpolled_checkpoint_age_seconds = checkpoints
+            .iter()
+            .map(|(cp, _, race_start)| match cache_times.get(&cp.id) {
+                Some((fetched_at, _)) => (now - *fetched_at).num_seconds(),
+                // Never fetched: time since the race start as a proxy
+                None => (now - *race_start).num_seconds().max(0),
+            })
+            .max();
+    }
+
+    /// Replace the checkpoint statuses and recount overdue model runs.
+    pub fn set_checkpoints(&mut self, checkpoints: Vec<CheckpointPollStatus>) {
+        self.overdue_checkpoints_count = checkpoints.iter().filter(|c| c.model_run_overdue).count();
+        self.checkpoints = checkpoints;
+    }
+
+    /// Insert or replace statuses by `checkpoint_id` while a cycle is in
+    /// progress. Checkpoints the cycle hasn't reached keep their previous
+    /// status (after the new ones) unless last seen before `stale_before`.
+    /// A completed cycle replaces the list with
+    /// [`set_checkpoints`](Self::set_checkpoints).
+    pub fn upsert_checkpoints(
+        &mut self,
+        statuses: Vec<CheckpointPollStatus>,
+        stale_before: DateTime<Utc>,
+    ) {
+        let polled: HashSet<Uuid> = statuses.iter().map(|c| c.checkpoint_id).collect();
+        let previous = std::mem::take(&mut self.checkpoints);
+        let merged =
+            statuses
+                .into_iter()
+                .chain(previous.into_iter().filter(|c| {
+                    !polled.contains(&c.checkpoint_id) && c.last_seen_at >= stale_before
+                }))
+                .collect();
+        self.set_checkpoints(merged);
+    }
+
+    /// Recompute model run ages, next expected runs and stale warnings as of
+    /// `now`.
+    ///
+    /// Ages grow between poll cycles, so this runs on every status read rather
+    /// than being stored by the poller.
+    pub fn annotate_staleness(&mut self, now: DateTime<Utc>, warn_minutes: u32) {
+        for cp in &mut self.checkpoints {
+            cp.model_run_age_minutes = cp
+                .last_model_run_at
+                .map(|run_at| model_run_age_minutes(run_at, now));
+            cp.next_model_run_expected_at = cp.last_model_run_at.map(estimate_next_model_run);
+            cp.stale_warning = cp
+                .model_run_age_minutes
+                .is_some_and(|age| age > warn_minutes);
+        }
+        self.checkpoints_with_stale_warning = self
+            .checkpoints
+            .iter()
+            .filter(|cp| cp.stale_warning)
+            .count();
+    }
+
+    /// Recompute `next_wakeup_in_seconds` as of `now`.
+    pub fn annotate_next_wakeup(&mut self, now: DateTime<Utc>) {
+        self.next_wakeup_in_seconds = self
+            .next_wakeup_at
+            .map(|at| (at - now).num_seconds().max(0));
+    }
+}
+
+/// Whether a model run is more than `overdue_hours` old. yr.no normally
+/// publishes a new run every ~6 hours.
+fn is_model_run_overdue(
+    model_run_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    overdue_hours: i64,
+) -> bool {
+    now - model_run_at > Duration::hours(overdue_hours)
+}
+
+/// Minutes elapsed since a yr.no model run (0 if the run is in the future).
+pub fn model_run_age_minutes(model_run_at: DateTime<Utc>, now: DateTime<Utc>) -> u32 {
+    (now - model_run_at).num_minutes().clamp(0, u32::MAX as i64) as u32
 }
 
 /// Shared poller state handle.
 pub type SharedPollerState = Arc<RwLock<PollerState>>;
 
+// ---------------------------------------------------------------------------
+// Manual trigger
+// ---------------------------------------------------------------------------
+
+/// Result of a manual trigger request.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TriggerOutcome {
+    /// The poller will start a cycle as soon as it is idle.
+    Triggered,
+    /// A poll cycle is running right now.
+    AlreadyRunning,
+    /// Polling is paused; resume it first.
+    Paused,
+    /// The poller task has exited (e.g. during shutdown).
+    PollerStopped,
+}
+
+/// Handle for requesting an immediate poll cycle from outside the poller task.
+#[derive(Debug, Clone)]
+pub struct PollerControl {
+    trigger: mpsc::Sender<()>,
+    cycle_running: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    cycles_completed: watch::Receiver<u64>,
+}
+
+impl PollerControl {
+    /// Ask the poller to skip its current sleep and poll immediately.
+    pub fn trigger(&self) -> TriggerOutcome {
+        if self.paused.load(Ordering::SeqCst) {
+            return TriggerOutcome::Paused;
+        }
+        if self.cycle_running.load(Ordering::SeqCst) {
+            return TriggerOutcome::AlreadyRunning;
+        }
+        match self.trigger.try_send(()) {
+            // Full means a trigger is already queued — same effect
+            Ok(()) | Err(mpsc::error::TrySendError::Full(())) => TriggerOutcome::Triggered,
+            Err(mpsc::error::TrySendError::Closed(())) => TriggerOutcome::PollerStopped,
+        }
+    }
+
+    /// Number of completed poll cycles; changes after every cycle that
+    /// refreshed upcoming races.
+    pub fn cycles_completed(&self) -> watch::Receiver<u64> {
+        self.cycles_completed.clone()
+    }
+
+    /// Pause polling. Returns `false` if already paused.
+    pub fn pause(&self) -> bool {
+        !self.paused.swap(true, Ordering::SeqCst)
+    }
+
+    /// Resume polling and wake the poller so it doesn't wait out its pause
+    /// sleep. Returns `false` if it wasn't paused.
+    pub fn resume(&self) -> bool {
+        let was_paused = self.paused.swap(false, Ordering::SeqCst);
+        if was_paused {
+            let _ = self.trigger.try_send(());
+        }
+        was_paused
+    }
+}
+
+/// Receiving side of [`PollerControl`], owned by `run_poller`.
+#[derive(Debug)]
+pub struct PollerTrigger {
+    rx: mpsc::Receiver<()>,
+    cycle_running: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    cycles_completed: watch::Sender<u64>,
+}
+
+/// Create a connected control/trigger pair.
+pub fn poller_control() -> (PollerControl, PollerTrigger) {
+    let (tx, rx) = mpsc::channel(1);
+    let cycle_running = Arc::new(AtomicBool::new(false));
+    let paused = Arc::new(AtomicBool::new(false));
+    let (cycles_tx, cycles_rx) = watch::channel(0);
+    (
+        PollerControl {
+            trigger: tx,
+            cycle_running: cycle_running.clone(),
+            paused: paused.clone(),
+            cycles_completed: cycles_rx,
+        },
+        PollerTrigger {
+            rx,
+            cycle_running,
+            paused,
+            cycles_completed: cycles_tx,
+        },
+    )
+}
+
 // ---------------------------------------------------------------------------
 // Time-band calculation
 // ---------------------------------------------------------------------------
@@ -111,140 +404,270 @@ pub type SharedPollerState = Arc<RwLock<PollerState>>;
 /// speed bounds.
 ///
 /// Returns a sorted, deduplicated list of hourly UTC times.
-pub fn compute_extraction_times(race_start: DateTime<Utc>, distance_km: f64) -> Vec<DateTime<Utc>> {
+pub fn compute_extraction_times(
+    race_start: DateTime<Utc>,
+    distance_km: f64,
+    config: &PollerConfig,
+) -> Vec<DateTime<Utc>> {
+    compute_extraction_times_with_interval(race_start, distance_km, config, 1)
+}
+
+/// Like [`compute_extraction_times`], but with a slot every `interval_hours`
+/// hours on a grid anchored at the race start hour.
+///
+/// Every slot is also an hourly slot, so a coarser grid only ever needs
+/// forecasts the poller already extracts.
+pub fn compute_extraction_times_with_interval(
+    race_start: DateTime<Utc>,
+    distance_km: f64,
+    config: &PollerConfig,
+    interval_hours: u32,
+) -> Vec<DateTime<Utc>> {
+    let start_hour = floor_to_hour(race_start);
     if distance_km <= 0.0 {
         // Start checkpoint — extract at race start time (floored to hour)
-        let start_hour = floor_to_hour(race_start);
         return vec![start_hour];
     }
 
     // Earliest arrival: fastest pace
-    let earliest_hours = distance_km / POLLER_MAX_SPEED_KMH;
+    let earliest_hours = distance_km / config.max_speed_kmh;
     // Latest arrival: slowest pace
-    let latest_hours = distance_km / POLLER_MIN_SPEED_KMH;
+    let latest_hours = distance_km / config.min_speed_kmh;
 
     let earliest_arrival = race_start + Duration::seconds((earliest_hours * 3600.0) as i64);
     let latest_arrival = race_start + Duration::seconds((latest_hours * 3600.0) as i64);
 
-    let first_slot = floor_to_hour(earliest_arrival);
-    let last_slot = ceil_to_hour(latest_arrival);
-
-    let mut times = Vec::new();
-    let mut current = first_slot;
-    while current <= last_slot {
-        times.push(current);
-        current += Duration::hours(1);
+    // Last slot at or before the earliest arrival through the first slot at
+    // or after the latest arrival
+    let interval = Duration::hours(i64::from(interval_hours.max(1)));
+    let slot_index = |t: DateTime<Utc>| {
+        (t - start_hour)
+            .num_seconds()
+            .div_euclid(interval.num_seconds())
+    };
+    let first = slot_index(earliest_arrival);
+    let mut last = slot_index(latest_arrival);
+    if start_hour + interval * last as i32 != latest_arrival {
+        last += 1;
     }
-
-    times
-}
-
-/// Floor a datetime to the start of its hour.
-fn floor_to_hour(dt: DateTime<Utc>) -> DateTime<Utc> {
-    dt.date_naive()
-        .and_hms_opt(dt.time().hour(), 0, 0)
-        .map(|naive| DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc))
-        .unwrap_or(dt)
+    (first..=last)
+        .map(|k| start_hour + interval * k as i32)
+        .collect()
 }
 
-/// Ceil a datetime to the next hour (or same if already on the hour).
-fn ceil_to_hour(dt: DateTime<Utc>) -> DateTime<Utc> {
-    if dt.time().minute() == 0 && dt.time().second() == 0 && dt.time().nanosecond() == 0 {
-        dt
-    } else {
-        floor_to_hour(dt) + Duration::hours(1)
-    }
+/// The slot of the `interval_hours` grid anchored at the race start hour
+/// (see [`compute_extraction_times_with_interval`]) nearest to `t`.
+pub fn nearest_time_slot(
+    race_start: DateTime<Utc>,
+    t: DateTime<Utc>,
+    interval_hours: u32,
+) -> DateTime<Utc> {
+    let start_hour = floor_to_hour(race_start);
+    let interval_secs = i64::from(interval_hours.max(1)) * 3600;
+    let offset_secs = (t - start_hour).num_seconds();
+    let k = (offset_secs + interval_secs / 2).div_euclid(interval_secs);
+    start_hour + Duration::seconds(k * interval_secs)
 }
 
 // ---------------------------------------------------------------------------
 // Main poller loop
 // ---------------------------------------------------------------------------
 
-/// Run the background poller. This function never returns (runs until process exit).
+/// Run the background poller until `shutdown` is cancelled.
 ///
-/// Should be spawned via `tokio::spawn(run_poller(...))`.
-pub async fn run_poller(pool: PgPool, yr_client: YrClient, state: SharedPollerState) {
-    tracing::info!("Background poller started");
+/// Should be spawned via `tokio::spawn(run_poller(...))`. Cancellation is
+/// observed between poll cycles, so an in-progress cycle is never cut short
+/// mid-write. A message on `trigger` cuts the current sleep short.
+pub async fn run_poller(
+    pool: PgPool,
+    yr_client: YrClient,
+    state: SharedPollerState,
+    shutdown: CancellationToken,
+    mut trigger: PollerTrigger,
+    config: PollerConfig,
+) {
+    tracing::info!(
+        "Background poller started: lookahead {} days, speeds {}–{} km/h, \
+         wakeup buffer {}s, sleep {}–{}s, retry delay {}s, no-races sleep {}s, \
+         model run overdue after {}h",
+        config.lookahead_days,
+        config.min_speed_kmh,
+        config.max_speed_kmh,
+        config.wakeup_buffer_secs,
+        config.min_sleep_secs,
+        config.max_sleep_secs,
+        config.retry_delay_secs,
+        config.no_races_sleep_secs,
+        config.model_run_overdue_hours,
+    );
+    let mut previously_overdue: HashSet<Uuid> = HashSet::new();
 
     loop {
-        let poll_start = Utc::now();
-
-        // 1. Find upcoming races and their checkpoints
-        let races = match queries::get_upcoming_races_with_checkpoints(&pool, POLLER_LOOKAHEAD_DAYS)
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                tracing::error!("Poller: failed to query upcoming races: {}", e);
-                sleep_secs(POLLER_MIN_SLEEP_SECS).await;
-                continue;
-            }
-        };
-
-        if races.is_empty() {
-            handle_no_races(&state).await;
-            sleep_secs(POLLER_NO_RACES_SLEEP_SECS).await;
+        if shutdown.is_cancelled() {
+            break;
+        }
+        if trigger.paused.load(Ordering::SeqCst) {
+            tracing::info!(
+                "Poller: paused, checking again in {} seconds",
+                config.min_sleep_secs
+            );
+            sleep_until_next_cycle(config.min_sleep_secs, &shutdown, &mut trigger, &state).await;
             continue;
         }
+        trigger.cycle_running.store(true, Ordering::SeqCst);
 
-        // 2. Build list of all checkpoints to poll
-        let all_checkpoints = collect_checkpoints(&races);
-        let checkpoint_ids: Vec<Uuid> = all_checkpoints.iter().map(|(cp, _, _)| cp.id).collect();
+        // Only one instance polls at a time (see `queries::try_acquire_poll_lock`)
+        let lock = queries::try_acquire_poll_lock(&pool).await;
+        let cycle = run_poll_cycle(
+            &pool,
+            &yr_client,
+            &state,
+            &config,
+            &mut previously_overdue,
+            &trigger.cycles_completed,
+        );
+        let sleep_duration =
+            poll_if_locked(lock, &state, &config, cycle, queries::release_poll_lock).await;
 
-        // 3. Get pre-poll fetched_at for each checkpoint (to detect 304 vs new data)
-        let pre_fetched_at = build_pre_fetched_map(&pool, &all_checkpoints).await;
+        sleep_until_next_cycle(sleep_duration, &shutdown, &mut trigger, &state).await;
+    }
 
-        // 4. Refresh yr.no cache for all checkpoints
-        let (mut checkpoint_statuses, any_got_304) =
-            poll_all_checkpoints(&pool, &yr_client, &all_checkpoints, &pre_fetched_at).await;
+    trigger.cycle_running.store(false, Ordering::SeqCst);
+    state.write().await.active = false;
+    tracing::info!("Background poller stopped");
+}
 
-        // 5. Publish intermediate state so the status endpoint is useful mid-cycle
-        {
+/// Run `cycle` and then `release` the lock if this instance got the poll
+/// lock; otherwise stand by. Returns the number of seconds to sleep.
+async fn poll_if_locked<L, R>(
+    lock: Result<Option<L>, sqlx::Error>,
+    state: &SharedPollerState,
+    config: &PollerConfig,
+    cycle: impl Future<Output = u64>,
+    release: impl FnOnce(L) -> R,
+) -> u64
+where
+    R: Future<Output = Result<(), sqlx::Error>>,
+{
+    match lock {
+        Ok(Some(lock)) => {
+            state.write().await.acquired_poll_lock = true;
+            let sleep_duration = cycle.await;
+            if let Err(e) = release(lock).await {
+                tracing::error!("Poller: failed to release poll lock: {}", e);
+            }
+            sleep_duration
+        }
+        Ok(None) => {
+            tracing::debug!(
+                "Poller: another instance holds the poll lock, retrying in {} seconds",
+                config.min_sleep_secs
+            );
+            // Keep the wakeup current so standby instances stay healthy
             let mut s = state.write().await;
-            s.checkpoints = checkpoint_statuses.clone();
-        }
-
-        // 6. Retry logic — if we got 304s, wait and retry up to MAX_RETRIES
-        if any_got_304 {
-            retry_304_checkpoints(
-                &pool,
-                &yr_client,
-                &all_checkpoints,
-                &pre_fetched_at,
-                &mut checkpoint_statuses,
-                &state,
-            )
-            .await;
+            s.acquired_poll_lock = false;
+            s.next_wakeup_at = Some(Utc::now() + Duration::seconds(config.min_sleep_secs as i64));
+            config.min_sleep_secs
         }
+        Err(e) => {
+            tracing::error!("Poller: failed to acquire poll lock: {}", e);
+            config.min_sleep_secs
+        }
+    }
+}
 
-        // 7–8. Compute next wakeup and update final state
-        let sleep_duration = finalize_poll_cycle(
-            &pool,
-            &state,
-            &checkpoint_ids,
-            checkpoint_statuses,
-            poll_start,
+/// One poll cycle. Returns the number of seconds to sleep before the next.
+async fn run_poll_cycle(
+    pool: &PgPool,
+    yr_client: &YrClient,
+    state: &SharedPollerState,
+    config: &PollerConfig,
+    previously_overdue: &mut HashSet<Uuid>,
+    cycles_completed: &watch::Sender<u64>,
+) -> u64 {
+    let poll_start = Utc::now();
+
+    // 1. Find upcoming races and their checkpoints
+    let races =
+        match queries::get_upcoming_races_with_checkpoints(pool, config.lookahead_days).await {
+            Ok(r) => r,
+            Err(e) => {
+                tracing::error!("Poller: failed to query upcoming races: {}", e);
+                return config.min_sleep_secs;
+            }
+        };
+
+    if races.is_empty() {
+        handle_no_races(state, config).await;
+        return config.no_races_sleep_secs;
+    }
+
+    // 2. Build list of all checkpoints to poll
+    let all_checkpoints = collect_checkpoints(&races);
+
+    // 3. Get pre-poll fetched_at for each checkpoint (to detect 304 vs new data)
+    let pre_fetched_at = build_pre_fetched_map(pool, &all_checkpoints).await;
+
+    // 4. Refresh yr.no cache for all checkpoints
+    let (mut checkpoint_statuses, any_got_304) =
+        poll_all_checkpoints(pool, yr_client, &all_checkpoints, &pre_fetched_at, config).await;
+
+    // 5. Publish intermediate state so the status endpoint is useful mid-cycle
+    state
+        .write()
+        .await
+        .upsert_checkpoints(checkpoint_statuses.clone(), status_cutoff(config));
+
+    // 6. Retry logic — if we got 304s, wait and retry up to MAX_RETRIES
+    if any_got_304 {
+        retry_304_checkpoints(
+            pool,
+            yr_client,
+            &all_checkpoints,
+            &pre_fetched_at,
+            &mut checkpoint_statuses,
+            state,
+            config,
         )
         .await;
-
-        sleep_secs(sleep_duration).await;
     }
+
+    warn_newly_overdue(&checkpoint_statuses, previously_overdue);
+
+    // 7–8. Compute next wakeup and update final state
+    let sleep_duration = finalize_poll_cycle(
+        pool,
+        state,
+        &all_checkpoints,
+        checkpoint_statuses,
+        poll_start,
+        config,
+    )
+    .await;
+
+    // 9. Let listeners (e.g. the forecast summary task) pick up the new data
+    cycles_completed.send_modify(|n| *n += 1);
+    sleep_duration
 }
 
 /// Update state and sleep when no upcoming races exist.
-async fn handle_no_races(state: &SharedPollerState) {
+async fn handle_no_races(state: &SharedPollerState, config: &PollerConfig) {
     tracing::debug!(
         "Poller: no upcoming races within {} days, sleeping {} seconds",
-        POLLER_LOOKAHEAD_DAYS,
-        POLLER_NO_RACES_SLEEP_SECS
+        config.lookahead_days,
+        config.no_races_sleep_secs
     );
     let mut s = state.write().await;
-    s.checkpoints.clear();
-    s.next_wakeup_at = Some(Utc::now() + Duration::seconds(POLLER_NO_RACES_SLEEP_SECS as i64));
+    s.set_checkpoints(Vec::new());
+    s.set_cache_freshness(&[], &HashMap::new(), Utc::now());
+    s.next_wakeup_at = Some(Utc::now() + Duration::seconds(config.no_races_sleep_secs as i64));
     s.last_poll_completed_at = Some(Utc::now());
 }
 
-/// Flatten races into `(Checkpoint, race_name, race_start)` tuples.
+/// Flatten races into `(Checkpoint, race_name, race_start)` tuples, highest
+/// `poll_priority` first so important checkpoints are refreshed (and pay the
+/// cache-miss cost) before remote ones. Course order is kept within a
+/// priority, and the lowest priorities come last if a cycle is cut short.
 fn collect_checkpoints(
     races: &[queries::RaceWithCheckpoints],
 ) -> Vec<(Checkpoint, String, DateTime<Utc>)> {
@@ -254,6 +677,7 @@ fn collect_checkpoints(
             all.push((cp.clone(), rwc.race.name.clone(), rwc.race.start_time));
         }
     }
+    all.sort_by_key(|(cp, _, _)| std::cmp::Reverse(cp.poll_priority));
     all
 }
 
@@ -290,26 +714,64 @@ async fn poll_all_checkpoints(
     yr_client: &YrClient,
     all_checkpoints: &[(Checkpoint, String, DateTime<Utc>)],
     pre_fetched_at: &std::collections::HashMap<Uuid, Option<DateTime<Utc>>>,
+    config: &PollerConfig,
 ) -> (Vec<CheckpointPollStatus>, bool) {
     let mut statuses = Vec::with_capacity(all_checkpoints.len());
     let mut any_got_304 = false;
 
     for (cp, race_name, race_start) in all_checkpoints {
-        let result = poll_single_checkpoint(pool, yr_client, cp, *race_start, pre_fetched_at).await;
-        let status = build_poll_status(cp, race_name, result, &mut any_got_304);
+        let result =
+            poll_single_checkpoint(pool, yr_client, cp, *race_start, pre_fetched_at, config).await;
+        let status = build_poll_status(
+            cp,
+            race_name,
+            result,
+            &mut any_got_304,
+            config.model_run_overdue_hours,
+        );
         statuses.push(status);
     }
 
     (statuses, any_got_304)
 }
 
+/// Log a warning for checkpoints whose model run became overdue this cycle,
+/// then remember the current overdue set for the next cycle.
+fn warn_newly_overdue(statuses: &[CheckpointPollStatus], previously_overdue: &mut HashSet<Uuid>) {
+    let overdue: HashSet<Uuid> = statuses
+        .iter()
+        .filter(|s| s.model_run_overdue)
+        .map(|s| s.checkpoint_id)
+        .collect();
+    for status in statuses
+        .iter()
+        .filter(|s| s.model_run_overdue && !previously_overdue.contains(&s.checkpoint_id))
+    {
+        tracing::warn!(
+            "Poller: no new yr.no model run for checkpoint {} ({}) since {}",
+            status.checkpoint_name,
+            status.race_name,
+            status
+                .last_model_run_at
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default()
+        );
+    }
+    *previously_overdue = overdue;
+}
+
 /// Convert a `PollResult` into a `CheckpointPollStatus`.
 fn build_poll_status(
     cp: &Checkpoint,
     race_name: &str,
     result: PollResult,
     any_got_304: &mut bool,
+    overdue_hours: i64,
 ) -> CheckpointPollStatus {
+    let now = Utc::now();
+    let overdue = |run_at: Option<DateTime<Utc>>| {
+        run_at.is_some_and(|t| is_model_run_overdue(t, now, overdue_hours))
+    };
     match result {
         PollResult::NewData {
             expires_at,
@@ -321,11 +783,17 @@ fn build_poll_status(
             checkpoint_name: cp.name.clone(),
             race_name: race_name.to_string(),
             distance_km: dec_to_f64(cp.distance_km),
+            poll_priority: cp.poll_priority,
             expires_at: Some(expires_at),
             last_fetched_at: Some(fetched_at),
             last_model_run_at: model_run_at,
             last_poll_result: "new_data".to_string(),
             extraction_count,
+            model_run_age_minutes: None,
+            next_model_run_expected_at: None,
+            stale_warning: false,
+            model_run_overdue: overdue(model_run_at),
+            last_seen_at: now,
         },
         PollResult::NotModified {
             expires_at,
@@ -338,11 +806,17 @@ fn build_poll_status(
                 checkpoint_name: cp.name.clone(),
                 race_name: race_name.to_string(),
                 distance_km: dec_to_f64(cp.distance_km),
+                poll_priority: cp.poll_priority,
                 expires_at: Some(expires_at),
                 last_fetched_at: fetched_at,
                 last_model_run_at: model_run_at,
                 last_poll_result: "not_modified".to_string(),
                 extraction_count: 0,
+                model_run_age_minutes: None,
+                next_model_run_expected_at: None,
+                stale_warning: false,
+                model_run_overdue: overdue(model_run_at),
+                last_seen_at: now,
             }
         }
         PollResult::Error(msg) => CheckpointPollStatus {
@@ -350,11 +824,17 @@ fn build_poll_status(
             checkpoint_name: cp.name.clone(),
             race_name: race_name.to_string(),
             distance_km: dec_to_f64(cp.distance_km),
+            poll_priority: cp.poll_priority,
             expires_at: None,
             last_fetched_at: None,
             last_model_run_at: None,
             last_poll_result: format!("error: {}", msg),
             extraction_count: 0,
+            model_run_age_minutes: None,
+            next_model_run_expected_at: None,
+            stale_warning: false,
+            model_run_overdue: false,
+            last_seen_at: now,
         },
     }
 }
@@ -367,6 +847,7 @@ async fn retry_304_checkpoints(
     pre_fetched_at: &std::collections::HashMap<Uuid, Option<DateTime<Utc>>>,
     checkpoint_statuses: &mut [CheckpointPollStatus],
     state: &SharedPollerState,
+    config: &PollerConfig,
 ) {
     for retry in 1..=POLLER_MAX_RETRIES {
         tracing::info!(
@@ -374,7 +855,7 @@ async fn retry_304_checkpoints(
             retry,
             POLLER_MAX_RETRIES
         );
-        sleep_secs(POLLER_RETRY_DELAY_SECS).await;
+        sleep_secs(config.retry_delay_secs).await;
 
         let mut still_304 = false;
         for (i, (cp, race_name, race_start)) in all_checkpoints.iter().enumerate() {
@@ -382,7 +863,8 @@ async fn retry_304_checkpoints(
                 continue;
             }
             let result =
-                poll_single_checkpoint(pool, yr_client, cp, *race_start, pre_fetched_at).await;
+                poll_single_checkpoint(pool, yr_client, cp, *race_start, pre_fetched_at, config)
+                    .await;
             match result {
                 PollResult::NewData {
                     expires_at,
@@ -395,11 +877,19 @@ async fn retry_304_checkpoints(
                         checkpoint_name: cp.name.clone(),
                         race_name: race_name.clone(),
                         distance_km: dec_to_f64(cp.distance_km),
+                        poll_priority: cp.poll_priority,
                         expires_at: Some(expires_at),
                         last_fetched_at: Some(fetched_at),
                         last_model_run_at: model_run_at,
                         last_poll_result: "new_data".to_string(),
                         extraction_count,
+                        model_run_age_minutes: None,
+                        next_model_run_expected_at: None,
+                        stale_warning: false,
+                        model_run_overdue: model_run_at.is_some_and(|t| {
+                            is_model_run_overdue(t, Utc::now(), config.model_run_overdue_hours)
+                        }),
+                        last_seen_at: Utc::now(),
                     };
                 }
                 PollResult::NotModified { .. } => {
@@ -414,7 +904,7 @@ async fn retry_304_checkpoints(
         // Update state after each retry pass
         {
             let mut s = state.write().await;
-            s.checkpoints = checkpoint_statuses.to_vec();
+            s.upsert_checkpoints(checkpoint_statuses.to_vec(), status_cutoff(config));
         }
 
         if !still_304 {
@@ -424,35 +914,59 @@ async fn retry_304_checkpoints(
     }
 }
 
+/// Statuses not refreshed since this are dropped from the mid-cycle view.
+fn status_cutoff(config: &PollerConfig) -> DateTime<Utc> {
+    Utc::now() - Duration::hours(config.checkpoint_status_retention_hours)
+}
+
 /// Compute next wakeup, update final state, and return the sleep duration in seconds.
 async fn finalize_poll_cycle(
     pool: &PgPool,
     state: &SharedPollerState,
-    checkpoint_ids: &[Uuid],
+    all_checkpoints: &[(Checkpoint, String, DateTime<Utc>)],
     checkpoint_statuses: Vec<CheckpointPollStatus>,
     poll_start: DateTime<Utc>,
+    config: &PollerConfig,
 ) -> u64 {
-    let earliest_expiry = match queries::get_earliest_expiry(pool, checkpoint_ids).await {
+    let checkpoint_ids: Vec<Uuid> = all_checkpoints.iter().map(|(cp, _, _)| cp.id).collect();
+    // On failure the previous freshness summary is kept
+    let cache_times: Option<YrCacheTimes> =
+        match queries::get_yr_cache_times(pool, &checkpoint_ids).await {
+            Ok(rows) => Some(
+                rows.into_iter()
+                    .map(|(id, fetched_at, expires_at)| (id, (fetched_at, expires_at)))
+                    .collect(),
+            ),
+            Err(e) => {
+                tracing::error!("Poller: failed to query yr.no cache times: {}", e);
+                None
+            }
+        };
+    let earliest_expiry = match queries::get_earliest_expiry(pool, &checkpoint_ids).await {
         Ok(Some(exp)) => exp,
-        Ok(None) => Utc::now() + Duration::seconds(POLLER_MAX_SLEEP_SECS as i64),
+        Ok(None) => Utc::now() + Duration::seconds(config.max_sleep_secs as i64),
         Err(e) => {
             tracing::error!("Poller: failed to query earliest expiry: {}", e);
-            Utc::now() + Duration::seconds(POLLER_MAX_SLEEP_SECS as i64)
+            Utc::now() + Duration::seconds(config.max_sleep_secs as i64)
         }
     };
 
-    let next_wakeup = earliest_expiry + Duration::seconds(POLLER_WAKEUP_BUFFER_SECS as i64);
+    let next_wakeup = earliest_expiry + Duration::seconds(config.wakeup_buffer_secs as i64);
 
     let sleep_duration = {
         let until_wakeup = (next_wakeup - Utc::now()).num_seconds().max(0) as u64;
-        until_wakeup.clamp(POLLER_MIN_SLEEP_SECS, POLLER_MAX_SLEEP_SECS)
+        until_wakeup.clamp(config.min_sleep_secs, config.max_sleep_secs)
     };
 
     let poll_duration_ms = (Utc::now() - poll_start).num_milliseconds().max(0) as u64;
 
     {
         let mut s = state.write().await;
-        s.checkpoints = checkpoint_statuses;
+        // The cycle polled every active checkpoint: ended or deleted races drop out
+        s.set_checkpoints(checkpoint_statuses);
+        if let Some(cache_times) = &cache_times {
+            s.set_cache_freshness(all_checkpoints, cache_times, Utc::now());
+        }
         s.next_wakeup_at = Some(Utc::now() + Duration::seconds(sleep_duration as i64));
         s.last_poll_completed_at = Some(Utc::now());
         s.last_poll_duration_ms = Some(poll_duration_ms);
@@ -494,6 +1008,7 @@ async fn poll_single_checkpoint(
     checkpoint: &Checkpoint,
     race_start: DateTime<Utc>,
     pre_fetched_at: &std::collections::HashMap<Uuid, Option<DateTime<Utc>>>,
+    config: &PollerConfig,
 ) -> PollResult {
     // Step 1: Ensure yr.no cache is fresh
     let raw_json = match ensure_yr_cache_fresh(pool, yr_client, checkpoint).await {
@@ -539,7 +1054,7 @@ async fn poll_single_checkpoint(
 
     // Step 3: Extract forecasts at realistic time bands
     let distance_km = dec_to_f64(checkpoint.distance_km);
-    let extraction_times = compute_extraction_times(race_start, distance_km);
+    let extraction_times = compute_extraction_times(race_start, distance_km, config);
 
     if extraction_times.is_empty() {
         return PollResult::NewData {
@@ -550,7 +1065,11 @@ async fn poll_single_checkpoint(
         };
     }
 
-    let extraction_result = match extract_forecasts_at_times(raw_json.clone(), &extraction_times) {
+    let extraction_result = match extract_forecasts_at_times(
+        raw_json.clone(),
+        &extraction_times,
+        ForecastResolution::SixHourly,
+    ) {
         Ok(r) => r,
         Err(e) => {
             tracing::warn!(
@@ -609,15 +1128,114 @@ async fn poll_single_checkpoint(
     }
 }
 
-/// Extract the model run timestamp from a yr.no raw JSON response.
-fn extract_model_run_at(raw_json: &serde_json::Value) -> Option<DateTime<Utc>> {
-    raw_json
-        .get("properties")?
-        .get("meta")?
-        .get("updated_at")?
-        .as_str()
-        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-        .map(|dt| dt.with_timezone(&Utc))
+// ---------------------------------------------------------------------------
+// Re-extraction after start time changes
+// ---------------------------------------------------------------------------
+
+/// Re-extract forecasts for all checkpoints of a race using a new start time.
+///
+/// Called in the background after a race's start time is changed. Only the
+/// cached yr.no responses are used (they are location-based, so still valid);
+/// checkpoints without a cache row are skipped and picked up by the next
+/// poll cycle. Should be spawned via `tokio::spawn`.
+pub async fn reextract_race_forecasts(
+    pool: PgPool,
+    race_id: Uuid,
+    race_start: DateTime<Utc>,
+    config: PollerConfig,
+) {
+    let checkpoints = match queries::get_checkpoints(&pool, race_id).await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!(
+                "Re-extraction: failed to load checkpoints for race {}: {}",
+                race_id,
+                e
+            );
+            return;
+        }
+    };
+
+    let mut total_inserted = 0;
+    for checkpoint in &checkpoints {
+        let cached = match queries::get_yr_cached_response_any(&pool, checkpoint.id).await {
+            Ok(Some(c)) => c,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::warn!(
+                    "Re-extraction: failed to read cache for checkpoint {}: {}",
+                    checkpoint.id,
+                    e
+                );
+                continue;
+            }
+        };
+
+        let rows = match reextraction_rows(
+            checkpoint,
+            cached.raw_response,
+            cached.fetched_at,
+            race_start,
+            &config,
+        ) {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::warn!(
+                    "Re-extraction: extraction failed for checkpoint {}: {}",
+                    checkpoint.id,
+                    e
+                );
+                continue;
+            }
+        };
+
+        for params in rows {
+            let forecast_time = params.forecast_time;
+            match queries::insert_forecast(&pool, params).await {
+                Ok(Some(_)) => total_inserted += 1,
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        "Re-extraction: failed to insert forecast for checkpoint {} at {}: {}",
+                        checkpoint.id,
+                        forecast_time,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    tracing::info!(
+        "Re-extraction: race {} — {} checkpoints processed, inserted {} new rows",
+        race_id,
+        checkpoints.len(),
+        total_inserted
+    );
+}
+
+/// Forecast rows for a checkpoint's pass-through times with the race starting
+/// at `race_start`, extracted from its cached yr.no response.
+fn reextraction_rows(
+    checkpoint: &Checkpoint,
+    raw_response: serde_json::Value,
+    fetched_at: DateTime<Utc>,
+    race_start: DateTime<Utc>,
+    config: &PollerConfig,
+) -> Result<Vec<queries::InsertForecastParams>, AppError> {
+    let extraction_times =
+        compute_extraction_times(race_start, dec_to_f64(checkpoint.distance_km), config);
+    let extraction_result = extract_forecasts_at_times(
+        raw_response,
+        &extraction_times,
+        ForecastResolution::SixHourly,
+    )?;
+    Ok(extraction_result
+        .forecasts
+        .iter()
+        .flatten()
+        .map(|parsed| build_single_insert_params(checkpoint.id, parsed, fetched_at))
+        .collect())
 }
 
 /// Async sleep helper.
@@ -625,6 +1243,27 @@ async fn sleep_secs(secs: u64) {
     tokio::time::sleep(std::time::Duration::from_secs(secs)).await;
 }
 
+/// Sleep for `secs` between poll cycles, returning early if `shutdown` is
+/// cancelled or a manual trigger arrives.
+async fn sleep_until_next_cycle(
+    secs: u64,
+    shutdown: &CancellationToken,
+    trigger: &mut PollerTrigger,
+    state: &SharedPollerState,
+) {
+    trigger.cycle_running.store(false, Ordering::SeqCst);
+    tokio::select! {
+        _ = sleep_secs(secs) => {}
+        _ = shutdown.cancelled() => {
+            tracing::info!("Poller: shutdown requested, stopping");
+        }
+        Some(()) = trigger.rx.recv() => {
+            tracing::info!("Poller: manual trigger received, polling now");
+            state.write().await.next_wakeup_at = Some(Utc::now());
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -632,45 +1271,16 @@ async fn sleep_secs(secs: u64) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Timelike;
 
-    #[test]
-    fn test_floor_to_hour() {
-        let dt = "2026-03-01T07:45:30Z".parse::<DateTime<Utc>>().unwrap();
-        let floored = floor_to_hour(dt);
-        assert_eq!(
-            floored,
-            "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap()
-        );
-    }
-
-    #[test]
-    fn test_floor_to_hour_exact() {
-        let dt = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
-        let floored = floor_to_hour(dt);
-        assert_eq!(floored, dt);
-    }
-
-    #[test]
-    fn test_ceil_to_hour() {
-        let dt = "2026-03-01T07:00:01Z".parse::<DateTime<Utc>>().unwrap();
-        let ceiled = ceil_to_hour(dt);
-        assert_eq!(
-            ceiled,
-            "2026-03-01T08:00:00Z".parse::<DateTime<Utc>>().unwrap()
-        );
-    }
-
-    #[test]
-    fn test_ceil_to_hour_exact() {
-        let dt = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
-        let ceiled = ceil_to_hour(dt);
-        assert_eq!(ceiled, dt, "Exact hour should not be rounded up");
+    fn config() -> PollerConfig {
+        PollerConfig::defaults()
     }
 
     #[test]
     fn test_compute_extraction_times_start_checkpoint() {
         let race_start = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
-        let times = compute_extraction_times(race_start, 0.0);
+        let times = compute_extraction_times(race_start, 0.0, &config());
         assert_eq!(times.len(), 1);
         assert_eq!(times[0], race_start);
     }
@@ -682,7 +1292,7 @@ mod tests {
         // latest   = 45/10 = 4.5 hours → 11:30 → ceil to 12:00
         // Expect: 08:00, 09:00, 10:00, 11:00, 12:00 = 5 slots
         let race_start = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
-        let times = compute_extraction_times(race_start, 45.0);
+        let times = compute_extraction_times(race_start, 45.0, &config());
         assert_eq!(times.len(), 5, "Expected 5 hourly slots, got {:?}", times);
         assert_eq!(
             times[0],
@@ -701,7 +1311,7 @@ mod tests {
         // latest   = 90/10 = 9.0 hours → 16:00 (exact)
         // Expect: 10:00 through 16:00 = 7 slots
         let race_start = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
-        let times = compute_extraction_times(race_start, 90.0);
+        let times = compute_extraction_times(race_start, 90.0, &config());
         assert_eq!(times.len(), 7, "Expected 7 hourly slots, got {:?}", times);
         assert_eq!(
             times[0],
@@ -720,7 +1330,7 @@ mod tests {
         // latest   = 5/10 = 0.5 hours = 30 min → 07:30 → ceil to 08:00
         // Expect: 07:00, 08:00 = 2 slots
         let race_start = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
-        let times = compute_extraction_times(race_start, 5.0);
+        let times = compute_extraction_times(race_start, 5.0, &config());
         assert_eq!(times.len(), 2, "Expected 2 hourly slots, got {:?}", times);
         assert_eq!(
             times[0],
@@ -732,10 +1342,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compute_extraction_times_with_interval() {
+        let at = |t: &str| {
+            format!("2026-03-01T{}:00Z", t)
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        };
+        let times = |start: &str, distance_km: f64, interval_hours: u32| {
+            compute_extraction_times_with_interval(
+                at(start),
+                distance_km,
+                &config(),
+                interval_hours,
+            )
+        };
+
+        // 45 km from 07:20: arrival between 08:50 and 11:50, grid from 07:00
+        assert_eq!(
+            times("07:20", 45.0, 2),
+            vec![at("07:00"), at("09:00"), at("11:00"), at("13:00")]
+        );
+        assert_eq!(
+            times("07:20", 45.0, 3),
+            vec![at("07:00"), at("10:00"), at("13:00")]
+        );
+
+        // 60 km from 07:00: arrival between 09:00 and 13:00. Arrivals on a
+        // grid slot need no slot beyond it
+        assert_eq!(
+            times("07:00", 60.0, 2),
+            vec![at("09:00"), at("11:00"), at("13:00")]
+        );
+        assert_eq!(
+            times("07:00", 60.0, 3),
+            vec![at("07:00"), at("10:00"), at("13:00")]
+        );
+
+        // The start checkpoint only needs the race start hour
+        assert_eq!(times("07:20", 0.0, 2), vec![at("07:00")]);
+        assert_eq!(times("07:20", 0.0, 3), vec![at("07:00")]);
+    }
+
+    #[test]
+    fn test_nearest_time_slot() {
+        let race_start = "2026-03-01T07:20:00Z".parse::<DateTime<Utc>>().unwrap();
+        let at = |t: &str| {
+            format!("2026-03-01T{}:00Z", t)
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        };
+        assert_eq!(nearest_time_slot(race_start, at("07:20"), 2), at("07:00"));
+        assert_eq!(nearest_time_slot(race_start, at("07:59"), 2), at("07:00"));
+        assert_eq!(nearest_time_slot(race_start, at("08:00"), 2), at("09:00"));
+        assert_eq!(nearest_time_slot(race_start, at("10:10"), 2), at("11:00"));
+        assert_eq!(nearest_time_slot(race_start, at("10:10"), 1), at("10:00"));
+    }
+
     #[test]
     fn test_compute_extraction_times_monotonically_increasing() {
         let race_start = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
-        let times = compute_extraction_times(race_start, 60.0);
+        let times = compute_extraction_times(race_start, 60.0, &config());
         for i in 1..times.len() {
             assert!(
                 times[i] > times[i - 1],
@@ -749,7 +1416,7 @@ mod tests {
     #[test]
     fn test_compute_extraction_times_all_on_hour_boundary() {
         let race_start = "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
-        let times = compute_extraction_times(race_start, 45.0);
+        let times = compute_extraction_times(race_start, 45.0, &config());
         for t in &times {
             assert_eq!(
                 t.time().minute(),
@@ -793,4 +1460,440 @@ mod tests {
         let result = extract_model_run_at(&json);
         assert_eq!(result, None);
     }
+
+    #[tokio::test]
+    async fn test_sleep_until_next_cycle_returns_on_cancel() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let (_control, mut trigger) = poller_control();
+        let state: SharedPollerState = Arc::new(RwLock::new(PollerState::new()));
+        // Would sleep for an hour if cancellation were ignored
+        tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            sleep_until_next_cycle(3600, &token, &mut trigger, &state),
+        )
+        .await
+        .expect("sleep should return immediately when cancelled");
+    }
+
+    #[tokio::test]
+    async fn test_sleep_until_next_cycle_returns_on_trigger() {
+        let token = CancellationToken::new();
+        let (control, mut trigger) = poller_control();
+        let state: SharedPollerState = Arc::new(RwLock::new(PollerState::new()));
+        assert_eq!(control.trigger(), TriggerOutcome::Triggered);
+        tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            sleep_until_next_cycle(3600, &token, &mut trigger, &state),
+        )
+        .await
+        .expect("sleep should return immediately when triggered");
+        assert!(state.read().await.next_wakeup_at.is_some());
+    }
+
+    #[test]
+    fn test_trigger_rejected_while_cycle_running() {
+        let (control, trigger) = poller_control();
+        trigger.cycle_running.store(true, Ordering::SeqCst);
+        assert_eq!(control.trigger(), TriggerOutcome::AlreadyRunning);
+    }
+
+    #[test]
+    fn test_trigger_rejected_while_paused() {
+        let (control, _trigger) = poller_control();
+        assert!(control.pause());
+        assert!(!control.pause());
+        assert_eq!(control.trigger(), TriggerOutcome::Paused);
+        assert!(control.resume());
+        assert!(!control.resume());
+    }
+
+    #[test]
+    fn test_trigger_after_poller_stopped() {
+        let (control, trigger) = poller_control();
+        drop(trigger);
+        assert_eq!(control.trigger(), TriggerOutcome::PollerStopped);
+    }
+
+    fn poll_status(model_run_at: Option<DateTime<Utc>>) -> CheckpointPollStatus {
+        CheckpointPollStatus {
+            checkpoint_id: Uuid::new_v4(),
+            checkpoint_name: "Smågan".to_string(),
+            race_name: "Vasaloppet".to_string(),
+            distance_km: 11.0,
+            poll_priority: 3,
+            expires_at: None,
+            last_fetched_at: None,
+            last_model_run_at: model_run_at,
+            last_poll_result: "new_data".to_string(),
+            extraction_count: 0,
+            model_run_age_minutes: None,
+            next_model_run_expected_at: None,
+            stale_warning: false,
+            model_run_overdue: false,
+            last_seen_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_collect_checkpoints_highest_priority_first() {
+        use crate::db::models::Race;
+        use rust_decimal::Decimal;
+
+        let checkpoint = |name: &str, sort_order: i32, poll_priority: i32| Checkpoint {
+            sort_order,
+            poll_priority,
+            ..Checkpoint::fixture(name)
+        };
+        let races = vec![queries::RaceWithCheckpoints {
+            race: Race {
+                id: Uuid::nil(),
+                name: "Vasaloppet".to_string(),
+                year: 2026,
+                start_time: Utc::now(),
+                distance_km: Decimal::from(90),
+                computed_track_length_km: None,
+                country: None,
+                website: None,
+                discipline: None,
+                category: None,
+                elevation_gain_m: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                checkpoint_count: 3,
+                has_start_checkpoint: true,
+                has_finish_checkpoint: false,
+            },
+            checkpoints: vec![
+                checkpoint("Berga", 0, 3),
+                checkpoint("Smågan", 1, 1),
+                checkpoint("Mångsbodarna", 2, 3),
+                checkpoint("Mora", 3, 5),
+            ],
+        }];
+
+        let names: Vec<String> = collect_checkpoints(&races)
+            .into_iter()
+            .map(|(cp, _, _)| cp.name)
+            .collect();
+        // Equal priorities keep course order
+        assert_eq!(names, ["Mora", "Berga", "Mångsbodarna", "Smågan"]);
+    }
+
+    #[test]
+    fn test_annotate_staleness_counts_only_stale_checkpoints() {
+        let now = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut state = PollerState::new();
+        state.checkpoints = vec![
+            poll_status(Some(now - Duration::minutes(60))),
+            poll_status(Some(now - Duration::minutes(240))),
+            poll_status(Some(now - Duration::minutes(181))),
+            poll_status(None),
+        ];
+
+        state.annotate_staleness(now, 180);
+
+        assert_eq!(state.checkpoints_with_stale_warning, 2);
+        assert_eq!(state.checkpoints[0].model_run_age_minutes, Some(60));
+        // Run at 11:00 → next expected at 18:00 (17:00 snapped)
+        assert_eq!(
+            state.checkpoints[0].next_model_run_expected_at,
+            "2026-03-01T18:00:00Z".parse::<DateTime<Utc>>().ok()
+        );
+        assert!(!state.checkpoints[0].stale_warning);
+        assert!(state.checkpoints[1].stale_warning);
+        assert!(state.checkpoints[2].stale_warning);
+        // Unknown model run: no age, no warning
+        assert_eq!(state.checkpoints[3].model_run_age_minutes, None);
+        assert_eq!(state.checkpoints[3].next_model_run_expected_at, None);
+        assert!(!state.checkpoints[3].stale_warning);
+    }
+
+    #[test]
+    fn test_next_wakeup_in_seconds_bounded_by_max_sleep() {
+        let now = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut state = PollerState::new();
+        state.annotate_next_wakeup(now);
+        assert_eq!(state.next_wakeup_in_seconds, None);
+
+        state.next_wakeup_at = Some(now + Duration::seconds(config().max_sleep_secs as i64));
+        state.annotate_next_wakeup(now);
+        let secs = state.next_wakeup_in_seconds.unwrap();
+        assert!(secs <= config().max_sleep_secs as i64);
+
+        // Overdue wakeup is reported as 0, not negative
+        state.annotate_next_wakeup(now + Duration::hours(1));
+        assert_eq!(state.next_wakeup_in_seconds, Some(0));
+    }
+
+    #[test]
+    fn test_model_run_overdue_after_threshold() {
+        let now = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert!(!is_model_run_overdue(now - Duration::hours(6), now, 12));
+        assert!(!is_model_run_overdue(now - Duration::hours(12), now, 12));
+        assert!(is_model_run_overdue(
+            now - Duration::hours(12) - Duration::minutes(1),
+            now,
+            12
+        ));
+    }
+
+    #[test]
+    fn test_set_checkpoints_counts_overdue() {
+        let mut state = PollerState::new();
+        let mut overdue = poll_status(None);
+        overdue.model_run_overdue = true;
+        state.set_checkpoints(vec![poll_status(None), overdue.clone(), overdue]);
+        assert_eq!(state.overdue_checkpoints_count, 2);
+        state.set_checkpoints(Vec::new());
+        assert_eq!(state.overdue_checkpoints_count, 0);
+    }
+
+    #[test]
+    fn test_mid_cycle_upsert_keeps_recent_untouched_statuses() {
+        let now = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut recent = poll_status(None);
+        recent.last_seen_at = now - Duration::hours(1);
+        let mut stale = poll_status(None);
+        stale.last_seen_at = now - Duration::hours(49);
+        stale.model_run_overdue = true;
+        let mut state = PollerState::new();
+        state.set_checkpoints(vec![recent.clone(), stale]);
+        assert_eq!(state.overdue_checkpoints_count, 1);
+
+        // The cycle has only reached one checkpoint so far
+        let mut polled = poll_status(None);
+        polled.last_seen_at = now;
+        state.upsert_checkpoints(vec![polled.clone()], now - Duration::hours(48));
+        let ids: Vec<Uuid> = state.checkpoints.iter().map(|c| c.checkpoint_id).collect();
+        assert_eq!(ids, [polled.checkpoint_id, recent.checkpoint_id]);
+        assert_eq!(state.overdue_checkpoints_count, 0);
+    }
+
+    #[test]
+    fn test_completed_cycle_drops_checkpoints_it_no_longer_polls() {
+        let now = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut ended = poll_status(None);
+        ended.last_seen_at = now - Duration::hours(1);
+        ended.model_run_overdue = true;
+        let mut state = PollerState::new();
+        state.set_checkpoints(vec![ended.clone()]);
+
+        // Mid-cycle the ended race's checkpoint is still shown ...
+        let mut active = poll_status(None);
+        active.last_seen_at = now;
+        state.upsert_checkpoints(vec![active.clone()], now - Duration::hours(48));
+        assert_eq!(state.checkpoints.len(), 2);
+
+        // ... but the finished cycle's list replaces it right away
+        state.set_checkpoints(vec![active.clone()]);
+        assert_eq!(state.checkpoints.len(), 1);
+        assert_eq!(state.checkpoints[0].checkpoint_id, active.checkpoint_id);
+        assert_eq!(state.overdue_checkpoints_count, 0);
+    }
+
+    /// yr.no response with hourly entries from `first` for `hours` hours.
+    fn hourly_yr_json(first: DateTime<Utc>, hours: i64) -> serde_json::Value {
+        let timeseries: Vec<serde_json::Value> = (0..hours)
+            .map(|h| {
+                serde_json::json!({
+                    "time": (first + Duration::hours(h)).to_rfc3339(),
+                    "data": {
+                        "instant": { "details": {
+                            "air_temperature": -5.0 - h as f64,
+                            "wind_speed": 3.0,
+                            "wind_from_direction": 180.0,
+                            "relative_humidity": 80.0,
+                            "dew_point_temperature": -8.0,
+                            "cloud_area_fraction": 50.0
+                        } },
+                        "next_1_hours": {
+                            "summary": { "symbol_code": "cloudy" },
+                            "details": { "precipitation_amount": 0.0 }
+                        }
+                    }
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "properties": {
+                "meta": { "updated_at": "2026-03-01T00:00:00Z" },
+                "timeseries": timeseries
+            }
+        })
+    }
+
+    #[test]
+    fn test_reextraction_rows_follow_new_start_time() {
+        let cp = Checkpoint {
+            distance_km: rust_decimal::Decimal::from(20),
+            ..Checkpoint::fixture("Smågan")
+        };
+        let first = "2026-03-01T06:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let fetched_at = first - Duration::hours(1);
+        let json = hourly_yr_json(first, 12);
+        let times = |start: &str| -> Vec<DateTime<Utc>> {
+            let rows = reextraction_rows(
+                &cp,
+                json.clone(),
+                fetched_at,
+                start.parse().unwrap(),
+                &config(),
+            )
+            .unwrap();
+            assert!(rows
+                .iter()
+                .all(|r| r.checkpoint_id == cp.id && r.fetched_at == fetched_at));
+            rows.iter().map(|r| r.forecast_time).collect()
+        };
+
+        // 20 km at 10–30 km/h: 40 min to 2 h after the start
+        let hour = |h: i64| first + Duration::hours(h);
+        assert_eq!(times("2026-03-01T08:00:00Z"), [hour(2), hour(3), hour(4)]);
+        assert_eq!(times("2026-03-01T10:00:00Z"), [hour(4), hour(5), hour(6)]);
+    }
+
+    #[test]
+    fn test_reextraction_rows_invalid_cache_is_error() {
+        let cp = Checkpoint::fixture("Mora");
+        let broken = serde_json::json!({ "properties": "not a timeseries" });
+        assert!(reextraction_rows(&cp, broken, Utc::now(), Utc::now(), &config()).is_err());
+    }
+
+    fn new_state() -> SharedPollerState {
+        Arc::new(RwLock::new(PollerState::new()))
+    }
+
+    #[tokio::test]
+    async fn test_poll_if_locked_runs_cycle_and_releases() {
+        let state = new_state();
+        let released = AtomicBool::new(false);
+        let sleep = poll_if_locked(Ok(Some("lock")), &state, &config(), async { 42 }, |lock| {
+            assert_eq!(lock, "lock");
+            released.store(true, Ordering::SeqCst);
+            async { Ok(()) }
+        })
+        .await;
+        assert_eq!(sleep, 42);
+        assert!(released.load(Ordering::SeqCst));
+        assert!(state.read().await.acquired_poll_lock);
+
+        // A failed release is logged; the cycle's sleep still applies
+        let sleep = poll_if_locked(Ok(Some(())), &state, &config(), async { 7 }, |_| async {
+            Err(sqlx::Error::PoolClosed)
+        })
+        .await;
+        assert_eq!(sleep, 7);
+    }
+
+    #[tokio::test]
+    async fn test_poll_if_locked_stands_by_when_held_elsewhere() {
+        let state = new_state();
+        state.write().await.acquired_poll_lock = true;
+        let ran = AtomicBool::new(false);
+        let sleep = poll_if_locked(
+            Ok(None::<()>),
+            &state,
+            &config(),
+            async {
+                ran.store(true, Ordering::SeqCst);
+                1
+            },
+            |_| async { Ok(()) },
+        )
+        .await;
+        assert_eq!(sleep, config().min_sleep_secs);
+        assert!(!ran.load(Ordering::SeqCst));
+        let s = state.read().await;
+        assert!(!s.acquired_poll_lock);
+        assert!(s.next_wakeup_at.is_some_and(|t| t > Utc::now()));
+    }
+
+    #[tokio::test]
+    async fn test_poll_if_locked_retries_after_db_error() {
+        let state = new_state();
+        let ran = AtomicBool::new(false);
+        let sleep = poll_if_locked(
+            Err::<Option<()>, _>(sqlx::Error::PoolTimedOut),
+            &state,
+            &config(),
+            async {
+                ran.store(true, Ordering::SeqCst);
+                1
+            },
+            |_| async { Ok(()) },
+        )
+        .await;
+        assert_eq!(sleep, config().min_sleep_secs);
+        assert!(!ran.load(Ordering::SeqCst));
+        assert!(state.read().await.next_wakeup_at.is_none());
+    }
+
+    #[test]
+    fn test_cache_freshness_oldest_age_and_valid_count() {
+        let now = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let checkpoint = |race_start: DateTime<Utc>| {
+            (
+                Checkpoint::fixture("Mora"),
+                "Vasaloppet".to_string(),
+                race_start,
+            )
+        };
+        let fresh = checkpoint(now + Duration::days(2));
+        let expired = checkpoint(now + Duration::days(2));
+        let never_fetched = checkpoint(now + Duration::days(2));
+        let cache_times = HashMap::from([
+            (
+                fresh.0.id,
+                (now - Duration::minutes(30), now + Duration::minutes(30)),
+            ),
+            (
+                expired.0.id,
+                (now - Duration::hours(5), now - Duration::hours(4)),
+            ),
+        ]);
+
+        let mut state = PollerState::new();
+        let checkpoints = vec![fresh, expired, never_fetched.clone()];
+        state.set_cache_freshness(&checkpoints, &cache_times, now);
+        assert_eq!(state.checkpoints_total, 3);
+        assert_eq!(state.checkpoints_with_valid_cache, 1);
+        assert_eq!(state.oldest_unpolled_checkpoint_age_seconds, Some(5 * 3600));
+
+        // Before its race starts, a never-fetched checkpoint has age 0
+        state.set_cache_freshness(&[never_fetched], &cache_times, now);
+        assert_eq!(state.checkpoints_total, 1);
+        assert_eq!(state.checkpoints_with_valid_cache, 0);
+        assert_eq!(state.oldest_unpolled_checkpoint_age_seconds, Some(0));
+
+        // Once the race has started, it ages from the start
+        let started = checkpoint(now - Duration::hours(6));
+        state.set_cache_freshness(&[started], &cache_times, now);
+        assert_eq!(state.oldest_unpolled_checkpoint_age_seconds, Some(6 * 3600));
+
+        state.set_cache_freshness(&[], &HashMap::new(), now);
+        assert_eq!(state.oldest_unpolled_checkpoint_age_seconds, None);
+    }
+
+    #[test]
+    fn test_warn_newly_overdue_tracks_overdue_set() {
+        let mut overdue = poll_status(None);
+        overdue.model_run_overdue = true;
+        let fresh = poll_status(None);
+        let mut previously = HashSet::new();
+
+        warn_newly_overdue(&[overdue.clone(), fresh.clone()], &mut previously);
+        assert_eq!(previously, HashSet::from([overdue.checkpoint_id]));
+
+        // Recovered checkpoints drop out of the set
+        overdue.model_run_overdue = false;
+        warn_newly_overdue(&[overdue, fresh], &mut previously);
+        assert!(previously.is_empty());
+    }
+
+    #[test]
+    fn test_model_run_age_future_run_is_zero() {
+        let now = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(model_run_age_minutes(now + Duration::minutes(5), now), 0);
+    }
 }
@@ -0,0 +1,143 @@
+//! Static database of Nordic ski resorts near race checkpoints.
+//!
+//! Loaded once at startup from `RESORT_DATA_FILE` (default
+//! `./data/resorts.yaml`) and kept in memory. The file is a list of resorts;
+//! snow depths are updated by hand:
+//!
+//! ```yaml
+//! - name: Lindvallen (Sälen)        # required
+//!   latitude: 61.157                # required
+//!   longitude: 13.186               # required
+//!   elevation_m: 590.0              # required, base area
+//!   has_snowmaking: true            # optional, default false
+//!   reported_snow_depth_cm: 85.0    # optional
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::services::gpx::haversine_distance_km;
+
+/// Errors that can occur while loading the resort file.
+#[derive(Debug, Error)]
+pub enum ResortError {
+    #[error("IO error reading resort file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid resort file: {0}")]
+    Parse(#[from] serde_yaml::Error),
+}
+
+/// A ski resort from the resort file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Resort {
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub elevation_m: f64,
+    #[serde(default)]
+    pub has_snowmaking: bool,
+    pub reported_snow_depth_cm: Option<f64>,
+}
+
+/// A resort near a checkpoint.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NearbyResort {
+    /// Resort name
+    pub name: String,
+    /// Latitude (WGS84)
+    pub latitude: f64,
+    /// Longitude (WGS84)
+    pub longitude: f64,
+    /// Base elevation in metres
+    pub elevation_m: f64,
+    /// Great-circle distance from the checkpoint in kilometres
+    pub distance_km: f64,
+    /// Whether the resort can make artificial snow
+    pub has_snowmaking: bool,
+    /// Last manually reported snow depth in cm (null when unknown)
+    pub reported_snow_depth_cm: Option<f64>,
+}
+
+/// Load the resort list. A missing file yields an empty list (with a warning).
+pub fn load_resorts(path: &Path) -> Result<Vec<Resort>, ResortError> {
+    if !path.exists() {
+        tracing::warn!("Resort file does not exist: {}", path.display());
+        return Ok(Vec::new());
+    }
+    parse_resorts(&std::fs::read_to_string(path)?)
+}
+
+fn parse_resorts(yaml: &str) -> Result<Vec<Resort>, ResortError> {
+    Ok(serde_yaml::from_str::<Option<Vec<Resort>>>(yaml)?.unwrap_or_default())
+}
+
+/// Resorts within `radius_km` of a point, nearest first.
+pub fn resorts_within(resorts: &[Resort], lat: f64, lon: f64, radius_km: f64) -> Vec<NearbyResort> {
+    let mut nearby: Vec<NearbyResort> = resorts
+        .iter()
+        .filter_map(|r| {
+            let distance_km = haversine_distance_km(lat, lon, r.latitude, r.longitude);
+            (distance_km <= radius_km).then(|| NearbyResort {
+                name: r.name.clone(),
+                latitude: r.latitude,
+                longitude: r.longitude,
+                elevation_m: r.elevation_m,
+                distance_km,
+                has_snowmaking: r.has_snowmaking,
+                reported_snow_depth_cm: r.reported_snow_depth_cm,
+            })
+        })
+        .collect();
+    nearby.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+    nearby
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESORTS: &str = r#"
+- name: Far
+  latitude: 61.30
+  longitude: 13.60
+  elevation_m: 500.0
+- name: Near
+  latitude: 61.11
+  longitude: 13.50
+  elevation_m: 450.0
+  has_snowmaking: true
+  reported_snow_depth_cm: 72.0
+"#;
+
+    #[test]
+    fn test_parse_resorts() {
+        let resorts = parse_resorts(RESORTS).unwrap();
+        assert_eq!(resorts.len(), 2);
+        assert!(!resorts[0].has_snowmaking);
+        assert_eq!(resorts[1].reported_snow_depth_cm, Some(72.0));
+        assert!(parse_resorts("").unwrap().is_empty());
+        assert!(parse_resorts(
+            "- name: X\n  latitude: 1\n  longitude: 2\n  elevation_m: 3\n  lifts: 4\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_resorts_within_filters_and_sorts() {
+        let resorts = parse_resorts(RESORTS).unwrap();
+        // Berga (Vasaloppet start)
+        let nearby = resorts_within(&resorts, 61.1001, 13.5100, 5.0);
+        assert_eq!(nearby.len(), 1);
+        assert_eq!(nearby[0].name, "Near");
+        assert!(nearby[0].distance_km < 2.0);
+
+        let all = resorts_within(&resorts, 61.1001, 13.5100, 50.0);
+        assert_eq!(
+            all.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(),
+            ["Near", "Far"]
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Pre-computed race forecast summaries.
+//!
+//! After each poll cycle, [`run_forecast_summaries`] computes the race
+//! forecast of every upcoming race for a few pinned target durations and
+//! stores it in `forecast_summaries`, where
+//! GET /api/v1/forecasts/race/:id/cached-summary serves it without any yr.no
+//! or pacing work. Computing one forecast is left to the caller (the race
+//! forecast route), so this module doesn't depend on the HTTP layer.
+
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use std::future::Future;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+use crate::db::{models, queries};
+use crate::errors::AppError;
+use crate::helpers::f64_to_decimal_full;
+
+/// Target durations (hours) whose race forecasts are pre-computed.
+pub(crate) const PINNED_SUMMARY_DURATIONS_HOURS: [f64; 3] = [6.0, 8.0, 10.0];
+
+/// Key of a target duration in `forecast_summaries` (NUMERIC(5,2)).
+pub(crate) fn duration_key(target_duration_hours: f64) -> Decimal {
+    f64_to_decimal_full(target_duration_hours).round_dp(2)
+}
+
+/// Compute with `compute` and store the pinned-duration forecasts of all
+/// races within `lookahead_days`. Failures are logged and skipped.
+pub(crate) async fn precompute_race_summaries<F, Fut>(
+    pool: &PgPool,
+    lookahead_days: i64,
+    compute: &F,
+) where
+    F: Fn(models::Race, f64) -> Fut,
+    Fut: Future<Output = Result<serde_json::Value, AppError>>,
+{
+    let races = match queries::get_upcoming_races_with_checkpoints(pool, lookahead_days).await {
+        Ok(races) => races,
+        Err(e) => {
+            tracing::error!("Forecast summaries: failed to query upcoming races: {}", e);
+            return;
+        }
+    };
+
+    let mut stored = 0;
+    for rwc in races {
+        for hours in PINNED_SUMMARY_DURATIONS_HOURS {
+            let summary = match compute(rwc.race.clone(), hours).await {
+                Ok(summary) => summary,
+                Err(e) => {
+                    tracing::warn!(
+                        "Forecast summaries: race '{}' ({}) at {}h failed: {}",
+                        rwc.race.name,
+                        rwc.race.year,
+                        hours,
+                        e
+                    );
+                    continue;
+                }
+            };
+            match queries::upsert_forecast_summary(pool, rwc.race.id, duration_key(hours), &summary)
+                .await
+            {
+                Ok(()) => stored += 1,
+                Err(e) => tracing::warn!("Forecast summaries: failed to store: {}", e),
+            }
+        }
+    }
+    tracing::info!("Forecast summaries: stored {} summaries", stored);
+}
+
+/// Background task: re-compute summaries whenever the poller completes a
+/// cycle, until shutdown.
+///
+/// Should be spawned via `tokio::spawn(run_forecast_summaries(...))`.
+pub async fn run_forecast_summaries<F, Fut>(
+    pool: PgPool,
+    lookahead_days: i64,
+    compute: F,
+    mut cycles_completed: watch::Receiver<u64>,
+    shutdown: CancellationToken,
+) where
+    F: Fn(models::Race, f64) -> Fut,
+    Fut: Future<Output = Result<serde_json::Value, AppError>>,
+{
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            changed = cycles_completed.changed() => {
+                if changed.is_err() {
+                    // Poller gone: no more new data
+                    break;
+                }
+                precompute_race_summaries(&pool, lookahead_days, &compute).await;
+            }
+        }
+    }
+    tracing::info!("Forecast summary task stopped");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_duration_key_matches_numeric_column() {
+        assert_eq!(duration_key(8.0), Decimal::from(8));
+        assert_eq!(duration_key(7.5), Decimal::from_str("7.50").unwrap());
+        assert_eq!(duration_key(8.004), Decimal::from(8));
+    }
+}
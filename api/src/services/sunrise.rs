@@ -0,0 +1,210 @@
+//! MET Norway Sunrise 3.0 client.
+//!
+//! Looks up sunrise and sunset for a location and date, cached in the
+//! `sunrise_cache` table for 24 hours.
+//! See: https://api.met.no/weatherapi/sunrise/3.0/documentation
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use reqwest::header::USER_AGENT;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::db::models::SunTimes;
+use crate::db::queries;
+use crate::errors::AppError;
+use crate::helpers::f64_to_decimal_full;
+use crate::services::yr::YrClient;
+
+/// Sunrise 3.0 endpoint for the sun.
+const SUNRISE_API_URL: &str = "https://api.met.no/weatherapi/sunrise/3.0/sun";
+
+/// Client for the MET Norway Sunrise API.
+///
+/// Shares the HTTP client (timeout, proxy, TLS settings) and User-Agent
+/// of the [`YrClient`].
+#[derive(Debug, Clone)]
+pub struct SunriseClient {
+    client: reqwest::Client,
+    user_agent: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SunResponse {
+    properties: SunProperties,
+}
+
+#[derive(Debug, Deserialize)]
+struct SunProperties {
+    sunrise: Option<SunEvent>,
+    sunset: Option<SunEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SunEvent {
+    /// e.g. "2026-03-01T05:58+00:00"; null when the event does not occur
+    time: Option<String>,
+}
+
+impl SunriseClient {
+    pub fn from_yr_client(yr_client: &YrClient) -> Self {
+        Self {
+            client: yr_client.http_client(),
+            user_agent: yr_client.user_agent().to_string(),
+        }
+    }
+
+    /// Sunrise and sunset at a location on the local date of `at`.
+    ///
+    /// Served from `sunrise_cache` when a row younger than 24 hours exists;
+    /// otherwise fetched from MET Norway and stored.
+    pub async fn get_sun_times(
+        &self,
+        pool: &PgPool,
+        lat: f64,
+        lon: f64,
+        at: DateTime<Utc>,
+    ) -> Result<SunTimes, AppError> {
+        // Limit to 4 decimal places per MET Norway terms of service
+        let lat_key = f64_to_decimal_full(lat).round_dp(4);
+        let lon_key = f64_to_decimal_full(lon).round_dp(4);
+        let date = local_solar_date(at, lon);
+
+        if let Some(cached) = queries::get_sunrise_cached(pool, lat_key, lon_key, date).await? {
+            return Ok(cached);
+        }
+        let times = self.fetch(lat_key, lon_key, date).await?;
+        queries::upsert_sunrise_cache(pool, lat_key, lon_key, date, &times).await?;
+        Ok(times)
+    }
+
+    async fn fetch(
+        &self,
+        lat: Decimal,
+        lon: Decimal,
+        date: NaiveDate,
+    ) -> Result<SunTimes, AppError> {
+        let url = format!(
+            "{}?lat={}&lon={}&date={}&offset=+00:00",
+            SUNRISE_API_URL,
+            lat,
+            lon,
+            date.format("%Y-%m-%d")
+        );
+        let response = self
+            .client
+            .get(&url)
+            .header(USER_AGENT, &self.user_agent)
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::ExternalServiceError(format!("Sunrise request failed: {}", e))
+            })?;
+        if !response.status().is_success() {
+            return Err(AppError::ExternalServiceError(format!(
+                "Sunrise API returned HTTP {}",
+                response.status()
+            )));
+        }
+        let body: SunResponse = response.json().await.map_err(|e| {
+            AppError::ExternalServiceError(format!("Sunrise JSON parse error: {}", e))
+        })?;
+        Ok(parse_sun_response(body))
+    }
+}
+
+fn parse_sun_response(body: SunResponse) -> SunTimes {
+    let time =
+        |event: Option<SunEvent>| event.and_then(|e| e.time).and_then(|t| parse_sun_time(&t));
+    SunTimes {
+        sunrise: time(body.properties.sunrise),
+        sunset: time(body.properties.sunset),
+    }
+}
+
+/// Parse a Sunrise API time, which omits seconds ("2026-03-01T05:58+00:00").
+fn parse_sun_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M%:z")
+        .or_else(|_| DateTime::parse_from_rfc3339(s))
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Calendar date at `at` in local mean solar time (15° of longitude per hour),
+/// so a morning race in a UTC+X time zone looks up the right day.
+fn local_solar_date(at: DateTime<Utc>, lon: f64) -> NaiveDate {
+    (at + Duration::minutes((lon * 4.0).round() as i64)).date_naive()
+}
+
+/// Minutes between sunrise and `at`: positive when the sun is already up,
+/// negative when `at` is before sunrise (skiers need lights).
+pub fn dawn_minutes_before(sun: &SunTimes, at: DateTime<Utc>) -> Option<i64> {
+    sun.sunrise.map(|sunrise| (at - sunrise).num_minutes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn parse(json: &str) -> SunTimes {
+        parse_sun_response(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn test_parse_sun_response() {
+        let sun = parse(
+            r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[13.5,61.1]},
+                "when":{"interval":["2026-03-01T00:00:00Z","2026-03-02T00:00:00Z"]},
+                "properties":{"body":"Sun",
+                  "sunrise":{"time":"2026-03-01T06:08+00:00","azimuth":101.2},
+                  "sunset":{"time":"2026-03-01T16:16+00:00","azimuth":258.6},
+                  "solarnoon":{"time":"2026-03-01T11:12+00:00","disc_centre_elevation":19.4,"visible":true}}}"#,
+        );
+        assert_eq!(
+            sun.sunrise,
+            Some(Utc.with_ymd_and_hms(2026, 3, 1, 6, 8, 0).unwrap())
+        );
+        assert_eq!(
+            sun.sunset,
+            Some(Utc.with_ymd_and_hms(2026, 3, 1, 16, 16, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_sun_response_polar_night() {
+        let sun = parse(
+            r#"{"properties":{"body":"Sun","sunrise":{"time":null},"sunset":{"time":null}}}"#,
+        );
+        assert_eq!(sun.sunrise, None);
+        assert_eq!(sun.sunset, None);
+        assert_eq!(dawn_minutes_before(&sun, Utc::now()), None);
+    }
+
+    #[test]
+    fn test_dawn_minutes_before_start() {
+        let sun = SunTimes {
+            sunrise: Some(Utc.with_ymd_and_hms(2026, 3, 1, 6, 8, 0).unwrap()),
+            sunset: None,
+        };
+        // Vasaloppet starts at 07:00 CET = 06:00 UTC, before sunrise
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 6, 0, 0).unwrap();
+        assert_eq!(dawn_minutes_before(&sun, start), Some(-8));
+        let later = Utc.with_ymd_and_hms(2026, 3, 1, 7, 8, 0).unwrap();
+        assert_eq!(dawn_minutes_before(&sun, later), Some(60));
+    }
+
+    #[test]
+    fn test_local_solar_date_shifts_with_longitude() {
+        // 22:00 UTC is already the next morning at 150°E (+10 h)
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 22, 0, 0).unwrap();
+        assert_eq!(
+            local_solar_date(at, 150.0),
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+        );
+        assert_eq!(
+            local_solar_date(at, 14.0),
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()
+        );
+    }
+}
@@ -7,17 +7,22 @@ use chrono::{DateTime, Utc};
 use reqwest::header::{HeaderMap, HeaderValue, IF_MODIFIED_SINCE, USER_AGENT};
 use rust_decimal::Decimal;
 use serde::Deserialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
+use crate::config::{mask_connection_string, AppConfig};
 use crate::errors::AppError;
-use crate::helpers::{f64_to_decimal_1dp, opt_f64_to_decimal_1dp};
-
-const YR_API_URL: &str = "https://api.met.no/weatherapi/locationforecast/2.0/complete";
-/// HTTP request timeout for yr.no API calls (seconds).
-const YR_HTTP_TIMEOUT_SECS: u64 = 30;
+use crate::helpers::{f64_to_decimal_1dp, opt_f64_to_decimal_1dp, SingleflightMap};
 
 /// Temporal resolution of a yr.no timeseries entry, determined by which
 /// period blocks (`next_1_hours` / `next_6_hours`) are present.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Also used as the `?min_resolution=` filter on checkpoint forecast
+/// endpoints (`hourly` or `six_hourly`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum ForecastResolution {
     /// Short-range: `next_1_hours` present, data at 1-hour intervals.
     Hourly,
@@ -37,6 +42,85 @@ impl ForecastResolution {
             ForecastResolution::SixHourly => 10_800,
         }
     }
+
+    /// Stable string form stored in `forecasts.data_resolution`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ForecastResolution::Hourly => "hourly",
+            ForecastResolution::SixHourly => "six_hourly",
+        }
+    }
+
+    /// Whether data at this resolution satisfies a `min_resolution` filter:
+    /// hourly data always does, 6-hourly data only when 6-hourly is accepted.
+    pub fn meets(self, min_resolution: ForecastResolution) -> bool {
+        self == ForecastResolution::Hourly || min_resolution == ForecastResolution::SixHourly
+    }
+
+    /// Like [`meets`](Self::meets) for a stored `forecasts.data_resolution`.
+    /// Rows without a resolution (legacy) only pass the 6-hourly filter.
+    pub fn stored_meets(data_resolution: Option<&str>, min_resolution: ForecastResolution) -> bool {
+        match data_resolution {
+            Some("hourly") => true,
+            _ => min_resolution == ForecastResolution::SixHourly,
+        }
+    }
+}
+
+/// The circuit breaker opens when consecutive yr.no failures exceed this.
+const YR_CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
+
+/// How long an open circuit short-circuits yr.no calls before letting one
+/// through again (seconds).
+const YR_CIRCUIT_BREAKER_RESET_SECS: u64 = 60;
+
+/// Stops calling yr.no after repeated failures, so requests fall back to the
+/// cache immediately instead of each waiting for the HTTP timeout.
+///
+/// After the reset period the next call goes through; success closes the
+/// circuit, another failure reopens it for a further reset period.
+#[derive(Debug, Default)]
+struct CircuitBreaker {
+    consecutive_failures: AtomicU32,
+    /// When the circuit last opened; `None` while closed.
+    opened_at: Mutex<Option<Instant>>,
+}
+
+impl CircuitBreaker {
+    fn is_open(&self, now: Instant) -> bool {
+        match *self.lock() {
+            Some(opened) => {
+                now.duration_since(opened) < Duration::from_secs(YR_CIRCUIT_BREAKER_RESET_SECS)
+            }
+            None => false,
+        }
+    }
+
+    fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.lock() = None;
+    }
+
+    fn record_failure(&self, now: Instant) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures > YR_CIRCUIT_BREAKER_THRESHOLD {
+            let mut opened_at = self.lock();
+            if opened_at.is_none() {
+                tracing::warn!(
+                    "yr.no failed {} times in a row — pausing calls for {}s",
+                    failures,
+                    YR_CIRCUIT_BREAKER_RESET_SECS
+                );
+            }
+            *opened_at = Some(now);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Instant>> {
+        // A poisoned lock only means another thread panicked mid-update of
+        // a plain timestamp; the value is still usable.
+        self.opened_at.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 /// Client for the yr.no Locationforecast API.
@@ -44,9 +128,16 @@ impl ForecastResolution {
 pub struct YrClient {
     client: reqwest::Client,
     user_agent: String,
+    /// Locationforecast endpoint, e.g. `https://api.met.no/weatherapi/locationforecast/2.0/complete`
+    base_url: String,
+    /// Shared by all clones, so the poller and request handlers see the same state.
+    circuit_breaker: Arc<CircuitBreaker>,
+    /// In-flight fetches by checkpoint, shared by all clones.
+    inflight: Arc<SingleflightMap<Uuid, YrTimeseriesResult>>,
 }
 
 /// The result of a yr.no timeseries fetch.
+#[derive(Debug, Clone)]
 pub enum YrTimeseriesResult {
     /// New timeseries data received (HTTP 200).
     NewData {
@@ -89,8 +180,15 @@ pub struct YrParsedForecast {
     pub humidity_pct: Decimal,
     pub dew_point_c: Decimal,
     pub cloud_cover_pct: Decimal,
+    pub cloud_cover_low_pct: Option<Decimal>,
+    pub cloud_cover_medium_pct: Option<Decimal>,
+    pub cloud_cover_high_pct: Option<Decimal>,
+    /// Snow depth in cm (only provided for some locations)
+    pub snow_depth_cm: Option<Decimal>,
     pub uv_index: Option<Decimal>,
     pub symbol_code: String,
+    /// Probability of thunder in % for the following period (often absent)
+    pub thunder_probability_pct: Option<Decimal>,
     /// When yr.no's weather model generated this forecast (`properties.meta.updated_at`).
     /// `None` if the meta block is missing or unparseable.
     pub yr_model_run_at: Option<DateTime<Utc>>,
@@ -157,6 +255,10 @@ struct YrInstantDetails {
     relative_humidity: Option<f64>,
     dew_point_temperature: Option<f64>,
     cloud_area_fraction: Option<f64>,
+    cloud_area_fraction_low: Option<f64>,
+    cloud_area_fraction_medium: Option<f64>,
+    cloud_area_fraction_high: Option<f64>,
+    snow_depth: Option<f64>,
     ultraviolet_index_clear_sky: Option<f64>,
 }
 
@@ -176,6 +278,7 @@ struct YrPeriodDetails {
     precipitation_amount: Option<f64>,
     precipitation_amount_min: Option<f64>,
     precipitation_amount_max: Option<f64>,
+    probability_of_thunder: Option<f64>,
 }
 
 fn f64_to_decimal(v: f64) -> Decimal {
@@ -187,21 +290,79 @@ fn opt_f64_to_decimal(v: Option<f64>) -> Option<Decimal> {
 }
 
 impl YrClient {
-    pub fn new(user_agent: &str) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(YR_HTTP_TIMEOUT_SECS))
-            .build()
-            .expect("Failed to build HTTP client");
+    /// Build the client from the `YR_*` settings (timeout, proxy, TLS).
+    ///
+    /// Panics at startup if `YR_HTTPS_PROXY` is not a valid proxy URL.
+    pub fn from_config(config: &AppConfig) -> Self {
+        let mut builder = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(config.yr_http_timeout_secs));
+
+        if config.yr_danger_accept_invalid_certs {
+            tracing::warn!(
+                "YR_DANGER_ACCEPT_INVALID_CERTS is enabled: TLS certificates from yr.no are NOT verified"
+            );
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        if let Some(proxy_url) = &config.yr_https_proxy {
+            let masked_proxy_url = mask_connection_string(proxy_url.expose());
+            let proxy = reqwest::Proxy::https(proxy_url.expose()).unwrap_or_else(|e| {
+                panic!(
+                    "YR_HTTPS_PROXY is not a valid proxy URL ('{}'): {}",
+                    masked_proxy_url, e
+                )
+            });
+            tracing::info!(
+                "Routing yr.no requests through HTTPS proxy {}",
+                masked_proxy_url
+            );
+            builder = builder.proxy(proxy);
+        }
+
+        let client = builder.build().expect("Failed to build HTTP client");
+        Self::with_client(client, &config.yr_user_agent, &config.yr_api_base_url)
+    }
+
+    /// The underlying HTTP client, shared with other MET Norway APIs.
+    pub(crate) fn http_client(&self) -> reqwest::Client {
+        self.client.clone()
+    }
+
+    /// User-Agent sent with every MET Norway request.
+    pub(crate) fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    pub(crate) fn with_client(client: reqwest::Client, user_agent: &str, base_url: &str) -> Self {
         Self {
             client,
             user_agent: user_agent.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            circuit_breaker: Arc::new(CircuitBreaker::default()),
+            inflight: Arc::new(SingleflightMap::default()),
         }
     }
 
+    /// Whether yr.no calls are currently short-circuited after repeated failures.
+    pub fn circuit_breaker_open(&self) -> bool {
+        self.circuit_breaker.is_open(Instant::now())
+    }
+
+    /// Build the request URL for a location.
+    fn timeseries_url(&self, lat: f64, lon: f64, altitude: f64) -> String {
+        // Limit to 4 decimal places per yr.no terms of service
+        format!(
+            "{}?lat={:.4}&lon={:.4}&altitude={:.0}",
+            self.base_url, lat, lon, altitude
+        )
+    }
+
     /// Fetch the full timeseries from yr.no for a given location.
     ///
     /// Returns the raw JSON and caching headers. The caller is responsible
     /// for storing this in `yr_responses` and extracting individual forecasts.
+    /// Fails immediately, without a network call, while the circuit breaker
+    /// is open.
     pub async fn fetch_timeseries(
         &self,
         lat: f64,
@@ -209,15 +370,58 @@ impl YrClient {
         altitude: f64,
         if_modified_since: Option<&str>,
     ) -> Result<YrTimeseriesResult, AppError> {
-        // Limit to 4 decimal places per yr.no terms of service
-        let lat_str = format!("{:.4}", lat);
-        let lon_str = format!("{:.4}", lon);
-        let alt_str = format!("{:.0}", altitude);
+        if self.circuit_breaker.is_open(Instant::now()) {
+            return Err(AppError::ExternalServiceError(
+                "yr.no circuit breaker open after repeated failures".to_string(),
+            ));
+        }
 
-        let url = format!(
-            "{}?lat={}&lon={}&altitude={}",
-            YR_API_URL, lat_str, lon_str, alt_str
-        );
+        let result = self
+            .request_timeseries(lat, lon, altitude, if_modified_since)
+            .await;
+        match &result {
+            Ok(_) => self.circuit_breaker.record_success(),
+            Err(AppError::ExternalServiceError(_)) => {
+                self.circuit_breaker.record_failure(Instant::now())
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Like [`fetch_timeseries`](Self::fetch_timeseries), but concurrent
+    /// calls for the same checkpoint share one request: callers arriving
+    /// while a fetch is in flight wait for its result. Errors reach waiters
+    /// as `ExternalServiceError`.
+    pub async fn fetch_timeseries_deduplicated(
+        &self,
+        checkpoint_id: Uuid,
+        lat: f64,
+        lon: f64,
+        altitude: f64,
+        if_modified_since: Option<&str>,
+    ) -> Result<YrTimeseriesResult, AppError> {
+        self.inflight
+            .run(checkpoint_id, || async {
+                self.fetch_timeseries(lat, lon, altitude, if_modified_since)
+                    .await
+                    .map_err(|e| match e {
+                        AppError::ExternalServiceError(message) => message,
+                        other => other.to_string(),
+                    })
+            })
+            .await
+            .map_err(AppError::ExternalServiceError)
+    }
+
+    async fn request_timeseries(
+        &self,
+        lat: f64,
+        lon: f64,
+        altitude: f64,
+        if_modified_since: Option<&str>,
+    ) -> Result<YrTimeseriesResult, AppError> {
+        let url = self.timeseries_url(lat, lon, altitude);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -301,11 +505,15 @@ impl YrClient {
 ///     yr.no's forecast horizon).
 /// - The `forecast_horizon`: the last (furthest future) timestamp in the yr.no timeseries.
 ///
+/// With `min_resolution` = `Hourly`, times whose closest entry is only
+/// 6-hourly are `None` as well. `SixHourly` accepts both.
+///
 /// Much more efficient than calling `extract_forecast_at_time` N times because
 /// we deserialize the JSON only once.
 pub fn extract_forecasts_at_times(
     raw_json: serde_json::Value,
     forecast_times: &[DateTime<Utc>],
+    min_resolution: ForecastResolution,
 ) -> Result<ExtractionResult, AppError> {
     let yr_response: YrResponse = serde_json::from_value(raw_json).map_err(|e| {
         AppError::ExternalServiceError(format!("yr.no response structure error: {}", e))
@@ -363,6 +571,12 @@ pub fn extract_forecasts_at_times(
         })?
     };
 
+    // Whether any entry in this response is hourly — used to detect requests
+    // that fall past the hourly → 6-hourly boundary (~60–66 h ahead).
+    let has_hourly = parsed_entries
+        .iter()
+        .any(|(_, entry)| entry.data.next_1_hours.is_some());
+
     let mut results = Vec::with_capacity(forecast_times.len());
 
     for &ft in forecast_times {
@@ -382,6 +596,14 @@ pub fn extract_forecasts_at_times(
         let distance_secs = (parsed.forecast_time.timestamp() - target_ts).unsigned_abs() as i64;
         let tolerance = parsed.resolution.max_tolerance_secs();
 
+        if has_hourly && parsed.resolution == ForecastResolution::SixHourly {
+            tracing::debug!(
+                "Resolution boundary crossed for {}: using 6-hourly data from {}",
+                ft,
+                parsed.forecast_time,
+            );
+        }
+
         if distance_secs > tolerance {
             tracing::debug!(
                 "Closest yr.no entry to {} is {} ({} secs away, tolerance {} secs for {:?}) — skipping",
@@ -392,6 +614,14 @@ pub fn extract_forecasts_at_times(
                 parsed.resolution,
             );
             results.push(None);
+        } else if !parsed.resolution.meets(min_resolution) {
+            tracing::debug!(
+                "Closest yr.no entry to {} is {:?}, below the requested {:?} resolution — skipping",
+                ft,
+                parsed.resolution,
+                min_resolution,
+            );
+            results.push(None);
         } else {
             results.push(Some(parsed));
         }
@@ -403,6 +633,17 @@ pub fn extract_forecasts_at_times(
     })
 }
 
+/// Extract the model run timestamp from a yr.no raw JSON response.
+pub(crate) fn extract_model_run_at(raw_json: &serde_json::Value) -> Option<DateTime<Utc>> {
+    raw_json
+        .get("properties")?
+        .get("meta")?
+        .get("updated_at")?
+        .as_str()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
 /// Parse a single yr.no timeseries entry into a `YrParsedForecast`.
 fn parse_timeseries_entry(entry: &YrTimeseries) -> Result<YrParsedForecast, AppError> {
     let entry_time = DateTime::parse_from_rfc3339(&entry.time)
@@ -416,6 +657,15 @@ fn parse_timeseries_entry(entry: &YrTimeseries) -> Result<YrParsedForecast, AppE
 
     let instant = &entry.data.instant.details;
 
+    // Snow depth is location-dependent — note when a location provides it
+    if let Some(depth) = instant.snow_depth {
+        tracing::debug!(
+            "yr.no entry {} includes snow_depth: {} cm",
+            entry.time,
+            depth
+        );
+    }
+
     // Detect temporal resolution from which period blocks are present
     let resolution = if entry.data.next_1_hours.is_some() {
         ForecastResolution::Hourly
@@ -485,8 +735,13 @@ fn parse_timeseries_entry(entry: &YrTimeseries) -> Result<YrParsedForecast, AppE
             instant.cloud_area_fraction,
             "cloud_area_fraction",
         )),
+        cloud_cover_low_pct: opt_f64_to_decimal(instant.cloud_area_fraction_low),
+        cloud_cover_medium_pct: opt_f64_to_decimal(instant.cloud_area_fraction_medium),
+        cloud_cover_high_pct: opt_f64_to_decimal(instant.cloud_area_fraction_high),
+        snow_depth_cm: opt_f64_to_decimal(instant.snow_depth),
         uv_index: opt_f64_to_decimal(instant.ultraviolet_index_clear_sky),
         symbol_code,
+        thunder_probability_pct: opt_f64_to_decimal(precip.and_then(|p| p.probability_of_thunder)),
         // Set to None here; overwritten by callers after parsing meta.
         yr_model_run_at: None,
         resolution,
@@ -496,18 +751,22 @@ fn parse_timeseries_entry(entry: &YrTimeseries) -> Result<YrParsedForecast, AppE
 /// Parse an HTTP date string (e.g. "Sat, 14 Feb 2026 12:00:00 GMT") into a
 /// `DateTime<Utc>`. Falls back to `Utc::now() + 1 hour` if parsing fails.
 pub fn parse_expires_header(expires: &str) -> DateTime<Utc> {
-    DateTime::parse_from_rfc2822(expires)
+    parse_http_date(expires).unwrap_or_else(|_| {
+        tracing::warn!(
+            "Failed to parse Expires header '{}', defaulting to now + 1h",
+            expires
+        );
+        Utc::now() + chrono::Duration::hours(1)
+    })
+}
+
+/// Parse an HTTP date (RFC 2822, or any of the HTTP-date formats).
+pub(crate) fn parse_http_date(s: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc2822(s)
         .map(|dt| dt.with_timezone(&Utc))
         .or_else(|_| {
             // Try alternative format "Sat, 14 Feb 2026 12:00:00 GMT" (httpdate)
-            httpdate_parse(expires)
-        })
-        .unwrap_or_else(|_| {
-            tracing::warn!(
-                "Failed to parse Expires header '{}', defaulting to now + 1h",
-                expires
-            );
-            Utc::now() + chrono::Duration::hours(1)
+            httpdate_parse(s)
         })
 }
 
@@ -538,12 +797,58 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[test]
+    fn test_circuit_breaker_opens_and_resets() {
+        let breaker = CircuitBreaker::default();
+        let t0 = Instant::now();
+
+        // Exactly the threshold of failures keeps the circuit closed
+        for _ in 0..YR_CIRCUIT_BREAKER_THRESHOLD {
+            breaker.record_failure(t0);
+        }
+        assert!(!breaker.is_open(t0));
+
+        // The 6th consecutive failure opens it
+        breaker.record_failure(t0);
+        assert!(breaker.is_open(t0));
+        assert!(breaker.is_open(t0 + Duration::from_secs(59)));
+
+        // Half-open after the reset period: a further failure reopens it
+        let reset = t0 + Duration::from_secs(YR_CIRCUIT_BREAKER_RESET_SECS);
+        assert!(!breaker.is_open(reset));
+        breaker.record_failure(reset);
+        assert!(breaker.is_open(reset + Duration::from_secs(1)));
+
+        // Success closes the circuit and clears the failure count
+        breaker.record_success();
+        assert!(!breaker.is_open(reset + Duration::from_secs(1)));
+        breaker.record_failure(reset);
+        assert!(!breaker.is_open(reset));
+    }
+
+    #[test]
+    fn test_timeseries_url_uses_configured_base() {
+        let client = YrClient::with_client(
+            reqwest::Client::new(),
+            "test",
+            "http://localhost:9999/locationforecast/2.0/classic/",
+        );
+        assert_eq!(
+            client.timeseries_url(61.123456, 13.98765, 350.4),
+            "http://localhost:9999/locationforecast/2.0/classic?lat=61.1235&lon=13.9877&altitude=350"
+        );
+    }
+
     /// Test-only convenience wrapper: extract a forecast for a single time.
     fn extract_forecast_at_time(
         raw_json: &serde_json::Value,
         forecast_time: DateTime<Utc>,
     ) -> Result<Option<YrParsedForecast>, AppError> {
-        let result = extract_forecasts_at_times(raw_json.clone(), &[forecast_time])?;
+        let result = extract_forecasts_at_times(
+            raw_json.clone(),
+            &[forecast_time],
+            ForecastResolution::SixHourly,
+        )?;
         Ok(result.forecasts.into_iter().next().flatten())
     }
 
@@ -675,6 +980,166 @@ mod tests {
         assert_eq!(forecast.resolution, ForecastResolution::Hourly);
     }
 
+    #[test]
+    fn test_extract_forecast_cloud_layers() {
+        let json = serde_json::json!({
+            "type": "Feature",
+            "properties": {
+                "timeseries": [
+                    {
+                        "time": "2026-03-01T07:00:00Z",
+                        "data": {
+                            "instant": {
+                                "details": {
+                                    "air_temperature": -5.0,
+                                    "wind_speed": 3.2,
+                                    "wind_from_direction": 180.0,
+                                    "relative_humidity": 75.0,
+                                    "dew_point_temperature": -8.5,
+                                    "cloud_area_fraction": 50.0,
+                                    "cloud_area_fraction_low": 12.5,
+                                    "cloud_area_fraction_medium": 0.0,
+                                    "cloud_area_fraction_high": 43.8
+                                }
+                            },
+                            "next_1_hours": {
+                                "summary": { "symbol_code": "partlycloudy_day" },
+                                "details": { "precipitation_amount": 0.0 }
+                            }
+                        }
+                    },
+                    {
+                        "time": "2026-03-01T08:00:00Z",
+                        "data": {
+                            "instant": {
+                                "details": {
+                                    "air_temperature": -4.0,
+                                    "wind_speed": 3.0,
+                                    "wind_from_direction": 180.0,
+                                    "relative_humidity": 75.0,
+                                    "dew_point_temperature": -8.0,
+                                    "cloud_area_fraction": 50.0
+                                }
+                            },
+                            "next_1_hours": {
+                                "summary": { "symbol_code": "cloudy" },
+                                "details": { "precipitation_amount": 0.0 }
+                            }
+                        }
+                    }
+                ]
+            }
+        });
+
+        let with_layers = extract_forecast_at_time(&json, "2026-03-01T07:00:00Z".parse().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            with_layers.cloud_cover_low_pct,
+            Some(Decimal::from_str("12.5").unwrap())
+        );
+        assert_eq!(with_layers.cloud_cover_medium_pct, Some(Decimal::ZERO));
+        assert_eq!(
+            with_layers.cloud_cover_high_pct,
+            Some(Decimal::from_str("43.8").unwrap())
+        );
+
+        // Layers are optional — missing fields must not fail parsing
+        let without = extract_forecast_at_time(&json, "2026-03-01T08:00:00Z".parse().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(without.cloud_cover_low_pct, None);
+        assert_eq!(without.cloud_cover_high_pct, None);
+    }
+
+    #[test]
+    fn test_extract_forecast_snow_depth() {
+        let entry = |time: &str, details: serde_json::Value| {
+            serde_json::json!({
+                "time": time,
+                "data": {
+                    "instant": { "details": details },
+                    "next_1_hours": {
+                        "summary": { "symbol_code": "snow" },
+                        "details": { "precipitation_amount": 0.4 }
+                    }
+                }
+            })
+        };
+        let json = serde_json::json!({
+            "type": "Feature",
+            "properties": {
+                "timeseries": [
+                    entry("2026-03-01T07:00:00Z", serde_json::json!({
+                        "air_temperature": -5.0,
+                        "wind_speed": 3.2,
+                        "wind_from_direction": 180.0,
+                        "relative_humidity": 75.0,
+                        "dew_point_temperature": -8.5,
+                        "cloud_area_fraction": 90.0,
+                        "snow_depth": 62.5
+                    })),
+                    entry("2026-03-01T08:00:00Z", serde_json::json!({
+                        "air_temperature": -4.0,
+                        "wind_speed": 3.0,
+                        "wind_from_direction": 180.0,
+                        "relative_humidity": 75.0,
+                        "dew_point_temperature": -8.0,
+                        "cloud_area_fraction": 90.0
+                    }))
+                ]
+            }
+        });
+
+        let with_depth = extract_forecast_at_time(&json, "2026-03-01T07:00:00Z".parse().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            with_depth.snow_depth_cm,
+            Some(Decimal::from_str("62.5").unwrap())
+        );
+
+        let without = extract_forecast_at_time(&json, "2026-03-01T08:00:00Z".parse().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(without.snow_depth_cm, None);
+    }
+
+    #[test]
+    fn test_extract_forecast_thunder_probability() {
+        let json = serde_json::json!({
+            "type": "Feature",
+            "properties": {
+                "timeseries": [{
+                    "time": "2026-07-01T14:00:00Z",
+                    "data": {
+                        "instant": { "details": {
+                            "air_temperature": 22.0,
+                            "wind_speed": 4.0,
+                            "wind_from_direction": 200.0,
+                            "relative_humidity": 70.0,
+                            "dew_point_temperature": 16.0,
+                            "cloud_area_fraction": 80.0
+                        }},
+                        "next_1_hours": {
+                            "summary": { "symbol_code": "heavyrainshowersandthunder_day" },
+                            "details": { "precipitation_amount": 3.1, "probability_of_thunder": 45.0 }
+                        }
+                    }
+                }]
+            }
+        });
+
+        let parsed = extract_forecast_at_time(&json, "2026-07-01T14:00:00Z".parse().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.thunder_probability_pct, Some(Decimal::from(45)));
+        assert_eq!(
+            crate::services::forecast::classify_lightning_risk(45.0),
+            "moderate"
+        );
+    }
+
     #[test]
     fn test_extract_forecast_with_meta_updated_at() {
         let json = serde_json::json!({
@@ -768,7 +1233,8 @@ mod tests {
             "2026-03-01T10:00:00Z".parse::<DateTime<Utc>>().unwrap(),
         ];
 
-        let result = extract_forecasts_at_times(json, &times).unwrap();
+        let result =
+            extract_forecasts_at_times(json, &times, ForecastResolution::SixHourly).unwrap();
         assert_eq!(result.forecasts.len(), 2);
         let f0 = result.forecasts[0]
             .as_ref()
@@ -1157,7 +1623,8 @@ mod tests {
             "2026-04-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap(), // way out → None
         ];
 
-        let result = extract_forecasts_at_times(json, &times).unwrap();
+        let result =
+            extract_forecasts_at_times(json, &times, ForecastResolution::SixHourly).unwrap();
         assert_eq!(result.forecasts.len(), 3);
         assert!(result.forecasts[0].is_some(), "Exact match should be Some");
         assert!(
@@ -1172,9 +1639,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_min_resolution_filter() {
+        // Hourly entry at 07:00, then a 6-hourly entry at 12:00
+        let entry = |time: &str, period: &str| {
+            serde_json::json!({
+                "time": time,
+                "data": {
+                    "instant": { "details": {
+                        "air_temperature": -4.0,
+                        "wind_speed": 3.0,
+                        "wind_from_direction": 180.0,
+                        "relative_humidity": 75.0,
+                        "dew_point_temperature": -8.0,
+                        "cloud_area_fraction": 50.0
+                    }},
+                    period: {
+                        "summary": { "symbol_code": "cloudy" },
+                        "details": { "precipitation_amount": 0.0 }
+                    }
+                }
+            })
+        };
+        let json = serde_json::json!({
+            "type": "Feature",
+            "properties": { "timeseries": [
+                entry("2026-03-01T07:00:00Z", "next_1_hours"),
+                entry("2026-03-01T12:00:00Z", "next_6_hours"),
+            ]}
+        });
+        let times = vec![
+            "2026-03-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+            "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+        ];
+
+        // hourly rejects the 6-hourly entry but keeps the hourly one
+        let hourly =
+            extract_forecasts_at_times(json.clone(), &times, ForecastResolution::Hourly).unwrap();
+        assert!(hourly.forecasts[0].is_some());
+        assert!(hourly.forecasts[1].is_none());
+        assert_eq!(
+            hourly.forecast_horizon,
+            "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+
+        // six_hourly (default) accepts both
+        let six_hourly =
+            extract_forecasts_at_times(json, &times, ForecastResolution::SixHourly).unwrap();
+        assert!(six_hourly.forecasts.iter().all(Option::is_some));
+
+        // Stored rows: legacy NULL resolution only passes the 6-hourly filter
+        use ForecastResolution::{Hourly, SixHourly};
+        assert!(ForecastResolution::stored_meets(Some("hourly"), Hourly));
+        assert!(!ForecastResolution::stored_meets(
+            Some("six_hourly"),
+            Hourly
+        ));
+        assert!(!ForecastResolution::stored_meets(None, Hourly));
+        assert!(ForecastResolution::stored_meets(
+            Some("six_hourly"),
+            SixHourly
+        ));
+        assert!(ForecastResolution::stored_meets(None, SixHourly));
+    }
+
     #[test]
     fn test_resolution_max_tolerance_values() {
         assert_eq!(ForecastResolution::Hourly.max_tolerance_secs(), 3_600);
         assert_eq!(ForecastResolution::SixHourly.max_tolerance_secs(), 10_800);
+        assert_eq!(ForecastResolution::Hourly.as_str(), "hourly");
+        assert_eq!(ForecastResolution::SixHourly.as_str(), "six_hourly");
     }
 }